@@ -1,5 +1,8 @@
 -- dummy data for fe/be development
--- run with sqlite3 data.db < seed.sql
+-- run with `cargo run --bin circa-admin -- seed`, which migrates databases from before a schema change first
+
+-- bump this whenever a table changes, restores refuse backups from a different version
+PRAGMA user_version = 22;
 
 CREATE TABLE IF NOT EXISTS users (
     id TEXT PRIMARY KEY NOT NULL,
@@ -7,18 +10,477 @@ CREATE TABLE IF NOT EXISTS users (
     surname TEXT NOT NULL,
     email TEXT NOT NULL,
     phone TEXT NOT NULL,
-    role TEXT NOT NULL,
     status TEXT NOT NULL
 );
 
+-- one account per email, stored lowercase
+CREATE UNIQUE INDEX IF NOT EXISTS users_email ON users (lower(email));
+
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    starts_at TEXT NOT NULL,
+    ends_at TEXT NOT NULL,
+    -- draft, published, live, wrapped_up, archived; only ever moves forward
+    status TEXT NOT NULL DEFAULT 'draft',
+    -- JSON wrap-up report, written when the event wraps up
+    report TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- roles are per event, the same person can run one event and volunteer at another
+CREATE TABLE IF NOT EXISTS event_members (
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    joined_at TEXT NOT NULL,
+    PRIMARY KEY (event_id, user_id)
+);
+
+-- event_id stays set after an event is gone, NULL for things like backups
+CREATE TABLE IF NOT EXISTS audit_entries (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT,
+    actor_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    target_type TEXT NOT NULL,
+    target_id TEXT,
+    details TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS export_jobs (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    requested_by TEXT NOT NULL,
+    entity TEXT NOT NULL,
+    format TEXT NOT NULL,
+    columns TEXT,
+    filter TEXT NOT NULL,
+    status TEXT NOT NULL,
+    row_count INTEGER,
+    file_name TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    finished_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS teams (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (event_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS team_members (
+    team_id TEXT NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    is_lead INTEGER NOT NULL DEFAULT 0,
+    joined_at TEXT NOT NULL,
+    PRIMARY KEY (team_id, user_id)
+);
+
+-- venue -> area -> zone -> point, removing a location takes everything below it along
+CREATE TABLE IF NOT EXISTS locations (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    parent_id TEXT REFERENCES locations(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    latitude REAL,
+    longitude REAL,
+    floor_plan TEXT,
+    floor_x REAL,
+    floor_y REAL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- recurring shifts and tasks, materialized into rows that point back here
+CREATE TABLE IF NOT EXISTS series (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    rule TEXT NOT NULL,
+    starts_at TEXT NOT NULL,
+    exdates TEXT NOT NULL,
+    template TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS tasks (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    priority TEXT NOT NULL,
+    status TEXT NOT NULL,
+    due_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    completed_at TEXT,
+    completed_by TEXT,
+    series_id TEXT REFERENCES series(id) ON DELETE SET NULL,
+    occurrence_at TEXT,
+    is_exception INTEGER NOT NULL DEFAULT 0,
+    team_id TEXT REFERENCES teams(id) ON DELETE SET NULL,
+    location_id TEXT REFERENCES locations(id) ON DELETE SET NULL
+);
+
+CREATE TABLE IF NOT EXISTS task_assignees (
+    task_id TEXT NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (task_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS shifts (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    location TEXT NOT NULL,
+    starts_at TEXT NOT NULL,
+    ends_at TEXT NOT NULL,
+    notes TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    series_id TEXT REFERENCES series(id) ON DELETE SET NULL,
+    occurrence_at TEXT,
+    is_exception INTEGER NOT NULL DEFAULT 0,
+    team_id TEXT REFERENCES teams(id) ON DELETE SET NULL,
+    location_id TEXT REFERENCES locations(id) ON DELETE SET NULL
+);
+
+CREATE TABLE IF NOT EXISTS shift_requirements (
+    shift_id TEXT NOT NULL REFERENCES shifts(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    headcount INTEGER NOT NULL,
+    PRIMARY KEY (shift_id, role)
+);
+
+CREATE TABLE IF NOT EXISTS shift_assignments (
+    shift_id TEXT NOT NULL REFERENCES shifts(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    assigned_at TEXT NOT NULL,
+    PRIMARY KEY (shift_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS shift_skills (
+    shift_id TEXT NOT NULL REFERENCES shifts(id) ON DELETE CASCADE,
+    skill TEXT NOT NULL,
+    PRIMARY KEY (shift_id, skill)
+);
+
+CREATE TABLE IF NOT EXISTS user_skills (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    skill TEXT NOT NULL,
+    PRIMARY KEY (user_id, skill)
+);
+
+CREATE TABLE IF NOT EXISTS user_preferred_areas (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    area TEXT NOT NULL,
+    PRIMARY KEY (user_id, area)
+);
+
+CREATE TABLE IF NOT EXISTS user_availability (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    starts_at TEXT NOT NULL,
+    ends_at TEXT NOT NULL,
+    note TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS rosters (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    status TEXT NOT NULL,
+    seed INTEGER NOT NULL,
+    shift_ids TEXT NOT NULL,
+    unfilled TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    published_at TEXT,
+    published_by TEXT
+);
+
+CREATE TABLE IF NOT EXISTS roster_assignments (
+    roster_id TEXT NOT NULL REFERENCES rosters(id) ON DELETE CASCADE,
+    shift_id TEXT NOT NULL REFERENCES shifts(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    PRIMARY KEY (roster_id, shift_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS calendar_feeds (
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    token TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (event_id, user_id, kind)
+);
+
+CREATE TABLE IF NOT EXISTS assets (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    location TEXT NOT NULL,
+    -- owned or rented
+    ownership TEXT NOT NULL,
+    -- requested, ordered, delivered, in_use, returned, lost
+    status TEXT NOT NULL,
+    notes TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    location_id TEXT REFERENCES locations(id) ON DELETE SET NULL
+);
+
+CREATE TABLE IF NOT EXISTS asset_status_history (
+    id TEXT PRIMARY KEY NOT NULL,
+    asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    note TEXT NOT NULL,
+    changed_by TEXT NOT NULL,
+    changed_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS asset_checkouts (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    -- the custody log outlives memberships, accounts with one aren't deleted
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    quantity INTEGER NOT NULL,
+    note TEXT NOT NULL,
+    checked_out_by TEXT NOT NULL,
+    checked_out_at TEXT NOT NULL,
+    due_at TEXT NOT NULL,
+    -- empty while the item is still out
+    checked_in_by TEXT,
+    checked_in_at TEXT,
+    -- good, damaged or broken
+    condition TEXT,
+    return_note TEXT
+);
+
+-- short codes printed on labels, target is an asset or a user depending on kind
+CREATE TABLE IF NOT EXISTS scan_codes (
+    code TEXT PRIMARY KEY NOT NULL,
+    kind TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (kind, target_id)
+);
+
+CREATE TABLE IF NOT EXISTS vendors (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    email TEXT NOT NULL,
+    phone TEXT NOT NULL,
+    website TEXT NOT NULL,
+    notes TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS vendor_contacts (
+    id TEXT PRIMARY KEY NOT NULL,
+    vendor_id TEXT NOT NULL REFERENCES vendors(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    role TEXT NOT NULL,
+    email TEXT NOT NULL,
+    phone TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS vendor_documents (
+    id TEXT PRIMARY KEY NOT NULL,
+    vendor_id TEXT NOT NULL REFERENCES vendors(id) ON DELETE CASCADE,
+    -- contract, invoice or other
+    kind TEXT NOT NULL,
+    title TEXT NOT NULL,
+    url TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS vendor_deliveries (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    vendor_id TEXT NOT NULL REFERENCES vendors(id) ON DELETE CASCADE,
+    description TEXT NOT NULL,
+    window_start TEXT NOT NULL,
+    window_end TEXT NOT NULL,
+    receiver_id TEXT REFERENCES users(id) ON DELETE SET NULL,
+    dock TEXT NOT NULL,
+    location_id TEXT REFERENCES locations(id) ON DELETE SET NULL,
+    -- expected or received
+    status TEXT NOT NULL,
+    -- empty until someone confirms it arrived
+    received_by TEXT,
+    received_at TEXT,
+    receive_note TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS consumables (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    unit TEXT NOT NULL,
+    -- kept in step with stock_movements
+    stock INTEGER NOT NULL,
+    reorder_threshold INTEGER NOT NULL,
+    location_id TEXT REFERENCES locations(id) ON DELETE SET NULL,
+    notes TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS stock_movements (
+    id TEXT PRIMARY KEY NOT NULL,
+    consumable_id TEXT NOT NULL REFERENCES consumables(id) ON DELETE CASCADE,
+    -- received, issued or adjusted
+    kind TEXT NOT NULL,
+    change INTEGER NOT NULL,
+    stock_after INTEGER NOT NULL,
+    reason TEXT NOT NULL,
+    moved_by TEXT NOT NULL,
+    moved_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS branding_versions (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    -- draft or published
+    status TEXT NOT NULL,
+    -- JSON colors, typography, logos and social images
+    config TEXT NOT NULL,
+    note TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    published_by TEXT,
+    published_at TEXT,
+    UNIQUE (event_id, version)
+);
+
+CREATE TABLE IF NOT EXISTS branding_settings (
+    event_id TEXT PRIMARY KEY NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    volunteer_access INTEGER NOT NULL DEFAULT 0,
+    updated_by TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS files (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    -- hex SHA-256, uploads with the same content share one storage key
+    sha256 TEXT NOT NULL,
+    storage_key TEXT NOT NULL,
+    -- pending, ready or failed for images, NULL for everything else
+    image_status TEXT,
+    uploaded_by TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS files_sha256 ON files (event_id, sha256);
+
+CREATE TABLE IF NOT EXISTS file_variants (
+    id TEXT PRIMARY KEY NOT NULL,
+    file_id TEXT NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    -- thumbnail, medium or original, all WebP
+    kind TEXT NOT NULL,
+    storage_key TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (file_id, kind)
+);
+
+CREATE TABLE IF NOT EXISTS user_avatars (
+    user_id TEXT PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    file_id TEXT NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS social_posts (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    text TEXT NOT NULL,
+    scheduled_at TEXT,
+    -- draft, in_review, approved, scheduled, published or failed
+    status TEXT NOT NULL,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    review_note TEXT,
+    published_at TEXT,
+    published_url TEXT,
+    failure_reason TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS social_posts_scheduled_at ON social_posts (event_id, scheduled_at);
+
+CREATE TABLE IF NOT EXISTS social_post_channels (
+    post_id TEXT NOT NULL REFERENCES social_posts(id) ON DELETE CASCADE,
+    channel TEXT NOT NULL,
+    PRIMARY KEY (post_id, channel)
+);
+
+CREATE TABLE IF NOT EXISTS social_post_media (
+    post_id TEXT NOT NULL REFERENCES social_posts(id) ON DELETE CASCADE,
+    file_id TEXT NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (post_id, file_id)
+);
+
 -- clear past dev data if present
 DELETE FROM users WHERE email IN ('alice@circa.local', 'bob@circa.local');
+DELETE FROM events WHERE name = 'Circa Summer Camp';
+
+INSERT INTO events (id, name, starts_at, ends_at, status, created_at, updated_at)
+VALUES ('019c8555-7a32-7d41-a5c4-6f0b1e2d3c4b', 'Circa Summer Camp', '2026-07-01T08:00:00+00:00', '2026-07-05T20:00:00+00:00', 'published', '2026-01-15T10:00:00+00:00', '2026-01-15T10:00:00+00:00');
+
+INSERT INTO users (id, name, surname, email, phone, status)
+VALUES ('019c8555-7a32-719a-bbfc-289d208c2996', 'Alice', 'Lovelace', 'alice@circa.local', '+1-023-456-789', 'active');
+
+INSERT INTO users (id, name, surname, email, phone, status)
+VALUES ('019c8555-7a32-7972-8961-f2c2b29ebd22', 'Bob', 'Birkenstock', 'bob@circa.local', '+1-321-654-987', 'inactive');
 
-INSERT INTO users (id, name, surname, email, phone, role, status)
-VALUES ('019c8555-7a32-719a-bbfc-289d208c2996', 'Alice', 'Lovelace', 'alice@circa.local', '+1-023-456-789', 'admin', 'active');
+INSERT INTO event_members (event_id, user_id, role, joined_at)
+VALUES ('019c8555-7a32-7d41-a5c4-6f0b1e2d3c4b', '019c8555-7a32-719a-bbfc-289d208c2996', 'admin', '2026-01-15T10:00:00+00:00');
 
-INSERT INTO users (id, name, surname, email, phone, role, status)
-VALUES ('019c8555-7a32-7972-8961-f2c2b29ebd22', 'Bob', 'Birkenstock', 'bob@circa.local', '+1-321-654-987', 'organizer', 'inactive');
+INSERT INTO event_members (event_id, user_id, role, joined_at)
+VALUES ('019c8555-7a32-7d41-a5c4-6f0b1e2d3c4b', '019c8555-7a32-7972-8961-f2c2b29ebd22', 'organizer', '2026-01-15T10:00:00+00:00');
 
 -- check if added correctly :3
-SELECT id, name, email, role, status FROM users;
+SELECT u.id, u.name, u.email, m.role, u.status FROM users u JOIN event_members m ON m.user_id = u.id;
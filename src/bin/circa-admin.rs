@@ -0,0 +1,387 @@
+// maintenance tool for things the api can't do (yet), like making the very first admin
+// run with cargo run --bin circa-admin -- --help
+
+use chrono::{DateTime, Utc};
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::config::Config;
+use circa_backend::db;
+use circa_backend::error::AppError;
+use circa_backend::event::models::{CreateEventRequest, Event};
+use circa_backend::event::{repository::EventRepository, service::EventService};
+use circa_backend::file::{repository::FileRepository, signer::UrlSigner};
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::user::models::{
+    CreateUserRequest, UpdateUserRequest, User, UserRole, UserStatus,
+};
+use circa_backend::user::{repository::UserRepository, service::UserService};
+use clap::{Parser, Subcommand, ValueEnum};
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection};
+use serde::Serialize;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(
+    name = "circa-admin",
+    about = "Bootstrap and maintenance tasks for Circa"
+)]
+struct Cli {
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+
+    /// Event to work in, by id or name; can be left out while there is only one
+    #[arg(long, global = true)]
+    event: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Table,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new event, add people to it with create-user --event
+    CreateEvent {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        starts_at: DateTime<Utc>,
+        #[arg(long)]
+        ends_at: DateTime<Utc>,
+    },
+    /// List all events
+    Events,
+    /// Create a new user with the given role, or add an existing one to the event
+    CreateUser {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        surname: String,
+        #[arg(long)]
+        email: String,
+        #[arg(long, default_value = "")]
+        phone: String,
+        #[arg(long, default_value = "volunteer")]
+        role: UserRole,
+    },
+    /// Move a user one role up (volunteer -> staff -> organizer -> admin)
+    Promote { email: String },
+    /// Move a user one role down (admin -> organizer -> staff -> volunteer)
+    Demote { email: String },
+    /// Mark a user as inactive
+    Deactivate {
+        email: String,
+        /// Deactivate even if they still hold checked out equipment
+        #[arg(long)]
+        force: bool,
+    },
+    /// List all users of the event
+    List,
+    /// Mint a token for a user at the event, handy for testing the api by hand
+    Token { email: String },
+    /// Run a seed file against the database
+    Seed {
+        #[arg(default_value = "seed.sql")]
+        file: String,
+    },
+}
+
+// the cli is trusted the same way direct db access is
+fn cli_claims(event: &Event) -> Claims {
+    Claims {
+        sub: "circa-admin".to_string(),
+        event: event.id.clone(),
+        role: UserRole::Admin.as_str().to_string(),
+        exp: 0,
+    }
+}
+
+fn exit_code(err: &AppError) -> u8 {
+    match err {
+        AppError::InternalServerError => 1,
+        AppError::BadRequest(_) | AppError::Conflict(_, _) => 2,
+        AppError::NotFound(_) => 3,
+        AppError::Unauthorized | AppError::Forbidden => 4,
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let config = Config::init();
+
+    // connecting directly keeps stdout clean for --format json
+    let db_conn = match Database::connect(&config.database_url).await {
+        Ok(conn) => conn,
+        Err(err) => {
+            eprintln!("Failed to connect to the database: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // older databases are brought up to date before anything, seeding included
+    if let Err(err) = db::migrate(&db_conn).await {
+        eprintln!("Failed to migrate the database: {}", err);
+        return ExitCode::FAILURE;
+    }
+
+    match run(cli, &config, db_conn).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            if let AppError::Conflict(_, details) = &err {
+                eprintln!("{}", details);
+            }
+            ExitCode::from(exit_code(&err))
+        }
+    }
+}
+
+async fn run(cli: Cli, config: &Config, db_conn: DatabaseConnection) -> Result<(), AppError> {
+    let db_conn = Arc::new(db_conn);
+    let service = UserService::new(
+        UserRepository::new(db_conn.clone()),
+        LogisticsRepository::new(db_conn.clone()),
+        FileRepository::new(db_conn.clone()),
+        UrlSigner::new(
+            config.jwt_secret.clone(),
+            chrono::Duration::seconds(config.download_url_ttl_seconds),
+        ),
+    );
+    let events = EventService::new(
+        EventRepository::new(db_conn.clone()),
+        PlannerRepository::new(db_conn.clone()),
+        TeamRepository::new(db_conn.clone()),
+        AuditService::new(AuditRepository::new(db_conn.clone())),
+    );
+
+    // event commands and seeding work outside of any one event
+    let command = match cli.command {
+        Command::CreateEvent {
+            name,
+            starts_at,
+            ends_at,
+        } => {
+            let event = events
+                .create_unattended(CreateEventRequest {
+                    name,
+                    starts_at,
+                    ends_at,
+                })
+                .await?;
+            print_events(cli.format, &[event]);
+            return Ok(());
+        }
+        Command::Events => {
+            print_events(cli.format, &events.get_all().await?);
+            return Ok(());
+        }
+        Command::Seed { file } => {
+            let sql = std::fs::read_to_string(&file)
+                .map_err(|err| AppError::BadRequest(format!("Cannot read {}: {}", file, err)))?;
+
+            db_conn
+                .execute_unprepared(&sql)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+
+            println!("Seeded database from {}", file);
+            return Ok(());
+        }
+        command => command,
+    };
+
+    let event = pick_event(&events, cli.event.as_deref()).await?;
+    let claims = cli_claims(&event);
+
+    match command {
+        Command::CreateUser {
+            name,
+            surname,
+            email,
+            phone,
+            role,
+        } => {
+            let user = service
+                .create_user(
+                    CreateUserRequest {
+                        name,
+                        surname,
+                        email,
+                        phone,
+                        role,
+                    },
+                    &claims,
+                )
+                .await?;
+            print_users(cli.format, &[user]);
+        }
+        Command::Promote { email } => {
+            let user = service.get_user_by_email(&email, &claims).await?;
+            let role = user.role.promoted();
+            let user = set_role(&service, &user, role, &claims).await?;
+            print_users(cli.format, &[user]);
+        }
+        Command::Demote { email } => {
+            let user = service.get_user_by_email(&email, &claims).await?;
+            let role = user.role.demoted();
+            let user = set_role(&service, &user, role, &claims).await?;
+            print_users(cli.format, &[user]);
+        }
+        Command::Deactivate { email, force } => {
+            let user = service.get_user_by_email(&email, &claims).await?;
+            let req = UpdateUserRequest {
+                name: None,
+                surname: None,
+                email: None,
+                phone: None,
+                role: None,
+                status: Some(UserStatus::Inactive),
+            };
+            let user = service.update_user(&user.id, req, force, &claims).await?;
+            print_users(cli.format, &[user]);
+        }
+        Command::List => {
+            let users = service.get_users(&claims).await?;
+            print_users(cli.format, &users);
+        }
+        Command::Token { email } => {
+            let user = service.get_user_by_email(&email, &claims).await?;
+            let token = generate_jwt(&user.id, &event.id, user.role.as_str(), &config.jwt_secret)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+
+            match cli.format {
+                Format::Json => print_json(&token),
+                Format::Table => println!("{}", token.token),
+            }
+        }
+        Command::CreateEvent { .. } | Command::Events | Command::Seed { .. } => {
+            unreachable!("handled before picking an event")
+        }
+    }
+
+    Ok(())
+}
+
+async fn pick_event(events: &EventService, wanted: Option<&str>) -> Result<Event, AppError> {
+    if let Some(wanted) = wanted {
+        return events.find(wanted).await;
+    }
+
+    let mut all = events.get_all().await?;
+    match all.len() {
+        0 => Err(AppError::BadRequest(
+            "No events yet, create one with create-event".to_string(),
+        )),
+        1 => Ok(all.remove(0)),
+        _ => Err(AppError::BadRequest(
+            "Several events exist, pick one with --event".to_string(),
+        )),
+    }
+}
+
+async fn set_role(
+    service: &UserService,
+    user: &User,
+    role: UserRole,
+    claims: &Claims,
+) -> Result<User, AppError> {
+    let req = UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: None,
+        phone: None,
+        role: Some(role),
+        status: None,
+    };
+
+    service.update_user(&user.id, req, false, claims).await
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(err) => eprintln!("Failed to serialize output: {}", err),
+    }
+}
+
+fn print_events(format: Format, events: &[Event]) {
+    match format {
+        Format::Json => print_json(&events),
+        Format::Table => {
+            let rows: Vec<[String; 4]> = events
+                .iter()
+                .map(|e| {
+                    [
+                        e.id.clone(),
+                        e.name.clone(),
+                        e.starts_at.to_rfc3339(),
+                        e.ends_at.to_rfc3339(),
+                    ]
+                })
+                .collect();
+            print_table(["id", "name", "starts_at", "ends_at"], &rows);
+        }
+    }
+}
+
+fn print_users(format: Format, users: &[User]) {
+    match format {
+        Format::Json => print_json(&users),
+        Format::Table => {
+            let rows: Vec<[String; 7]> = users
+                .iter()
+                .map(|u| {
+                    [
+                        u.id.clone(),
+                        u.name.clone(),
+                        u.surname.clone(),
+                        u.email.clone(),
+                        u.phone.clone(),
+                        u.role.as_str().to_string(),
+                        u.status.to_string().to_lowercase(),
+                    ]
+                })
+                .collect();
+            print_table(
+                ["id", "name", "surname", "email", "phone", "role", "status"],
+                &rows,
+            );
+        }
+    }
+}
+
+fn print_table<const N: usize>(headers: [&str; N], rows: &[[String; N]]) {
+    let mut widths = headers.map(|h| h.len());
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| format!("{:<width$}", c, width = widths[i]))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", line(headers.to_vec()));
+    for row in rows {
+        println!("{}", line(row.iter().map(|c| c.as_str()).collect()));
+    }
+}
@@ -4,6 +4,21 @@ use std::env;
 pub struct Config {
     pub database_url: String,
     pub jwt_secret: String,
+    pub export_dir: String,
+    pub backup_dir: String,
+    // 0 turns scheduled backups off
+    pub backup_interval_minutes: u64,
+    pub backup_retention: usize,
+    // 0 turns the respective shift rule off
+    pub max_shift_hours_per_day: u32,
+    pub min_rest_minutes: u32,
+    // local, memory or s3
+    pub storage: String,
+    pub upload_dir: String,
+    pub max_upload_bytes: usize,
+    pub download_url_ttl_seconds: i64,
+    // user ids allowed to touch the whole database, whatever their role in an event
+    pub system_admins: Vec<String>,
 }
 
 impl Config {
@@ -12,10 +27,57 @@ impl Config {
 
         let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env");
         let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set in .env");
+        let export_dir = env::var("EXPORT_DIR").unwrap_or_else(|_| "exports".to_string());
+        let backup_dir = env::var("BACKUP_DIR").unwrap_or_else(|_| "backups".to_string());
+        let backup_interval_minutes = env::var("BACKUP_INTERVAL_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+        let backup_retention = env::var("BACKUP_RETENTION")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24);
+        let max_shift_hours_per_day = env::var("MAX_SHIFT_HOURS_PER_DAY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10);
+        let min_rest_minutes = env::var("MIN_REST_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+        let storage = env::var("STORAGE").unwrap_or_else(|_| "local".to_string());
+        let upload_dir = env::var("UPLOAD_DIR").unwrap_or_else(|_| "uploads".to_string());
+        let max_upload_bytes = env::var("MAX_UPLOAD_MB")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(20)
+            * 1024
+            * 1024;
+        let download_url_ttl_seconds = env::var("DOWNLOAD_URL_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(900);
+        let system_admins = env::var("SYSTEM_ADMINS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect();
 
         Config {
             database_url,
             jwt_secret,
+            export_dir,
+            backup_dir,
+            backup_interval_minutes,
+            backup_retention,
+            max_shift_hours_per_day,
+            min_rest_minutes,
+            storage,
+            upload_dir,
+            max_upload_bytes,
+            download_url_ttl_seconds,
+            system_admins,
         }
     }
 }
@@ -1,4 +1,7 @@
-use sea_orm::{Database, DatabaseConnection, DbErr};
+use sea_orm::{
+    ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, DbErr, Statement,
+    TransactionTrait,
+};
 
 pub async fn establish_connection(database_url: &str) -> Result<DatabaseConnection, DbErr> {
     let db = Database::connect(database_url).await?;
@@ -6,3 +9,159 @@ pub async fn establish_connection(database_url: &str) -> Result<DatabaseConnecti
     println!("Database connected successfully");
     Ok(db)
 }
+
+// brings databases from before a schema change up to date, seed.sql takes it from there
+pub async fn migrate(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let version = user_version(db).await?;
+
+    // version 9 moved roles from users into per-event memberships
+    if version < 9 && has_column(db, "users", "role").await? {
+        move_roles_to_events(db).await?;
+    }
+    // version 21 made emails case-insensitive and unique
+    if version < 21 && has_column(db, "users", "email").await? {
+        lowercase_emails(db).await?;
+    }
+    // version 22 stopped deleting someone's custody log along with their account
+    if version < 22 && has_column(db, "asset_checkouts", "user_id").await? {
+        keep_checkouts_of_deleted_users(db).await?;
+    }
+
+    Ok(())
+}
+
+async fn move_roles_to_events(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let event_id = uuid::Uuid::now_v7().to_string();
+    let txn = db.begin().await?;
+
+    txn.execute_unprepared(
+        "CREATE TABLE IF NOT EXISTS events (
+            id TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL UNIQUE,
+            starts_at TEXT NOT NULL,
+            ends_at TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'draft',
+            report TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS event_members (
+            event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+            user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+            role TEXT NOT NULL,
+            joined_at TEXT NOT NULL,
+            PRIMARY KEY (event_id, user_id)
+        );",
+    )
+    .await?;
+
+    // everyone keeps their role in one event standing in for the old single-event setup
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO events (id, name, starts_at, ends_at, status, created_at, updated_at)
+         SELECT ?, 'Default event', ?, ?, 'draft', ?, ? WHERE EXISTS (SELECT 1 FROM users)",
+        [
+            event_id.clone().into(),
+            now.clone().into(),
+            now.clone().into(),
+            now.clone().into(),
+            now.clone().into(),
+        ],
+    ))
+    .await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO event_members (event_id, user_id, role, joined_at)
+         SELECT ?, id, role, ? FROM users",
+        [event_id.into(), now.into()],
+    ))
+    .await?;
+
+    // dropping the column rather than rebuilding the table keeps rows pointing at users intact
+    txn.execute_unprepared("ALTER TABLE users DROP COLUMN role")
+        .await?;
+
+    txn.commit().await
+}
+
+async fn lowercase_emails(db: &DatabaseConnection) -> Result<(), DbErr> {
+    // accounts that only differ by case have to be merged by hand, nothing here can tell which one to keep
+    let clashes = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT lower(trim(email)) FROM users GROUP BY lower(trim(email)) HAVING COUNT(*) > 1",
+        ))
+        .await?
+        .iter()
+        .map(|row| row.try_get_by_index::<String>(0))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !clashes.is_empty() {
+        return Err(DbErr::Custom(format!(
+            "more than one account uses {}, merge them before upgrading",
+            clashes.join(", ")
+        )));
+    }
+
+    let txn = db.begin().await?;
+    txn.execute_unprepared(
+        "UPDATE users SET email = lower(trim(email));
+        CREATE UNIQUE INDEX IF NOT EXISTS users_email ON users (lower(email));",
+    )
+    .await?;
+    txn.commit().await
+}
+
+// sqlite can't change a foreign key in place, so the table is copied over
+async fn keep_checkouts_of_deleted_users(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    txn.execute_unprepared(
+        "CREATE TABLE asset_checkouts_new (
+            id TEXT PRIMARY KEY NOT NULL,
+            event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+            asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            user_id TEXT NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+            quantity INTEGER NOT NULL,
+            note TEXT NOT NULL,
+            checked_out_by TEXT NOT NULL,
+            checked_out_at TEXT NOT NULL,
+            due_at TEXT NOT NULL,
+            checked_in_by TEXT,
+            checked_in_at TEXT,
+            condition TEXT,
+            return_note TEXT
+        );
+        INSERT INTO asset_checkouts_new SELECT * FROM asset_checkouts;
+        DROP TABLE asset_checkouts;
+        ALTER TABLE asset_checkouts_new RENAME TO asset_checkouts;",
+    )
+    .await?;
+    txn.commit().await
+}
+
+async fn user_version(db: &DatabaseConnection) -> Result<i64, DbErr> {
+    let row = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "PRAGMA user_version",
+        ))
+        .await?
+        .ok_or_else(|| DbErr::Custom("PRAGMA user_version returned nothing".to_string()))?;
+
+    row.try_get_by_index::<i64>(0)
+}
+
+async fn has_column(db: &DatabaseConnection, table: &str, column: &str) -> Result<bool, DbErr> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?",
+            [table.into(), column.into()],
+        ))
+        .await?;
+
+    Ok(match row {
+        Some(row) => row.try_get_by_index::<i64>(0)? > 0,
+        None => false,
+    })
+}
@@ -12,12 +12,19 @@ pub enum AppError {
     NotFound(String),
     Unauthorized,
     Forbidden,
+    // details is a machine readable list of what clashed, sent back next to the message
+    #[display("Conflict: {}", _0)]
+    Conflict(String, serde_json::Value),
 }
 
 impl ResponseError for AppError {
     fn error_response(&self) -> HttpResponse {
-        HttpResponse::build(self.status_code())
-            .json(serde_json::json!({"error": self.to_string() }))
+        match self {
+            AppError::Conflict(_, details) => HttpResponse::build(self.status_code())
+                .json(serde_json::json!({"error": self.to_string(), "details": details })),
+            _ => HttpResponse::build(self.status_code())
+                .json(serde_json::json!({"error": self.to_string() })),
+        }
     }
 
     fn status_code(&self) -> StatusCode {
@@ -27,6 +34,7 @@ impl ResponseError for AppError {
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::Unauthorized => StatusCode::UNAUTHORIZED,
             AppError::Forbidden => StatusCode::FORBIDDEN,
+            AppError::Conflict(_, _) => StatusCode::CONFLICT,
         }
     }
 }
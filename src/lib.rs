@@ -4,4 +4,7 @@ pub mod db;
 pub mod error;
 pub mod models;
 pub mod modules;
-pub use modules::{auth, user};
+pub use modules::{
+    audit, auth, backup, branding, calendar, consumable, event, export, file, label, location,
+    logistics, planner, privacy, social, team, user, vendor,
+};
@@ -1,28 +1,218 @@
+use actix_web::middleware::from_fn;
 use actix_web::{App, HttpServer, web};
+use circa_backend::audit;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
 use circa_backend::auth;
+use circa_backend::backup;
+use circa_backend::backup::models::BackupSchedule;
+use circa_backend::backup::{repository::BackupRepository, service::BackupService};
+use circa_backend::branding;
+use circa_backend::branding::{repository::BrandingRepository, service::BrandingService};
+use circa_backend::calendar;
+use circa_backend::calendar::{repository::CalendarRepository, service::CalendarService};
 use circa_backend::config::Config;
+use circa_backend::consumable;
+use circa_backend::consumable::{repository::ConsumableRepository, service::ConsumableService};
 use circa_backend::db;
+use circa_backend::event;
+use circa_backend::event::{repository::EventRepository, service::EventService};
+use circa_backend::export;
+use circa_backend::export::{repository::ExportRepository, service::ExportService};
+use circa_backend::file;
+use circa_backend::file::storage;
+use circa_backend::file::{repository::FileRepository, service::FileService, signer::UrlSigner};
+use circa_backend::label;
+use circa_backend::label::{repository::LabelRepository, service::LabelService};
+use circa_backend::location;
+use circa_backend::location::{repository::LocationRepository, service::LocationService};
+use circa_backend::logistics;
+use circa_backend::logistics::{repository::LogisticsRepository, service::LogisticsService};
+use circa_backend::planner;
+use circa_backend::planner::{
+    models::ShiftLimits, repository::PlannerRepository, service::PlannerService,
+};
+use circa_backend::privacy::service::PrivacyService;
+use circa_backend::social;
+use circa_backend::social::{repository::SocialRepository, service::SocialService};
+use circa_backend::team;
+use circa_backend::team::{repository::TeamRepository, service::TeamService};
 use circa_backend::user;
 use circa_backend::user::{repository::UserRepository, service::UserService};
+use circa_backend::vendor;
+use circa_backend::vendor::{repository::VendorRepository, service::VendorService};
+use std::sync::Arc;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let config = Config::init();
-    let db_conn = db::establish_connection(&config.database_url)
+    let db_conn = Arc::new(
+        db::establish_connection(&config.database_url)
+            .await
+            .expect("Failed to connect to the database :c"),
+    );
+    db::migrate(&db_conn)
         .await
-        .expect("Failed to connect to the database :c");
+        .expect("Failed to migrate the database :c");
 
-    let user_service = web::Data::new(UserService::new(UserRepository::new(db_conn)));
+    // download links for files and the image variants hanging off other entities
+    let url_signer = UrlSigner::new(
+        config.jwt_secret.clone(),
+        chrono::Duration::seconds(config.download_url_ttl_seconds),
+    );
+
+    let user_service = web::Data::new(UserService::new(
+        UserRepository::new(db_conn.clone()),
+        LogisticsRepository::new(db_conn.clone()),
+        FileRepository::new(db_conn.clone()),
+        url_signer.clone(),
+    ));
+    let event_service = web::Data::new(EventService::new(
+        EventRepository::new(db_conn.clone()),
+        PlannerRepository::new(db_conn.clone()),
+        TeamRepository::new(db_conn.clone()),
+        AuditService::new(AuditRepository::new(db_conn.clone())),
+    ));
+    let audit_service = web::Data::new(AuditService::new(AuditRepository::new(db_conn.clone())));
+    let export_service = web::Data::new(ExportService::new(
+        ExportRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+        event_service.clone().into_inner(),
+        AuditService::new(AuditRepository::new(db_conn.clone())),
+        config.export_dir,
+    ));
+    let backup_service = web::Data::new(BackupService::new(
+        BackupRepository::new(db_conn.clone()),
+        AuditService::new(AuditRepository::new(db_conn.clone())),
+        config.backup_dir,
+        config.system_admins.clone(),
+    ));
+    backup_service
+        .clone()
+        .into_inner()
+        .spawn_schedule(BackupSchedule {
+            interval_minutes: config.backup_interval_minutes,
+            retention: config.backup_retention,
+        });
+    let file_service = web::Data::new(FileService::new(
+        FileRepository::new(db_conn.clone()),
+        SocialRepository::new(db_conn.clone()),
+        storage::from_config(&config.storage, &config.upload_dir),
+        config.max_upload_bytes,
+        url_signer.clone(),
+    ));
+    file_service.clone().into_inner().spawn_image_worker();
+    let privacy_service = web::Data::new(PrivacyService::new(
+        UserRepository::new(db_conn.clone()),
+        EventRepository::new(db_conn.clone()),
+        AuditRepository::new(db_conn.clone()),
+        ExportRepository::new(db_conn.clone()),
+        PlannerRepository::new(db_conn.clone()),
+        CalendarRepository::new(db_conn.clone()),
+        LogisticsRepository::new(db_conn.clone()),
+        SocialRepository::new(db_conn.clone()),
+        TeamRepository::new(db_conn.clone()),
+        VendorRepository::new(db_conn.clone()),
+        ConsumableRepository::new(db_conn.clone()),
+        file_service.clone().into_inner(),
+        config.system_admins,
+    ));
+    let planner_service = web::Data::new(PlannerService::new(
+        PlannerRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+        TeamRepository::new(db_conn.clone()),
+        LocationRepository::new(db_conn.clone()),
+        AuditService::new(AuditRepository::new(db_conn.clone())),
+        ShiftLimits {
+            max_hours_per_day: config.max_shift_hours_per_day,
+            min_rest_minutes: config.min_rest_minutes,
+        },
+    ));
+    let calendar_service = web::Data::new(CalendarService::new(
+        CalendarRepository::new(db_conn.clone()),
+        PlannerRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+    ));
+    let team_service = web::Data::new(TeamService::new(
+        TeamRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+    ));
+    let logistics_service = web::Data::new(LogisticsService::new(
+        LogisticsRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+        LocationRepository::new(db_conn.clone()),
+    ));
+    let location_service = web::Data::new(LocationService::new(
+        LocationRepository::new(db_conn.clone()),
+        PlannerRepository::new(db_conn.clone()),
+        LogisticsRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+    ));
+    let label_service = web::Data::new(LabelService::new(
+        LabelRepository::new(db_conn.clone()),
+        LogisticsRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+    ));
+    let vendor_service = web::Data::new(VendorService::new(
+        VendorRepository::new(db_conn.clone()),
+        UserRepository::new(db_conn.clone()),
+        LocationRepository::new(db_conn.clone()),
+    ));
+    let consumable_service = web::Data::new(ConsumableService::new(
+        ConsumableRepository::new(db_conn.clone()),
+        LocationRepository::new(db_conn.clone()),
+    ));
+    let branding_service = web::Data::new(BrandingService::new(
+        BrandingRepository::new(db_conn.clone()),
+        AuditService::new(AuditRepository::new(db_conn.clone())),
+    ));
+    let social_service = web::Data::new(SocialService::new(
+        SocialRepository::new(db_conn.clone()),
+        FileRepository::new(db_conn.clone()),
+        url_signer,
+        AuditService::new(AuditRepository::new(db_conn.clone())),
+    ));
     let jwt_secret = web::Data::new(config.jwt_secret);
 
     println!("Server starting at 0.0.0.0:8080");
 
     HttpServer::new(move || {
         App::new()
+            .wrap(from_fn(event::middleware::event_path))
             .app_data(user_service.clone())
+            .app_data(event_service.clone())
+            .app_data(audit_service.clone())
+            .app_data(export_service.clone())
+            .app_data(backup_service.clone())
+            .app_data(privacy_service.clone())
+            .app_data(planner_service.clone())
+            .app_data(calendar_service.clone())
+            .app_data(team_service.clone())
+            .app_data(logistics_service.clone())
+            .app_data(label_service.clone())
+            .app_data(location_service.clone())
+            .app_data(vendor_service.clone())
+            .app_data(consumable_service.clone())
+            .app_data(branding_service.clone())
+            .app_data(file_service.clone())
+            .app_data(social_service.clone())
             .app_data(jwt_secret.clone())
             .configure(user::routes::config)
             .configure(auth::routes::config)
+            .configure(event::routes::config)
+            .configure(audit::routes::config)
+            .configure(export::routes::config)
+            .configure(backup::routes::config)
+            .configure(planner::routes::config)
+            .configure(calendar::routes::config)
+            .configure(team::routes::config)
+            .configure(logistics::routes::config)
+            .configure(label::routes::config)
+            .configure(location::routes::config)
+            .configure(vendor::routes::config)
+            .configure(consumable::routes::config)
+            .configure(branding::routes::config)
+            .configure(file::routes::config)
+            .configure(social::routes::config)
     })
     .bind(("0.0.0.0", 8080))?
     .run()
@@ -0,0 +1,51 @@
+use super::entity;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub id: String,
+    pub event_id: Option<String>,
+    pub actor_id: String,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub details: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<entity::Model> for AuditEntry {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            id: model.id,
+            event_id: model.event_id,
+            actor_id: model.actor_id,
+            action: model.action,
+            target_type: model.target_type,
+            target_id: model.target_id,
+            details: model
+                .details
+                .and_then(|details| serde_json::from_str(&details).ok()),
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NewAuditEntry {
+    // None for things outside any one event, like backups
+    pub event_id: Option<String>,
+    pub actor_id: String,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct AuditQuery {
+    pub actor_id: Option<String>,
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+}
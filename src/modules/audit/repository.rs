@@ -0,0 +1,85 @@
+use super::entity::{ActiveModel, Column, Entity as AuditEntity};
+use super::models::{AuditEntry, AuditQuery, NewAuditEntry};
+use crate::error::AppError;
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct AuditRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl AuditRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    // the event's own entries plus the ones that belong to no event
+    pub async fn find(
+        &self,
+        event_id: &str,
+        query: &AuditQuery,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let mut select = AuditEntity::find().filter(
+            Condition::any()
+                .add(Column::EventId.eq(event_id))
+                .add(Column::EventId.is_null()),
+        );
+
+        if let Some(actor_id) = &query.actor_id {
+            select = select.filter(Column::ActorId.eq(actor_id));
+        }
+        if let Some(action) = &query.action {
+            select = select.filter(Column::Action.eq(action));
+        }
+        if let Some(target_type) = &query.target_type {
+            select = select.filter(Column::TargetType.eq(target_type));
+        }
+        if let Some(target_id) = &query.target_id {
+            select = select.filter(Column::TargetId.eq(target_id));
+        }
+
+        let models = select
+            .order_by_desc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    // everything the user did or that was done to them
+    pub async fn find_involving(&self, user_id: &str) -> Result<Vec<AuditEntry>, AppError> {
+        let models = AuditEntity::find()
+            .filter(
+                Condition::any()
+                    .add(Column::ActorId.eq(user_id))
+                    .add(Column::TargetId.eq(user_id)),
+            )
+            .order_by_asc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn create(&self, entry: NewAuditEntry) -> Result<AuditEntry, AppError> {
+        let new_entry = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(entry.event_id),
+            actor_id: Set(entry.actor_id),
+            action: Set(entry.action),
+            target_type: Set(entry.target_type),
+            target_id: Set(entry.target_id),
+            details: Set(entry.details.map(|details| details.to_string())),
+            created_at: Set(chrono::Utc::now()),
+        };
+
+        let result = new_entry
+            .insert(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::audit::models::AuditQuery;
+use crate::modules::audit::service::AuditService;
+use crate::modules::auth::middleware::jwt_validator;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/audit")
+            .wrap(auth_middleware)
+            .route("", web::get().to(get_entries)),
+    );
+}
+
+async fn get_entries(
+    req: HttpRequest,
+    service: web::Data<AuditService>,
+    query: web::Query<AuditQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let entries = service.get_entries(&query, &claims).await?;
+    Ok(HttpResponse::Ok().json(entries))
+}
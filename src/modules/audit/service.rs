@@ -0,0 +1,30 @@
+use super::models::{AuditEntry, AuditQuery, NewAuditEntry};
+use super::repository::AuditRepository;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+
+pub struct AuditService {
+    repository: AuditRepository,
+}
+
+impl AuditService {
+    pub fn new(repository: AuditRepository) -> Self {
+        Self { repository }
+    }
+
+    pub async fn record(&self, entry: NewAuditEntry) -> Result<AuditEntry, AppError> {
+        self.repository.create(entry).await
+    }
+
+    pub async fn get_entries(
+        &self,
+        query: &AuditQuery,
+        claims: &Claims,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        if claims.role != "admin" {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.find(&claims.event, query).await
+    }
+}
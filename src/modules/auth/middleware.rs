@@ -1,8 +1,14 @@
-use actix_web::{Error, HttpMessage, dev::ServiceRequest, error::ErrorUnauthorized, web};
+use actix_web::{
+    Error, HttpMessage,
+    dev::ServiceRequest,
+    error::{ErrorForbidden, ErrorUnauthorized},
+    web,
+};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use jsonwebtoken::{DecodingKey, Validation, decode};
 
 use crate::auth::models::Claims;
+use crate::event::middleware::{check_writable, requested_event};
 
 pub async fn jwt_validator(
     req: ServiceRequest,
@@ -21,6 +27,18 @@ pub async fn jwt_validator(
         &validation,
     ) {
         Ok(token_data) => {
+            // roles differ per event, so a token never reaches into another one
+            if requested_event(&req).is_some_and(|event| event != token_data.claims.event) {
+                return Err((
+                    ErrorForbidden("Token was issued for another event, switch events first"),
+                    req,
+                ));
+            }
+
+            if let Err(err) = check_writable(&req, &token_data.claims).await {
+                return Err((err, req));
+            }
+
             req.extensions_mut().insert(token_data.claims);
             Ok(req)
         }
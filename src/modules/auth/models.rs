@@ -3,6 +3,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
     pub email: String,
+    // defaults to the event the user joined most recently
+    #[serde(default)]
+    pub event_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -13,10 +16,24 @@ pub struct TokenResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String,
+    // the event the token is good for, role is the role there
+    pub event: String,
     pub role: String,
     pub exp: usize,
 }
 
+impl Claims {
+    // admins and organizers run the event
+    pub fn is_manager(&self) -> bool {
+        self.role == "admin" || self.role == "organizer"
+    }
+
+    // staff and up, for the parts of the event volunteers stay out of
+    pub fn is_staff(&self) -> bool {
+        self.is_manager() || self.role == "staff"
+    }
+}
+
 // TODO: check how things like these are even made
 // ideally this would be a magic link sent to the user's inbox
 // mail servers are a pain to set up correctly tho QwQ
@@ -1,7 +1,7 @@
 use crate::{
     auth::{middleware::jwt_validator, models::Claims, service::generate_jwt},
+    event::service::EventService,
     modules::auth::models::LoginRequest,
-    user::service::UserService,
 };
 use actix_web::{HttpMessage, HttpRequest, HttpResponse, Responder, web};
 use actix_web_httpauth::middleware::HttpAuthentication;
@@ -21,14 +21,24 @@ pub fn config(cfg: &mut web::ServiceConfig) {
 async fn login(
     body: web::Json<LoginRequest>,
     jwt_secret: web::Data<String>,
-    user_service: web::Data<UserService>,
+    event_service: web::Data<EventService>,
 ) -> impl Responder {
-    let user = match user_service.get_user_by_email(&body.email).await {
-        Ok(user) => user,
+    let membership = match event_service
+        .sign_in(&body.email, body.event_id.as_deref())
+        .await
+    {
+        Ok(membership) => membership,
         Err(_) => return HttpResponse::Unauthorized().body("User not found"),
     };
 
-    match generate_jwt(&body.email, user.role.as_str(), &jwt_secret).await {
+    match generate_jwt(
+        &membership.user_id,
+        &membership.event_id,
+        membership.role.as_str(),
+        &jwt_secret,
+    )
+    .await
+    {
         Ok(token_response) => HttpResponse::Ok().json(token_response),
         Err(_) => HttpResponse::InternalServerError().body("Failed to generate JWT"),
     }
@@ -4,6 +4,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 pub async fn generate_jwt(
     user_id: &str,
+    event_id: &str,
     role: &str,
     secret: &str,
 ) -> Result<TokenResponse, jsonwebtoken::errors::Error> {
@@ -15,6 +16,7 @@ pub async fn generate_jwt(
 
     let claims = Claims {
         sub: user_id.to_string(),
+        event: event_id.to_string(),
         role: role.to_string(),
         exp: expiration,
     };
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupKind {
+    #[display("manual")]
+    Manual,
+    #[display("scheduled")]
+    Scheduled,
+    // taken right before a restore, so a bad restore can be undone
+    #[display("pre_restore")]
+    PreRestore,
+    #[display("upload")]
+    Upload,
+}
+
+impl BackupKind {
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let stem = name.strip_suffix(".db")?;
+        [
+            BackupKind::Manual,
+            BackupKind::Scheduled,
+            BackupKind::PreRestore,
+            BackupKind::Upload,
+        ]
+        .into_iter()
+        .find(|kind| stem.ends_with(&format!("-{}", kind)))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Backup {
+    pub name: String,
+    pub kind: BackupKind,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct RestoreQuery {
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestoreReport {
+    pub restored_from: String,
+    pub schema_version: i64,
+    pub tables: usize,
+    pub safety_backup: String,
+}
+
+pub struct BackupSchedule {
+    pub interval_minutes: u64,
+    // how many scheduled snapshots to keep, manual ones are never pruned
+    pub retention: usize,
+}
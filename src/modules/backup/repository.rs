@@ -0,0 +1,137 @@
+use crate::error::AppError;
+use sea_orm::sqlx::{self, Connection};
+use sea_orm::*;
+use std::path::Path;
+use std::sync::Arc;
+
+pub struct BackupRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn quote_ident(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+impl BackupRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    // VACUUM INTO gives a consistent copy without stopping writers
+    pub async fn snapshot_to(&self, path: &Path) -> Result<(), AppError> {
+        let sql = format!("VACUUM INTO {}", quote_literal(&path.to_string_lossy()));
+
+        self.db
+            .execute_unprepared(&sql)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn schema_version(&self) -> Result<i64, AppError> {
+        read_schema_version(self.db.as_ref()).await
+    }
+
+    // opens the file on its own read-only connection, returns its schema version if it's intact
+    pub async fn inspect(&self, path: &Path) -> Result<i64, AppError> {
+        let url = format!("sqlite://{}?mode=ro", path.to_string_lossy());
+        let candidate = Database::connect(&url)
+            .await
+            .map_err(|_| AppError::BadRequest("Backup is not a SQLite database".to_string()))?;
+
+        let check = candidate
+            .query_one(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                "PRAGMA quick_check",
+            ))
+            .await
+            .map_err(|_| AppError::BadRequest("Backup is not a SQLite database".to_string()))?
+            .and_then(|row| row.try_get_by_index::<String>(0).ok());
+
+        if check.as_deref() != Some("ok") {
+            let _ = candidate.close().await;
+            return Err(AppError::BadRequest(
+                "Backup failed integrity check".to_string(),
+            ));
+        }
+
+        let version = read_schema_version(&candidate).await;
+        let _ = candidate.close().await;
+        version
+    }
+
+    // copies every table of the backup into the live database in one transaction,
+    // open connections keep working and a failure leaves the current data untouched
+    pub async fn restore_from(&self, path: &Path) -> Result<usize, AppError> {
+        let pool = self.db.get_sqlite_connection_pool();
+        let mut conn = pool
+            .acquire()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        sqlx::query(&format!(
+            "ATTACH DATABASE {} AS restore",
+            quote_literal(&path.to_string_lossy())
+        ))
+        .execute(&mut *conn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        let result = async {
+            let tables: Vec<String> = sqlx::query_scalar(
+                "SELECT name FROM restore.sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+                 AND name IN (SELECT name FROM main.sqlite_master WHERE type = 'table')",
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            let mut txn = conn.begin().await?;
+            sqlx::query("PRAGMA defer_foreign_keys = ON")
+                .execute(&mut *txn)
+                .await?;
+
+            for table in &tables {
+                let table = quote_ident(table);
+                sqlx::query(&format!("DELETE FROM main.{}", table))
+                    .execute(&mut *txn)
+                    .await?;
+                sqlx::query(&format!(
+                    "INSERT INTO main.{} SELECT * FROM restore.{}",
+                    table, table
+                ))
+                .execute(&mut *txn)
+                .await?;
+            }
+
+            txn.commit().await?;
+            Ok::<usize, sqlx::Error>(tables.len())
+        }
+        .await;
+
+        let _ = sqlx::query("DETACH DATABASE restore")
+            .execute(&mut *conn)
+            .await;
+
+        result.map_err(|_| AppError::InternalServerError)
+    }
+}
+
+async fn read_schema_version(db: &DatabaseConnection) -> Result<i64, AppError> {
+    let row = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "PRAGMA user_version",
+        ))
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or(AppError::InternalServerError)?;
+
+    row.try_get_by_index::<i64>(0)
+        .map_err(|_| AppError::InternalServerError)
+}
@@ -0,0 +1,100 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::backup::models::RestoreQuery;
+use crate::modules::backup::service::BackupService;
+use actix_web::http::header::ContentDisposition;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+// uploaded backups can be as big as the whole event
+const MAX_UPLOAD_BYTES: usize = 512 * 1024 * 1024;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/backups")
+            .wrap(auth_middleware)
+            .app_data(web::PayloadConfig::new(MAX_UPLOAD_BYTES))
+            .route("", web::get().to(get_backups))
+            .route("", web::post().to(create_backup))
+            .route("/restore", web::post().to(restore_upload))
+            .route("/{name}", web::get().to(download_backup))
+            .route("/{name}/restore", web::post().to(restore_backup)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_backups(
+    req: HttpRequest,
+    service: web::Data<BackupService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let backups = service.get_backups(&claims).await?;
+    Ok(HttpResponse::Ok().json(backups))
+}
+
+async fn create_backup(
+    req: HttpRequest,
+    service: web::Data<BackupService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let backup = service.create_backup(&claims).await?;
+    Ok(HttpResponse::Ok().json(backup))
+}
+
+async fn download_backup(
+    req: HttpRequest,
+    service: web::Data<BackupService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+    let name = path.into_inner();
+
+    let file = service.backup_path(&name, &claims).await?;
+    let content = tokio::fs::read(file)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/vnd.sqlite3")
+        .insert_header(ContentDisposition::attachment(name))
+        .body(content))
+}
+
+async fn restore_backup(
+    req: HttpRequest,
+    service: web::Data<BackupService>,
+    path: web::Path<String>,
+    query: web::Query<RestoreQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let report = service
+        .restore_backup(&path.into_inner(), query.confirm, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+async fn restore_upload(
+    req: HttpRequest,
+    service: web::Data<BackupService>,
+    query: web::Query<RestoreQuery>,
+    body: web::Bytes,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let report = service
+        .restore_upload(&body, query.confirm, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(report))
+}
@@ -0,0 +1,290 @@
+use super::models::{Backup, BackupKind, BackupSchedule, RestoreReport};
+use super::repository::BackupRepository;
+use crate::audit::models::NewAuditEntry;
+use crate::audit::service::AuditService;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct BackupService {
+    repository: BackupRepository,
+    audit: AuditService,
+    backup_dir: PathBuf,
+    // backups hold every event, so event admins alone don't get to them
+    system_admins: Vec<String>,
+}
+
+impl BackupService {
+    pub fn new(
+        repository: BackupRepository,
+        audit: AuditService,
+        backup_dir: impl Into<PathBuf>,
+        system_admins: Vec<String>,
+    ) -> Self {
+        Self {
+            repository,
+            audit,
+            backup_dir: backup_dir.into(),
+            system_admins,
+        }
+    }
+
+    pub async fn get_backups(&self, claims: &Claims) -> Result<Vec<Backup>, AppError> {
+        self.require_system_admin(claims)?;
+
+        self.list().await
+    }
+
+    pub async fn create_backup(&self, claims: &Claims) -> Result<Backup, AppError> {
+        self.require_system_admin(claims)?;
+
+        let backup = self.snapshot(BackupKind::Manual).await?;
+        self.record(claims, "backup.create", &backup.name).await?;
+
+        Ok(backup)
+    }
+
+    pub async fn backup_path(&self, name: &str, claims: &Claims) -> Result<PathBuf, AppError> {
+        self.require_system_admin(claims)?;
+
+        let path = self.resolve(name)?;
+        self.record(claims, "backup.download", name).await?;
+
+        Ok(path)
+    }
+
+    pub async fn restore_backup(
+        &self,
+        name: &str,
+        confirm: bool,
+        claims: &Claims,
+    ) -> Result<RestoreReport, AppError> {
+        self.require_system_admin(claims)?;
+        require_confirmation(confirm)?;
+
+        let path = self.resolve(name)?;
+        self.restore(&path, name, claims).await
+    }
+
+    pub async fn restore_upload(
+        &self,
+        content: &[u8],
+        confirm: bool,
+        claims: &Claims,
+    ) -> Result<RestoreReport, AppError> {
+        self.require_system_admin(claims)?;
+        require_confirmation(confirm)?;
+
+        // kept next to the other backups, so the uploaded file can be inspected later if needed
+        let name = backup_file_name(Utc::now(), BackupKind::Upload);
+        let path = self.backup_dir.join(&name);
+
+        tokio::fs::create_dir_all(&self.backup_dir)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let result = self.restore(&path, &name, claims).await;
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&path).await;
+        }
+
+        result
+    }
+
+    // snapshots on a timer and prunes old scheduled ones, runs for the lifetime of the server
+    pub fn spawn_schedule(self: Arc<Self>, schedule: BackupSchedule) {
+        if schedule.interval_minutes == 0 {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(schedule.interval_minutes * 60));
+            // the first tick fires immediately, no need for a snapshot right at boot
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                match self.snapshot(BackupKind::Scheduled).await {
+                    Ok(backup) => println!("Scheduled backup written to {}", backup.name),
+                    Err(err) => eprintln!("Scheduled backup failed: {}", err),
+                }
+
+                if let Err(err) = self.prune(schedule.retention).await {
+                    eprintln!("Pruning old backups failed: {}", err);
+                }
+            }
+        });
+    }
+
+    pub async fn prune(&self, retention: usize) -> Result<usize, AppError> {
+        let scheduled: Vec<Backup> = self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|b| b.kind == BackupKind::Scheduled)
+            .collect();
+
+        // list() is newest first
+        let mut removed = 0;
+        for backup in scheduled.iter().skip(retention) {
+            tokio::fs::remove_file(self.backup_dir.join(&backup.name))
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+
+    async fn restore(
+        &self,
+        path: &Path,
+        name: &str,
+        claims: &Claims,
+    ) -> Result<RestoreReport, AppError> {
+        let current = self.repository.schema_version().await?;
+        let candidate = self.repository.inspect(path).await?;
+
+        if candidate != current {
+            return Err(AppError::BadRequest(format!(
+                "Backup has schema version {}, but the database is at {}",
+                candidate, current
+            )));
+        }
+
+        let safety = self.snapshot(BackupKind::PreRestore).await?;
+        let tables = self.repository.restore_from(path).await?;
+
+        self.record(claims, "backup.restore", name).await?;
+
+        Ok(RestoreReport {
+            restored_from: name.to_string(),
+            schema_version: candidate,
+            tables,
+            safety_backup: safety.name,
+        })
+    }
+
+    async fn snapshot(&self, kind: BackupKind) -> Result<Backup, AppError> {
+        tokio::fs::create_dir_all(&self.backup_dir)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let name = backup_file_name(Utc::now(), kind);
+        let path = self.backup_dir.join(&name);
+        self.repository.snapshot_to(&path).await?;
+
+        describe(&path, &name)
+            .await
+            .ok_or(AppError::InternalServerError)
+    }
+
+    async fn list(&self) -> Result<Vec<Backup>, AppError> {
+        let mut entries = match tokio::fs::read_dir(&self.backup_dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(_) => return Err(AppError::InternalServerError),
+        };
+
+        let mut backups = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+        {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(backup) = describe(&entry.path(), &name).await {
+                backups.push(backup);
+            }
+        }
+
+        // names start with a timestamp, so this is newest first
+        backups.sort_by(|a, b| b.name.cmp(&a.name));
+        Ok(backups)
+    }
+
+    fn require_system_admin(&self, claims: &Claims) -> Result<(), AppError> {
+        if !self.system_admins.contains(&claims.sub) {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&self, name: &str) -> Result<PathBuf, AppError> {
+        if !is_valid_backup_name(name) {
+            return Err(AppError::BadRequest("Invalid backup name".to_string()));
+        }
+
+        let path = self.backup_dir.join(name);
+        if !path.is_file() {
+            return Err(AppError::NotFound("Backup not found".to_string()));
+        }
+
+        Ok(path)
+    }
+
+    async fn record(&self, claims: &Claims, action: &str, name: &str) -> Result<(), AppError> {
+        self.audit
+            .record(NewAuditEntry {
+                event_id: None,
+                actor_id: claims.sub.clone(),
+                action: action.to_string(),
+                target_type: "backup".to_string(),
+                target_id: Some(name.to_string()),
+                details: None,
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub fn backup_file_name(at: DateTime<Utc>, kind: BackupKind) -> String {
+    format!("circa-{}-{}.db", at.format("%Y%m%dT%H%M%S%3fZ"), kind)
+}
+
+// only names we generate ourselves, which also keeps ../ and friends out
+pub fn is_valid_backup_name(name: &str) -> bool {
+    name.starts_with("circa-")
+        && BackupKind::from_file_name(name).is_some()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.contains("..")
+}
+
+async fn describe(path: &Path, name: &str) -> Option<Backup> {
+    if !is_valid_backup_name(name) {
+        return None;
+    }
+
+    let kind = BackupKind::from_file_name(name)?;
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    let created_at = metadata.modified().ok().map(DateTime::<Utc>::from)?;
+
+    Some(Backup {
+        name: name.to_string(),
+        kind,
+        size_bytes: metadata.len(),
+        created_at,
+    })
+}
+
+fn require_confirmation(confirm: bool) -> Result<(), AppError> {
+    if !confirm {
+        return Err(AppError::BadRequest(
+            "Restoring replaces all data, pass confirm=true to continue".to_string(),
+        ));
+    }
+
+    Ok(())
+}
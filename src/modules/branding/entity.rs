@@ -0,0 +1,41 @@
+// who gets to look at the branding, one row per event
+pub mod settings;
+
+use derive_more::Display;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::StringLen;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Status {
+    #[sea_orm(string_value = "draft")]
+    Draft,
+    #[sea_orm(string_value = "published")]
+    Published,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "branding_versions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub event_id: String,
+    // counts up from 1 within the event
+    pub version: i32,
+    pub status: Status,
+    // JSON colors, typography, logos and social images
+    pub config: String,
+    pub note: String,
+    pub created_by: String,
+    pub created_at: ChronoDateTimeUtc,
+    pub updated_at: ChronoDateTimeUtc,
+    // the latest published version is the one in use
+    pub published_by: Option<String>,
+    pub published_at: Option<ChronoDateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
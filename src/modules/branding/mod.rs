@@ -0,0 +1,5 @@
+pub mod entity;
+pub mod models;
+pub mod repository;
+pub mod routes;
+pub mod service;
@@ -0,0 +1,207 @@
+use super::entity::{self, settings};
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrandingStatus {
+    #[display("Draft")]
+    Draft,
+    #[display("Published")]
+    Published,
+}
+
+impl From<BrandingStatus> for entity::Status {
+    fn from(item: BrandingStatus) -> Self {
+        match item {
+            BrandingStatus::Draft => entity::Status::Draft,
+            BrandingStatus::Published => entity::Status::Published,
+        }
+    }
+}
+
+impl From<entity::Status> for BrandingStatus {
+    fn from(item: entity::Status) -> Self {
+        match item {
+            entity::Status::Draft => BrandingStatus::Draft,
+            entity::Status::Published => BrandingStatus::Published,
+        }
+    }
+}
+
+// token name -> value, e.g. "primary" -> "#ff6600" or "heading_font" -> "Inter"
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BrandingConfig {
+    #[serde(default)]
+    pub colors: BTreeMap<String, String>,
+    #[serde(default)]
+    pub typography: BTreeMap<String, String>,
+    // links to the images, e.g. "primary", "dark" or "icon"
+    #[serde(default)]
+    pub logos: BTreeMap<String, String>,
+    // e.g. "og_image" or "banner"
+    #[serde(default)]
+    pub social_images: BTreeMap<String, String>,
+}
+
+impl BrandingConfig {
+    // everything wrong with it at once, so the editor can show it all
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let sections = [
+            ("colors", &self.colors),
+            ("typography", &self.typography),
+            ("logos", &self.logos),
+            ("social_images", &self.social_images),
+        ];
+        for (section, tokens) in sections {
+            for (name, value) in tokens {
+                if !is_token_name(name) {
+                    problems.push(format!(
+                        "{}: '{}' isn't a valid token name, use lowercase letters, digits, '-' and '_'",
+                        section, name
+                    ));
+                }
+                if value.trim().is_empty() {
+                    problems.push(format!("{}.{} is empty", section, name));
+                }
+            }
+        }
+
+        for (name, value) in &self.colors {
+            if !value.trim().is_empty() && !is_hex_color(value) {
+                problems.push(format!("colors.{}: '{}' isn't a hex color", name, value));
+            }
+        }
+        for (section, images) in [
+            ("logos", &self.logos),
+            ("social_images", &self.social_images),
+        ] {
+            for (name, value) in images {
+                if !value.trim().is_empty() && !is_link(value) {
+                    problems.push(format!(
+                        "{}.{}: '{}' isn't an http(s) URL or a path",
+                        section, name, value
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+fn is_token_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+// #rgb, #rrggbb or #rrggbbaa
+pub fn is_hex_color(value: &str) -> bool {
+    match value.strip_prefix('#') {
+        Some(hex) => matches!(hex.len(), 3 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
+// absolute links, or paths on this server
+fn is_link(value: &str) -> bool {
+    value.starts_with("https://") || value.starts_with("http://") || value.starts_with('/')
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrandingVersion {
+    pub id: String,
+    pub version: u32,
+    pub status: BrandingStatus,
+    pub config: BrandingConfig,
+    pub note: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub published_by: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl BrandingVersion {
+    pub fn from_model(model: entity::Model) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            config: serde_json::from_str(&model.config)?,
+            id: model.id,
+            version: model.version.max(0) as u32,
+            status: model.status.into(),
+            note: model.note,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            published_by: model.published_by,
+            published_at: model.published_at,
+        })
+    }
+
+    pub fn is_draft(&self) -> bool {
+        self.status == BrandingStatus::Draft
+    }
+}
+
+// what the frontend themes itself with, without anything about who edited it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PublicBranding {
+    pub version: u32,
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub config: BrandingConfig,
+}
+
+impl From<BrandingVersion> for PublicBranding {
+    fn from(version: BrandingVersion) -> Self {
+        Self {
+            version: version.version,
+            published_at: version.published_at,
+            config: version.config,
+        }
+    }
+}
+
+// volunteers stay out until someone decides otherwise
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BrandingSettings {
+    pub volunteer_access: bool,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<settings::Model> for BrandingSettings {
+    fn from(model: settings::Model) -> Self {
+        Self {
+            volunteer_access: model.volunteer_access,
+            updated_by: Some(model.updated_by),
+            updated_at: Some(model.updated_at),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct CreateDraftRequest {
+    // starts from the version in use when left out
+    pub config: Option<BrandingConfig>,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateDraftRequest {
+    pub config: Option<BrandingConfig>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateSettingsRequest {
+    pub volunteer_access: bool,
+}
@@ -0,0 +1,221 @@
+use super::entity::{self, ActiveModel, Column, Entity as BrandingEntity, settings};
+use super::models::{BrandingConfig, BrandingSettings, BrandingVersion};
+use crate::error::AppError;
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct BrandingRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+fn to_version(model: entity::Model) -> Result<BrandingVersion, AppError> {
+    BrandingVersion::from_model(model).map_err(|_| AppError::InternalServerError)
+}
+
+fn to_json(config: &BrandingConfig) -> Result<String, AppError> {
+    serde_json::to_string(config).map_err(|_| AppError::InternalServerError)
+}
+
+impl BrandingRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    // the most recently published version is the one in use
+    pub async fn find_current(&self, event_id: &str) -> Result<Option<BrandingVersion>, AppError> {
+        let model = BrandingEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::Status.eq(entity::Status::Published))
+            .order_by_desc(Column::PublishedAt)
+            .order_by_desc(Column::Version)
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        model.map(to_version).transpose()
+    }
+
+    // newest first
+    pub async fn find_versions(&self, event_id: &str) -> Result<Vec<BrandingVersion>, AppError> {
+        let models = BrandingEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .order_by_desc(Column::Version)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        models.into_iter().map(to_version).collect()
+    }
+
+    pub async fn find_version(
+        &self,
+        event_id: &str,
+        id: &str,
+    ) -> Result<Option<BrandingVersion>, AppError> {
+        let model = BrandingEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        model.map(to_version).transpose()
+    }
+
+    // numbered after the latest version, inside the same transaction
+    pub async fn create_version(
+        &self,
+        event_id: &str,
+        config: &BrandingConfig,
+        note: String,
+        created_by: &str,
+    ) -> Result<BrandingVersion, AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let latest = BrandingEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .order_by_desc(Column::Version)
+            .one(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let version = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            version: Set(latest.map(|m| m.version).unwrap_or(0) + 1),
+            status: Set(entity::Status::Draft),
+            config: Set(to_json(config)?),
+            note: Set(note),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+            published_by: Set(None),
+            published_at: Set(None),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        to_version(version)
+    }
+
+    pub async fn update_version(
+        &self,
+        id: &str,
+        config: Option<&BrandingConfig>,
+        note: Option<String>,
+    ) -> Result<BrandingVersion, AppError> {
+        let model = BrandingEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Branding version not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(config) = config {
+            active_model.config = Set(to_json(config)?);
+        }
+        if let Some(note) = note {
+            active_model.note = Set(note);
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        to_version(result)
+    }
+
+    // publishing an older version again makes it the current one, which is how rollbacks work
+    pub async fn publish(&self, id: &str, published_by: &str) -> Result<BrandingVersion, AppError> {
+        let model = BrandingEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Branding version not found".to_string()))?;
+
+        let now = chrono::Utc::now();
+        let mut active_model: ActiveModel = model.into();
+        active_model.status = Set(entity::Status::Published);
+        active_model.published_by = Set(Some(published_by.to_string()));
+        active_model.published_at = Set(Some(now));
+        active_model.updated_at = Set(now);
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        to_version(result)
+    }
+
+    pub async fn delete_version(&self, event_id: &str, id: &str) -> Result<(), AppError> {
+        let result = BrandingEntity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::EventId.eq(event_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Branding version not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // events nobody has configured get the defaults
+    pub async fn find_settings(&self, event_id: &str) -> Result<BrandingSettings, AppError> {
+        let model = settings::Entity::find_by_id(event_id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()).unwrap_or_default())
+    }
+
+    pub async fn save_settings(
+        &self,
+        event_id: &str,
+        volunteer_access: bool,
+        updated_by: &str,
+    ) -> Result<BrandingSettings, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        settings::Entity::delete_many()
+            .filter(settings::Column::EventId.eq(event_id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = settings::ActiveModel {
+            event_id: Set(event_id.to_string()),
+            volunteer_access: Set(volunteer_access),
+            updated_by: Set(updated_by.to_string()),
+            updated_at: Set(chrono::Utc::now()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.into())
+    }
+}
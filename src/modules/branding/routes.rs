@@ -0,0 +1,152 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::branding::models::{
+    CreateDraftRequest, UpdateDraftRequest, UpdateSettingsRequest,
+};
+use crate::modules::branding::service::BrandingService;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    // the login page is themed too, so the published branding needs no token
+    cfg.service(
+        web::resource("/branding/public/{event_id}").route(web::get().to(get_public_branding)),
+    )
+    .service(
+        web::scope("/branding")
+            .wrap(auth_middleware)
+            .route("", web::get().to(get_current))
+            .route("/versions", web::get().to(get_versions))
+            .route("/versions", web::post().to(create_draft))
+            .route("/versions/{id}", web::get().to(get_version))
+            .route("/versions/{id}", web::patch().to(update_draft))
+            .route("/versions/{id}", web::delete().to(delete_draft))
+            .route("/versions/{id}/publish", web::post().to(publish))
+            .route("/settings", web::get().to(get_settings))
+            .route("/settings", web::patch().to(update_settings)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_public_branding(
+    service: web::Data<BrandingService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let branding = service.get_public(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(300),
+        ]))
+        .json(branding))
+}
+
+async fn get_current(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let version = service.get_current(&claims).await?;
+    Ok(HttpResponse::Ok().json(version))
+}
+
+async fn get_versions(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let versions = service.get_versions(&claims).await?;
+    Ok(HttpResponse::Ok().json(versions))
+}
+
+async fn get_version(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let version = service.get_version(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(version))
+}
+
+async fn create_draft(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+    body: web::Json<CreateDraftRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let version = service.create_draft(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(version))
+}
+
+async fn update_draft(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateDraftRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let version = service
+        .update_draft(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(version))
+}
+
+async fn delete_draft(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service.delete_draft(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("Branding draft deleted successfully"))
+}
+
+async fn publish(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let version = service.publish(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(version))
+}
+
+async fn get_settings(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let settings = service.get_settings(&claims).await?;
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+async fn update_settings(
+    req: HttpRequest,
+    service: web::Data<BrandingService>,
+    body: web::Json<UpdateSettingsRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let settings = service.update_settings(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(settings))
+}
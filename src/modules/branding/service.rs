@@ -0,0 +1,207 @@
+use super::models::{
+    BrandingConfig, BrandingSettings, BrandingVersion, CreateDraftRequest, PublicBranding,
+    UpdateDraftRequest, UpdateSettingsRequest,
+};
+use super::repository::BrandingRepository;
+use crate::audit::models::NewAuditEntry;
+use crate::audit::service::AuditService;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+
+pub struct BrandingService {
+    repository: BrandingRepository,
+    audit: AuditService,
+}
+
+fn check_config(config: &BrandingConfig) -> Result<(), AppError> {
+    let problems = config.problems();
+    if !problems.is_empty() {
+        return Err(AppError::BadRequest(problems.join("; ")));
+    }
+
+    Ok(())
+}
+
+impl BrandingService {
+    pub fn new(repository: BrandingRepository, audit: AuditService) -> Self {
+        Self { repository, audit }
+    }
+
+    // for the frontend to theme itself before anyone logs in
+    pub async fn get_public(&self, event_id: &str) -> Result<PublicBranding, AppError> {
+        self.repository
+            .find_current(event_id)
+            .await?
+            .map(PublicBranding::from)
+            .ok_or_else(|| AppError::NotFound("Branding not published".to_string()))
+    }
+
+    // volunteers only see it when the event turned that on
+    pub async fn get_current(&self, claims: &Claims) -> Result<BrandingVersion, AppError> {
+        if !claims.is_staff()
+            && !self
+                .repository
+                .find_settings(&claims.event)
+                .await?
+                .volunteer_access
+        {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository
+            .find_current(&claims.event)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Branding not published".to_string()))
+    }
+
+    pub async fn get_versions(&self, claims: &Claims) -> Result<Vec<BrandingVersion>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.find_versions(&claims.event).await
+    }
+
+    pub async fn get_version(
+        &self,
+        id: &str,
+        claims: &Claims,
+    ) -> Result<BrandingVersion, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.find_version(id, claims).await
+    }
+
+    pub async fn create_draft(
+        &self,
+        req: CreateDraftRequest,
+        claims: &Claims,
+    ) -> Result<BrandingVersion, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let config = match req.config {
+            Some(config) => config,
+            None => self
+                .repository
+                .find_current(&claims.event)
+                .await?
+                .map(|current| current.config)
+                .unwrap_or_default(),
+        };
+        check_config(&config)?;
+
+        self.repository
+            .create_version(
+                &claims.event,
+                &config,
+                req.note.trim().to_string(),
+                &claims.sub,
+            )
+            .await
+    }
+
+    pub async fn update_draft(
+        &self,
+        id: &str,
+        req: UpdateDraftRequest,
+        claims: &Claims,
+    ) -> Result<BrandingVersion, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let version = self.find_version(id, claims).await?;
+        if !version.is_draft() {
+            return Err(AppError::BadRequest(
+                "Published versions can't be edited, start a new draft".to_string(),
+            ));
+        }
+        if let Some(config) = &req.config {
+            check_config(config)?;
+        }
+
+        self.repository
+            .update_version(
+                id,
+                req.config.as_ref(),
+                req.note.map(|n| n.trim().to_string()),
+            )
+            .await
+    }
+
+    // also rolls back to an older published version
+    pub async fn publish(&self, id: &str, claims: &Claims) -> Result<BrandingVersion, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let version = self.find_version(id, claims).await?;
+        let previous = self.repository.find_current(&claims.event).await?;
+        let published = self.repository.publish(id, &claims.sub).await?;
+
+        self.audit
+            .record(NewAuditEntry {
+                event_id: Some(claims.event.clone()),
+                actor_id: claims.sub.clone(),
+                action: "branding.publish".to_string(),
+                target_type: "branding_version".to_string(),
+                target_id: Some(id.to_string()),
+                details: Some(serde_json::json!({
+                    "version": version.version,
+                    "previous": previous.map(|p| p.version),
+                })),
+            })
+            .await?;
+
+        Ok(published)
+    }
+
+    // published versions stay as history
+    pub async fn delete_draft(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let version = self.find_version(id, claims).await?;
+        if !version.is_draft() {
+            return Err(AppError::BadRequest(
+                "Published versions can't be deleted".to_string(),
+            ));
+        }
+
+        self.repository.delete_version(&claims.event, id).await
+    }
+
+    pub async fn get_settings(&self, claims: &Claims) -> Result<BrandingSettings, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.find_settings(&claims.event).await
+    }
+
+    pub async fn update_settings(
+        &self,
+        req: UpdateSettingsRequest,
+        claims: &Claims,
+    ) -> Result<BrandingSettings, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository
+            .save_settings(&claims.event, req.volunteer_access, &claims.sub)
+            .await
+    }
+
+    async fn find_version(&self, id: &str, claims: &Claims) -> Result<BrandingVersion, AppError> {
+        self.repository
+            .find_version(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Branding version not found".to_string()))
+    }
+}
@@ -0,0 +1,108 @@
+use super::entity::{self, Kind};
+use crate::planner::models::{Shift, Task, TaskStatus};
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedKind {
+    // the owner's own shifts and tasks
+    #[display("Personal")]
+    Personal,
+    // every shift and task, organizers and admins only
+    #[display("Event")]
+    Event,
+}
+
+impl From<FeedKind> for Kind {
+    fn from(item: FeedKind) -> Self {
+        match item {
+            FeedKind::Personal => Kind::Personal,
+            FeedKind::Event => Kind::Event,
+        }
+    }
+}
+
+impl From<Kind> for FeedKind {
+    fn from(item: Kind) -> Self {
+        match item {
+            Kind::Personal => FeedKind::Personal,
+            Kind::Event => FeedKind::Event,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarFeed {
+    pub kind: FeedKind,
+    pub event_id: String,
+    pub user_id: String,
+    pub token: String,
+    // relative, the client knows which host it talks to
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<entity::Model> for CalendarFeed {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            kind: model.kind.into(),
+            url: format!("/calendar/{}.ics", model.token),
+            event_id: model.event_id,
+            user_id: model.user_id,
+            token: model.token,
+            created_at: model.created_at,
+        }
+    }
+}
+
+// one VEVENT, uid stays the same for the lifetime of the shift or task so clients update in place
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+    pub category: String,
+}
+
+impl From<&Shift> for CalendarEvent {
+    fn from(shift: &Shift) -> Self {
+        Self {
+            uid: format!("shift-{}@circa", shift.id),
+            summary: shift.title.clone(),
+            location: Some(shift.location.clone()).filter(|l| !l.is_empty()),
+            description: Some(shift.notes.clone()).filter(|n| !n.is_empty()),
+            starts_at: shift.starts_at,
+            ends_at: Some(shift.ends_at),
+            updated_at: shift.updated_at,
+            category: "Shift".to_string(),
+        }
+    }
+}
+
+impl CalendarEvent {
+    // only tasks with a due date end up in a calendar
+    pub fn from_task(task: &Task) -> Option<Self> {
+        let due_at = task.due_at?;
+        let summary = match task.status {
+            TaskStatus::Done => format!("Done: {}", task.title),
+            _ => format!("Due: {}", task.title),
+        };
+
+        Some(Self {
+            uid: format!("task-{}@circa", task.id),
+            summary,
+            location: None,
+            description: Some(task.description.clone()).filter(|d| !d.is_empty()),
+            starts_at: due_at,
+            ends_at: None,
+            updated_at: task.updated_at,
+            category: "Task".to_string(),
+        })
+    }
+}
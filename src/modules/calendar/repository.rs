@@ -0,0 +1,119 @@
+use super::entity::{ActiveModel, Column, Entity as CalendarFeedEntity, Kind};
+use super::models::{CalendarFeed, FeedKind};
+use crate::error::AppError;
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct CalendarRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl CalendarRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_by_user(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<CalendarFeed>, AppError> {
+        let models = CalendarFeedEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Kind)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_by_token(&self, token: &str) -> Result<Option<CalendarFeed>, AppError> {
+        let model = CalendarFeedEntity::find()
+            .filter(Column::Token.eq(token))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // drops the old token in the same transaction, so there is never more than one live link
+    pub async fn regenerate(
+        &self,
+        event_id: &str,
+        user_id: &str,
+        kind: FeedKind,
+    ) -> Result<CalendarFeed, AppError> {
+        let kind = Kind::from(kind);
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        CalendarFeedEntity::delete_many()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Kind.eq(kind.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let new_feed = ActiveModel {
+            event_id: Set(event_id.to_string()),
+            user_id: Set(user_id.to_string()),
+            kind: Set(kind),
+            token: Set(new_token()),
+            created_at: Set(chrono::Utc::now()),
+        };
+        let result = new_feed
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    pub async fn revoke(
+        &self,
+        event_id: &str,
+        user_id: &str,
+        kind: FeedKind,
+    ) -> Result<bool, AppError> {
+        let result = CalendarFeedEntity::delete_many()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Kind.eq(Kind::from(kind)))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    // every feed of the user, across all events
+    pub async fn revoke_all(&self, user_id: &str) -> Result<(), AppError> {
+        CalendarFeedEntity::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+}
+
+// 244 random bits, hex so it sits in a url as is
+fn new_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
@@ -0,0 +1,72 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::calendar::models::FeedKind;
+use crate::modules::calendar::service::CalendarService;
+use actix_web::http::header::ContentType;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    // calendar apps can't send a bearer token, the feed itself is public behind its secret
+    cfg.service(web::resource("/calendar/{token}.ics").route(web::get().to(get_feed)))
+        .service(
+            web::scope("/calendar")
+                .wrap(auth_middleware)
+                .route("/feeds", web::get().to(get_feeds))
+                .route("/feeds/{kind}", web::post().to(regenerate_feed))
+                .route("/feeds/{kind}", web::delete().to(revoke_feed)),
+        );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_feed(
+    service: web::Data<CalendarService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let body = service.render_feed(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentType("text/calendar; charset=utf-8".parse().unwrap()))
+        .body(body))
+}
+
+async fn get_feeds(
+    req: HttpRequest,
+    service: web::Data<CalendarService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let feeds = service.get_feeds(&claims).await?;
+    Ok(HttpResponse::Ok().json(feeds))
+}
+
+async fn regenerate_feed(
+    req: HttpRequest,
+    service: web::Data<CalendarService>,
+    path: web::Path<FeedKind>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let feed = service.regenerate_feed(path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(feed))
+}
+
+async fn revoke_feed(
+    req: HttpRequest,
+    service: web::Data<CalendarService>,
+    path: web::Path<FeedKind>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service.revoke_feed(path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("Calendar feed revoked"))
+}
@@ -0,0 +1,122 @@
+use super::ics;
+use super::models::{CalendarEvent, CalendarFeed, FeedKind};
+use super::repository::CalendarRepository;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::planner::models::{ShiftFilter, TaskFilter};
+use crate::planner::repository::PlannerRepository;
+use crate::user::models::{UserRole, UserStatus};
+use crate::user::repository::UserRepository;
+use chrono::{Duration, Utc};
+
+// how far back feeds reach, older shifts are of no use on a phone
+const HISTORY_DAYS: i64 = 30;
+
+pub struct CalendarService {
+    repository: CalendarRepository,
+    planner: PlannerRepository,
+    users: UserRepository,
+}
+
+impl CalendarService {
+    pub fn new(
+        repository: CalendarRepository,
+        planner: PlannerRepository,
+        users: UserRepository,
+    ) -> Self {
+        Self {
+            repository,
+            planner,
+            users,
+        }
+    }
+
+    pub async fn get_feeds(&self, claims: &Claims) -> Result<Vec<CalendarFeed>, AppError> {
+        self.repository
+            .find_by_user(&claims.event, &claims.sub)
+            .await
+    }
+
+    // creates the feed on first use, afterwards every call revokes the previous link
+    pub async fn regenerate_feed(
+        &self,
+        kind: FeedKind,
+        claims: &Claims,
+    ) -> Result<CalendarFeed, AppError> {
+        if kind == FeedKind::Event && !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository
+            .regenerate(&claims.event, &claims.sub, kind)
+            .await
+    }
+
+    pub async fn revoke_feed(&self, kind: FeedKind, claims: &Claims) -> Result<(), AppError> {
+        if !self
+            .repository
+            .revoke(&claims.event, &claims.sub, kind)
+            .await?
+        {
+            return Err(AppError::NotFound("Calendar feed not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // no claims here, the token is the credential. unknown tokens, inactive owners, people who
+    // left the event and event feeds of people who are no longer organizers all look the same
+    pub async fn render_feed(&self, token: &str) -> Result<String, AppError> {
+        let not_found = || AppError::NotFound("Calendar not found".to_string());
+
+        let feed = self
+            .repository
+            .find_by_token(token)
+            .await?
+            .ok_or_else(not_found)?;
+        let owner = self
+            .users
+            .find_by_id(&feed.event_id, &feed.user_id)
+            .await?
+            .filter(|u| u.status == UserStatus::Active)
+            .ok_or_else(not_found)?;
+
+        let assignee = match feed.kind {
+            FeedKind::Personal => Some(owner.id.clone()),
+            FeedKind::Event if matches!(owner.role, UserRole::Admin | UserRole::Organizer) => None,
+            FeedKind::Event => return Err(not_found()),
+        };
+
+        let shifts = self
+            .planner
+            .find_shifts(
+                &feed.event_id,
+                &ShiftFilter {
+                    assignee: assignee.clone(),
+                    from: Some(Utc::now() - Duration::days(HISTORY_DAYS)),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        let tasks = self
+            .planner
+            .find_tasks(
+                &feed.event_id,
+                &TaskFilter {
+                    assignee,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let mut events: Vec<CalendarEvent> = shifts.iter().map(CalendarEvent::from).collect();
+        events.extend(tasks.iter().filter_map(CalendarEvent::from_task));
+
+        let name = match feed.kind {
+            FeedKind::Personal => format!("Circa: {} {}", owner.name, owner.surname),
+            FeedKind::Event => "Circa: all shifts and tasks".to_string(),
+        };
+
+        Ok(ics::render(&name, &events))
+    }
+}
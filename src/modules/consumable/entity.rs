@@ -0,0 +1,31 @@
+// every change to the stock level is kept in its own table
+pub mod movement;
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "consumables")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub event_id: String,
+    pub name: String,
+    pub category: String,
+    // what the stock is counted in, e.g. "bottles" or "packs"
+    pub unit: String,
+    // kept in step with the movements, never changed directly
+    pub stock: i32,
+    // at or below this it shows up on the low-stock report
+    pub reorder_threshold: i32,
+    pub location_id: Option<String>,
+    pub notes: String,
+    pub created_by: String,
+    pub created_at: ChronoDateTimeUtc,
+    pub updated_at: ChronoDateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,35 @@
+use derive_more::Display;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::StringLen;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Kind {
+    #[sea_orm(string_value = "received")]
+    Received,
+    #[sea_orm(string_value = "issued")]
+    Issued,
+    #[sea_orm(string_value = "adjusted")]
+    Adjusted,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "stock_movements")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub consumable_id: String,
+    pub kind: Kind,
+    // signed, issued stock goes out as a negative change
+    pub change: i32,
+    pub stock_after: i32,
+    pub reason: String,
+    pub moved_by: String,
+    pub moved_at: ChronoDateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
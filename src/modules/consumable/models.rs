@@ -0,0 +1,153 @@
+use super::entity::{self, movement};
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MovementKind {
+    #[display("Received")]
+    Received,
+    #[display("Issued")]
+    Issued,
+    #[display("Adjusted")]
+    Adjusted,
+}
+
+impl MovementKind {
+    // received and issued take a positive quantity, adjustments are signed
+    pub fn change_for(&self, quantity: i32) -> i32 {
+        match self {
+            MovementKind::Received | MovementKind::Adjusted => quantity,
+            MovementKind::Issued => -quantity,
+        }
+    }
+}
+
+impl From<MovementKind> for movement::Kind {
+    fn from(item: MovementKind) -> Self {
+        match item {
+            MovementKind::Received => movement::Kind::Received,
+            MovementKind::Issued => movement::Kind::Issued,
+            MovementKind::Adjusted => movement::Kind::Adjusted,
+        }
+    }
+}
+
+impl From<movement::Kind> for MovementKind {
+    fn from(item: movement::Kind) -> Self {
+        match item {
+            movement::Kind::Received => MovementKind::Received,
+            movement::Kind::Issued => MovementKind::Issued,
+            movement::Kind::Adjusted => MovementKind::Adjusted,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Consumable {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub unit: String,
+    pub stock: i32,
+    pub reorder_threshold: u32,
+    // at or below the reorder threshold
+    pub low_stock: bool,
+    pub location_id: Option<String>,
+    pub notes: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<entity::Model> for Consumable {
+    fn from(model: entity::Model) -> Self {
+        let reorder_threshold = model.reorder_threshold.max(0) as u32;
+        Self {
+            id: model.id,
+            name: model.name,
+            category: model.category,
+            unit: model.unit,
+            stock: model.stock,
+            reorder_threshold,
+            low_stock: i64::from(model.stock) <= i64::from(reorder_threshold),
+            location_id: model.location_id,
+            notes: model.notes,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StockMovement {
+    pub id: String,
+    pub consumable_id: String,
+    pub kind: MovementKind,
+    pub change: i32,
+    pub stock_after: u32,
+    pub reason: String,
+    pub moved_by: String,
+    pub moved_at: DateTime<Utc>,
+}
+
+impl From<movement::Model> for StockMovement {
+    fn from(model: movement::Model) -> Self {
+        Self {
+            id: model.id,
+            consumable_id: model.consumable_id,
+            kind: model.kind.into(),
+            change: model.change,
+            stock_after: model.stock_after.max(0) as u32,
+            reason: model.reason,
+            moved_by: model.moved_by,
+            moved_at: model.moved_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CreateConsumableRequest {
+    pub name: String,
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub unit: String,
+    // what is on hand to begin with, recorded as received
+    #[serde(default)]
+    pub stock: u32,
+    #[serde(default)]
+    pub reorder_threshold: u32,
+    #[serde(default)]
+    pub location_id: Option<String>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+// the stock level only changes through movements
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateConsumableRequest {
+    pub name: Option<String>,
+    pub category: Option<String>,
+    pub unit: Option<String>,
+    pub reorder_threshold: Option<u32>,
+    pub location_id: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecordMovementRequest {
+    pub kind: MovementKind,
+    pub quantity: i32,
+    // required for adjustments
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct ConsumableFilter {
+    pub category: Option<String>,
+    pub location_id: Option<String>,
+}
@@ -0,0 +1,287 @@
+use super::entity::{ActiveModel, Column, Entity as ConsumableEntity, movement};
+use super::models::{
+    Consumable, ConsumableFilter, CreateConsumableRequest, MovementKind, StockMovement,
+    UpdateConsumableRequest,
+};
+use crate::error::AppError;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct ConsumableRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ConsumableRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_consumables(
+        &self,
+        event_id: &str,
+        filter: &ConsumableFilter,
+    ) -> Result<Vec<Consumable>, AppError> {
+        let mut select = ConsumableEntity::find().filter(Column::EventId.eq(event_id));
+
+        if let Some(category) = &filter.category {
+            select = select.filter(Column::Category.eq(category.as_str()));
+        }
+        if let Some(location_id) = &filter.location_id {
+            select = select.filter(Column::LocationId.eq(location_id.as_str()));
+        }
+
+        let models = select
+            .order_by_asc(Column::Category)
+            .order_by_asc(Column::Name)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    // everything at or below its reorder threshold
+    pub async fn find_low_stock(&self, event_id: &str) -> Result<Vec<Consumable>, AppError> {
+        let models = ConsumableEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Expr::col(Column::Stock).lte(Expr::col(Column::ReorderThreshold)))
+            .order_by_asc(Column::Category)
+            .order_by_asc(Column::Name)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_consumable(
+        &self,
+        event_id: &str,
+        id: &str,
+    ) -> Result<Option<Consumable>, AppError> {
+        let model = ConsumableEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // the starting stock goes into the movements right away
+    pub async fn create_consumable(
+        &self,
+        event_id: &str,
+        dto: CreateConsumableRequest,
+        created_by: &str,
+    ) -> Result<Consumable, AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let consumable = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            name: Set(dto.name),
+            category: Set(dto.category),
+            unit: Set(dto.unit),
+            stock: Set(dto.stock as i32),
+            reorder_threshold: Set(dto.reorder_threshold as i32),
+            location_id: Set(dto.location_id),
+            notes: Set(dto.notes),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        if dto.stock > 0 {
+            movement::ActiveModel {
+                id: Set(uuid::Uuid::now_v7().to_string()),
+                consumable_id: Set(consumable.id.clone()),
+                kind: Set(movement::Kind::Received),
+                change: Set(dto.stock as i32),
+                stock_after: Set(dto.stock as i32),
+                reason: Set("Starting stock".to_string()),
+                moved_by: Set(created_by.to_string()),
+                moved_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(consumable.into())
+    }
+
+    pub async fn update_consumable(
+        &self,
+        id: &str,
+        dto: UpdateConsumableRequest,
+    ) -> Result<Consumable, AppError> {
+        let model = ConsumableEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Consumable not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(name) = dto.name {
+            active_model.name = Set(name);
+        }
+        if let Some(category) = dto.category {
+            active_model.category = Set(category);
+        }
+        if let Some(unit) = dto.unit {
+            active_model.unit = Set(unit);
+        }
+        if let Some(reorder_threshold) = dto.reorder_threshold {
+            active_model.reorder_threshold = Set(reorder_threshold as i32);
+        }
+        if let Some(location_id) = dto.location_id {
+            active_model.location_id = Set(Some(location_id));
+        }
+        if let Some(notes) = dto.notes {
+            active_model.notes = Set(notes);
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    // the movements go with it
+    pub async fn delete_consumable(&self, event_id: &str, id: &str) -> Result<(), AppError> {
+        let result = ConsumableEntity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::EventId.eq(event_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Consumable not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // the new level and its movement are written together, starting from the stored level
+    pub async fn record_movement(
+        &self,
+        consumable_id: &str,
+        kind: MovementKind,
+        change: i32,
+        reason: String,
+        moved_by: &str,
+    ) -> Result<StockMovement, AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = ConsumableEntity::find_by_id(consumable_id.to_string())
+            .one(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Consumable not found".to_string()))?;
+
+        // checked inside the transaction so two movements can't both take the last units
+        let stock_after = model
+            .stock
+            .checked_add(change)
+            .ok_or_else(|| AppError::BadRequest(format!("Stock can't go above {}", i32::MAX)))?;
+        if stock_after < 0 {
+            return Err(AppError::BadRequest(format!(
+                "Only {} left in stock",
+                model.stock
+            )));
+        }
+
+        let mut active_model: ActiveModel = model.into();
+        active_model.stock = Set(stock_after);
+        active_model.updated_at = Set(now);
+        active_model
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let movement = movement::ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            consumable_id: Set(consumable_id.to_string()),
+            kind: Set(kind.into()),
+            change: Set(change),
+            stock_after: Set(stock_after),
+            reason: Set(reason),
+            moved_by: Set(moved_by.to_string()),
+            moved_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(movement.into())
+    }
+
+    // oldest first, so it reads like a timeline
+    pub async fn find_movements(
+        &self,
+        consumable_id: &str,
+    ) -> Result<Vec<StockMovement>, AppError> {
+        let models = movement::Entity::find()
+            .filter(movement::Column::ConsumableId.eq(consumable_id))
+            .order_by_asc(movement::Column::MovedAt)
+            .order_by_asc(movement::Column::Id)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    // stock someone moved in or out of the event's consumables
+    pub async fn find_movements_by(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<StockMovement>, AppError> {
+        let models = movement::Entity::find()
+            .filter(movement::Column::MovedBy.eq(user_id))
+            .filter(
+                movement::Column::ConsumableId.in_subquery(
+                    sea_query::Query::select()
+                        .column(Column::Id)
+                        .from(ConsumableEntity)
+                        .and_where(Column::EventId.eq(event_id))
+                        .to_owned(),
+                ),
+            )
+            .order_by_asc(movement::Column::MovedAt)
+            .order_by_asc(movement::Column::Id)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+}
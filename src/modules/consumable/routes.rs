@@ -0,0 +1,130 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::consumable::models::{
+    ConsumableFilter, CreateConsumableRequest, RecordMovementRequest, UpdateConsumableRequest,
+};
+use crate::modules::consumable::service::ConsumableService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/consumables")
+            .wrap(auth_middleware)
+            .route("", web::get().to(get_consumables))
+            .route("", web::post().to(create_consumable))
+            .route("/low-stock", web::get().to(get_low_stock))
+            .route("/{id}", web::get().to(get_consumable))
+            .route("/{id}", web::patch().to(update_consumable))
+            .route("/{id}", web::delete().to(delete_consumable))
+            .route("/{id}/movements", web::get().to(get_movements))
+            .route("/{id}/movements", web::post().to(record_movement)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_consumables(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+    query: web::Query<ConsumableFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let consumables = service.get_consumables(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(consumables))
+}
+
+async fn get_low_stock(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let consumables = service.get_low_stock(&claims).await?;
+    Ok(HttpResponse::Ok().json(consumables))
+}
+
+async fn get_consumable(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let consumable = service.get_consumable(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(consumable))
+}
+
+async fn create_consumable(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+    body: web::Json<CreateConsumableRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let consumable = service
+        .create_consumable(body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(consumable))
+}
+
+async fn update_consumable(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateConsumableRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let consumable = service
+        .update_consumable(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(consumable))
+}
+
+async fn delete_consumable(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service
+        .delete_consumable(&path.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().body("Consumable deleted successfully"))
+}
+
+async fn get_movements(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let movements = service.get_movements(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(movements))
+}
+
+async fn record_movement(
+    req: HttpRequest,
+    service: web::Data<ConsumableService>,
+    path: web::Path<String>,
+    body: web::Json<RecordMovementRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let movement = service
+        .record_movement(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(movement))
+}
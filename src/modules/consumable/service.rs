@@ -0,0 +1,191 @@
+use super::models::{
+    Consumable, ConsumableFilter, CreateConsumableRequest, MovementKind, RecordMovementRequest,
+    StockMovement, UpdateConsumableRequest,
+};
+use super::repository::ConsumableRepository;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::location::repository::LocationRepository;
+
+pub struct ConsumableService {
+    repository: ConsumableRepository,
+    locations: LocationRepository,
+}
+
+impl ConsumableService {
+    pub fn new(repository: ConsumableRepository, locations: LocationRepository) -> Self {
+        Self {
+            repository,
+            locations,
+        }
+    }
+
+    pub async fn get_consumables(
+        &self,
+        filter: ConsumableFilter,
+        claims: &Claims,
+    ) -> Result<Vec<Consumable>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository
+            .find_consumables(&claims.event, &filter)
+            .await
+    }
+
+    pub async fn get_consumable(&self, id: &str, claims: &Claims) -> Result<Consumable, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.find_consumable(id, claims).await
+    }
+
+    // what needs reordering, for whoever does the ordering
+    pub async fn get_low_stock(&self, claims: &Claims) -> Result<Vec<Consumable>, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.find_low_stock(&claims.event).await
+    }
+
+    pub async fn create_consumable(
+        &self,
+        req: CreateConsumableRequest,
+        claims: &Claims,
+    ) -> Result<Consumable, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let name = req.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+
+        let req = CreateConsumableRequest {
+            name,
+            category: req.category.trim().to_string(),
+            unit: req.unit.trim().to_string(),
+            ..req
+        };
+        self.repository
+            .create_consumable(&claims.event, req, &claims.sub)
+            .await
+    }
+
+    pub async fn update_consumable(
+        &self,
+        id: &str,
+        req: UpdateConsumableRequest,
+        claims: &Claims,
+    ) -> Result<Consumable, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.find_consumable(id, claims).await?;
+
+        let name = req.name.map(|n| n.trim().to_string());
+        if name.as_deref() == Some("") {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+
+        let req = UpdateConsumableRequest {
+            name,
+            category: req.category.map(|c| c.trim().to_string()),
+            unit: req.unit.map(|u| u.trim().to_string()),
+            ..req
+        };
+        self.repository.update_consumable(id, req).await
+    }
+
+    pub async fn delete_consumable(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.delete_consumable(&claims.event, id).await
+    }
+
+    pub async fn get_movements(
+        &self,
+        id: &str,
+        claims: &Claims,
+    ) -> Result<Vec<StockMovement>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.find_consumable(id, claims).await?;
+        self.repository.find_movements(id).await
+    }
+
+    // staff hand things out and take deliveries in, so they record movements too
+    pub async fn record_movement(
+        &self,
+        id: &str,
+        req: RecordMovementRequest,
+        claims: &Claims,
+    ) -> Result<StockMovement, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        let consumable = self.find_consumable(id, claims).await?;
+        let reason = req.reason.trim().to_string();
+        match req.kind {
+            MovementKind::Received | MovementKind::Issued if req.quantity <= 0 => {
+                return Err(AppError::BadRequest(
+                    "Quantity must be at least 1".to_string(),
+                ));
+            }
+            MovementKind::Adjusted if req.quantity == 0 => {
+                return Err(AppError::BadRequest(
+                    "Adjustments can't be zero".to_string(),
+                ));
+            }
+            MovementKind::Adjusted if reason.is_empty() => {
+                return Err(AppError::BadRequest(
+                    "Adjustments need a reason".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        let change = req.kind.change_for(req.quantity);
+        self.repository
+            .record_movement(&consumable.id, req.kind, change, reason, &claims.sub)
+            .await
+    }
+
+    async fn check_location(
+        &self,
+        location_id: Option<&str>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if let Some(location_id) = location_id
+            && !self.locations.exists(&claims.event, location_id).await?
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unknown location '{}'",
+                location_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_consumable(&self, id: &str, claims: &Claims) -> Result<Consumable, AppError> {
+        self.repository
+            .find_consumable(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Consumable not found".to_string()))
+    }
+}
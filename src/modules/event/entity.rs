@@ -0,0 +1,44 @@
+// memberships live in their own table, they carry the person's role at this event
+pub mod member;
+
+use derive_more::Display;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::StringLen;
+use serde::{Deserialize, Serialize};
+
+// only ever moves forward, one step at a time
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Status {
+    #[sea_orm(string_value = "draft")]
+    Draft,
+    #[sea_orm(string_value = "published")]
+    Published,
+    #[sea_orm(string_value = "live")]
+    Live,
+    #[sea_orm(string_value = "wrapped_up")]
+    WrappedUp,
+    #[sea_orm(string_value = "archived")]
+    Archived,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "events")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    #[sea_orm(unique)]
+    pub name: String,
+    pub starts_at: ChronoDateTimeUtc,
+    pub ends_at: ChronoDateTimeUtc,
+    pub status: Status,
+    // JSON wrap-up report, written when the event wraps up
+    pub report: Option<String>,
+    pub created_at: ChronoDateTimeUtc,
+    pub updated_at: ChronoDateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
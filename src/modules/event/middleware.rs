@@ -0,0 +1,95 @@
+use super::service::EventService;
+use crate::auth::models::Claims;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Uri;
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, web};
+
+// picks the active event, has to match the event the token was issued for
+pub const EVENT_HEADER: &str = "x-event-id";
+
+// sub-routes of /events/{id} that belong to the event module itself
+const EVENT_ROUTES: &[&str] = &["token", "status", "report"];
+
+// still writable once an event is archived, matched segment by segment with * for any one.
+// the event routes check for themselves, and people keep their privacy rights after an event
+const ARCHIVE_EXEMPT: &[&[&str]] = &[
+    &["events"],
+    &["backups"],
+    &["users", "*", "erase"],
+    &["users", "*", "data-export"],
+];
+
+// /events/{id}/planner/shifts is served as /planner/shifts with the event in the header,
+// so every module gets the path form without knowing about it
+pub async fn event_path(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    if let Some((event_id, rest)) = split_event_path(req.path()) {
+        let target = match req.uri().query() {
+            Some(query) => format!("{}?{}", rest, query),
+            None => rest,
+        };
+
+        if let (Ok(uri), Ok(value)) = (target.parse::<Uri>(), HeaderValue::from_str(&event_id)) {
+            req.match_info_mut().get_mut().update(&uri);
+            req.head_mut().uri = uri;
+            req.headers_mut()
+                .insert(HeaderName::from_static(EVENT_HEADER), value);
+        }
+    }
+
+    next.call(req).await
+}
+
+// the event id and the rest of the path, None when the path isn't scoped to an event
+pub fn split_event_path(path: &str) -> Option<(String, String)> {
+    let scoped = path.strip_prefix("/events/")?;
+    let (event_id, rest) = scoped.split_once('/')?;
+    let module = rest.split('/').next().unwrap_or_default();
+
+    if event_id.is_empty() || module.is_empty() || EVENT_ROUTES.contains(&module) {
+        return None;
+    }
+
+    Some((event_id.to_string(), format!("/{}", rest)))
+}
+
+// the event a request asks for, None leaves it to the token
+pub fn requested_event(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(EVENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn is_archive_exempt(path: &str) -> bool {
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+
+    ARCHIVE_EXEMPT.iter().any(|pattern| {
+        segments.len() >= pattern.len()
+            && pattern
+                .iter()
+                .zip(&segments)
+                .all(|(want, got)| *want == "*" || want == got)
+    })
+}
+
+// reads always go through, anything else is refused once the token's event is archived
+pub async fn check_writable(req: &ServiceRequest, claims: &Claims) -> Result<(), Error> {
+    if req.method().is_safe() || is_archive_exempt(req.path()) {
+        return Ok(());
+    }
+
+    match req.app_data::<web::Data<EventService>>() {
+        Some(events) => events
+            .ensure_writable(&claims.event)
+            .await
+            .map_err(Error::from),
+        None => Ok(()),
+    }
+}
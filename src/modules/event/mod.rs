@@ -0,0 +1,6 @@
+pub mod entity;
+pub mod middleware;
+pub mod models;
+pub mod repository;
+pub mod routes;
+pub mod service;
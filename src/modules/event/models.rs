@@ -0,0 +1,169 @@
+use super::entity;
+use crate::user::models::UserRole;
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventStatus {
+    #[display("Draft")]
+    Draft,
+    #[display("Published")]
+    Published,
+    #[display("Live")]
+    Live,
+    #[display("Wrapped up")]
+    WrappedUp,
+    #[display("Archived")]
+    Archived,
+}
+
+impl EventStatus {
+    // the only state an event can move on to, None once archived
+    pub fn next(&self) -> Option<EventStatus> {
+        match self {
+            EventStatus::Draft => Some(EventStatus::Published),
+            EventStatus::Published => Some(EventStatus::Live),
+            EventStatus::Live => Some(EventStatus::WrappedUp),
+            EventStatus::WrappedUp => Some(EventStatus::Archived),
+            EventStatus::Archived => None,
+        }
+    }
+}
+
+impl From<EventStatus> for entity::Status {
+    fn from(item: EventStatus) -> Self {
+        match item {
+            EventStatus::Draft => entity::Status::Draft,
+            EventStatus::Published => entity::Status::Published,
+            EventStatus::Live => entity::Status::Live,
+            EventStatus::WrappedUp => entity::Status::WrappedUp,
+            EventStatus::Archived => entity::Status::Archived,
+        }
+    }
+}
+
+impl From<entity::Status> for EventStatus {
+    fn from(item: entity::Status) -> Self {
+        match item {
+            entity::Status::Draft => EventStatus::Draft,
+            entity::Status::Published => EventStatus::Published,
+            entity::Status::Live => EventStatus::Live,
+            entity::Status::WrappedUp => EventStatus::WrappedUp,
+            entity::Status::Archived => EventStatus::Archived,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EventMember {
+    pub event_id: String,
+    pub user_id: String,
+    pub role: UserRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl From<entity::member::Model> for EventMember {
+    fn from(model: entity::member::Model) -> Self {
+        Self {
+            event_id: model.event_id,
+            user_id: model.user_id,
+            role: model.role.into(),
+            joined_at: model.joined_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Event {
+    pub id: String,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub status: EventStatus,
+    // the caller's role there, only set when listing their own events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<UserRole>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<entity::Model> for Event {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            starts_at: model.starts_at,
+            ends_at: model.ends_at,
+            status: model.status.into(),
+            role: None,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateEventRequest {
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateEventRequest {
+    pub name: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangeStatusRequest {
+    pub status: EventStatus,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ConfirmQuery {
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+// a snapshot of how the event went, taken when it wraps up
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WrapUpReport {
+    pub event_id: String,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub people: PeopleSummary,
+    pub shifts: ShiftSummary,
+    pub tasks: TaskSummary,
+    pub teams: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PeopleSummary {
+    pub admins: u64,
+    pub organizers: u64,
+    pub staff: u64,
+    pub volunteers: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ShiftSummary {
+    pub total: u64,
+    // headcount asked for across all shifts, and how much of it was covered
+    pub slots: u64,
+    pub filled: u64,
+    // summed over everyone assigned
+    pub staffed_hours: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct TaskSummary {
+    pub total: u64,
+    pub done: u64,
+    pub open: u64,
+}
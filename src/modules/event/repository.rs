@@ -0,0 +1,265 @@
+use super::entity::{ActiveModel, Column, Entity as EventEntity, Status, member};
+use super::models::{
+    CreateEventRequest, Event, EventMember, EventStatus, UpdateEventRequest, WrapUpReport,
+};
+use crate::error::AppError;
+use crate::user::entity as user;
+use crate::user::models::{UserRole, normalize_email};
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct EventRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl EventRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<Event>, AppError> {
+        let models = EventEntity::find()
+            .order_by_asc(Column::StartsAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_by_id(&self, id: &str) -> Result<Option<Event>, AppError> {
+        let model = EventEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<Event>, AppError> {
+        let model = EventEntity::find()
+            .filter(Column::Name.eq(name))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // events the user is part of, each with their role there
+    pub async fn find_by_member(&self, user_id: &str) -> Result<Vec<Event>, AppError> {
+        let memberships = self.find_memberships(user_id).await?;
+        if memberships.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let models = EventEntity::find()
+            .filter(Column::Id.is_in(memberships.iter().map(|m| m.event_id.clone())))
+            .order_by_asc(Column::StartsAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| {
+                let role = memberships
+                    .iter()
+                    .find(|membership| membership.event_id == m.id)
+                    .map(|membership| membership.role.clone());
+                Event { role, ..m.into() }
+            })
+            .collect())
+    }
+
+    // the creator, if any, joins as admin in the same transaction
+    pub async fn create(
+        &self,
+        dto: CreateEventRequest,
+        admin_id: Option<&str>,
+    ) -> Result<Event, AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let event = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            name: Set(dto.name),
+            starts_at: Set(dto.starts_at),
+            ends_at: Set(dto.ends_at),
+            status: Set(Status::Draft),
+            report: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        if let Some(admin_id) = admin_id {
+            member::ActiveModel {
+                event_id: Set(event.id.clone()),
+                user_id: Set(admin_id.to_string()),
+                role: Set(user::Role::Admin),
+                joined_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let role = admin_id.map(|_| UserRole::Admin);
+        Ok(Event {
+            role,
+            ..event.into()
+        })
+    }
+
+    pub async fn update(&self, id: &str, dto: UpdateEventRequest) -> Result<Event, AppError> {
+        let model = EventEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(name) = dto.name {
+            active_model.name = Set(name);
+        }
+        if let Some(starts_at) = dto.starts_at {
+            active_model.starts_at = Set(starts_at);
+        }
+        if let Some(ends_at) = dto.ends_at {
+            active_model.ends_at = Set(ends_at);
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    // the report is only ever written once, on the way to wrapped up
+    pub async fn set_status(
+        &self,
+        id: &str,
+        status: EventStatus,
+        report: Option<&WrapUpReport>,
+    ) -> Result<Event, AppError> {
+        let model = EventEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        active_model.status = Set(status.into());
+        if let Some(report) = report {
+            let json = serde_json::to_string(report).map_err(|_| AppError::InternalServerError)?;
+            active_model.report = Set(Some(json));
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    pub async fn find_report(&self, id: &str) -> Result<Option<WrapUpReport>, AppError> {
+        let model = EventEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        match model.and_then(|m| m.report) {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|_| AppError::InternalServerError),
+            None => Ok(None),
+        }
+    }
+
+    // everything scoped to the event goes with it, audit entries stay
+    pub async fn delete(&self, id: &str) -> Result<(), AppError> {
+        let result = EventEntity::delete_by_id(id.to_string())
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Event not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn find_members(&self, event_id: &str) -> Result<Vec<EventMember>, AppError> {
+        let models = member::Entity::find()
+            .filter(member::Column::EventId.eq(event_id))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_membership(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Option<EventMember>, AppError> {
+        let model = member::Entity::find_by_id((event_id.to_string(), user_id.to_string()))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // newest first, that's the event people land in when they don't pick one
+    pub async fn find_memberships(&self, user_id: &str) -> Result<Vec<EventMember>, AppError> {
+        let models = member::Entity::find()
+            .filter(member::Column::UserId.eq(user_id))
+            .order_by_desc(member::Column::JoinedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_memberships_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Vec<EventMember>, AppError> {
+        let models = member::Entity::find()
+            .filter(
+                member::Column::UserId.in_subquery(
+                    sea_query::Query::select()
+                        .column(user::Column::Id)
+                        .from(user::Entity)
+                        .and_where(user::Column::Email.eq(normalize_email(email)))
+                        .to_owned(),
+                ),
+            )
+            .order_by_desc(member::Column::JoinedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+}
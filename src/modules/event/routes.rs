@@ -0,0 +1,146 @@
+use crate::auth::models::Claims;
+use crate::auth::service::generate_jwt;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::event::models::{
+    ChangeStatusRequest, ConfirmQuery, CreateEventRequest, UpdateEventRequest,
+};
+use crate::modules::event::service::EventService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/events")
+            .wrap(auth_middleware)
+            .route("", web::get().to(get_events))
+            .route("", web::post().to(create_event))
+            .route("/{id}", web::get().to(get_event))
+            .route("/{id}", web::patch().to(update_event))
+            .route("/{id}", web::delete().to(delete_event))
+            .route("/{id}/status", web::post().to(change_status))
+            .route("/{id}/report", web::get().to(get_report))
+            .route("/{id}/token", web::post().to(switch_event)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_events(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let events = service.get_events(&claims).await?;
+    Ok(HttpResponse::Ok().json(events))
+}
+
+async fn get_event(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let event = service.get_event(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(event))
+}
+
+async fn create_event(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+    body: web::Json<CreateEventRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let event = service.create_event(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(event))
+}
+
+async fn update_event(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateEventRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let event = service
+        .update_event(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(event))
+}
+
+async fn change_status(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+    path: web::Path<String>,
+    query: web::Query<ConfirmQuery>,
+    body: web::Json<ChangeStatusRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let event = service
+        .change_status(
+            &path.into_inner(),
+            body.into_inner(),
+            query.confirm,
+            &claims,
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(event))
+}
+
+async fn delete_event(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+    path: web::Path<String>,
+    query: web::Query<ConfirmQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service
+        .delete_event(&path.into_inner(), query.confirm, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().body("Event deleted successfully"))
+}
+
+async fn get_report(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let report = service.get_report(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+// a fresh token for another event the caller is part of, carrying their role there
+async fn switch_event(
+    req: HttpRequest,
+    service: web::Data<EventService>,
+    path: web::Path<String>,
+    jwt_secret: web::Data<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let membership = service.membership(&path.into_inner(), &claims.sub).await?;
+    let token = generate_jwt(
+        &membership.user_id,
+        &membership.event_id,
+        membership.role.as_str(),
+        &jwt_secret,
+    )
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+    Ok(HttpResponse::Ok().json(token))
+}
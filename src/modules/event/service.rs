@@ -0,0 +1,365 @@
+use super::models::{
+    ChangeStatusRequest, CreateEventRequest, Event, EventMember, EventStatus, PeopleSummary,
+    ShiftSummary, TaskSummary, UpdateEventRequest, WrapUpReport,
+};
+use super::repository::EventRepository;
+use crate::audit::models::NewAuditEntry;
+use crate::audit::service::AuditService;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::planner::models::{ShiftFilter, TaskFilter, TaskStatus};
+use crate::planner::repository::PlannerRepository;
+use crate::team::repository::TeamRepository;
+use crate::user::models::UserRole;
+use chrono::{DateTime, Utc};
+
+pub struct EventService {
+    repository: EventRepository,
+    planner: PlannerRepository,
+    teams: TeamRepository,
+    audit: AuditService,
+}
+
+impl EventService {
+    pub fn new(
+        repository: EventRepository,
+        planner: PlannerRepository,
+        teams: TeamRepository,
+        audit: AuditService,
+    ) -> Self {
+        Self {
+            repository,
+            planner,
+            teams,
+            audit,
+        }
+    }
+
+    pub async fn get_events(&self, claims: &Claims) -> Result<Vec<Event>, AppError> {
+        self.repository.find_by_member(&claims.sub).await
+    }
+
+    // events someone isn't part of look the same as ones that don't exist
+    pub async fn get_event(&self, id: &str, claims: &Claims) -> Result<Event, AppError> {
+        let membership = self.membership(id, &claims.sub).await?;
+        let event = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
+
+        Ok(Event {
+            role: Some(membership.role),
+            ..event
+        })
+    }
+
+    // admins of the current event may start another one, they become its first admin
+    pub async fn create_event(
+        &self,
+        req: CreateEventRequest,
+        claims: &Claims,
+    ) -> Result<Event, AppError> {
+        if claims.role != "admin" {
+            return Err(AppError::Forbidden);
+        }
+
+        let req = CreateEventRequest {
+            name: req.name.trim().to_string(),
+            ..req
+        };
+        validate_dates(req.starts_at, req.ends_at)?;
+        self.check_name(&req.name, None).await?;
+
+        self.repository.create(req, Some(&claims.sub)).await
+    }
+
+    // for the cli, which has nobody to make admin yet
+    pub async fn create_unattended(&self, req: CreateEventRequest) -> Result<Event, AppError> {
+        let req = CreateEventRequest {
+            name: req.name.trim().to_string(),
+            ..req
+        };
+        validate_dates(req.starts_at, req.ends_at)?;
+        self.check_name(&req.name, None).await?;
+
+        self.repository.create(req, None).await
+    }
+
+    // only through a token for that very event, the role in it is what counts
+    pub async fn update_event(
+        &self,
+        id: &str,
+        req: UpdateEventRequest,
+        claims: &Claims,
+    ) -> Result<Event, AppError> {
+        if claims.event != id || (claims.role != "admin" && claims.role != "organizer") {
+            return Err(AppError::Forbidden);
+        }
+
+        let current = self.find_own(id).await?;
+        if current.status == EventStatus::Archived {
+            return Err(read_only());
+        }
+
+        let name = req.name.as_deref().map(str::trim);
+        if let Some(name) = name {
+            self.check_name(name, Some(id)).await?;
+        }
+        validate_dates(
+            req.starts_at.unwrap_or(current.starts_at),
+            req.ends_at.unwrap_or(current.ends_at),
+        )?;
+
+        let req = UpdateEventRequest {
+            name: name.map(str::to_string),
+            ..req
+        };
+        self.repository.update(id, req).await
+    }
+
+    // one step forward at a time; archiving is for admins only and has to be confirmed
+    pub async fn change_status(
+        &self,
+        id: &str,
+        req: ChangeStatusRequest,
+        confirm: bool,
+        claims: &Claims,
+    ) -> Result<Event, AppError> {
+        if claims.event != id || (claims.role != "admin" && claims.role != "organizer") {
+            return Err(AppError::Forbidden);
+        }
+        if req.status == EventStatus::Archived {
+            if claims.role != "admin" {
+                return Err(AppError::Forbidden);
+            }
+            if !confirm {
+                return Err(AppError::BadRequest(
+                    "Archived events can't be changed anymore, pass confirm=true to continue"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let current = self.find_own(id).await?;
+        if current.status.next().as_ref() != Some(&req.status) {
+            return Err(AppError::BadRequest(format!(
+                "{} events can't become {}",
+                current.status,
+                req.status.to_string().to_lowercase()
+            )));
+        }
+
+        let report = match req.status {
+            EventStatus::WrappedUp => Some(self.wrap_up_report(&current).await?),
+            _ => None,
+        };
+        let event = self
+            .repository
+            .set_status(id, req.status.clone(), report.as_ref())
+            .await?;
+
+        self.audit
+            .record(NewAuditEntry {
+                event_id: Some(id.to_string()),
+                actor_id: claims.sub.clone(),
+                action: "event.status".to_string(),
+                target_type: "event".to_string(),
+                target_id: Some(id.to_string()),
+                details: Some(serde_json::json!({
+                    "from": current.status,
+                    "to": req.status,
+                })),
+            })
+            .await?;
+
+        Ok(event)
+    }
+
+    // takes every shift, task and team of the event with it
+    pub async fn delete_event(
+        &self,
+        id: &str,
+        confirm: bool,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if claims.event != id || claims.role != "admin" {
+            return Err(AppError::Forbidden);
+        }
+        if !confirm {
+            return Err(AppError::BadRequest(
+                "Deleting an event removes all of its data, pass confirm=true to continue"
+                    .to_string(),
+            ));
+        }
+
+        let event = self.find_own(id).await?;
+        self.repository.delete(id).await?;
+
+        self.audit
+            .record(NewAuditEntry {
+                event_id: Some(id.to_string()),
+                actor_id: claims.sub.clone(),
+                action: "event.delete".to_string(),
+                target_type: "event".to_string(),
+                target_id: Some(id.to_string()),
+                details: Some(serde_json::json!({ "name": event.name })),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_report(&self, id: &str, claims: &Claims) -> Result<WrapUpReport, AppError> {
+        if claims.event != id || (claims.role != "admin" && claims.role != "organizer") {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository
+            .find_report(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("The event hasn't wrapped up yet".to_string()))
+    }
+
+    // archived events can still be read, but nothing in them changes anymore
+    pub async fn ensure_writable(&self, event_id: &str) -> Result<(), AppError> {
+        match self.repository.find_by_id(event_id).await? {
+            Some(event) if event.status == EventStatus::Archived => Err(read_only()),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<Event>, AppError> {
+        self.repository.find_all().await
+    }
+
+    // by id or by name, whichever the caller had at hand
+    pub async fn find(&self, id_or_name: &str) -> Result<Event, AppError> {
+        if let Some(event) = self.repository.find_by_id(id_or_name).await? {
+            return Ok(event);
+        }
+
+        self.repository
+            .find_by_name(id_or_name)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Unknown event '{}'", id_or_name)))
+    }
+
+    pub async fn membership(&self, event_id: &str, user_id: &str) -> Result<EventMember, AppError> {
+        self.repository
+            .find_membership(event_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))
+    }
+
+    // the event to sign in to, the most recently joined one unless asked for another
+    pub async fn sign_in(
+        &self,
+        email: &str,
+        event_id: Option<&str>,
+    ) -> Result<EventMember, AppError> {
+        let memberships = self.repository.find_memberships_by_email(email).await?;
+
+        let membership = match event_id {
+            Some(event_id) => memberships.into_iter().find(|m| m.event_id == event_id),
+            None => memberships.into_iter().next(),
+        };
+        membership.ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    async fn find_own(&self, id: &str) -> Result<Event, AppError> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))
+    }
+
+    async fn wrap_up_report(&self, event: &Event) -> Result<WrapUpReport, AppError> {
+        let mut people = PeopleSummary::default();
+        for member in self.repository.find_members(&event.id).await? {
+            match member.role {
+                UserRole::Admin => people.admins += 1,
+                UserRole::Organizer => people.organizers += 1,
+                UserRole::Staff => people.staff += 1,
+                UserRole::Volunteer => people.volunteers += 1,
+            }
+        }
+
+        let mut shifts = ShiftSummary::default();
+        for shift in self
+            .planner
+            .find_shifts(&event.id, &ShiftFilter::default())
+            .await?
+        {
+            shifts.total += 1;
+            for requirement in &shift.requirements {
+                let assigned = shift
+                    .assignments
+                    .iter()
+                    .filter(|a| a.role == requirement.role)
+                    .count() as u64;
+                shifts.slots += u64::from(requirement.headcount);
+                shifts.filled += assigned.min(u64::from(requirement.headcount));
+            }
+            let hours = (shift.ends_at - shift.starts_at).num_minutes() as f64 / 60.0;
+            shifts.staffed_hours += hours * shift.assignments.len() as f64;
+        }
+
+        let mut tasks = TaskSummary::default();
+        for task in self
+            .planner
+            .find_tasks(&event.id, &TaskFilter::default())
+            .await?
+        {
+            tasks.total += 1;
+            match task.status {
+                TaskStatus::Done => tasks.done += 1,
+                _ => tasks.open += 1,
+            }
+        }
+
+        let teams = self.teams.find_all(&event.id).await?.len() as u64;
+
+        Ok(WrapUpReport {
+            event_id: event.id.clone(),
+            name: event.name.clone(),
+            starts_at: event.starts_at,
+            ends_at: event.ends_at,
+            generated_at: Utc::now(),
+            people,
+            shifts,
+            tasks,
+            teams,
+        })
+    }
+
+    async fn check_name(&self, name: &str, id: Option<&str>) -> Result<(), AppError> {
+        if name.is_empty() {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+
+        if let Some(existing) = self.repository.find_by_name(name).await?
+            && Some(existing.id.as_str()) != id
+        {
+            return Err(AppError::BadRequest(
+                "An event with this name already exists".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_dates(starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> Result<(), AppError> {
+    if ends_at <= starts_at {
+        return Err(AppError::BadRequest(
+            "Event must end after it starts".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn read_only() -> AppError {
+    AppError::BadRequest("Archived events are read-only".to_string())
+}
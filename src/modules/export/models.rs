@@ -0,0 +1,240 @@
+use super::entity;
+use crate::error::AppError;
+use crate::user::models::User;
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[display("csv")]
+    Csv,
+    #[display("jsonl")]
+    Jsonl,
+    #[display("xlsx")]
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Jsonl => "application/x-ndjson",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "xlsx" => Ok(ExportFormat::Xlsx),
+            other => Err(AppError::BadRequest(format!(
+                "Unknown export format '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+// everything that can be exported, new modules add a variant here and an Exportable impl below
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportEntity {
+    #[display("users")]
+    Users,
+}
+
+impl FromStr for ExportEntity {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "users" => Ok(ExportEntity::Users),
+            other => Err(AppError::BadRequest(format!(
+                "Unknown export entity '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+// columns are the serialized field names of the model, in the order they show up in the file
+pub trait Exportable: Serialize {
+    fn columns() -> &'static [&'static str];
+}
+
+impl Exportable for User {
+    fn columns() -> &'static [&'static str] {
+        &["id", "name", "surname", "email", "phone", "role", "status"]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+impl ExportTable {
+    // checks the requested columns against the model, empty means all of them
+    pub fn select_columns<T: Exportable>(columns: &[String]) -> Result<Vec<String>, AppError> {
+        if columns.is_empty() {
+            return Ok(T::columns().iter().map(|c| c.to_string()).collect());
+        }
+
+        if let Some(unknown) = columns.iter().find(|c| !T::columns().contains(&c.as_str())) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown column '{}'",
+                unknown
+            )));
+        }
+
+        Ok(columns.to_vec())
+    }
+
+    pub fn from_items<T: Exportable>(items: &[T], columns: &[String]) -> Result<Self, AppError> {
+        let columns = Self::select_columns::<T>(columns)?;
+
+        let mut rows = Vec::with_capacity(items.len());
+        for item in items {
+            let value = serde_json::to_value(item).map_err(|_| AppError::InternalServerError)?;
+            rows.push(
+                columns
+                    .iter()
+                    .map(|c| value.get(c).cloned().unwrap_or(serde_json::Value::Null))
+                    .collect(),
+            );
+        }
+
+        Ok(Self { columns, rows })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ExportParams {
+    pub format: Option<ExportFormat>,
+    // comma separated, e.g. columns=name,surname,email
+    pub columns: Option<String>,
+    // skip the size check and always run as a job
+    #[serde(default)]
+    pub background: bool,
+}
+
+impl ExportParams {
+    pub fn format(&self) -> ExportFormat {
+        self.format.unwrap_or(ExportFormat::Csv)
+    }
+
+    pub fn column_list(&self) -> Vec<String> {
+        self.columns
+            .as_deref()
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .map(|c| c.trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportJobStatus {
+    #[display("Pending")]
+    Pending,
+    #[display("Running")]
+    Running,
+    #[display("Completed")]
+    Completed,
+    #[display("Failed")]
+    Failed,
+}
+
+impl From<entity::JobStatus> for ExportJobStatus {
+    fn from(item: entity::JobStatus) -> Self {
+        match item {
+            entity::JobStatus::Pending => ExportJobStatus::Pending,
+            entity::JobStatus::Running => ExportJobStatus::Running,
+            entity::JobStatus::Completed => ExportJobStatus::Completed,
+            entity::JobStatus::Failed => ExportJobStatus::Failed,
+        }
+    }
+}
+
+impl From<ExportJobStatus> for entity::JobStatus {
+    fn from(item: ExportJobStatus) -> Self {
+        match item {
+            ExportJobStatus::Pending => entity::JobStatus::Pending,
+            ExportJobStatus::Running => entity::JobStatus::Running,
+            ExportJobStatus::Completed => entity::JobStatus::Completed,
+            ExportJobStatus::Failed => entity::JobStatus::Failed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportJob {
+    pub id: String,
+    pub event_id: String,
+    pub requested_by: String,
+    pub entity: String,
+    pub format: String,
+    pub columns: Option<String>,
+    pub filter: String,
+    pub status: ExportJobStatus,
+    pub row_count: Option<i64>,
+    pub file_name: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<entity::Model> for ExportJob {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            id: model.id,
+            event_id: model.event_id,
+            requested_by: model.requested_by,
+            entity: model.entity,
+            format: model.format,
+            columns: model.columns,
+            filter: model.filter,
+            status: model.status.into(),
+            row_count: model.row_count,
+            file_name: model.file_name,
+            error: model.error,
+            created_at: model.created_at,
+            finished_at: model.finished_at,
+        }
+    }
+}
+
+pub struct ExportFile {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub chunks: Vec<Vec<u8>>,
+}
+
+pub enum ExportOutcome {
+    Ready(ExportFile),
+    Queued(ExportJob),
+}
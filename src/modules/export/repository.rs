@@ -0,0 +1,136 @@
+use super::entity::{ActiveModel, Column, Entity as ExportJobEntity, JobStatus};
+use super::models::{ExportEntity, ExportFormat, ExportJob};
+use crate::error::AppError;
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct ExportRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ExportRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_all(&self, event_id: &str) -> Result<Vec<ExportJob>, AppError> {
+        let models = ExportJobEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_by_requester(&self, user_id: &str) -> Result<Vec<ExportJob>, AppError> {
+        let models = ExportJobEntity::find()
+            .filter(Column::RequestedBy.eq(user_id))
+            .order_by_asc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_by_id(
+        &self,
+        event_id: &str,
+        id: &str,
+    ) -> Result<Option<ExportJob>, AppError> {
+        let model = ExportJobEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    pub async fn create(
+        &self,
+        event_id: &str,
+        requested_by: &str,
+        entity: ExportEntity,
+        format: ExportFormat,
+        columns: Option<String>,
+        filter: &str,
+    ) -> Result<ExportJob, AppError> {
+        let new_job = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            requested_by: Set(requested_by.to_string()),
+            entity: Set(entity.to_string()),
+            format: Set(format.to_string()),
+            columns: Set(columns),
+            filter: Set(filter.to_string()),
+            status: Set(JobStatus::Pending),
+            row_count: Set(None),
+            file_name: Set(None),
+            error: Set(None),
+            created_at: Set(chrono::Utc::now()),
+            finished_at: Set(None),
+        };
+
+        let result = new_job
+            .insert(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    pub async fn mark_running(&self, id: &str) -> Result<(), AppError> {
+        let job = ActiveModel {
+            id: Set(id.to_string()),
+            status: Set(JobStatus::Running),
+            ..Default::default()
+        };
+
+        job.update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn mark_completed(
+        &self,
+        id: &str,
+        row_count: i64,
+        file_name: &str,
+    ) -> Result<(), AppError> {
+        let job = ActiveModel {
+            id: Set(id.to_string()),
+            status: Set(JobStatus::Completed),
+            row_count: Set(Some(row_count)),
+            file_name: Set(Some(file_name.to_string())),
+            finished_at: Set(Some(chrono::Utc::now())),
+            ..Default::default()
+        };
+
+        job.update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn mark_failed(&self, id: &str, error: &str) -> Result<(), AppError> {
+        let job = ActiveModel {
+            id: Set(id.to_string()),
+            status: Set(JobStatus::Failed),
+            error: Set(Some(error.to_string())),
+            finished_at: Set(Some(chrono::Utc::now())),
+            ..Default::default()
+        };
+
+        job.update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::export::models::{ExportEntity, ExportFile, ExportOutcome, ExportParams};
+use crate::modules::export::service::ExportService;
+use actix_web::http::header::{ContentDisposition, ContentType};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use futures_util::stream;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/export")
+            .wrap(auth_middleware)
+            .route("/jobs", web::get().to(get_jobs))
+            .route("/jobs/{id}", web::get().to(get_job))
+            .route("/jobs/{id}/download", web::get().to(download_job))
+            .route("/{entity}", web::get().to(export))
+            .route("/{entity}/jobs", web::post().to(start_job)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+fn file_response(file: ExportFile) -> HttpResponse {
+    let chunks = file
+        .chunks
+        .into_iter()
+        .map(|chunk| Ok::<_, actix_web::Error>(web::Bytes::from(chunk)));
+
+    HttpResponse::Ok()
+        .insert_header(ContentType(file.content_type.parse().unwrap()))
+        .insert_header(ContentDisposition::attachment(file.file_name))
+        .streaming(stream::iter(chunks))
+}
+
+async fn export(
+    req: HttpRequest,
+    service: web::Data<ExportService>,
+    path: web::Path<ExportEntity>,
+    params: web::Query<ExportParams>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let outcome = service
+        .into_inner()
+        .export(
+            path.into_inner(),
+            params.into_inner(),
+            req.query_string(),
+            &claims,
+        )
+        .await?;
+
+    match outcome {
+        ExportOutcome::Ready(file) => Ok(file_response(file)),
+        ExportOutcome::Queued(job) => Ok(HttpResponse::Accepted().json(job)),
+    }
+}
+
+async fn start_job(
+    req: HttpRequest,
+    service: web::Data<ExportService>,
+    path: web::Path<ExportEntity>,
+    params: web::Query<ExportParams>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let job = service
+        .into_inner()
+        .start_job(
+            path.into_inner(),
+            params.into_inner(),
+            req.query_string(),
+            &claims,
+        )
+        .await?;
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+async fn get_jobs(
+    req: HttpRequest,
+    service: web::Data<ExportService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let jobs = service.get_jobs(&claims).await?;
+    Ok(HttpResponse::Ok().json(jobs))
+}
+
+async fn get_job(
+    req: HttpRequest,
+    service: web::Data<ExportService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let job = service.get_job(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(job))
+}
+
+async fn download_job(
+    req: HttpRequest,
+    service: web::Data<ExportService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let file = service.download_job(&path.into_inner(), &claims).await?;
+    Ok(file_response(file))
+}
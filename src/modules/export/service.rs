@@ -0,0 +1,302 @@
+use super::models::{
+    ExportEntity, ExportFile, ExportFormat, ExportJob, ExportJobStatus, ExportOutcome,
+    ExportParams, ExportTable,
+};
+use super::repository::ExportRepository;
+use super::writer;
+use crate::audit::models::NewAuditEntry;
+use crate::audit::service::AuditService;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::event::service::EventService;
+use crate::user::models::User;
+use crate::user::models::UserFilter;
+use crate::user::repository::UserRepository;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// anything bigger than this is written to disk by a background job instead of sent right away
+pub const SYNC_ROW_LIMIT: usize = 5000;
+
+pub struct ExportService {
+    repository: ExportRepository,
+    users: UserRepository,
+    events: Arc<EventService>,
+    audit: AuditService,
+    export_dir: PathBuf,
+}
+
+impl ExportService {
+    pub fn new(
+        repository: ExportRepository,
+        users: UserRepository,
+        events: Arc<EventService>,
+        audit: AuditService,
+        export_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            repository,
+            users,
+            events,
+            audit,
+            export_dir: export_dir.into(),
+        }
+    }
+
+    pub async fn export(
+        self: Arc<Self>,
+        entity: ExportEntity,
+        params: ExportParams,
+        filter: &str,
+        claims: &Claims,
+    ) -> Result<ExportOutcome, AppError> {
+        require_admin(claims)?;
+
+        if params.background {
+            let job = self.start_job(entity, params, filter, claims).await?;
+            return Ok(ExportOutcome::Queued(job));
+        }
+
+        // counted first so big exports aren't loaded here only to be loaded again by the job
+        if self.count(&claims.event, entity, filter).await? > SYNC_ROW_LIMIT as u64 {
+            let job = self.start_job(entity, params, filter, claims).await?;
+            return Ok(ExportOutcome::Queued(job));
+        }
+
+        let format = params.format();
+        let table = self
+            .load(&claims.event, entity, filter, &params.column_list())
+            .await?;
+
+        let chunks = writer::encode(&table, format)?;
+
+        self.audit
+            .record(export_audit_entry(
+                claims,
+                "export.stream",
+                entity,
+                &params,
+                filter,
+                None,
+                Some(table.rows.len()),
+            ))
+            .await?;
+
+        Ok(ExportOutcome::Ready(ExportFile {
+            file_name: format!("{}.{}", entity, format.extension()),
+            content_type: format.content_type(),
+            chunks,
+        }))
+    }
+
+    pub async fn start_job(
+        self: Arc<Self>,
+        entity: ExportEntity,
+        params: ExportParams,
+        filter: &str,
+        claims: &Claims,
+    ) -> Result<ExportJob, AppError> {
+        require_admin(claims)?;
+
+        // fail fast on bad columns or filters instead of in the background
+        validate(entity, filter, &params.column_list())?;
+        // a big GET export ends up here too, past the archive check that only looks at writes
+        self.events.ensure_writable(&claims.event).await?;
+
+        let job = self
+            .repository
+            .create(
+                &claims.event,
+                &claims.sub,
+                entity,
+                params.format(),
+                params.columns.clone(),
+                filter,
+            )
+            .await?;
+
+        self.audit
+            .record(export_audit_entry(
+                claims,
+                "export.job",
+                entity,
+                &params,
+                filter,
+                Some(&job.id),
+                None,
+            ))
+            .await?;
+
+        let service = self.clone();
+        let queued = job.clone();
+        tokio::spawn(async move {
+            service.run_job(queued).await;
+        });
+
+        Ok(job)
+    }
+
+    pub async fn get_jobs(&self, claims: &Claims) -> Result<Vec<ExportJob>, AppError> {
+        require_admin(claims)?;
+
+        self.repository.find_all(&claims.event).await
+    }
+
+    pub async fn get_job(&self, id: &str, claims: &Claims) -> Result<ExportJob, AppError> {
+        require_admin(claims)?;
+
+        let job = self.repository.find_by_id(&claims.event, id).await?;
+        job.ok_or_else(|| AppError::NotFound("Export job not found".to_string()))
+    }
+
+    pub async fn download_job(&self, id: &str, claims: &Claims) -> Result<ExportFile, AppError> {
+        let job = self.get_job(id, claims).await?;
+
+        let file_name = match (&job.status, &job.file_name) {
+            (ExportJobStatus::Completed, Some(file_name)) => file_name.clone(),
+            _ => {
+                return Err(AppError::BadRequest(
+                    "Export job has not finished yet".to_string(),
+                ));
+            }
+        };
+
+        let format: ExportFormat = job.format.parse()?;
+        let content = tokio::fs::read(self.export_dir.join(&file_name))
+            .await
+            .map_err(|_| AppError::NotFound("Export file not found".to_string()))?;
+
+        self.audit
+            .record(NewAuditEntry {
+                event_id: Some(claims.event.clone()),
+                actor_id: claims.sub.clone(),
+                action: "export.job_download".to_string(),
+                target_type: "export_job".to_string(),
+                target_id: Some(job.id.clone()),
+                details: None,
+            })
+            .await?;
+
+        Ok(ExportFile {
+            file_name,
+            content_type: format.content_type(),
+            chunks: vec![content],
+        })
+    }
+
+    // runs detached, so failures end up on the job row instead of a response
+    async fn run_job(&self, job: ExportJob) {
+        if let Err(err) = self.try_run_job(&job).await {
+            let _ = self.repository.mark_failed(&job.id, &err.to_string()).await;
+        }
+    }
+
+    async fn try_run_job(&self, job: &ExportJob) -> Result<(), AppError> {
+        self.repository.mark_running(&job.id).await?;
+
+        let entity: ExportEntity = job.entity.parse()?;
+        let format: ExportFormat = job.format.parse()?;
+        let params = ExportParams {
+            format: Some(format),
+            columns: job.columns.clone(),
+            background: true,
+        };
+
+        let table = self
+            .load(&job.event_id, entity, &job.filter, &params.column_list())
+            .await?;
+        let chunks = writer::encode(&table, format)?;
+
+        tokio::fs::create_dir_all(&self.export_dir)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let file_name = format!("{}.{}", job.id, format.extension());
+        tokio::fs::write(self.export_dir.join(&file_name), chunks.concat())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.repository
+            .mark_completed(&job.id, table.rows.len() as i64, &file_name)
+            .await
+    }
+
+    async fn count(
+        &self,
+        event_id: &str,
+        entity: ExportEntity,
+        filter: &str,
+    ) -> Result<u64, AppError> {
+        match entity {
+            ExportEntity::Users => {
+                let filter: UserFilter = parse_filter(filter)?;
+                self.users.count_filtered(event_id, &filter).await
+            }
+        }
+    }
+
+    // filter is the raw query string of the request, parsed the same way the list endpoint does
+    async fn load(
+        &self,
+        event_id: &str,
+        entity: ExportEntity,
+        filter: &str,
+        columns: &[String],
+    ) -> Result<ExportTable, AppError> {
+        match entity {
+            ExportEntity::Users => {
+                let filter: UserFilter = parse_filter(filter)?;
+                let users = self.users.find_filtered(event_id, &filter).await?;
+                ExportTable::from_items(&users, columns)
+            }
+        }
+    }
+}
+
+fn validate(entity: ExportEntity, filter: &str, columns: &[String]) -> Result<(), AppError> {
+    match entity {
+        ExportEntity::Users => {
+            parse_filter::<UserFilter>(filter)?;
+            ExportTable::select_columns::<User>(columns)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn require_admin(claims: &Claims) -> Result<(), AppError> {
+    if claims.role != "admin" {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(())
+}
+
+fn parse_filter<T: serde::de::DeserializeOwned>(filter: &str) -> Result<T, AppError> {
+    serde_urlencoded::from_str(filter)
+        .map_err(|err| AppError::BadRequest(format!("Invalid filter: {}", err)))
+}
+
+fn export_audit_entry(
+    claims: &Claims,
+    action: &str,
+    entity: ExportEntity,
+    params: &ExportParams,
+    filter: &str,
+    job_id: Option<&str>,
+    rows: Option<usize>,
+) -> NewAuditEntry {
+    NewAuditEntry {
+        event_id: Some(claims.event.clone()),
+        actor_id: claims.sub.clone(),
+        action: action.to_string(),
+        target_type: entity.to_string(),
+        target_id: job_id.map(|id| id.to_string()),
+        details: Some(serde_json::json!({
+            "format": params.format(),
+            "columns": params.columns,
+            "filter": filter,
+            "rows": rows,
+        })),
+    }
+}
@@ -0,0 +1,98 @@
+use super::models::{ExportFormat, ExportTable};
+use crate::error::AppError;
+use rust_xlsxwriter::Workbook;
+use serde_json::Value;
+
+// csv and jsonl come out one chunk per row, xlsx is a single zip blob
+pub fn encode(table: &ExportTable, format: ExportFormat) -> Result<Vec<Vec<u8>>, AppError> {
+    match format {
+        ExportFormat::Csv => encode_csv(table),
+        ExportFormat::Jsonl => encode_jsonl(table),
+        ExportFormat::Xlsx => encode_xlsx(table).map(|buffer| vec![buffer]),
+    }
+}
+
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_line<I, S>(fields: I) -> Result<Vec<u8>, AppError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(fields)
+        .map_err(|_| AppError::InternalServerError)?;
+    writer
+        .into_inner()
+        .map_err(|_| AppError::InternalServerError)
+}
+
+fn encode_csv(table: &ExportTable) -> Result<Vec<Vec<u8>>, AppError> {
+    let mut chunks = Vec::with_capacity(table.rows.len() + 1);
+    chunks.push(csv_line(&table.columns)?);
+
+    for row in &table.rows {
+        chunks.push(csv_line(row.iter().map(cell_text))?);
+    }
+
+    Ok(chunks)
+}
+
+fn encode_jsonl(table: &ExportTable) -> Result<Vec<Vec<u8>>, AppError> {
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let object: serde_json::Map<String, Value> = table
+                .columns
+                .iter()
+                .cloned()
+                .zip(row.iter().cloned())
+                .collect();
+
+            let mut line =
+                serde_json::to_vec(&object).map_err(|_| AppError::InternalServerError)?;
+            line.push(b'\n');
+            Ok(line)
+        })
+        .collect()
+}
+
+fn encode_xlsx(table: &ExportTable) -> Result<Vec<u8>, AppError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    for (col, name) in table.columns.iter().enumerate() {
+        worksheet
+            .write_string(0, col as u16, name)
+            .map_err(|_| AppError::InternalServerError)?;
+    }
+
+    for (i, row) in table.rows.iter().enumerate() {
+        let row_idx = (i + 1) as u32;
+        for (col, value) in row.iter().enumerate() {
+            let col = col as u16;
+            let result = match value {
+                Value::Null => continue,
+                Value::Bool(b) => worksheet.write_boolean(row_idx, col, *b),
+                Value::Number(n) => match n.as_f64() {
+                    Some(n) => worksheet.write_number(row_idx, col, n),
+                    None => worksheet.write_string(row_idx, col, n.to_string()),
+                },
+                other => worksheet.write_string(row_idx, col, cell_text(other)),
+            };
+            result.map_err(|_| AppError::InternalServerError)?;
+        }
+    }
+
+    workbook
+        .save_to_buffer()
+        .map_err(|_| AppError::InternalServerError)
+}
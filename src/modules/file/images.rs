@@ -0,0 +1,82 @@
+use super::models::VariantKind;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageReader, Limits};
+use std::io::Cursor;
+
+// square crop for avatars and lists
+pub const THUMBNAIL_SIZE: u32 = 256;
+// longest side, enough for a phone screen
+pub const MEDIUM_SIZE: u32 = 1280;
+// WebP can't go past this on either side
+const MAX_SIDE: u32 = 16383;
+const WEBP_QUALITY: f32 = 80.0;
+
+#[derive(Debug, Clone)]
+pub struct ProcessedImage {
+    pub kind: VariantKind,
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+// every variant as WebP, turned upright and without the metadata (EXIF location included)
+pub fn process(bytes: &[u8]) -> Result<Vec<ProcessedImage>, String> {
+    let image = decode(bytes)?;
+
+    let medium = if image.width() > MEDIUM_SIZE || image.height() > MEDIUM_SIZE {
+        image.resize(MEDIUM_SIZE, MEDIUM_SIZE, FilterType::Lanczos3)
+    } else {
+        image.clone()
+    };
+    let thumbnail = medium.resize_to_fill(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Lanczos3);
+
+    Ok(vec![
+        encode(VariantKind::Thumbnail, &thumbnail),
+        encode(VariantKind::Medium, &medium),
+        encode(VariantKind::Original, &image),
+    ])
+}
+
+fn decode(bytes: &[u8]) -> Result<DynamicImage, String> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SIDE);
+    limits.max_image_height = Some(MAX_SIDE);
+
+    let mut reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(|e| e.to_string())?;
+    // phones store pictures sideways and rely on EXIF to turn them, which is about to be dropped
+    let orientation = decoder
+        .orientation()
+        .unwrap_or(image::metadata::Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|e| e.to_string())?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+// only the pixels go in, so nothing from the source file's metadata survives
+fn encode(kind: VariantKind, image: &DynamicImage) -> ProcessedImage {
+    let (width, height) = (image.width(), image.height());
+    let bytes = if image.color().has_alpha() {
+        let pixels = image.to_rgba8();
+        webp::Encoder::from_rgba(&pixels, width, height)
+            .encode(WEBP_QUALITY)
+            .to_vec()
+    } else {
+        let pixels = image.to_rgb8();
+        webp::Encoder::from_rgb(&pixels, width, height)
+            .encode(WEBP_QUALITY)
+            .to_vec()
+    };
+
+    ProcessedImage {
+        kind,
+        bytes,
+        width,
+        height,
+    }
+}
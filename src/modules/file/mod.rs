@@ -0,0 +1,8 @@
+pub mod entity;
+pub mod images;
+pub mod models;
+pub mod repository;
+pub mod routes;
+pub mod service;
+pub mod signer;
+pub mod storage;
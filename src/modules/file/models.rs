@@ -0,0 +1,223 @@
+use super::entity::{self, variant};
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+// what can be uploaded, checked against the content itself rather than what the client claims
+pub const ALLOWED_CONTENT_TYPES: [&str; 5] = [
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "image/gif",
+    "application/pdf",
+];
+
+// the content type from the first bytes of the file, None for anything not allowed
+pub fn sniff_content_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        None
+    }
+}
+
+// drops any directories a browser sent along and keeps it to a sane length
+pub fn clean_file_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or("").trim();
+    let name: String = name.chars().filter(|c| !c.is_control()).take(255).collect();
+    if name.is_empty() {
+        "upload".to_string()
+    } else {
+        name
+    }
+}
+
+// these get resized variants after upload, see the images module
+pub fn is_image(content_type: &str) -> bool {
+    content_type.starts_with("image/")
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageStatus {
+    #[display("Pending")]
+    Pending,
+    #[display("Ready")]
+    Ready,
+    #[display("Failed")]
+    Failed,
+}
+
+impl From<ImageStatus> for entity::ImageStatus {
+    fn from(item: ImageStatus) -> Self {
+        match item {
+            ImageStatus::Pending => entity::ImageStatus::Pending,
+            ImageStatus::Ready => entity::ImageStatus::Ready,
+            ImageStatus::Failed => entity::ImageStatus::Failed,
+        }
+    }
+}
+
+impl From<entity::ImageStatus> for ImageStatus {
+    fn from(item: entity::ImageStatus) -> Self {
+        match item {
+            entity::ImageStatus::Pending => ImageStatus::Pending,
+            entity::ImageStatus::Ready => ImageStatus::Ready,
+            entity::ImageStatus::Failed => ImageStatus::Failed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantKind {
+    #[display("thumbnail")]
+    Thumbnail,
+    #[display("medium")]
+    Medium,
+    #[display("original")]
+    Original,
+}
+
+impl From<VariantKind> for variant::Kind {
+    fn from(item: VariantKind) -> Self {
+        match item {
+            VariantKind::Thumbnail => variant::Kind::Thumbnail,
+            VariantKind::Medium => variant::Kind::Medium,
+            VariantKind::Original => variant::Kind::Original,
+        }
+    }
+}
+
+impl From<variant::Kind> for VariantKind {
+    fn from(item: variant::Kind) -> Self {
+        match item {
+            variant::Kind::Thumbnail => VariantKind::Thumbnail,
+            variant::Kind::Medium => VariantKind::Medium,
+            variant::Kind::Original => VariantKind::Original,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StoredFile {
+    pub id: String,
+    pub name: String,
+    pub content_type: String,
+    pub size: u64,
+    pub sha256: String,
+    // absent for anything that isn't an image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_status: Option<ImageStatus>,
+    pub uploaded_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<entity::Model> for StoredFile {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            content_type: model.content_type,
+            size: model.size.max(0) as u64,
+            sha256: model.sha256,
+            image_status: model.image_status.map(|s| s.into()),
+            uploaded_by: model.uploaded_by,
+            created_at: model.created_at,
+        }
+    }
+}
+
+// a file as it came off the multipart form
+#[derive(Debug, Clone)]
+pub struct Upload {
+    pub name: String,
+    // None or application/octet-stream when the client didn't know
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewFile {
+    pub name: String,
+    pub content_type: String,
+    pub size: u64,
+    pub sha256: String,
+    pub storage_key: String,
+    pub image_status: Option<ImageStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub kind: VariantKind,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub size: u64,
+    pub storage_key: String,
+}
+
+impl From<variant::Model> for Variant {
+    fn from(model: variant::Model) -> Self {
+        Self {
+            kind: model.kind.into(),
+            content_type: model.content_type,
+            width: model.width.max(0) as u32,
+            height: model.height.max(0) as u32,
+            size: model.size.max(0) as u64,
+            storage_key: model.storage_key,
+        }
+    }
+}
+
+// signed links to each size of an image, for whatever owns it (like a user's avatar)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageLinks {
+    pub file_id: String,
+    pub status: ImageStatus,
+    // only once the variants are ready
+    pub thumbnail: Option<SignedUrl>,
+    pub medium: Option<SignedUrl>,
+    pub original: Option<SignedUrl>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignedUrl {
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+// what a signed link serves, images as their WebP variant
+#[derive(Debug, Clone)]
+pub struct FileContent {
+    pub name: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DownloadQuery {
+    // images only, the original when left out
+    pub variant: Option<VariantKind>,
+    // unix seconds
+    pub expires: i64,
+    pub signature: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UrlQuery {
+    pub variant: Option<VariantKind>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct FileFilter {
+    pub uploaded_by: Option<String>,
+}
@@ -0,0 +1,260 @@
+use super::entity::{self, ActiveModel, Column, Entity as FileEntity, variant};
+use super::models::{FileFilter, ImageStatus, NewFile, StoredFile, Variant, VariantKind};
+use crate::error::AppError;
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct FileRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl FileRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    // newest first
+    pub async fn find_files(
+        &self,
+        event_id: &str,
+        filter: &FileFilter,
+    ) -> Result<Vec<StoredFile>, AppError> {
+        let mut select = FileEntity::find().filter(Column::EventId.eq(event_id));
+
+        if let Some(uploaded_by) = &filter.uploaded_by {
+            select = select.filter(Column::UploadedBy.eq(uploaded_by.as_str()));
+        }
+
+        let models = select
+            .order_by_desc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_file(
+        &self,
+        event_id: &str,
+        id: &str,
+    ) -> Result<Option<StoredFile>, AppError> {
+        let model = FileEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // signed downloads carry no event, the signature already proves access
+    pub async fn find_stored(&self, id: &str) -> Result<Option<(StoredFile, String)>, AppError> {
+        let model = FileEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| {
+            let key = m.storage_key.clone();
+            (m.into(), key)
+        }))
+    }
+
+    pub async fn find_by_hash(
+        &self,
+        event_id: &str,
+        sha256: &str,
+    ) -> Result<Option<StoredFile>, AppError> {
+        let model = FileEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::Sha256.eq(sha256))
+            .order_by_asc(Column::CreatedAt)
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    pub async fn create_file(
+        &self,
+        event_id: &str,
+        file: NewFile,
+        uploaded_by: &str,
+    ) -> Result<StoredFile, AppError> {
+        let model = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            name: Set(file.name),
+            content_type: Set(file.content_type),
+            size: Set(file.size as i64),
+            sha256: Set(file.sha256),
+            storage_key: Set(file.storage_key),
+            image_status: Set(file.image_status.map(|s| s.into())),
+            uploaded_by: Set(uploaded_by.to_string()),
+            created_at: Set(chrono::Utc::now()),
+        }
+        .insert(self.db.as_ref())
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.into())
+    }
+
+    // hands back the storage key so the caller can clean up the bytes
+    pub async fn delete_file(&self, event_id: &str, id: &str) -> Result<String, AppError> {
+        let model = FileEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+
+        FileEntity::delete_by_id(id.to_string())
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.storage_key)
+    }
+
+    // variants and anything pointing at the file go with it
+    pub async fn delete_stored(&self, id: &str) -> Result<(), AppError> {
+        FileEntity::delete_by_id(id.to_string())
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    // other events may have uploaded the same content
+    pub async fn is_key_used(&self, storage_key: &str) -> Result<bool, AppError> {
+        let model = FileEntity::find()
+            .filter(Column::StorageKey.eq(storage_key))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.is_some())
+    }
+
+    // across every event, for the worker to pick up again after a restart
+    pub async fn find_pending_images(&self) -> Result<Vec<String>, AppError> {
+        let models = FileEntity::find()
+            .filter(Column::ImageStatus.eq(entity::ImageStatus::Pending))
+            .order_by_asc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.id).collect())
+    }
+
+    // another upload of the same content may still need the original bytes
+    pub async fn is_key_pending(&self, storage_key: &str) -> Result<bool, AppError> {
+        let model = FileEntity::find()
+            .filter(Column::StorageKey.eq(storage_key))
+            .filter(Column::ImageStatus.eq(entity::ImageStatus::Pending))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.is_some())
+    }
+
+    pub async fn set_image_status(&self, id: &str, status: ImageStatus) -> Result<(), AppError> {
+        let model = FileEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        active_model.image_status = Set(Some(status.into()));
+        active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn find_variant(
+        &self,
+        file_id: &str,
+        kind: VariantKind,
+    ) -> Result<Option<Variant>, AppError> {
+        let model = variant::Entity::find()
+            .filter(variant::Column::FileId.eq(file_id))
+            .filter(variant::Column::Kind.eq(variant::Kind::from(kind)))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // replaces whatever variants were there and marks the image ready
+    pub async fn find_variants(&self, file_id: &str) -> Result<Vec<Variant>, AppError> {
+        let models = variant::Entity::find()
+            .filter(variant::Column::FileId.eq(file_id))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn save_variants(&self, file_id: &str, variants: &[Variant]) -> Result<(), AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let file = FileEntity::find_by_id(file_id.to_string())
+            .one(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+
+        variant::Entity::delete_many()
+            .filter(variant::Column::FileId.eq(file_id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        for v in variants {
+            variant::ActiveModel {
+                id: Set(uuid::Uuid::now_v7().to_string()),
+                file_id: Set(file_id.to_string()),
+                kind: Set(v.kind.into()),
+                storage_key: Set(v.storage_key.clone()),
+                content_type: Set(v.content_type.clone()),
+                width: Set(v.width as i32),
+                height: Set(v.height as i32),
+                size: Set(v.size as i64),
+                created_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        }
+
+        let mut active_model: ActiveModel = file.into();
+        active_model.image_status = Set(Some(entity::ImageStatus::Ready));
+        active_model
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+}
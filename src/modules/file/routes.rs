@@ -0,0 +1,162 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::file::models::{DownloadQuery, FileFilter, Upload, UrlQuery};
+use crate::modules::file::service::FileService;
+use actix_multipart::Multipart;
+use actix_web::http::header::{ContentDisposition, ContentType, DispositionParam, DispositionType};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use futures_util::StreamExt;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    // signed links are opened straight from <img> tags and browsers, so no token here
+    cfg.service(web::resource("/files/{id}/download").route(web::get().to(download)))
+        .service(
+            web::scope("/files")
+                .wrap(auth_middleware)
+                .route("", web::get().to(get_files))
+                .route("", web::post().to(upload))
+                .route("/{id}", web::get().to(get_file))
+                .route("/{id}", web::delete().to(delete_file))
+                .route("/{id}/url", web::get().to(get_download_url)),
+        );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+// other form fields are skipped, but still read through to get past them
+const MAX_FIELD_BYTES: usize = 64 * 1024;
+
+// the first "file" field of the form, read no further than the size limit
+async fn read_upload(mut payload: Multipart, max_bytes: usize) -> Result<Upload, AppError> {
+    let malformed = || AppError::BadRequest("Malformed multipart upload".to_string());
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|_| malformed())?;
+        if field.name() != Some("file") {
+            let mut size = 0;
+            while let Some(chunk) = field.next().await {
+                size += chunk.map_err(|_| malformed())?.len();
+                if size > MAX_FIELD_BYTES {
+                    return Err(AppError::BadRequest(format!(
+                        "Form fields other than 'file' can't be larger than {} KB",
+                        MAX_FIELD_BYTES / 1024
+                    )));
+                }
+            }
+            continue;
+        }
+
+        let name = field
+            .content_disposition()
+            .and_then(|d| d.get_filename())
+            .unwrap_or("")
+            .to_string();
+        let content_type = field.content_type().map(|m| m.essence_str().to_string());
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.next().await {
+            bytes.extend_from_slice(&chunk.map_err(|_| malformed())?);
+            if bytes.len() > max_bytes {
+                return Err(AppError::BadRequest(format!(
+                    "File is larger than {} MB",
+                    max_bytes / (1024 * 1024)
+                )));
+            }
+        }
+
+        return Ok(Upload {
+            name,
+            content_type,
+            bytes,
+        });
+    }
+
+    Err(AppError::BadRequest(
+        "Missing 'file' field in the upload".to_string(),
+    ))
+}
+
+async fn upload(
+    req: HttpRequest,
+    service: web::Data<FileService>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let upload = read_upload(payload, service.max_upload_bytes()).await?;
+    let file = service.upload(upload, &claims).await?;
+    Ok(HttpResponse::Ok().json(file))
+}
+
+async fn get_files(
+    req: HttpRequest,
+    service: web::Data<FileService>,
+    query: web::Query<FileFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let files = service.get_files(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(files))
+}
+
+async fn get_file(
+    req: HttpRequest,
+    service: web::Data<FileService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let file = service.get_file(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(file))
+}
+
+async fn get_download_url(
+    req: HttpRequest,
+    service: web::Data<FileService>,
+    path: web::Path<String>,
+    query: web::Query<UrlQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let url = service
+        .get_download_url(&path.into_inner(), query.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(url))
+}
+
+async fn delete_file(
+    req: HttpRequest,
+    service: web::Data<FileService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service.delete_file(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("File deleted successfully"))
+}
+
+async fn download(
+    service: web::Data<FileService>,
+    path: web::Path<String>,
+    query: web::Query<DownloadQuery>,
+) -> Result<HttpResponse, AppError> {
+    let file = service.download(&path.into_inner(), &query).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentType(file.content_type.parse().unwrap()))
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Inline,
+            parameters: vec![DispositionParam::Filename(file.name)],
+        })
+        .insert_header(("X-Content-Type-Options", "nosniff"))
+        .body(file.bytes))
+}
@@ -0,0 +1,378 @@
+use super::images;
+use super::models::{
+    ALLOWED_CONTENT_TYPES, DownloadQuery, FileContent, FileFilter, ImageStatus, NewFile, SignedUrl,
+    StoredFile, Upload, UrlQuery, Variant, VariantKind, clean_file_name, is_image,
+    sniff_content_type,
+};
+use super::repository::FileRepository;
+use super::signer::UrlSigner;
+use super::storage::Storage;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::social::repository::SocialRepository;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+pub struct FileService {
+    repository: FileRepository,
+    socials: SocialRepository,
+    storage: Arc<dyn Storage>,
+    max_upload_bytes: usize,
+    signer: UrlSigner,
+    // ids of images waiting for their variants
+    queue: mpsc::UnboundedSender<String>,
+    // taken by the worker once it starts
+    queued: Mutex<Option<mpsc::UnboundedReceiver<String>>>,
+}
+
+// variants of the same content are shared, just like the uploads
+fn variant_key(sha256: &str, kind: VariantKind) -> String {
+    format!("{}-{}.webp", sha256, kind)
+}
+
+fn webp_name(name: &str) -> String {
+    let stem = name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name);
+    format!("{}.webp", stem)
+}
+
+impl FileService {
+    pub fn new(
+        repository: FileRepository,
+        socials: SocialRepository,
+        storage: Arc<dyn Storage>,
+        max_upload_bytes: usize,
+        signer: UrlSigner,
+    ) -> Self {
+        let (queue, queued) = mpsc::unbounded_channel();
+        Self {
+            repository,
+            socials,
+            storage,
+            max_upload_bytes,
+            signer,
+            queue,
+            queued: Mutex::new(Some(queued)),
+        }
+    }
+
+    // the multipart reader stops early once a file goes past this
+    pub fn max_upload_bytes(&self) -> usize {
+        self.max_upload_bytes
+    }
+
+    // everyone in the event uploads, the same content uploaded twice is stored once
+    pub async fn upload(&self, upload: Upload, claims: &Claims) -> Result<StoredFile, AppError> {
+        if upload.bytes.is_empty() {
+            return Err(AppError::BadRequest("File is empty".to_string()));
+        }
+        if upload.bytes.len() > self.max_upload_bytes {
+            return Err(AppError::BadRequest(format!(
+                "File is larger than {} MB",
+                self.max_upload_bytes / (1024 * 1024)
+            )));
+        }
+
+        let content_type = sniff_content_type(&upload.bytes).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Only these file types can be uploaded: {}",
+                ALLOWED_CONTENT_TYPES.join(", ")
+            ))
+        })?;
+        if let Some(claimed) = upload.content_type.as_deref()
+            && claimed != "application/octet-stream"
+            && claimed != content_type
+        {
+            return Err(AppError::BadRequest(format!(
+                "File content doesn't match its type '{}'",
+                claimed
+            )));
+        }
+
+        let sha256 = hex::encode(Sha256::digest(&upload.bytes));
+        let storage_key = sha256.clone();
+        let size = upload.bytes.len() as u64;
+        // a copy already here keeps its content and variants, this upload only gets its own name
+        // and owner; a processed image's original is gone, so it isn't stored again either
+        let existing = self.repository.find_by_hash(&claims.event, &sha256).await?;
+        let image_status = match &existing {
+            Some(existing) => existing.image_status.clone(),
+            None => {
+                if !self.storage.exists(&storage_key).await? {
+                    self.storage
+                        .put(&storage_key, upload.bytes, content_type)
+                        .await?;
+                }
+                is_image(content_type).then_some(ImageStatus::Pending)
+            }
+        };
+
+        let file = self
+            .repository
+            .create_file(
+                &claims.event,
+                NewFile {
+                    name: clean_file_name(&upload.name),
+                    content_type: content_type.to_string(),
+                    size,
+                    sha256,
+                    storage_key,
+                    image_status: image_status.clone(),
+                },
+                &claims.sub,
+            )
+            .await?;
+
+        if let Some(existing) = existing
+            && image_status == Some(ImageStatus::Ready)
+        {
+            let variants = self.repository.find_variants(&existing.id).await?;
+            self.repository.save_variants(&file.id, &variants).await?;
+        }
+        if image_status == Some(ImageStatus::Pending) {
+            // the worker also picks up anything still pending when it starts, so a lost send is fine
+            let _ = self.queue.send(file.id.clone());
+        }
+
+        Ok(file)
+    }
+
+    // works through queued images one at a time, starting with any left over from before a restart
+    pub fn spawn_image_worker(self: Arc<Self>) {
+        let Some(mut queued) = self.queued.lock().unwrap().take() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            match self.repository.find_pending_images().await {
+                Ok(ids) => {
+                    for id in ids {
+                        self.run_image(&id).await;
+                    }
+                }
+                Err(err) => eprintln!("Looking up pending images failed: {}", err),
+            }
+
+            while let Some(id) = queued.recv().await {
+                self.run_image(&id).await;
+            }
+        });
+    }
+
+    async fn run_image(&self, id: &str) {
+        if let Err(err) = self.process_image(id).await {
+            eprintln!("Processing image {} failed: {}", id, err);
+        }
+    }
+
+    // makes the variants and then drops the upload itself, which still has its EXIF data
+    pub async fn process_image(&self, id: &str) -> Result<(), AppError> {
+        let Some((file, storage_key)) = self.repository.find_stored(id).await? else {
+            return Ok(());
+        };
+        if file.image_status != Some(ImageStatus::Pending) {
+            return Ok(());
+        }
+
+        let Some(bytes) = self.storage.get(&storage_key).await? else {
+            return self
+                .repository
+                .set_image_status(id, ImageStatus::Failed)
+                .await;
+        };
+        let processed = tokio::task::spawn_blocking(move || images::process(&bytes))
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let processed = match processed {
+            Ok(processed) => processed,
+            Err(err) => {
+                eprintln!("Image {} could not be decoded: {}", id, err);
+                return self
+                    .repository
+                    .set_image_status(id, ImageStatus::Failed)
+                    .await;
+            }
+        };
+
+        let mut variants = Vec::with_capacity(processed.len());
+        for image in processed {
+            let key = variant_key(&file.sha256, image.kind);
+            let size = image.bytes.len() as u64;
+            self.storage.put(&key, image.bytes, "image/webp").await?;
+            variants.push(Variant {
+                kind: image.kind,
+                content_type: "image/webp".to_string(),
+                width: image.width,
+                height: image.height,
+                size,
+                storage_key: key,
+            });
+        }
+        self.repository.save_variants(id, &variants).await?;
+
+        if !self.repository.is_key_pending(&storage_key).await? {
+            self.storage.delete(&storage_key).await?;
+        }
+
+        Ok(())
+    }
+
+    // managers see every upload, everyone else their own
+    pub async fn get_files(
+        &self,
+        filter: FileFilter,
+        claims: &Claims,
+    ) -> Result<Vec<StoredFile>, AppError> {
+        let filter = if claims.is_manager() {
+            filter
+        } else {
+            FileFilter {
+                uploaded_by: Some(claims.sub.clone()),
+            }
+        };
+
+        self.repository.find_files(&claims.event, &filter).await
+    }
+
+    pub async fn get_file(&self, id: &str, claims: &Claims) -> Result<StoredFile, AppError> {
+        self.find_file(id, claims).await
+    }
+
+    // anyone in the event can link a file, the link works without a token until it expires
+    pub async fn get_download_url(
+        &self,
+        id: &str,
+        query: UrlQuery,
+        claims: &Claims,
+    ) -> Result<SignedUrl, AppError> {
+        let file = self.find_file(id, claims).await?;
+        if query.variant.is_some() && file.image_status.is_none() {
+            return Err(AppError::BadRequest(
+                "Only images have variants".to_string(),
+            ));
+        }
+
+        Ok(self.signer.sign(&file.id, query.variant))
+    }
+
+    // images are only ever served as their processed variants
+    pub async fn download(&self, id: &str, query: &DownloadQuery) -> Result<FileContent, AppError> {
+        self.signer.verify(id, query)?;
+
+        let (file, storage_key) = self
+            .repository
+            .find_stored(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+
+        let (key, content_type, name) = match file.image_status {
+            None => (storage_key, file.content_type, file.name),
+            Some(ImageStatus::Ready) => {
+                let variant = self
+                    .repository
+                    .find_variant(id, query.variant.unwrap_or(VariantKind::Original))
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+                (
+                    variant.storage_key,
+                    variant.content_type,
+                    webp_name(&file.name),
+                )
+            }
+            Some(ImageStatus::Pending) => {
+                return Err(AppError::NotFound(
+                    "Image is still being processed".to_string(),
+                ));
+            }
+            Some(ImageStatus::Failed) => {
+                return Err(AppError::NotFound(
+                    "Image could not be processed".to_string(),
+                ));
+            }
+        };
+
+        let bytes = self
+            .storage
+            .get(&key)
+            .await?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+
+        Ok(FileContent {
+            name,
+            content_type,
+            bytes,
+        })
+    }
+
+    pub async fn delete_file(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        let file = self.find_file(id, claims).await?;
+        if file.uploaded_by != claims.sub && !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+        let posts = self.socials.find_using_file(&claims.event, id).await?;
+        if !posts.is_empty() {
+            return Err(AppError::Conflict(
+                format!(
+                    "File is used by {} social post(s) past draft, take it out of them first",
+                    posts.len()
+                ),
+                serde_json::to_value(&posts).map_err(|_| AppError::InternalServerError)?,
+            ));
+        }
+
+        let storage_key = self.repository.delete_file(&claims.event, id).await?;
+        self.delete_content(&file, &storage_key).await
+    }
+
+    // for the data export, which has already checked who is asking
+    pub async fn get_uploads(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<StoredFile>, AppError> {
+        let filter = FileFilter {
+            uploaded_by: Some(user_id.to_string()),
+        };
+
+        self.repository.find_files(event_id, &filter).await
+    }
+
+    // for erasure, which has already decided the file goes whatever event it was uploaded to
+    pub async fn erase_file(&self, id: &str) -> Result<(), AppError> {
+        let Some((file, storage_key)) = self.repository.find_stored(id).await? else {
+            return Ok(());
+        };
+
+        self.repository.delete_stored(id).await?;
+        self.delete_content(&file, &storage_key).await
+    }
+
+    // the bytes and variants stay while another upload shares the content
+    async fn delete_content(&self, file: &StoredFile, storage_key: &str) -> Result<(), AppError> {
+        if self.repository.is_key_used(storage_key).await? {
+            return Ok(());
+        }
+
+        self.storage.delete(storage_key).await?;
+        if file.image_status.is_some() {
+            for kind in [
+                VariantKind::Thumbnail,
+                VariantKind::Medium,
+                VariantKind::Original,
+            ] {
+                self.storage
+                    .delete(&variant_key(&file.sha256, kind))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn find_file(&self, id: &str, claims: &Claims) -> Result<StoredFile, AppError> {
+        self.repository
+            .find_file(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))
+    }
+}
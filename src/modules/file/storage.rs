@@ -0,0 +1,143 @@
+// S3 and S3-compatible stores like MinIO or R2
+#[cfg(feature = "s3")]
+pub mod s3;
+
+use crate::error::AppError;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+// where uploaded bytes end up, keyed by content hash so the same file is only stored once
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), AppError>;
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError>;
+    async fn exists(&self, key: &str) -> Result<bool, AppError>;
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+// keys are generated by the server, but never let one step out of the storage root
+fn check_key(key: &str) -> Result<(), AppError> {
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !key.starts_with('.');
+    if !valid {
+        return Err(AppError::InternalServerError);
+    }
+
+    Ok(())
+}
+
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        check_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        tokio::fs::create_dir_all(&self.root)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        // written next to it first, so a half-written file is never served
+        let partial = self.root.join(format!("{}.partial", key));
+        tokio::fs::write(&partial, bytes)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .map_err(|_| AppError::InternalServerError)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(_) => Err(AppError::InternalServerError),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError> {
+        tokio::fs::try_exists(self.path(key)?)
+            .await
+            .map_err(|_| AppError::InternalServerError)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(_) => Err(AppError::InternalServerError),
+        }
+    }
+}
+
+// for tests and throwaway dev servers, everything is gone on restart
+#[derive(Default)]
+pub struct MemoryStorage {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), AppError> {
+        check_key(key)?;
+        self.objects.lock().unwrap().insert(key.to_string(), bytes);
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        Ok(self.objects.lock().unwrap().get(key).cloned())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError> {
+        Ok(self.objects.lock().unwrap().contains_key(key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.objects.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+// picks the backend named by the STORAGE setting
+pub fn from_config(kind: &str, upload_dir: &str) -> Arc<dyn Storage> {
+    match kind {
+        "local" => Arc::new(LocalStorage::new(upload_dir)),
+        "memory" => Arc::new(MemoryStorage::new()),
+        #[cfg(feature = "s3")]
+        "s3" => Arc::new(s3::S3Storage::from_env()),
+        #[cfg(not(feature = "s3"))]
+        "s3" => panic!("STORAGE=s3 needs the server built with the s3 feature"),
+        other => panic!("Unknown STORAGE '{}', use local, memory or s3", other),
+    }
+}
@@ -0,0 +1,110 @@
+use super::{Storage, check_key};
+use crate::error::AppError;
+use async_trait::async_trait;
+use aws_sdk_s3::Client;
+use aws_sdk_s3::config::{BehaviorVersion, Builder, Credentials, Region};
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::primitives::ByteStream;
+use std::env;
+
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+}
+
+impl S3Storage {
+    pub fn new(client: Client, bucket: impl Into<String>) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+        }
+    }
+
+    // S3_ENDPOINT points it at anything that speaks the S3 API instead of AWS itself
+    pub fn from_env() -> Self {
+        let bucket = env::var("S3_BUCKET").expect("S3_BUCKET must be set for STORAGE=s3");
+        let region = env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        let access_key =
+            env::var("S3_ACCESS_KEY_ID").expect("S3_ACCESS_KEY_ID must be set for STORAGE=s3");
+        let secret_key = env::var("S3_SECRET_ACCESS_KEY")
+            .expect("S3_SECRET_ACCESS_KEY must be set for STORAGE=s3");
+
+        let mut config = Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(region))
+            .credentials_provider(Credentials::new(access_key, secret_key, None, None, "env"))
+            .force_path_style(true);
+        if let Ok(endpoint) = env::var("S3_ENDPOINT") {
+            config = config.endpoint_url(endpoint);
+        }
+
+        Self::new(Client::from_conf(config.build()), bucket)
+    }
+}
+
+#[async_trait]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), AppError> {
+        check_key(key)?;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(SdkError::ServiceError(e)) if e.err().is_no_such_key() => return Ok(None),
+            Err(_) => return Err(AppError::InternalServerError),
+        };
+
+        let body = output
+            .body
+            .collect()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        Ok(Some(body.into_bytes().to_vec()))
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(SdkError::ServiceError(e)) if e.err().is_not_found() => Ok(false),
+            Err(_) => Err(AppError::InternalServerError),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod entity;
+pub mod models;
+pub mod qr;
+pub mod repository;
+pub mod routes;
+pub mod service;
+pub mod sheet;
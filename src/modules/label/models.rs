@@ -0,0 +1,168 @@
+use super::entity;
+use crate::logistics::models::Asset;
+use crate::user::models::User;
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+// crockford base32, no I, L, O or U so codes survive being read out loud
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const CODE_LENGTH: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelKind {
+    #[display("asset")]
+    Asset,
+    #[display("user")]
+    User,
+}
+
+impl LabelKind {
+    pub fn prefix(&self) -> char {
+        match self {
+            LabelKind::Asset => 'A',
+            LabelKind::User => 'U',
+        }
+    }
+}
+
+impl From<LabelKind> for entity::Kind {
+    fn from(item: LabelKind) -> Self {
+        match item {
+            LabelKind::Asset => entity::Kind::Asset,
+            LabelKind::User => entity::Kind::User,
+        }
+    }
+}
+
+impl From<entity::Kind> for LabelKind {
+    fn from(item: entity::Kind) -> Self {
+        match item {
+            entity::Kind::Asset => LabelKind::Asset,
+            entity::Kind::User => LabelKind::User,
+        }
+    }
+}
+
+// derived from the id, so the same thing always gets the same code,
+// later attempts salt it when the first one is taken by something else
+pub fn short_code(kind: LabelKind, id: &str, attempt: u32) -> String {
+    let salt = if attempt == 0 {
+        String::new()
+    } else {
+        format!("#{}", attempt)
+    };
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in id.bytes().chain(salt.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    let mut code = String::with_capacity(CODE_LENGTH + 1);
+    code.push(kind.prefix());
+    for i in (0..CODE_LENGTH).rev() {
+        code.push(ALPHABET[((hash >> (i * 5)) & 31) as usize] as char);
+    }
+    code
+}
+
+// scanners and people mix up cases and look-alike characters
+pub fn normalize_code(code: &str) -> String {
+    code.trim()
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScanCode {
+    pub code: String,
+    pub kind: LabelKind,
+    pub target_id: String,
+}
+
+impl From<entity::Model> for ScanCode {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            code: model.code,
+            kind: model.kind.into(),
+            target_id: model.target_id,
+        }
+    }
+}
+
+// what gets printed, the code plus two lines of text under it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Label {
+    pub code: String,
+    pub kind: LabelKind,
+    pub target_id: String,
+    pub title: String,
+    pub subtitle: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QrFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct QrQuery {
+    #[serde(default)]
+    pub format: QrFormat,
+    // width in pixels, 256 when left out
+    pub size: Option<u32>,
+}
+
+pub struct LabelImage {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct LabelSheetRequest {
+    #[serde(default)]
+    pub assets: Vec<String>,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanAction {
+    View,
+    ViewHistory,
+    ViewItems,
+    Edit,
+    Delete,
+    ChangeStatus,
+    CheckOut,
+    CheckIn,
+    Activate,
+    Deactivate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", content = "entity", rename_all = "snake_case")]
+pub enum Scanned {
+    Asset(Asset),
+    User(User),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScanResult {
+    pub code: String,
+    #[serde(flatten)]
+    pub target: Scanned,
+    // what the caller can do from here, depends on their role
+    pub actions: Vec<ScanAction>,
+}
@@ -0,0 +1,36 @@
+use crate::error::AppError;
+use qrcode::render::svg;
+use qrcode::{Color, EcLevel, QrCode};
+use std::io::Cursor;
+
+// medium error correction, labels get scratched and bent
+fn encode(data: &str) -> Result<QrCode, AppError> {
+    QrCode::with_error_correction_level(data, EcLevel::M).map_err(|_| AppError::InternalServerError)
+}
+
+pub fn svg(data: &str, size: u32) -> Result<String, AppError> {
+    Ok(encode(data)?
+        .render::<svg::Color>()
+        .min_dimensions(size, size)
+        .build())
+}
+
+pub fn png(data: &str, size: u32) -> Result<Vec<u8>, AppError> {
+    let image = encode(data)?
+        .render::<image::Luma<u8>>()
+        .min_dimensions(size, size)
+        .build();
+
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok(bytes)
+}
+
+// the raw grid, row by row, for drawing the code by hand
+pub fn modules(data: &str) -> Result<(usize, Vec<bool>), AppError> {
+    let code = encode(data)?;
+    let dark = code.to_colors().into_iter().map(|c| c == Color::Dark);
+    Ok((code.width(), dark.collect()))
+}
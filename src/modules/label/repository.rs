@@ -0,0 +1,73 @@
+use super::entity::{self, ActiveModel, Column, Entity as ScanCodeEntity};
+use super::models::{LabelKind, ScanCode, short_code};
+use crate::error::AppError;
+use sea_orm::*;
+use std::sync::Arc;
+
+// 40 bits per code, running out of salts means something else is wrong
+const MAX_CODE_ATTEMPTS: u32 = 8;
+
+pub struct LabelRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl LabelRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_by_code(&self, code: &str) -> Result<Option<ScanCode>, AppError> {
+        let model = ScanCodeEntity::find_by_id(code.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // the stored code if there is one, otherwise a new one is saved
+    pub async fn code_for(&self, kind: LabelKind, target_id: &str) -> Result<String, AppError> {
+        if let Some(code) = self.find_code(kind, target_id).await? {
+            return Ok(code);
+        }
+
+        for attempt in 0..MAX_CODE_ATTEMPTS {
+            let result = ActiveModel {
+                code: Set(short_code(kind, target_id, attempt)),
+                kind: Set(kind.into()),
+                target_id: Set(target_id.to_string()),
+                created_at: Set(chrono::Utc::now()),
+            }
+            .insert(self.db.as_ref())
+            .await;
+
+            match result {
+                Ok(model) => return Ok(model.code),
+                // a code for the same thing saved meanwhile, or this code belongs to something else
+                Err(err) if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                    if let Some(code) = self.find_code(kind, target_id).await? {
+                        return Ok(code);
+                    }
+                }
+                Err(_) => return Err(AppError::InternalServerError),
+            }
+        }
+
+        Err(AppError::InternalServerError)
+    }
+
+    async fn find_code(
+        &self,
+        kind: LabelKind,
+        target_id: &str,
+    ) -> Result<Option<String>, AppError> {
+        let model = ScanCodeEntity::find()
+            .filter(Column::Kind.eq(entity::Kind::from(kind)))
+            .filter(Column::TargetId.eq(target_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.code))
+    }
+}
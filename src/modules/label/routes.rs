@@ -0,0 +1,134 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::label::models::{LabelImage, LabelKind, LabelSheetRequest, QrQuery};
+use crate::modules::label::service::LabelService;
+use actix_web::http::header::{ContentDisposition, ContentType};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/labels")
+            .wrap(HttpAuthentication::bearer(jwt_validator))
+            .route("/assets/{id}", web::get().to(get_asset_label))
+            .route("/assets/{id}/qr", web::get().to(get_asset_qr))
+            .route("/users/{id}", web::get().to(get_user_label))
+            .route("/users/{id}/qr", web::get().to(get_user_qr))
+            .route("/sheet", web::post().to(get_sheet)),
+    )
+    .service(
+        web::scope("/scan")
+            .wrap(HttpAuthentication::bearer(jwt_validator))
+            .route("/{code}", web::get().to(scan)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+fn image_response(image: LabelImage) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(ContentType(image.content_type.parse().unwrap()))
+        .insert_header(ContentDisposition::attachment(image.file_name))
+        .body(image.bytes)
+}
+
+async fn get_label(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    kind: LabelKind,
+    id: String,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let label = service.get_label(kind, &id, &claims).await?;
+    Ok(HttpResponse::Ok().json(label))
+}
+
+async fn get_qr(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    kind: LabelKind,
+    id: String,
+    query: QrQuery,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let image = service.get_qr(kind, &id, query, &claims).await?;
+    Ok(image_response(image))
+}
+
+async fn get_asset_label(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    get_label(req, service, LabelKind::Asset, path.into_inner()).await
+}
+
+async fn get_asset_qr(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    path: web::Path<String>,
+    query: web::Query<QrQuery>,
+) -> Result<HttpResponse, AppError> {
+    get_qr(
+        req,
+        service,
+        LabelKind::Asset,
+        path.into_inner(),
+        query.into_inner(),
+    )
+    .await
+}
+
+async fn get_user_label(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    get_label(req, service, LabelKind::User, path.into_inner()).await
+}
+
+async fn get_user_qr(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    path: web::Path<String>,
+    query: web::Query<QrQuery>,
+) -> Result<HttpResponse, AppError> {
+    get_qr(
+        req,
+        service,
+        LabelKind::User,
+        path.into_inner(),
+        query.into_inner(),
+    )
+    .await
+}
+
+async fn get_sheet(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    body: web::Json<LabelSheetRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let sheet = service.get_sheet(body.into_inner(), &claims).await?;
+    Ok(image_response(sheet))
+}
+
+async fn scan(
+    req: HttpRequest,
+    service: web::Data<LabelService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let result = service.scan(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
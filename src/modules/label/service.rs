@@ -0,0 +1,241 @@
+use super::models::{
+    Label, LabelImage, LabelKind, LabelSheetRequest, QrFormat, QrQuery, ScanAction, ScanResult,
+    Scanned, normalize_code,
+};
+use super::repository::LabelRepository;
+use super::{qr, sheet};
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::logistics::models::{Asset, AssetStatus};
+use crate::logistics::repository::LogisticsRepository;
+use crate::user::models::{User, UserStatus};
+use crate::user::repository::UserRepository;
+
+const DEFAULT_QR_SIZE: u32 = 256;
+const MIN_QR_SIZE: u32 = 64;
+const MAX_QR_SIZE: u32 = 2048;
+// twenty full pages
+const MAX_SHEET_LABELS: usize = 480;
+
+pub struct LabelService {
+    repository: LabelRepository,
+    logistics: LogisticsRepository,
+    users: UserRepository,
+}
+
+impl LabelService {
+    pub fn new(
+        repository: LabelRepository,
+        logistics: LogisticsRepository,
+        users: UserRepository,
+    ) -> Self {
+        Self {
+            repository,
+            logistics,
+            users,
+        }
+    }
+
+    // asset labels for anyone who handles gear, badges for organizers and the user themselves
+    pub async fn get_label(
+        &self,
+        kind: LabelKind,
+        id: &str,
+        claims: &Claims,
+    ) -> Result<Label, AppError> {
+        let (title, subtitle) = match kind {
+            LabelKind::Asset => {
+                if !claims.is_staff() {
+                    return Err(AppError::Forbidden);
+                }
+                let asset = self.find_asset(id, claims).await?;
+                (asset.name, asset.category)
+            }
+            LabelKind::User => {
+                if claims.sub != id && !claims.is_manager() {
+                    return Err(AppError::Forbidden);
+                }
+                let user = self.find_user(id, claims).await?;
+                (
+                    format!("{} {}", user.name, user.surname),
+                    user.role.to_string(),
+                )
+            }
+        };
+
+        let code = self.repository.code_for(kind, id).await?;
+        Ok(Label {
+            code,
+            kind,
+            target_id: id.to_string(),
+            title,
+            subtitle,
+        })
+    }
+
+    pub async fn get_qr(
+        &self,
+        kind: LabelKind,
+        id: &str,
+        query: QrQuery,
+        claims: &Claims,
+    ) -> Result<LabelImage, AppError> {
+        let size = query.size.unwrap_or(DEFAULT_QR_SIZE);
+        if !(MIN_QR_SIZE..=MAX_QR_SIZE).contains(&size) {
+            return Err(AppError::BadRequest(format!(
+                "Size must be between {} and {} pixels",
+                MIN_QR_SIZE, MAX_QR_SIZE
+            )));
+        }
+
+        let label = self.get_label(kind, id, claims).await?;
+        let image = match query.format {
+            QrFormat::Svg => LabelImage {
+                file_name: format!("{}.svg", label.code),
+                content_type: "image/svg+xml",
+                bytes: qr::svg(&label.code, size)?.into_bytes(),
+            },
+            QrFormat::Png => LabelImage {
+                file_name: format!("{}.png", label.code),
+                content_type: "image/png",
+                bytes: qr::png(&label.code, size)?,
+            },
+        };
+        Ok(image)
+    }
+
+    // assets first, then users, in the order they were asked for
+    pub async fn get_sheet(
+        &self,
+        req: LabelSheetRequest,
+        claims: &Claims,
+    ) -> Result<LabelImage, AppError> {
+        let total = req.assets.len() + req.users.len();
+        if total == 0 {
+            return Err(AppError::BadRequest(
+                "Pick at least one asset or user".to_string(),
+            ));
+        }
+        if total > MAX_SHEET_LABELS {
+            return Err(AppError::BadRequest(format!(
+                "At most {} labels fit in one print",
+                MAX_SHEET_LABELS
+            )));
+        }
+
+        let mut labels = Vec::with_capacity(total);
+        for id in &req.assets {
+            labels.push(self.get_label(LabelKind::Asset, id, claims).await?);
+        }
+        for id in &req.users {
+            labels.push(self.get_label(LabelKind::User, id, claims).await?);
+        }
+
+        Ok(LabelImage {
+            file_name: "labels.pdf".to_string(),
+            content_type: "application/pdf",
+            bytes: sheet::render(&labels)?,
+        })
+    }
+
+    pub async fn scan(&self, code: &str, claims: &Claims) -> Result<ScanResult, AppError> {
+        let scanned = self
+            .repository
+            .find_by_code(&normalize_code(code))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Nothing is labelled with this code".to_string()))?;
+
+        let (target, actions) = match scanned.kind {
+            LabelKind::Asset => {
+                if !claims.is_staff() {
+                    return Err(AppError::Forbidden);
+                }
+                let asset = self.find_asset(&scanned.target_id, claims).await?;
+                let out = self.logistics.checked_out_quantity(&asset.id).await?;
+                let actions = asset_actions(&asset, out, claims);
+                (Scanned::Asset(asset), actions)
+            }
+            LabelKind::User => {
+                let user = self.find_user(&scanned.target_id, claims).await?;
+                let holds_items = claims.is_staff()
+                    && !self
+                        .logistics
+                        .find_held_items(&claims.event, &user.id)
+                        .await?
+                        .is_empty();
+                let actions = user_actions(&user, holds_items, claims);
+                (Scanned::User(user), actions)
+            }
+        };
+
+        Ok(ScanResult {
+            code: scanned.code,
+            target,
+            actions,
+        })
+    }
+
+    async fn find_asset(&self, id: &str, claims: &Claims) -> Result<Asset, AppError> {
+        self.logistics
+            .find_asset(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Asset not found".to_string()))
+    }
+
+    async fn find_user(&self, id: &str, claims: &Claims) -> Result<User, AppError> {
+        self.users
+            .find_by_id(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+}
+
+// mirrors the checks in the logistics service, so the app only offers what will go through
+fn asset_actions(asset: &Asset, checked_out: u32, claims: &Claims) -> Vec<ScanAction> {
+    let mut actions = vec![ScanAction::View, ScanAction::ViewHistory];
+
+    let handed_out = matches!(asset.status, AssetStatus::Delivered | AssetStatus::InUse);
+    if handed_out && checked_out < asset.quantity {
+        actions.push(ScanAction::CheckOut);
+    }
+    if checked_out > 0 {
+        actions.push(ScanAction::CheckIn);
+    }
+
+    let can_advance = !matches!(asset.status, AssetStatus::Returned | AssetStatus::Lost);
+    if claims.is_manager() || can_advance {
+        actions.push(ScanAction::ChangeStatus);
+    }
+    if claims.is_manager() {
+        actions.push(ScanAction::Edit);
+        actions.push(ScanAction::Delete);
+    }
+
+    actions
+}
+
+fn user_actions(user: &User, holds_items: bool, claims: &Claims) -> Vec<ScanAction> {
+    let is_self = claims.sub == user.id;
+    let mut actions = vec![ScanAction::View];
+
+    if is_self || claims.is_staff() {
+        actions.push(ScanAction::ViewItems);
+    }
+    if claims.is_staff() && user.status == UserStatus::Active {
+        actions.push(ScanAction::CheckOut);
+    }
+    if holds_items {
+        actions.push(ScanAction::CheckIn);
+    }
+    if is_self || claims.is_manager() {
+        actions.push(ScanAction::Edit);
+    }
+    if claims.is_manager() && !is_self {
+        actions.push(match user.status {
+            UserStatus::Active => ScanAction::Deactivate,
+            UserStatus::Inactive => ScanAction::Activate,
+        });
+    }
+
+    actions
+}
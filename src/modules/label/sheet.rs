@@ -0,0 +1,136 @@
+use super::models::Label;
+use super::qr;
+use crate::error::AppError;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+
+// a4 with 3 by 8 labels of 70 x 37 mm, the common sticker sheet layout
+const PAGE_WIDTH: f32 = 595.28;
+const PAGE_HEIGHT: f32 = 841.89;
+const COLUMNS: usize = 3;
+const ROWS: usize = 8;
+const PADDING: f32 = 8.0;
+
+const TITLE_FONT: Name = Name(b"F1");
+const TEXT_FONT: Name = Name(b"F2");
+const CODE_FONT: Name = Name(b"F3");
+
+pub fn render(labels: &[Label]) -> Result<Vec<u8>, AppError> {
+    let per_page = COLUMNS * ROWS;
+    let pages: Vec<&[Label]> = labels.chunks(per_page).collect();
+
+    let catalog_id = Ref::new(1);
+    let tree_id = Ref::new(2);
+    let fonts = [
+        (TITLE_FONT, Ref::new(3), Name(b"Helvetica-Bold")),
+        (TEXT_FONT, Ref::new(4), Name(b"Helvetica")),
+        (CODE_FONT, Ref::new(5), Name(b"Courier-Bold")),
+    ];
+    // every page takes two ids, the page itself and its content stream
+    let page_ids: Vec<Ref> = (0..pages.len())
+        .map(|i| Ref::new(6 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(tree_id);
+    pdf.pages(tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_ids.len() as i32);
+    for (_, id, base) in fonts {
+        pdf.type1_font(id)
+            .base_font(base)
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+
+    for (page_labels, page_id) in pages.iter().zip(&page_ids) {
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut page = pdf.page(*page_id);
+        page.parent(tree_id)
+            .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .contents(content_id);
+        let mut resources = page.resources();
+        let mut font_dict = resources.fonts();
+        for (name, id, _) in fonts {
+            font_dict.pair(name, id);
+        }
+        font_dict.finish();
+        resources.finish();
+        page.finish();
+
+        let mut content = Content::new();
+        for (i, label) in page_labels.iter().enumerate() {
+            draw_label(&mut content, label, i % COLUMNS, i / COLUMNS)?;
+        }
+        pdf.stream(content_id, &content.finish());
+    }
+
+    Ok(pdf.finish())
+}
+
+fn draw_label(
+    content: &mut Content,
+    label: &Label,
+    column: usize,
+    row: usize,
+) -> Result<(), AppError> {
+    let cell_width = PAGE_WIDTH / COLUMNS as f32;
+    let cell_height = PAGE_HEIGHT / ROWS as f32;
+    // pdf coordinates start at the bottom left
+    let x = column as f32 * cell_width;
+    let y = PAGE_HEIGHT - (row + 1) as f32 * cell_height;
+
+    let side = cell_height - 2.0 * PADDING;
+    let (width, dark) = qr::modules(&label.code)?;
+    let module = side / width as f32;
+    content.set_fill_gray(0.0);
+    for (i, _) in dark.iter().enumerate().filter(|(_, d)| **d) {
+        let (mx, my) = (i % width, i / width);
+        content.rect(
+            x + PADDING + mx as f32 * module,
+            y + PADDING + side - (my + 1) as f32 * module,
+            module,
+            module,
+        );
+    }
+    content.fill_nonzero();
+
+    let text_x = x + 2.0 * PADDING + side;
+    let top = y + cell_height - PADDING;
+    let lines = [
+        (TITLE_FONT, 10.0, 14.0, truncate(&label.title, 16)),
+        (TEXT_FONT, 8.0, 12.0, truncate(&label.subtitle, 20)),
+        (CODE_FONT, 11.0, 22.0, label.code.clone()),
+    ];
+
+    let mut baseline = top;
+    for (font, size, step, text) in lines {
+        baseline -= step;
+        content
+            .begin_text()
+            .set_font(font, size)
+            .next_line(text_x, baseline)
+            .show(Str(&win_ansi(&text)))
+            .end_text();
+    }
+
+    Ok(())
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(max - 1).collect();
+    short.push('…');
+    short
+}
+
+// the built in fonts only know latin-1, anything else shows up as a question mark
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '…' => 0x85,
+            ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
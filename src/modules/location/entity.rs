@@ -0,0 +1,44 @@
+use derive_more::Display;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::StringLen;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Kind {
+    #[sea_orm(string_value = "venue")]
+    Venue,
+    #[sea_orm(string_value = "area")]
+    Area,
+    #[sea_orm(string_value = "zone")]
+    Zone,
+    #[sea_orm(string_value = "point")]
+    Point,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "locations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub event_id: String,
+    // None for venues only
+    pub parent_id: Option<String>,
+    pub kind: Kind,
+    pub name: String,
+    pub description: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    // image url or file key of the plan drawn for this location
+    pub floor_plan: Option<String>,
+    // where it sits on the parent's floor plan, as fractions from the top left
+    pub floor_x: Option<f64>,
+    pub floor_y: Option<f64>,
+    pub created_at: ChronoDateTimeUtc,
+    pub updated_at: ChronoDateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,204 @@
+use super::entity;
+use crate::logistics::models::Asset;
+use crate::planner::models::{Shift, Task};
+use crate::user::models::{User, UserRole};
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationKind {
+    #[display("Venue")]
+    Venue,
+    #[display("Area")]
+    Area,
+    #[display("Zone")]
+    Zone,
+    #[display("Point")]
+    Point,
+}
+
+impl LocationKind {
+    // venue -> area -> zone -> point, venues sit at the top
+    pub fn parent(&self) -> Option<LocationKind> {
+        match self {
+            LocationKind::Venue => None,
+            LocationKind::Area => Some(LocationKind::Venue),
+            LocationKind::Zone => Some(LocationKind::Area),
+            LocationKind::Point => Some(LocationKind::Zone),
+        }
+    }
+}
+
+impl From<LocationKind> for entity::Kind {
+    fn from(item: LocationKind) -> Self {
+        match item {
+            LocationKind::Venue => entity::Kind::Venue,
+            LocationKind::Area => entity::Kind::Area,
+            LocationKind::Zone => entity::Kind::Zone,
+            LocationKind::Point => entity::Kind::Point,
+        }
+    }
+}
+
+impl From<entity::Kind> for LocationKind {
+    fn from(item: entity::Kind) -> Self {
+        match item {
+            entity::Kind::Venue => LocationKind::Venue,
+            entity::Kind::Area => LocationKind::Area,
+            entity::Kind::Zone => LocationKind::Zone,
+            entity::Kind::Point => LocationKind::Point,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Location {
+    pub id: String,
+    pub parent_id: Option<String>,
+    pub kind: LocationKind,
+    pub name: String,
+    pub description: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub floor_plan: Option<String>,
+    pub floor_x: Option<f64>,
+    pub floor_y: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<entity::Model> for Location {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            id: model.id,
+            parent_id: model.parent_id,
+            kind: model.kind.into(),
+            name: model.name,
+            description: model.description,
+            latitude: model.latitude,
+            longitude: model.longitude,
+            floor_plan: model.floor_plan,
+            floor_x: model.floor_x,
+            floor_y: model.floor_y,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LocationNode {
+    #[serde(flatten)]
+    pub location: Location,
+    pub children: Vec<LocationNode>,
+}
+
+// nests a flat list under its parents, keeping the order it came in
+pub fn build_tree(locations: Vec<Location>) -> Vec<LocationNode> {
+    let mut children: HashMap<Option<String>, Vec<Location>> = HashMap::new();
+    for location in locations {
+        children
+            .entry(location.parent_id.clone())
+            .or_default()
+            .push(location);
+    }
+
+    fn attach(
+        parent: Option<String>,
+        children: &mut HashMap<Option<String>, Vec<Location>>,
+    ) -> Vec<LocationNode> {
+        children
+            .remove(&parent)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|location| LocationNode {
+                children: attach(Some(location.id.clone()), children),
+                location,
+            })
+            .collect()
+    }
+
+    attach(None, &mut children)
+}
+
+// the location itself and everything below it
+pub fn subtree_ids(locations: &[Location], id: &str) -> Vec<String> {
+    let mut ids = vec![id.to_string()];
+    let mut i = 0;
+    while i < ids.len() {
+        let parent = ids[i].clone();
+        ids.extend(
+            locations
+                .iter()
+                .filter(|l| l.parent_id.as_deref() == Some(parent.as_str()))
+                .map(|l| l.id.clone()),
+        );
+        i += 1;
+    }
+    ids
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateLocationRequest {
+    pub name: String,
+    pub kind: LocationKind,
+    // required for everything but venues
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub floor_plan: Option<String>,
+    pub floor_x: Option<f64>,
+    pub floor_y: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateLocationRequest {
+    pub name: Option<String>,
+    // moves it under another location of the same level
+    pub parent_id: Option<String>,
+    pub description: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub floor_plan: Option<String>,
+    pub floor_x: Option<f64>,
+    pub floor_y: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct DeleteLocationQuery {
+    // takes everything below it along
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocationContents {
+    pub location: Location,
+    // everything below it, flat
+    pub sublocations: Vec<Location>,
+    pub shifts: Vec<Shift>,
+    pub tasks: Vec<Task>,
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct OnShiftQuery {
+    // defaults to now
+    pub at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OnShift {
+    pub user: User,
+    pub role: UserRole,
+    pub shift_id: String,
+    pub shift_title: String,
+    pub location_id: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
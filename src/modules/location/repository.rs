@@ -0,0 +1,126 @@
+use super::entity::{ActiveModel, Column, Entity as LocationEntity};
+use super::models::{CreateLocationRequest, Location, UpdateLocationRequest};
+use crate::error::AppError;
+use sea_orm::*;
+use std::sync::Arc;
+
+pub struct LocationRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl LocationRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_all(&self, event_id: &str) -> Result<Vec<Location>, AppError> {
+        let models = LocationEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .order_by_asc(Column::Name)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_by_id(&self, event_id: &str, id: &str) -> Result<Option<Location>, AppError> {
+        let model = LocationEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    pub async fn exists(&self, event_id: &str, id: &str) -> Result<bool, AppError> {
+        Ok(self.find_by_id(event_id, id).await?.is_some())
+    }
+
+    pub async fn create(
+        &self,
+        event_id: &str,
+        dto: CreateLocationRequest,
+    ) -> Result<Location, AppError> {
+        let now = chrono::Utc::now();
+        let model = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            parent_id: Set(dto.parent_id),
+            kind: Set(dto.kind.into()),
+            name: Set(dto.name),
+            description: Set(dto.description),
+            latitude: Set(dto.latitude),
+            longitude: Set(dto.longitude),
+            floor_plan: Set(dto.floor_plan),
+            floor_x: Set(dto.floor_x),
+            floor_y: Set(dto.floor_y),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(self.db.as_ref())
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.into())
+    }
+
+    pub async fn update(&self, id: &str, dto: UpdateLocationRequest) -> Result<Location, AppError> {
+        let model = LocationEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Location not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(name) = dto.name {
+            active_model.name = Set(name);
+        }
+        if let Some(parent_id) = dto.parent_id {
+            active_model.parent_id = Set(Some(parent_id));
+        }
+        if let Some(description) = dto.description {
+            active_model.description = Set(description);
+        }
+        if let Some(latitude) = dto.latitude {
+            active_model.latitude = Set(Some(latitude));
+        }
+        if let Some(longitude) = dto.longitude {
+            active_model.longitude = Set(Some(longitude));
+        }
+        if let Some(floor_plan) = dto.floor_plan {
+            active_model.floor_plan = Set(Some(floor_plan));
+        }
+        if let Some(floor_x) = dto.floor_x {
+            active_model.floor_x = Set(Some(floor_x));
+        }
+        if let Some(floor_y) = dto.floor_y {
+            active_model.floor_y = Set(Some(floor_y));
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    // sublocations go with it, shifts, tasks and assets just lose the reference
+    pub async fn delete(&self, event_id: &str, id: &str) -> Result<(), AppError> {
+        let result = LocationEntity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::EventId.eq(event_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Location not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::location::models::{
+    CreateLocationRequest, DeleteLocationQuery, OnShiftQuery, UpdateLocationRequest,
+};
+use crate::modules::location::service::LocationService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/locations")
+            .wrap(auth_middleware)
+            .route("", web::get().to(get_tree))
+            .route("", web::post().to(create_location))
+            .route("/{id}", web::get().to(get_location))
+            .route("/{id}", web::patch().to(update_location))
+            .route("/{id}", web::delete().to(delete_location))
+            .route("/{id}/contents", web::get().to(get_contents))
+            .route("/{id}/on-shift", web::get().to(get_on_shift)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_tree(
+    req: HttpRequest,
+    service: web::Data<LocationService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let tree = service.get_tree(&claims).await?;
+    Ok(HttpResponse::Ok().json(tree))
+}
+
+async fn get_location(
+    req: HttpRequest,
+    service: web::Data<LocationService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let location = service.get_location(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(location))
+}
+
+async fn create_location(
+    req: HttpRequest,
+    service: web::Data<LocationService>,
+    body: web::Json<CreateLocationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let location = service.create_location(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(location))
+}
+
+async fn update_location(
+    req: HttpRequest,
+    service: web::Data<LocationService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateLocationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let location = service
+        .update_location(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(location))
+}
+
+async fn delete_location(
+    req: HttpRequest,
+    service: web::Data<LocationService>,
+    path: web::Path<String>,
+    query: web::Query<DeleteLocationQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service
+        .delete_location(&path.into_inner(), query.force, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().body("Location deleted successfully"))
+}
+
+async fn get_contents(
+    req: HttpRequest,
+    service: web::Data<LocationService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let contents = service.get_contents(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(contents))
+}
+
+async fn get_on_shift(
+    req: HttpRequest,
+    service: web::Data<LocationService>,
+    path: web::Path<String>,
+    query: web::Query<OnShiftQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let people = service
+        .get_on_shift(&path.into_inner(), query.at, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(people))
+}
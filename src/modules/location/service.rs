@@ -0,0 +1,304 @@
+use super::models::{
+    CreateLocationRequest, Location, LocationContents, LocationKind, LocationNode, OnShift,
+    UpdateLocationRequest, build_tree, subtree_ids,
+};
+use super::repository::LocationRepository;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::logistics::repository::LogisticsRepository;
+use crate::planner::repository::PlannerRepository;
+use crate::user::repository::UserRepository;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+pub struct LocationService {
+    repository: LocationRepository,
+    planner: PlannerRepository,
+    logistics: LogisticsRepository,
+    users: UserRepository,
+}
+
+impl LocationService {
+    pub fn new(
+        repository: LocationRepository,
+        planner: PlannerRepository,
+        logistics: LogisticsRepository,
+        users: UserRepository,
+    ) -> Self {
+        Self {
+            repository,
+            planner,
+            logistics,
+            users,
+        }
+    }
+
+    // everyone needs to find their way around
+    pub async fn get_tree(&self, claims: &Claims) -> Result<Vec<LocationNode>, AppError> {
+        let locations = self.repository.find_all(&claims.event).await?;
+        Ok(build_tree(locations))
+    }
+
+    pub async fn get_location(&self, id: &str, claims: &Claims) -> Result<Location, AppError> {
+        self.find_location(id, claims).await
+    }
+
+    pub async fn create_location(
+        &self,
+        req: CreateLocationRequest,
+        claims: &Claims,
+    ) -> Result<Location, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let name = req.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+        self.check_parent(req.kind, req.parent_id.as_deref(), claims)
+            .await?;
+        validate_position(req.latitude, req.longitude, req.floor_x, req.floor_y)?;
+
+        let req = CreateLocationRequest { name, ..req };
+        self.repository.create(&claims.event, req).await
+    }
+
+    pub async fn update_location(
+        &self,
+        id: &str,
+        req: UpdateLocationRequest,
+        claims: &Claims,
+    ) -> Result<Location, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let current = self.find_location(id, claims).await?;
+        let name = req.name.map(|n| n.trim().to_string());
+        if name.as_deref() == Some("") {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+        if req.parent_id.is_some() {
+            self.check_parent(current.kind, req.parent_id.as_deref(), claims)
+                .await?;
+        }
+        validate_position(
+            req.latitude.or(current.latitude),
+            req.longitude.or(current.longitude),
+            req.floor_x.or(current.floor_x),
+            req.floor_y.or(current.floor_y),
+        )?;
+
+        let req = UpdateLocationRequest { name, ..req };
+        self.repository.update(id, req).await
+    }
+
+    pub async fn delete_location(
+        &self,
+        id: &str,
+        force: bool,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let locations = self.repository.find_all(&claims.event).await?;
+        if !locations.iter().any(|l| l.id == id) {
+            return Err(AppError::NotFound("Location not found".to_string()));
+        }
+
+        let below = subtree_ids(&locations, id).len() - 1;
+        if below > 0 && !force {
+            let children: Vec<&Location> = locations
+                .iter()
+                .filter(|l| l.parent_id.as_deref() == Some(id))
+                .collect();
+            return Err(AppError::Conflict(
+                format!(
+                    "Location still has {} sublocation(s), pass force=true to delete them too",
+                    below
+                ),
+                serde_json::to_value(&children).map_err(|_| AppError::InternalServerError)?,
+            ));
+        }
+
+        self.repository.delete(&claims.event, id).await
+    }
+
+    // everything at the location or anywhere below it
+    pub async fn get_contents(
+        &self,
+        id: &str,
+        claims: &Claims,
+    ) -> Result<LocationContents, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        let locations = self.repository.find_all(&claims.event).await?;
+        let location = locations
+            .iter()
+            .find(|l| l.id == id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Location not found".to_string()))?;
+        let ids = subtree_ids(&locations, id);
+
+        let shifts = self
+            .planner
+            .find_shifts_in(&claims.event, &ids, None)
+            .await?;
+        let tasks = self.planner.find_tasks_in(&claims.event, &ids).await?;
+        let assets = self.logistics.find_assets_in(&claims.event, &ids).await?;
+        let sublocations = locations
+            .into_iter()
+            .filter(|l| l.id != id && ids.contains(&l.id))
+            .collect();
+
+        Ok(LocationContents {
+            location,
+            sublocations,
+            shifts,
+            tasks,
+            assets,
+        })
+    }
+
+    // who is working at the location or anywhere below it at a given time
+    pub async fn get_on_shift(
+        &self,
+        id: &str,
+        at: Option<DateTime<Utc>>,
+        claims: &Claims,
+    ) -> Result<Vec<OnShift>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        let locations = self.repository.find_all(&claims.event).await?;
+        if !locations.iter().any(|l| l.id == id) {
+            return Err(AppError::NotFound("Location not found".to_string()));
+        }
+        let ids = subtree_ids(&locations, id);
+
+        let at = at.unwrap_or_else(Utc::now);
+        let shifts = self
+            .planner
+            .find_shifts_in(&claims.event, &ids, Some(at))
+            .await?;
+        let user_ids: Vec<String> = shifts
+            .iter()
+            .flat_map(|s| s.assignments.iter().map(|a| a.user_id.clone()))
+            .collect();
+        if user_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let users: HashMap<String, _> = self
+            .users
+            .find_by_ids(&claims.event, &user_ids)
+            .await?
+            .into_iter()
+            .map(|u| (u.id.clone(), u))
+            .collect();
+
+        Ok(shifts
+            .iter()
+            .flat_map(|shift| {
+                shift.assignments.iter().filter_map(|a| {
+                    Some(OnShift {
+                        user: users.get(&a.user_id)?.clone(),
+                        role: a.role.clone(),
+                        shift_id: shift.id.clone(),
+                        shift_title: shift.title.clone(),
+                        location_id: shift.location_id.clone(),
+                        starts_at: shift.starts_at,
+                        ends_at: shift.ends_at,
+                    })
+                })
+            })
+            .collect())
+    }
+
+    // venues sit at the top, everything else goes inside the level right above it
+    async fn check_parent(
+        &self,
+        kind: LocationKind,
+        parent_id: Option<&str>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let (expected, parent_id) = match (kind.parent(), parent_id) {
+            (None, None) => return Ok(()),
+            (None, Some(_)) => {
+                return Err(AppError::BadRequest(
+                    "Venues can't be inside another location".to_string(),
+                ));
+            }
+            (Some(expected), None) => {
+                return Err(AppError::BadRequest(format!(
+                    "{}s go inside {}s",
+                    kind,
+                    expected.to_string().to_lowercase()
+                )));
+            }
+            (Some(expected), Some(parent_id)) => (expected, parent_id),
+        };
+
+        let parent = self
+            .repository
+            .find_by_id(&claims.event, parent_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown location '{}'", parent_id)))?;
+        if parent.kind != expected {
+            return Err(AppError::BadRequest(format!(
+                "{}s go inside {}s",
+                kind,
+                expected.to_string().to_lowercase()
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_location(&self, id: &str, claims: &Claims) -> Result<Location, AppError> {
+        self.repository
+            .find_by_id(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Location not found".to_string()))
+    }
+}
+
+fn validate_position(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    floor_x: Option<f64>,
+    floor_y: Option<f64>,
+) -> Result<(), AppError> {
+    match (latitude, longitude) {
+        (None, None) => {}
+        (Some(lat), Some(lon)) => {
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+                return Err(AppError::BadRequest(
+                    "Coordinates are out of range".to_string(),
+                ));
+            }
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "Latitude and longitude go together".to_string(),
+            ));
+        }
+    }
+
+    match (floor_x, floor_y) {
+        (None, None) => Ok(()),
+        (Some(x), Some(y)) if (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y) => Ok(()),
+        (Some(_), Some(_)) => Err(AppError::BadRequest(
+            "Floor plan positions go from 0 to 1".to_string(),
+        )),
+        _ => Err(AppError::BadRequest(
+            "Floor plan x and y go together".to_string(),
+        )),
+    }
+}
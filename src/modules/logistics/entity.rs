@@ -0,0 +1,60 @@
+// who had which asset when, one row per check-out
+pub mod checkout;
+// every status change is kept in its own table
+pub mod history;
+
+use derive_more::Display;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::StringLen;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Status {
+    #[sea_orm(string_value = "requested")]
+    Requested,
+    #[sea_orm(string_value = "ordered")]
+    Ordered,
+    #[sea_orm(string_value = "delivered")]
+    Delivered,
+    #[sea_orm(string_value = "in_use")]
+    InUse,
+    #[sea_orm(string_value = "returned")]
+    Returned,
+    #[sea_orm(string_value = "lost")]
+    Lost,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Ownership {
+    #[sea_orm(string_value = "owned")]
+    Owned,
+    #[sea_orm(string_value = "rented")]
+    Rented,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "assets")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub event_id: String,
+    pub name: String,
+    pub category: String,
+    pub quantity: i32,
+    pub location: String,
+    pub ownership: Ownership,
+    pub status: Status,
+    pub notes: String,
+    pub created_by: String,
+    pub created_at: ChronoDateTimeUtc,
+    pub updated_at: ChronoDateTimeUtc,
+    // where it's kept, see the location module
+    pub location_id: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,42 @@
+use derive_more::Display;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::StringLen;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Condition {
+    #[sea_orm(string_value = "good")]
+    Good,
+    #[sea_orm(string_value = "damaged")]
+    Damaged,
+    #[sea_orm(string_value = "broken")]
+    Broken,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "asset_checkouts")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub event_id: String,
+    pub asset_id: String,
+    // the person holding it
+    pub user_id: String,
+    pub quantity: i32,
+    pub note: String,
+    pub checked_out_by: String,
+    pub checked_out_at: ChronoDateTimeUtc,
+    // when it is expected back
+    pub due_at: ChronoDateTimeUtc,
+    // all of these stay empty while the item is out
+    pub checked_in_by: Option<String>,
+    pub checked_in_at: Option<ChronoDateTimeUtc>,
+    pub condition: Option<Condition>,
+    pub return_note: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,300 @@
+use super::entity;
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetStatus {
+    #[display("Requested")]
+    Requested,
+    #[display("Ordered")]
+    Ordered,
+    #[display("Delivered")]
+    Delivered,
+    #[display("In use")]
+    InUse,
+    #[display("Returned")]
+    Returned,
+    #[display("Lost")]
+    Lost,
+}
+
+impl AssetStatus {
+    // requested -> ordered -> delivered -> in use -> returned or lost
+    pub fn can_advance_to(&self, next: &AssetStatus) -> bool {
+        matches!(
+            (self, next),
+            (AssetStatus::Requested, AssetStatus::Ordered)
+                | (AssetStatus::Ordered, AssetStatus::Delivered)
+                | (AssetStatus::Delivered, AssetStatus::InUse)
+                | (AssetStatus::InUse, AssetStatus::Returned)
+                | (AssetStatus::InUse, AssetStatus::Lost)
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetOwnership {
+    #[display("Owned")]
+    Owned,
+    #[display("Rented")]
+    Rented,
+}
+
+impl From<AssetStatus> for entity::Status {
+    fn from(item: AssetStatus) -> Self {
+        match item {
+            AssetStatus::Requested => entity::Status::Requested,
+            AssetStatus::Ordered => entity::Status::Ordered,
+            AssetStatus::Delivered => entity::Status::Delivered,
+            AssetStatus::InUse => entity::Status::InUse,
+            AssetStatus::Returned => entity::Status::Returned,
+            AssetStatus::Lost => entity::Status::Lost,
+        }
+    }
+}
+
+impl From<entity::Status> for AssetStatus {
+    fn from(item: entity::Status) -> Self {
+        match item {
+            entity::Status::Requested => AssetStatus::Requested,
+            entity::Status::Ordered => AssetStatus::Ordered,
+            entity::Status::Delivered => AssetStatus::Delivered,
+            entity::Status::InUse => AssetStatus::InUse,
+            entity::Status::Returned => AssetStatus::Returned,
+            entity::Status::Lost => AssetStatus::Lost,
+        }
+    }
+}
+
+impl From<AssetOwnership> for entity::Ownership {
+    fn from(item: AssetOwnership) -> Self {
+        match item {
+            AssetOwnership::Owned => entity::Ownership::Owned,
+            AssetOwnership::Rented => entity::Ownership::Rented,
+        }
+    }
+}
+
+impl From<entity::Ownership> for AssetOwnership {
+    fn from(item: entity::Ownership) -> Self {
+        match item {
+            entity::Ownership::Owned => AssetOwnership::Owned,
+            entity::Ownership::Rented => AssetOwnership::Rented,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Asset {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub quantity: u32,
+    pub location: String,
+    pub ownership: AssetOwnership,
+    pub status: AssetStatus,
+    pub notes: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub location_id: Option<String>,
+}
+
+impl From<entity::Model> for Asset {
+    fn from(model: entity::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            category: model.category,
+            quantity: model.quantity.max(0) as u32,
+            location: model.location,
+            ownership: model.ownership.into(),
+            status: model.status.into(),
+            notes: model.notes,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            location_id: model.location_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AssetStatusChange {
+    pub id: String,
+    pub asset_id: String,
+    pub from: Option<AssetStatus>,
+    pub to: AssetStatus,
+    pub note: String,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<entity::history::Model> for AssetStatusChange {
+    fn from(model: entity::history::Model) -> Self {
+        Self {
+            id: model.id,
+            asset_id: model.asset_id,
+            from: model.from_status.map(Into::into),
+            to: model.to_status.into(),
+            note: model.note,
+            changed_by: model.changed_by,
+            changed_at: model.changed_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CreateAssetRequest {
+    pub name: String,
+    #[serde(default)]
+    pub category: String,
+    // one when left out
+    pub quantity: Option<u32>,
+    #[serde(default)]
+    pub location: String,
+    // owned when left out
+    pub ownership: Option<AssetOwnership>,
+    // requested when left out, things already in storage can start further along
+    pub status: Option<AssetStatus>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateAssetRequest {
+    pub name: Option<String>,
+    pub category: Option<String>,
+    pub quantity: Option<u32>,
+    pub location: Option<String>,
+    pub ownership: Option<AssetOwnership>,
+    pub notes: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangeAssetStatusRequest {
+    pub status: AssetStatus,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct AssetFilter {
+    pub status: Option<AssetStatus>,
+    pub category: Option<String>,
+    pub ownership: Option<AssetOwnership>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemCondition {
+    #[display("Good")]
+    Good,
+    #[display("Damaged")]
+    Damaged,
+    #[display("Broken")]
+    Broken,
+}
+
+impl From<ItemCondition> for entity::checkout::Condition {
+    fn from(item: ItemCondition) -> Self {
+        match item {
+            ItemCondition::Good => entity::checkout::Condition::Good,
+            ItemCondition::Damaged => entity::checkout::Condition::Damaged,
+            ItemCondition::Broken => entity::checkout::Condition::Broken,
+        }
+    }
+}
+
+impl From<entity::checkout::Condition> for ItemCondition {
+    fn from(item: entity::checkout::Condition) -> Self {
+        match item {
+            entity::checkout::Condition::Good => ItemCondition::Good,
+            entity::checkout::Condition::Damaged => ItemCondition::Damaged,
+            entity::checkout::Condition::Broken => ItemCondition::Broken,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Checkout {
+    pub id: String,
+    pub asset_id: String,
+    pub asset_name: String,
+    pub user_id: String,
+    pub quantity: u32,
+    pub note: String,
+    pub checked_out_by: String,
+    pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    // still out past its due time
+    pub overdue: bool,
+    pub checked_in_by: Option<String>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+    pub condition: Option<ItemCondition>,
+    pub return_note: Option<String>,
+}
+
+impl Checkout {
+    pub fn from_model(
+        model: entity::checkout::Model,
+        asset_name: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            overdue: model.checked_in_at.is_none() && model.due_at < now,
+            id: model.id,
+            asset_id: model.asset_id,
+            asset_name,
+            user_id: model.user_id,
+            quantity: model.quantity.max(0) as u32,
+            note: model.note,
+            checked_out_by: model.checked_out_by,
+            checked_out_at: model.checked_out_at,
+            due_at: model.due_at,
+            checked_in_by: model.checked_in_by,
+            checked_in_at: model.checked_in_at,
+            condition: model.condition.map(Into::into),
+            return_note: model.return_note,
+        }
+    }
+
+    pub fn is_out(&self) -> bool {
+        self.checked_in_at.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheckOutRequest {
+    pub user_id: String,
+    // one when left out
+    pub quantity: Option<u32>,
+    pub due_at: DateTime<Utc>,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheckInRequest {
+    pub condition: ItemCondition,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct CheckoutFilter {
+    pub user_id: Option<String>,
+    pub asset_id: Option<String>,
+    #[serde(default)]
+    pub overdue: bool,
+    // only what is out right now unless asked for the whole log
+    #[serde(default)]
+    pub include_returned: bool,
+}
@@ -0,0 +1,438 @@
+use super::entity::{self, ActiveModel, Column, Entity as AssetEntity, checkout, history};
+use super::models::{
+    Asset, AssetFilter, AssetOwnership, AssetStatus, AssetStatusChange, CheckInRequest,
+    CheckOutRequest, Checkout, CheckoutFilter, CreateAssetRequest, UpdateAssetRequest,
+};
+use crate::error::AppError;
+use sea_orm::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct LogisticsRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl LogisticsRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_assets(
+        &self,
+        event_id: &str,
+        filter: &AssetFilter,
+    ) -> Result<Vec<Asset>, AppError> {
+        let mut select = AssetEntity::find().filter(Column::EventId.eq(event_id));
+
+        if let Some(status) = &filter.status {
+            select = select.filter(Column::Status.eq(entity::Status::from(status.clone())));
+        }
+        if let Some(category) = &filter.category {
+            select = select.filter(Column::Category.eq(category.as_str()));
+        }
+        if let Some(ownership) = &filter.ownership {
+            select =
+                select.filter(Column::Ownership.eq(entity::Ownership::from(ownership.clone())));
+        }
+        if let Some(location_id) = &filter.location_id {
+            select = select.filter(Column::LocationId.eq(location_id.as_str()));
+        }
+
+        let models = select
+            .order_by_asc(Column::Category)
+            .order_by_asc(Column::Name)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    // assets kept at any of the given locations
+    pub async fn find_assets_in(
+        &self,
+        event_id: &str,
+        location_ids: &[String],
+    ) -> Result<Vec<Asset>, AppError> {
+        let models = AssetEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::LocationId.is_in(location_ids.iter().cloned()))
+            .order_by_asc(Column::Category)
+            .order_by_asc(Column::Name)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_asset(&self, event_id: &str, id: &str) -> Result<Option<Asset>, AppError> {
+        let model = AssetEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| m.into()))
+    }
+
+    // the starting status goes into the history right away
+    pub async fn create_asset(
+        &self,
+        event_id: &str,
+        dto: CreateAssetRequest,
+        created_by: &str,
+    ) -> Result<Asset, AppError> {
+        let now = chrono::Utc::now();
+        let status = dto.status.unwrap_or(AssetStatus::Requested);
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let asset = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            name: Set(dto.name),
+            category: Set(dto.category),
+            quantity: Set(dto.quantity.unwrap_or(1) as i32),
+            location: Set(dto.location),
+            ownership: Set(dto.ownership.unwrap_or(AssetOwnership::Owned).into()),
+            status: Set(status.clone().into()),
+            notes: Set(dto.notes),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+            location_id: Set(dto.location_id),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        history::ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            asset_id: Set(asset.id.clone()),
+            from_status: Set(None),
+            to_status: Set(status.into()),
+            note: Set(String::new()),
+            changed_by: Set(created_by.to_string()),
+            changed_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(asset.into())
+    }
+
+    pub async fn update_asset(&self, id: &str, dto: UpdateAssetRequest) -> Result<Asset, AppError> {
+        let model = AssetEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Asset not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(name) = dto.name {
+            active_model.name = Set(name);
+        }
+        if let Some(category) = dto.category {
+            active_model.category = Set(category);
+        }
+        if let Some(quantity) = dto.quantity {
+            active_model.quantity = Set(quantity as i32);
+        }
+        if let Some(location) = dto.location {
+            active_model.location = Set(location);
+        }
+        if let Some(ownership) = dto.ownership {
+            active_model.ownership = Set(ownership.into());
+        }
+        if let Some(notes) = dto.notes {
+            active_model.notes = Set(notes);
+        }
+        if let Some(location_id) = dto.location_id {
+            active_model.location_id = Set(Some(location_id));
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    // the new status and its history entry are written together
+    pub async fn set_status(
+        &self,
+        asset: &Asset,
+        status: AssetStatus,
+        note: String,
+        changed_by: &str,
+    ) -> Result<Asset, AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = AssetEntity::find_by_id(asset.id.clone())
+            .one(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Asset not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        active_model.status = Set(status.clone().into());
+        active_model.updated_at = Set(now);
+        let result = active_model
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        history::ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            asset_id: Set(asset.id.clone()),
+            from_status: Set(Some(asset.status.clone().into())),
+            to_status: Set(status.into()),
+            note: Set(note),
+            changed_by: Set(changed_by.to_string()),
+            changed_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
+    }
+
+    // the history goes with it
+    pub async fn delete_asset(&self, event_id: &str, id: &str) -> Result<(), AppError> {
+        let result = AssetEntity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::EventId.eq(event_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Asset not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // oldest first, so it reads like a timeline
+    pub async fn find_history(&self, asset_id: &str) -> Result<Vec<AssetStatusChange>, AppError> {
+        let models = history::Entity::find()
+            .filter(history::Column::AssetId.eq(asset_id))
+            .order_by_asc(history::Column::ChangedAt)
+            .order_by_asc(history::Column::Id)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    // status changes someone made to the event's assets
+    pub async fn find_changes_by(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<AssetStatusChange>, AppError> {
+        let models = history::Entity::find()
+            .filter(history::Column::ChangedBy.eq(user_id))
+            .filter(
+                history::Column::AssetId.in_subquery(
+                    sea_query::Query::select()
+                        .column(Column::Id)
+                        .from(AssetEntity)
+                        .and_where(Column::EventId.eq(event_id))
+                        .to_owned(),
+                ),
+            )
+            .order_by_asc(history::Column::ChangedAt)
+            .order_by_asc(history::Column::Id)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    pub async fn find_checkouts(
+        &self,
+        event_id: &str,
+        filter: &CheckoutFilter,
+    ) -> Result<Vec<Checkout>, AppError> {
+        let now = chrono::Utc::now();
+        let mut select = checkout::Entity::find().filter(checkout::Column::EventId.eq(event_id));
+
+        if let Some(user_id) = &filter.user_id {
+            select = select.filter(checkout::Column::UserId.eq(user_id.as_str()));
+        }
+        if let Some(asset_id) = &filter.asset_id {
+            select = select.filter(checkout::Column::AssetId.eq(asset_id.as_str()));
+        }
+        if filter.overdue {
+            select = select.filter(checkout::Column::DueAt.lt(now));
+        }
+        if !filter.include_returned || filter.overdue {
+            select = select.filter(checkout::Column::CheckedInAt.is_null());
+        }
+
+        let models = select
+            .order_by_asc(checkout::Column::DueAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_asset_names(models).await
+    }
+
+    // what the user is holding right now
+    pub async fn find_held_items(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<Checkout>, AppError> {
+        let filter = CheckoutFilter {
+            user_id: Some(user_id.to_string()),
+            ..CheckoutFilter::default()
+        };
+        self.find_checkouts(event_id, &filter).await
+    }
+
+    pub async fn find_checkout(
+        &self,
+        event_id: &str,
+        id: &str,
+    ) -> Result<Option<Checkout>, AppError> {
+        let model = checkout::Entity::find_by_id(id.to_string())
+            .filter(checkout::Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(self
+            .with_asset_names(model.into_iter().collect())
+            .await?
+            .pop())
+    }
+
+    // how many units of the asset are out with someone right now
+    pub async fn checked_out_quantity(&self, asset_id: &str) -> Result<u32, AppError> {
+        let models = checkout::Entity::find()
+            .filter(checkout::Column::AssetId.eq(asset_id))
+            .filter(checkout::Column::CheckedInAt.is_null())
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models.iter().map(|m| m.quantity.max(0) as u32).sum())
+    }
+
+    pub async fn create_checkout(
+        &self,
+        event_id: &str,
+        asset: &Asset,
+        dto: CheckOutRequest,
+        checked_out_by: &str,
+    ) -> Result<Checkout, AppError> {
+        let model = checkout::ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            asset_id: Set(asset.id.clone()),
+            user_id: Set(dto.user_id),
+            quantity: Set(dto.quantity.unwrap_or(1) as i32),
+            note: Set(dto.note),
+            checked_out_by: Set(checked_out_by.to_string()),
+            checked_out_at: Set(chrono::Utc::now()),
+            due_at: Set(dto.due_at),
+            checked_in_by: Set(None),
+            checked_in_at: Set(None),
+            condition: Set(None),
+            return_note: Set(None),
+        }
+        .insert(self.db.as_ref())
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(Checkout::from_model(
+            model,
+            asset.name.clone(),
+            chrono::Utc::now(),
+        ))
+    }
+
+    pub async fn check_in(
+        &self,
+        checkout: &Checkout,
+        dto: CheckInRequest,
+        checked_in_by: &str,
+    ) -> Result<Checkout, AppError> {
+        let model = checkout::Entity::find_by_id(checkout.id.clone())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Checkout not found".to_string()))?;
+
+        let now = chrono::Utc::now();
+        let mut active_model: checkout::ActiveModel = model.into();
+        active_model.checked_in_by = Set(Some(checked_in_by.to_string()));
+        active_model.checked_in_at = Set(Some(now));
+        active_model.condition = Set(Some(dto.condition.into()));
+        active_model.return_note = Set(Some(dto.note));
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(Checkout::from_model(
+            result,
+            checkout.asset_name.clone(),
+            now,
+        ))
+    }
+
+    async fn with_asset_names(
+        &self,
+        models: Vec<checkout::Model>,
+    ) -> Result<Vec<Checkout>, AppError> {
+        if models.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let names: HashMap<String, String> = AssetEntity::find()
+            .filter(Column::Id.is_in(models.iter().map(|m| m.asset_id.clone())))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .into_iter()
+            .map(|a| (a.id, a.name))
+            .collect();
+
+        let now = chrono::Utc::now();
+        Ok(models
+            .into_iter()
+            .map(|m| {
+                let name = names.get(&m.asset_id).cloned().unwrap_or_default();
+                Checkout::from_model(m, name, now)
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::logistics::models::{
+    AssetFilter, ChangeAssetStatusRequest, CheckInRequest, CheckOutRequest, CheckoutFilter,
+    CreateAssetRequest, UpdateAssetRequest,
+};
+use crate::modules::logistics::service::LogisticsService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/logistics")
+            .wrap(auth_middleware)
+            .route("/assets", web::get().to(get_assets))
+            .route("/assets", web::post().to(create_asset))
+            .route("/assets/{id}", web::get().to(get_asset))
+            .route("/assets/{id}", web::patch().to(update_asset))
+            .route("/assets/{id}", web::delete().to(delete_asset))
+            .route("/assets/{id}/status", web::post().to(change_status))
+            .route("/assets/{id}/history", web::get().to(get_history))
+            .route("/assets/{id}/checkouts", web::post().to(check_out))
+            .route("/checkouts", web::get().to(get_checkouts))
+            .route("/checkouts/overdue", web::get().to(get_overdue))
+            .route("/checkouts/{id}/return", web::post().to(check_in))
+            .route("/users/{user_id}/items", web::get().to(get_held_items)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_assets(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    query: web::Query<AssetFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let assets = service.get_assets(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(assets))
+}
+
+async fn get_asset(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let asset = service.get_asset(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(asset))
+}
+
+async fn create_asset(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    body: web::Json<CreateAssetRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let asset = service.create_asset(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(asset))
+}
+
+async fn update_asset(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateAssetRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let asset = service
+        .update_asset(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(asset))
+}
+
+async fn delete_asset(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service.delete_asset(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("Asset deleted successfully"))
+}
+
+async fn change_status(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+    body: web::Json<ChangeAssetStatusRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let asset = service
+        .change_status(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(asset))
+}
+
+async fn get_history(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let history = service.get_history(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(history))
+}
+
+async fn check_out(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+    body: web::Json<CheckOutRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let checkout = service
+        .check_out(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(checkout))
+}
+
+async fn get_checkouts(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    query: web::Query<CheckoutFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let checkouts = service.get_checkouts(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(checkouts))
+}
+
+async fn get_overdue(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let checkouts = service.get_overdue(&claims).await?;
+    Ok(HttpResponse::Ok().json(checkouts))
+}
+
+async fn check_in(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+    body: web::Json<CheckInRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let checkout = service
+        .check_in(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(checkout))
+}
+
+async fn get_held_items(
+    req: HttpRequest,
+    service: web::Data<LogisticsService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let items = service.get_held_items(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(items))
+}
@@ -0,0 +1,304 @@
+use super::models::{
+    Asset, AssetFilter, AssetStatus, AssetStatusChange, ChangeAssetStatusRequest, CheckInRequest,
+    CheckOutRequest, Checkout, CheckoutFilter, CreateAssetRequest, UpdateAssetRequest,
+};
+use super::repository::LogisticsRepository;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::location::repository::LocationRepository;
+use crate::user::models::UserStatus;
+use crate::user::repository::UserRepository;
+
+pub struct LogisticsService {
+    repository: LogisticsRepository,
+    users: UserRepository,
+    locations: LocationRepository,
+}
+
+impl LogisticsService {
+    pub fn new(
+        repository: LogisticsRepository,
+        users: UserRepository,
+        locations: LocationRepository,
+    ) -> Self {
+        Self {
+            repository,
+            users,
+            locations,
+        }
+    }
+
+    pub async fn get_assets(
+        &self,
+        filter: AssetFilter,
+        claims: &Claims,
+    ) -> Result<Vec<Asset>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.find_assets(&claims.event, &filter).await
+    }
+
+    pub async fn get_asset(&self, id: &str, claims: &Claims) -> Result<Asset, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.find_asset(id, claims).await
+    }
+
+    pub async fn create_asset(
+        &self,
+        req: CreateAssetRequest,
+        claims: &Claims,
+    ) -> Result<Asset, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let name = req.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+        validate_quantity(req.quantity)?;
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+
+        let req = CreateAssetRequest {
+            name,
+            category: req.category.trim().to_string(),
+            ..req
+        };
+        self.repository
+            .create_asset(&claims.event, req, &claims.sub)
+            .await
+    }
+
+    // everything but the status, that one goes through change_status to keep the history
+    pub async fn update_asset(
+        &self,
+        id: &str,
+        req: UpdateAssetRequest,
+        claims: &Claims,
+    ) -> Result<Asset, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.find_asset(id, claims).await?;
+
+        let name = req.name.map(|n| n.trim().to_string());
+        if name.as_deref() == Some("") {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+        validate_quantity(req.quantity)?;
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+
+        let req = UpdateAssetRequest {
+            name,
+            category: req.category.map(|c| c.trim().to_string()),
+            ..req
+        };
+        self.repository.update_asset(id, req).await
+    }
+
+    // staff can only move an asset one step along, organizers and admins can also correct it
+    pub async fn change_status(
+        &self,
+        id: &str,
+        req: ChangeAssetStatusRequest,
+        claims: &Claims,
+    ) -> Result<Asset, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        let asset = self.find_asset(id, claims).await?;
+        if asset.status == req.status {
+            return Err(AppError::BadRequest(format!(
+                "Asset is already {}",
+                asset.status.to_string().to_lowercase()
+            )));
+        }
+        if !claims.is_manager() && !asset.status.can_advance_to(&req.status) {
+            return Err(AppError::BadRequest(format!(
+                "{} assets can't become {}",
+                asset.status,
+                req.status.to_string().to_lowercase()
+            )));
+        }
+
+        self.repository
+            .set_status(&asset, req.status, req.note.trim().to_string(), &claims.sub)
+            .await
+    }
+
+    pub async fn delete_asset(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.delete_asset(&claims.event, id).await
+    }
+
+    pub async fn get_history(
+        &self,
+        id: &str,
+        claims: &Claims,
+    ) -> Result<Vec<AssetStatusChange>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.find_asset(id, claims).await?;
+        self.repository.find_history(id).await
+    }
+
+    // who has what right now, or the whole log with include_returned
+    pub async fn get_checkouts(
+        &self,
+        filter: CheckoutFilter,
+        claims: &Claims,
+    ) -> Result<Vec<Checkout>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.find_checkouts(&claims.event, &filter).await
+    }
+
+    pub async fn get_overdue(&self, claims: &Claims) -> Result<Vec<Checkout>, AppError> {
+        let filter = CheckoutFilter {
+            overdue: true,
+            ..CheckoutFilter::default()
+        };
+        self.get_checkouts(filter, claims).await
+    }
+
+    // everyone can see what they are holding themselves
+    pub async fn get_held_items(
+        &self,
+        user_id: &str,
+        claims: &Claims,
+    ) -> Result<Vec<Checkout>, AppError> {
+        if claims.sub != user_id && !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository
+            .find_held_items(&claims.event, user_id)
+            .await
+    }
+
+    pub async fn check_out(
+        &self,
+        asset_id: &str,
+        req: CheckOutRequest,
+        claims: &Claims,
+    ) -> Result<Checkout, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        let asset = self.find_asset(asset_id, claims).await?;
+        if asset.status != AssetStatus::Delivered && asset.status != AssetStatus::InUse {
+            return Err(AppError::BadRequest(
+                "Only delivered or in use assets can be checked out".to_string(),
+            ));
+        }
+        validate_quantity(req.quantity)?;
+        if req.due_at <= chrono::Utc::now() {
+            return Err(AppError::BadRequest(
+                "Return time must be in the future".to_string(),
+            ));
+        }
+
+        let user = self
+            .users
+            .find_by_id(&claims.event, &req.user_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown user '{}'", req.user_id)))?;
+        if user.status == UserStatus::Inactive {
+            return Err(AppError::BadRequest(
+                "Inactive users can't check items out".to_string(),
+            ));
+        }
+
+        let out = self.repository.checked_out_quantity(asset_id).await?;
+        let available = asset.quantity.saturating_sub(out);
+        if req.quantity.unwrap_or(1) > available {
+            return Err(AppError::BadRequest(format!(
+                "Only {} of {} left to check out",
+                available, asset.quantity
+            )));
+        }
+
+        self.repository
+            .create_checkout(&claims.event, &asset, req, &claims.sub)
+            .await
+    }
+
+    pub async fn check_in(
+        &self,
+        id: &str,
+        req: CheckInRequest,
+        claims: &Claims,
+    ) -> Result<Checkout, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        let checkout = self
+            .repository
+            .find_checkout(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Checkout not found".to_string()))?;
+        if !checkout.is_out() {
+            return Err(AppError::BadRequest(
+                "Item was already checked in".to_string(),
+            ));
+        }
+
+        let req = CheckInRequest {
+            note: req.note.trim().to_string(),
+            ..req
+        };
+        self.repository.check_in(&checkout, req, &claims.sub).await
+    }
+
+    async fn check_location(
+        &self,
+        location_id: Option<&str>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if let Some(location_id) = location_id
+            && !self.locations.exists(&claims.event, location_id).await?
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unknown location '{}'",
+                location_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_asset(&self, id: &str, claims: &Claims) -> Result<Asset, AppError> {
+        self.repository
+            .find_asset(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Asset not found".to_string()))
+    }
+}
+
+fn validate_quantity(quantity: Option<u32>) -> Result<(), AppError> {
+    if quantity == Some(0) {
+        return Err(AppError::BadRequest(
+            "Quantity must be at least 1".to_string(),
+        ));
+    }
+
+    Ok(())
+}
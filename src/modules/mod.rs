@@ -1,2 +1,18 @@
+pub mod audit;
 pub mod auth;
+pub mod backup;
+pub mod branding;
+pub mod calendar;
+pub mod consumable;
+pub mod event;
+pub mod export;
+pub mod file;
+pub mod label;
+pub mod location;
+pub mod logistics;
+pub mod planner;
+pub mod privacy;
+pub mod social;
+pub mod team;
 pub mod user;
+pub mod vendor;
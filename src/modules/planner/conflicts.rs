@@ -0,0 +1,88 @@
+use super::models::{Shift, ShiftConflict, ShiftLimits};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+// checks a shift against someone's existing schedule, the shift itself is skipped if it's in there
+pub fn find_conflicts(
+    candidate: &Shift,
+    schedule: &[Shift],
+    limits: &ShiftLimits,
+) -> Vec<ShiftConflict> {
+    let others: Vec<&Shift> = schedule.iter().filter(|s| s.id != candidate.id).collect();
+    let mut conflicts = Vec::new();
+
+    for other in &others {
+        if overlaps(candidate, other) {
+            conflicts.push(ShiftConflict::Overlap {
+                shift_id: other.id.clone(),
+                title: other.title.clone(),
+                starts_at: other.starts_at,
+                ends_at: other.ends_at,
+            });
+            continue;
+        }
+
+        let required_minutes = limits.min_rest_minutes as i64;
+        let rest_minutes = if other.ends_at <= candidate.starts_at {
+            (candidate.starts_at - other.ends_at).num_minutes()
+        } else {
+            (other.starts_at - candidate.ends_at).num_minutes()
+        };
+        if rest_minutes < required_minutes {
+            conflicts.push(ShiftConflict::InsufficientRest {
+                shift_id: other.id.clone(),
+                title: other.title.clone(),
+                rest_minutes,
+                required_minutes,
+            });
+        }
+    }
+
+    if limits.max_hours_per_day > 0 {
+        let limit_minutes = limits.max_hours_per_day as i64 * 60;
+
+        for day in days_touched(candidate) {
+            let scheduled_minutes =
+                minutes_on(candidate, day) + others.iter().map(|s| minutes_on(s, day)).sum::<i64>();
+
+            if scheduled_minutes > limit_minutes {
+                conflicts.push(ShiftConflict::DailyHours {
+                    day,
+                    scheduled_minutes,
+                    limit_minutes,
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+pub fn overlaps(a: &Shift, b: &Shift) -> bool {
+    a.starts_at < b.ends_at && b.starts_at < a.ends_at
+}
+
+fn days_touched(shift: &Shift) -> Vec<NaiveDate> {
+    let last = (shift.ends_at - chrono::Duration::nanoseconds(1)).date_naive();
+    let mut day = shift.starts_at.date_naive();
+    let mut days = Vec::new();
+
+    while day <= last {
+        days.push(day);
+        day = match day.checked_add_days(Days::new(1)) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    days
+}
+
+fn minutes_on(shift: &Shift, day: NaiveDate) -> i64 {
+    let day_start: DateTime<Utc> = day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    let day_end = day_start + chrono::Duration::days(1);
+
+    let start = shift.starts_at.max(day_start);
+    let end = shift.ends_at.min(day_end);
+
+    (end - start).num_minutes().max(0)
+}
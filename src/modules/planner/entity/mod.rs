@@ -0,0 +1,9 @@
+pub mod roster;
+pub mod roster_assignment;
+pub mod series;
+pub mod shift;
+pub mod shift_assignment;
+pub mod shift_requirement;
+pub mod shift_skill;
+pub mod task;
+pub mod task_assignee;
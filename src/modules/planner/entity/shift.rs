@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shifts")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub event_id: String,
+    pub title: String,
+    pub location: String,
+    pub starts_at: ChronoDateTimeUtc,
+    pub ends_at: ChronoDateTimeUtc,
+    pub notes: String,
+    pub created_by: String,
+    pub created_at: ChronoDateTimeUtc,
+    pub updated_at: ChronoDateTimeUtc,
+    // set when the row was materialized from a recurring series
+    pub series_id: Option<String>,
+    // the slot the rule produced, kept even if the row is moved by hand
+    pub occurrence_at: Option<ChronoDateTimeUtc>,
+    // edited on its own, later edits to the whole series leave it alone
+    pub is_exception: bool,
+    // leads of this team may manage it, see the team module
+    pub team_id: Option<String>,
+    // where it happens, see the location module
+    pub location_id: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,20 @@
+use crate::user::entity::Role;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shift_assignments")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub shift_id: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: String,
+    // the slot this person fills, not necessarily their current user role
+    pub role: Role,
+    pub assigned_at: ChronoDateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,19 @@
+use crate::user::entity::Role;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// how many people of a role a shift needs
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shift_requirements")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub shift_id: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub role: Role,
+    pub headcount: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
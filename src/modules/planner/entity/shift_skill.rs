@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// skills everyone on the shift needs, normalized like user skills
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shift_skills")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub shift_id: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub skill: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,8 @@
+pub mod conflicts;
+pub mod entity;
+pub mod models;
+pub mod recurrence;
+pub mod repository;
+pub mod roster;
+pub mod routes;
+pub mod service;
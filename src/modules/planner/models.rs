@@ -0,0 +1,681 @@
+use super::entity::{
+    roster, roster_assignment, series, shift, shift_assignment, shift_requirement, task,
+};
+use crate::user::models::{User, UserRole};
+use chrono::{DateTime, NaiveDate, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPriority {
+    #[display("Low")]
+    Low,
+    #[display("Medium")]
+    Medium,
+    #[display("High")]
+    High,
+    #[display("Urgent")]
+    Urgent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    #[display("Todo")]
+    Todo,
+    #[display("In progress")]
+    InProgress,
+    #[display("Done")]
+    Done,
+    #[display("Blocked")]
+    Blocked,
+}
+
+impl From<TaskPriority> for task::Priority {
+    fn from(item: TaskPriority) -> Self {
+        match item {
+            TaskPriority::Low => task::Priority::Low,
+            TaskPriority::Medium => task::Priority::Medium,
+            TaskPriority::High => task::Priority::High,
+            TaskPriority::Urgent => task::Priority::Urgent,
+        }
+    }
+}
+
+impl From<task::Priority> for TaskPriority {
+    fn from(item: task::Priority) -> Self {
+        match item {
+            task::Priority::Low => TaskPriority::Low,
+            task::Priority::Medium => TaskPriority::Medium,
+            task::Priority::High => TaskPriority::High,
+            task::Priority::Urgent => TaskPriority::Urgent,
+        }
+    }
+}
+
+impl From<TaskStatus> for task::Status {
+    fn from(item: TaskStatus) -> Self {
+        match item {
+            TaskStatus::Todo => task::Status::Todo,
+            TaskStatus::InProgress => task::Status::InProgress,
+            TaskStatus::Done => task::Status::Done,
+            TaskStatus::Blocked => task::Status::Blocked,
+        }
+    }
+}
+
+impl From<task::Status> for TaskStatus {
+    fn from(item: task::Status) -> Self {
+        match item {
+            task::Status::Todo => TaskStatus::Todo,
+            task::Status::InProgress => TaskStatus::InProgress,
+            task::Status::Done => TaskStatus::Done,
+            task::Status::Blocked => TaskStatus::Blocked,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Task {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
+    pub due_at: Option<DateTime<Utc>>,
+    // user ids
+    pub assignees: Vec<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by: Option<String>,
+    pub team_id: Option<String>,
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurrence: Option<Occurrence>,
+}
+
+impl Task {
+    pub fn from_model(model: task::Model, assignees: Vec<String>) -> Self {
+        Self {
+            id: model.id,
+            title: model.title,
+            description: model.description,
+            priority: model.priority.into(),
+            status: model.status.into(),
+            due_at: model.due_at,
+            assignees,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            completed_at: model.completed_at,
+            completed_by: model.completed_by,
+            team_id: model.team_id,
+            location_id: model.location_id,
+            occurrence: Occurrence::of(model.series_id, model.occurrence_at, model.is_exception),
+        }
+    }
+
+    pub fn is_assigned_to(&self, user_id: &str) -> bool {
+        self.assignees.iter().any(|a| a == user_id)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CreateTaskRequest {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub priority: Option<TaskPriority>,
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    #[serde(default)]
+    pub team_id: Option<String>,
+    #[serde(default)]
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateTaskRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<TaskPriority>,
+    pub status: Option<TaskStatus>,
+    pub due_at: Option<DateTime<Utc>>,
+    // replaces the whole list when present
+    pub assignees: Option<Vec<String>>,
+    // moves it to another team, it can't be taken out of one
+    pub team_id: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    pub assignee: Option<String>,
+    pub due_before: Option<DateTime<Utc>>,
+    pub team_id: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ShiftRequirement {
+    pub role: UserRole,
+    pub headcount: u32,
+}
+
+impl From<shift_requirement::Model> for ShiftRequirement {
+    fn from(model: shift_requirement::Model) -> Self {
+        Self {
+            role: model.role.into(),
+            headcount: model.headcount.max(0) as u32,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ShiftAssignment {
+    pub user_id: String,
+    pub role: UserRole,
+    pub assigned_at: DateTime<Utc>,
+}
+
+impl From<shift_assignment::Model> for ShiftAssignment {
+    fn from(model: shift_assignment::Model) -> Self {
+        Self {
+            user_id: model.user_id,
+            role: model.role.into(),
+            assigned_at: model.assigned_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Shift {
+    pub id: String,
+    pub title: String,
+    pub location: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub notes: String,
+    pub requirements: Vec<ShiftRequirement>,
+    pub required_skills: Vec<String>,
+    pub assignments: Vec<ShiftAssignment>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub team_id: Option<String>,
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurrence: Option<Occurrence>,
+}
+
+impl Shift {
+    pub fn from_model(
+        model: shift::Model,
+        requirements: Vec<ShiftRequirement>,
+        required_skills: Vec<String>,
+        assignments: Vec<ShiftAssignment>,
+    ) -> Self {
+        Self {
+            id: model.id,
+            title: model.title,
+            location: model.location,
+            starts_at: model.starts_at,
+            ends_at: model.ends_at,
+            notes: model.notes,
+            requirements,
+            required_skills,
+            assignments,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            team_id: model.team_id,
+            location_id: model.location_id,
+            occurrence: Occurrence::of(model.series_id, model.occurrence_at, model.is_exception),
+        }
+    }
+
+    pub fn is_assigned_to(&self, user_id: &str) -> bool {
+        self.assignments.iter().any(|a| a.user_id == user_id)
+    }
+
+    pub fn assigned_count(&self, role: &UserRole) -> u32 {
+        self.assignments.iter().filter(|a| &a.role == role).count() as u32
+    }
+
+    // unfilled places per role, roles that are fully staffed are left out
+    pub fn open_slots(&self) -> Vec<OpenSlot> {
+        self.requirements
+            .iter()
+            .filter_map(|r| {
+                let assigned = self.assigned_count(&r.role);
+                (assigned < r.headcount).then(|| OpenSlot {
+                    role: r.role.clone(),
+                    required: r.headcount,
+                    assigned,
+                    open: r.headcount - assigned,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OpenSlot {
+    pub role: UserRole,
+    pub required: u32,
+    pub assigned: u32,
+    pub open: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShiftSlots {
+    pub shift_id: String,
+    pub title: String,
+    pub location: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub slots: Vec<OpenSlot>,
+}
+
+impl ShiftSlots {
+    pub fn of(shift: &Shift) -> Option<Self> {
+        let slots = shift.open_slots();
+        if slots.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            shift_id: shift.id.clone(),
+            title: shift.title.clone(),
+            location: shift.location.clone(),
+            starts_at: shift.starts_at,
+            ends_at: shift.ends_at,
+            slots,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CoverageReport {
+    pub total_shifts: usize,
+    pub understaffed_shifts: usize,
+    pub open_slots: u32,
+    pub shifts: Vec<ShiftSlots>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CreateShiftRequest {
+    pub title: String,
+    #[serde(default)]
+    pub location: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub requirements: Vec<ShiftRequirement>,
+    #[serde(default)]
+    pub required_skills: Vec<String>,
+    #[serde(default)]
+    pub team_id: Option<String>,
+    #[serde(default)]
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateShiftRequest {
+    pub title: Option<String>,
+    pub location: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    // these replace the whole list when present
+    pub requirements: Option<Vec<ShiftRequirement>>,
+    pub required_skills: Option<Vec<String>>,
+    // moves it to another team, it can't be taken out of one
+    pub team_id: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AssignShiftRequest {
+    pub user_id: String,
+    // defaults to the user's own role
+    pub role: Option<UserRole>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct AssignShiftQuery {
+    // organizers may assign despite conflicts, the override is audited
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct ShiftFilter {
+    pub location: Option<String>,
+    pub assignee: Option<String>,
+    // shifts still running at or after this time
+    pub from: Option<DateTime<Utc>>,
+    // shifts starting before this time
+    pub to: Option<DateTime<Utc>>,
+    pub team_id: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct CandidateQuery {
+    // only people who'd fill a slot of this role
+    pub role: Option<UserRole>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShiftCandidate {
+    pub user: User,
+    // the shift's location is one of their preferred areas
+    pub prefers_area: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct FreeQuery {
+    // defaults to now
+    pub at: Option<DateTime<Utc>>,
+    pub skill: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShiftLimits {
+    // 0 turns the rule off
+    pub max_hours_per_day: u32,
+    pub min_rest_minutes: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ShiftConflict {
+    Overlap {
+        shift_id: String,
+        title: String,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    },
+    // day is a UTC calendar day
+    DailyHours {
+        day: NaiveDate,
+        scheduled_minutes: i64,
+        limit_minutes: i64,
+    },
+    InsufficientRest {
+        shift_id: String,
+        title: String,
+        rest_minutes: i64,
+        required_minutes: i64,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RosterStatus {
+    #[display("Draft")]
+    Draft,
+    #[display("Published")]
+    Published,
+    #[display("Discarded")]
+    Discarded,
+}
+
+impl From<RosterStatus> for roster::Status {
+    fn from(item: RosterStatus) -> Self {
+        match item {
+            RosterStatus::Draft => roster::Status::Draft,
+            RosterStatus::Published => roster::Status::Published,
+            RosterStatus::Discarded => roster::Status::Discarded,
+        }
+    }
+}
+
+impl From<roster::Status> for RosterStatus {
+    fn from(item: roster::Status) -> Self {
+        match item {
+            roster::Status::Draft => RosterStatus::Draft,
+            roster::Status::Published => RosterStatus::Published,
+            roster::Status::Discarded => RosterStatus::Discarded,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProposedAssignment {
+    pub shift_id: String,
+    pub user_id: String,
+    pub role: UserRole,
+}
+
+impl From<roster_assignment::Model> for ProposedAssignment {
+    fn from(model: roster_assignment::Model) -> Self {
+        Self {
+            shift_id: model.shift_id,
+            user_id: model.user_id,
+            role: model.role.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnfilledSlot {
+    pub shift_id: String,
+    pub role: UserRole,
+    pub missing: u32,
+    // why nobody else could take it, e.g. "3 not available"
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Roster {
+    pub id: String,
+    pub status: RosterStatus,
+    pub seed: u64,
+    pub shift_ids: Vec<String>,
+    pub assignments: Vec<ProposedAssignment>,
+    pub unfilled: Vec<UnfilledSlot>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub published_by: Option<String>,
+}
+
+impl Roster {
+    pub fn from_model(model: roster::Model, assignments: Vec<ProposedAssignment>) -> Self {
+        Self {
+            id: model.id,
+            status: model.status.into(),
+            seed: model.seed as u64,
+            shift_ids: serde_json::from_str(&model.shift_ids).unwrap_or_default(),
+            assignments,
+            unfilled: serde_json::from_str(&model.unfilled).unwrap_or_default(),
+            created_by: model.created_by,
+            created_at: model.created_at,
+            published_at: model.published_at,
+            published_by: model.published_by,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct GenerateRosterRequest {
+    // picks these shifts, otherwise everything matching the window and location
+    #[serde(default)]
+    pub shift_ids: Vec<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub location: Option<String>,
+    // same seed and same data give the same roster, a random one is picked when missing
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RosterAssignmentRequest {
+    pub shift_id: String,
+    pub user_id: String,
+    // defaults to the user's own role
+    pub role: Option<UserRole>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SkippedAssignment {
+    pub assignment: ProposedAssignment,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PublishReport {
+    pub roster: Roster,
+    pub published: usize,
+    // things that changed on the shifts between drafting and publishing
+    pub skipped: Vec<SkippedAssignment>,
+}
+
+// where a shift or task sits in its recurring series
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Occurrence {
+    pub series_id: String,
+    pub occurrence_at: DateTime<Utc>,
+    pub is_exception: bool,
+}
+
+impl Occurrence {
+    fn of(
+        series_id: Option<String>,
+        occurrence_at: Option<DateTime<Utc>>,
+        is_exception: bool,
+    ) -> Option<Self> {
+        Some(Self {
+            series_id: series_id?,
+            occurrence_at: occurrence_at?,
+            is_exception,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesKind {
+    #[display("Shift")]
+    Shift,
+    #[display("Task")]
+    Task,
+}
+
+impl From<SeriesKind> for series::Kind {
+    fn from(item: SeriesKind) -> Self {
+        match item {
+            SeriesKind::Shift => series::Kind::Shift,
+            SeriesKind::Task => series::Kind::Task,
+        }
+    }
+}
+
+impl From<series::Kind> for SeriesKind {
+    fn from(item: series::Kind) -> Self {
+        match item {
+            series::Kind::Shift => SeriesKind::Shift,
+            series::Kind::Task => SeriesKind::Task,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Series {
+    pub id: String,
+    pub kind: SeriesKind,
+    pub recurrence: String,
+    // first occurrence, the rule counts from here
+    pub starts_at: DateTime<Utc>,
+    // occurrences deleted on their own, never recreated
+    pub exdates: Vec<DateTime<Utc>>,
+    // the create request new occurrences are made from
+    pub template: serde_json::Value,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<series::Model> for Series {
+    fn from(model: series::Model) -> Self {
+        Self {
+            id: model.id,
+            kind: model.kind.into(),
+            recurrence: model.rule,
+            starts_at: model.starts_at,
+            exdates: serde_json::from_str(&model.exdates).unwrap_or_default(),
+            template: serde_json::from_str(&model.template).unwrap_or_default(),
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl Series {
+    pub fn team_id(&self) -> Option<&str> {
+        self.template.get("team_id").and_then(|t| t.as_str())
+    }
+}
+
+// which occurrences an edit or delete reaches, like a calendar app asks
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EditScope {
+    #[default]
+    This,
+    Following,
+    All,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ScopeQuery {
+    #[serde(default)]
+    pub scope: EditScope,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct UpdateShiftQuery {
+    #[serde(default)]
+    pub scope: EditScope,
+    // organizers may move shifts despite conflicts for the people on them, the override is audited
+    #[serde(default)]
+    pub force: bool,
+}
+
+// the first occurrence plus an RRULE, e.g. "FREQ=HOURLY;INTERVAL=2;COUNT=6"
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecurringShiftRequest {
+    #[serde(flatten)]
+    pub shift: CreateShiftRequest,
+    pub recurrence: String,
+}
+
+// due_at of the task is the first occurrence
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecurringTaskRequest {
+    #[serde(flatten)]
+    pub task: CreateTaskRequest,
+    pub recurrence: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateSeriesRequest {
+    pub recurrence: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesOccurrences<T> {
+    pub series: Series,
+    pub occurrences: Vec<T>,
+}
@@ -0,0 +1,244 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, Utc, Weekday};
+use std::fmt;
+
+// more than this and the rule is almost certainly a typo
+pub const MAX_OCCURRENCES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+// the RRULE subset that event duties need: FREQ, INTERVAL, COUNT, UNTIL and BYDAY.
+// everything is expanded in UTC from the first occurrence, like DTSTART with a Z suffix
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<Weekday>,
+}
+
+impl RecurrenceRule {
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("'{}' is not KEY=VALUE", part))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "HOURLY" => Frequency::Hourly,
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        other => return Err(format!("FREQ={} is not supported", other)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse()
+                        .ok()
+                        .filter(|i| *i > 0)
+                        .ok_or("INTERVAL must be a positive number")?
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|c| *c > 0)
+                            .ok_or("COUNT must be a positive number")?,
+                    )
+                }
+                "UNTIL" => until = Some(parse_until(value)?),
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        let day = parse_weekday(day)?;
+                        if !by_day.contains(&day) {
+                            by_day.push(day);
+                        }
+                    }
+                    by_day.sort_by_key(|d: &Weekday| d.num_days_from_monday());
+                }
+                other => return Err(format!("{} is not supported", other)),
+            }
+        }
+
+        let frequency = frequency.ok_or("FREQ is required")?;
+        if count.is_some() && until.is_some() {
+            return Err("COUNT and UNTIL can't be used together".to_string());
+        }
+        if count.is_none() && until.is_none() {
+            return Err("The rule needs COUNT or UNTIL to end".to_string());
+        }
+        if frequency == Frequency::Hourly && !by_day.is_empty() {
+            return Err("BYDAY only works with DAILY or WEEKLY".to_string());
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            count,
+            until,
+            by_day,
+        })
+    }
+
+    // every start time of the series, exceptions are not taken out here
+    pub fn occurrences(&self, starts_at: DateTime<Utc>) -> Result<Vec<DateTime<Utc>>, String> {
+        let mut found = Vec::new();
+        let mut period = 0i64;
+
+        loop {
+            let mut candidates = self.period(starts_at, period);
+            candidates.retain(|c| *c >= starts_at);
+
+            for candidate in candidates {
+                if self.until.is_some_and(|until| candidate > until) {
+                    return Ok(found);
+                }
+                found.push(candidate);
+
+                if found.len() > MAX_OCCURRENCES {
+                    return Err(format!(
+                        "The rule makes more than {} occurrences",
+                        MAX_OCCURRENCES
+                    ));
+                }
+                if self
+                    .count
+                    .is_some_and(|count| found.len() >= count as usize)
+                {
+                    return Ok(found);
+                }
+            }
+
+            period += 1;
+            // an until before the start, or BYDAY with no match, would never stop otherwise
+            if period > (MAX_OCCURRENCES as i64) * 7 && found.is_empty() {
+                return Ok(found);
+            }
+        }
+    }
+
+    // the same rule ending just before the given occurrence
+    pub fn ending_before(&self, at: DateTime<Utc>) -> Self {
+        Self {
+            count: None,
+            until: Some(at - Duration::seconds(1)),
+            ..self.clone()
+        }
+    }
+
+    // the same rule picking up at the nth occurrence, so a COUNT keeps its total
+    pub fn continuing_from(&self, index: usize) -> Self {
+        Self {
+            count: self.count.map(|c| c.saturating_sub(index as u32).max(1)),
+            ..self.clone()
+        }
+    }
+
+    fn period(&self, starts_at: DateTime<Utc>, period: i64) -> Vec<DateTime<Utc>> {
+        let step = period * self.interval as i64;
+
+        match self.frequency {
+            Frequency::Hourly => vec![starts_at + Duration::hours(step)],
+            Frequency::Daily => {
+                let day = starts_at + Duration::days(step);
+                if self.by_day.is_empty() || self.by_day.contains(&day.weekday()) {
+                    vec![day]
+                } else {
+                    vec![]
+                }
+            }
+            Frequency::Weekly if self.by_day.is_empty() => {
+                vec![starts_at + Duration::weeks(step)]
+            }
+            Frequency::Weekly => {
+                let monday = starts_at + Duration::weeks(step)
+                    - Duration::days(starts_at.weekday().num_days_from_monday() as i64);
+                self.by_day
+                    .iter()
+                    .map(|d| monday + Duration::days(d.num_days_from_monday() as i64))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim_end_matches('Z');
+    if let Ok(at) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        return Ok(at.and_utc());
+    }
+
+    // a bare date means the whole of that day
+    chrono::NaiveDate::parse_from_str(value, "%Y%m%d")
+        .map(|d| d.and_hms_opt(23, 59, 59).unwrap().and_utc())
+        .map_err(|_| format!("UNTIL={} is not a date like 20300601T120000Z", value))
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        other => Err(format!("BYDAY={} is not a weekday", other)),
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
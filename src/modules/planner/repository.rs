@@ -0,0 +1,1238 @@
+use super::entity::{
+    roster, roster_assignment, series, shift, shift_assignment, shift_requirement, shift_skill,
+    task, task_assignee,
+};
+use super::models::{
+    CreateShiftRequest, CreateTaskRequest, Occurrence, ProposedAssignment, Roster, RosterStatus,
+    Series, SeriesKind, Shift, ShiftAssignment, ShiftFilter, ShiftRequirement, Task, TaskFilter,
+    TaskStatus, UnfilledSlot, UpdateShiftRequest, UpdateTaskRequest,
+};
+use crate::error::AppError;
+use crate::user::models::UserRole;
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct PlannerRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PlannerRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_tasks(
+        &self,
+        event_id: &str,
+        filter: &TaskFilter,
+    ) -> Result<Vec<Task>, AppError> {
+        let mut select = task::Entity::find().filter(task::Column::EventId.eq(event_id));
+
+        if let Some(status) = &filter.status {
+            select = select.filter(task::Column::Status.eq(task::Status::from(status.clone())));
+        }
+        if let Some(priority) = &filter.priority {
+            select =
+                select.filter(task::Column::Priority.eq(task::Priority::from(priority.clone())));
+        }
+        if let Some(due_before) = filter.due_before {
+            select = select.filter(task::Column::DueAt.lt(due_before));
+        }
+        if let Some(team_id) = &filter.team_id {
+            select = select.filter(task::Column::TeamId.eq(team_id.as_str()));
+        }
+        if let Some(location_id) = &filter.location_id {
+            select = select.filter(task::Column::LocationId.eq(location_id.as_str()));
+        }
+        if let Some(assignee) = &filter.assignee {
+            let task_ids = self.task_ids_assigned_to(assignee).await?;
+            select = select.filter(task::Column::Id.is_in(task_ids));
+        }
+
+        let models = select
+            .order_by_asc(task::Column::DueAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_assignees(models).await
+    }
+
+    // tasks at any of the given locations
+    pub async fn find_tasks_in(
+        &self,
+        event_id: &str,
+        location_ids: &[String],
+    ) -> Result<Vec<Task>, AppError> {
+        let models = task::Entity::find()
+            .filter(task::Column::EventId.eq(event_id))
+            .filter(task::Column::LocationId.is_in(location_ids.iter().cloned()))
+            .order_by_asc(task::Column::DueAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_assignees(models).await
+    }
+
+    pub async fn find_task(&self, event_id: &str, id: &str) -> Result<Option<Task>, AppError> {
+        let model = task::Entity::find_by_id(id.to_string())
+            .filter(task::Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        match model {
+            Some(model) => Ok(self.with_assignees(vec![model]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn create_task(
+        &self,
+        event_id: &str,
+        dto: CreateTaskRequest,
+        created_by: &str,
+    ) -> Result<Task, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = insert_task(&txn, event_id, &dto, created_by, None).await?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(Task::from_model(model, dto.assignees))
+    }
+
+    pub async fn update_task(&self, id: &str, dto: UpdateTaskRequest) -> Result<Task, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = task::Entity::find_by_id(id.to_string())
+            .one(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+        let mut active_model: task::ActiveModel = model.into();
+
+        if let Some(title) = dto.title {
+            active_model.title = Set(title);
+        }
+        if let Some(description) = dto.description {
+            active_model.description = Set(description);
+        }
+        if let Some(priority) = dto.priority {
+            active_model.priority = Set(priority.into());
+        }
+        if let Some(due_at) = dto.due_at {
+            active_model.due_at = Set(Some(due_at));
+        }
+        if let Some(team_id) = dto.team_id {
+            active_model.team_id = Set(Some(team_id));
+        }
+        if let Some(location_id) = dto.location_id {
+            active_model.location_id = Set(Some(location_id));
+        }
+        if let Some(status) = dto.status {
+            if status != TaskStatus::Done {
+                active_model.completed_at = Set(None);
+                active_model.completed_by = Set(None);
+            }
+            active_model.status = Set(status.into());
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let model = active_model
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let assignees = match dto.assignees {
+            Some(assignees) => {
+                task_assignee::Entity::delete_many()
+                    .filter(task_assignee::Column::TaskId.eq(id))
+                    .exec(&txn)
+                    .await
+                    .map_err(|_| AppError::InternalServerError)?;
+                insert_assignees(&txn, id, &assignees).await?;
+                Some(assignees)
+            }
+            None => None,
+        };
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        match assignees {
+            Some(assignees) => Ok(Task::from_model(model, assignees)),
+            None => Ok(self
+                .with_assignees(vec![model])
+                .await?
+                .pop()
+                .ok_or(AppError::InternalServerError)?),
+        }
+    }
+
+    pub async fn complete_task(
+        &self,
+        event_id: &str,
+        id: &str,
+        completed_by: &str,
+    ) -> Result<Task, AppError> {
+        let model = task::Entity::find_by_id(id.to_string())
+            .filter(task::Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+        let now = chrono::Utc::now();
+        let mut active_model: task::ActiveModel = model.into();
+        active_model.status = Set(task::Status::Done);
+        active_model.completed_at = Set(Some(now));
+        active_model.completed_by = Set(Some(completed_by.to_string()));
+        active_model.updated_at = Set(now);
+
+        let model = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_assignees(vec![model])
+            .await?
+            .pop()
+            .ok_or(AppError::InternalServerError)
+    }
+
+    pub async fn delete_task(&self, id: &str) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        task_assignee::Entity::delete_many()
+            .filter(task_assignee::Column::TaskId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let result = task::Entity::delete_by_id(id.to_string())
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Task not found".to_string()));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn find_shifts(
+        &self,
+        event_id: &str,
+        filter: &ShiftFilter,
+    ) -> Result<Vec<Shift>, AppError> {
+        let mut select = shift::Entity::find().filter(shift::Column::EventId.eq(event_id));
+
+        if let Some(location) = &filter.location {
+            select = select.filter(shift::Column::Location.eq(location.as_str()));
+        }
+        if let Some(from) = filter.from {
+            select = select.filter(shift::Column::EndsAt.gt(from));
+        }
+        if let Some(to) = filter.to {
+            select = select.filter(shift::Column::StartsAt.lt(to));
+        }
+        if let Some(team_id) = &filter.team_id {
+            select = select.filter(shift::Column::TeamId.eq(team_id.as_str()));
+        }
+        if let Some(location_id) = &filter.location_id {
+            select = select.filter(shift::Column::LocationId.eq(location_id.as_str()));
+        }
+        if let Some(assignee) = &filter.assignee {
+            let shift_ids = self.shift_ids_assigned_to(assignee).await?;
+            select = select.filter(shift::Column::Id.is_in(shift_ids));
+        }
+
+        let models = select
+            .order_by_asc(shift::Column::StartsAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_staffing(models).await
+    }
+
+    // shifts at any of the given locations, only those running at `at` when it's set
+    pub async fn find_shifts_in(
+        &self,
+        event_id: &str,
+        location_ids: &[String],
+        at: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Shift>, AppError> {
+        let mut select = shift::Entity::find()
+            .filter(shift::Column::EventId.eq(event_id))
+            .filter(shift::Column::LocationId.is_in(location_ids.iter().cloned()));
+        if let Some(at) = at {
+            select = select
+                .filter(shift::Column::StartsAt.lte(at))
+                .filter(shift::Column::EndsAt.gt(at));
+        }
+
+        let models = select
+            .order_by_asc(shift::Column::StartsAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_staffing(models).await
+    }
+
+    pub async fn find_shifts_by_ids(
+        &self,
+        event_id: &str,
+        ids: &[String],
+    ) -> Result<Vec<Shift>, AppError> {
+        let models = shift::Entity::find()
+            .filter(shift::Column::EventId.eq(event_id))
+            .filter(shift::Column::Id.is_in(ids.iter().cloned()))
+            .order_by_asc(shift::Column::StartsAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_staffing(models).await
+    }
+
+    pub async fn find_shift(&self, event_id: &str, id: &str) -> Result<Option<Shift>, AppError> {
+        let model = shift::Entity::find_by_id(id.to_string())
+            .filter(shift::Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        match model {
+            Some(model) => Ok(self.with_staffing(vec![model]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn create_shift(
+        &self,
+        event_id: &str,
+        dto: CreateShiftRequest,
+        created_by: &str,
+    ) -> Result<Shift, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = insert_shift(&txn, event_id, &dto, created_by, None).await?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(Shift::from_model(
+            model,
+            dto.requirements,
+            dto.required_skills,
+            Vec::new(),
+        ))
+    }
+
+    pub async fn update_shift(&self, id: &str, dto: UpdateShiftRequest) -> Result<Shift, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = shift::Entity::find_by_id(id.to_string())
+            .one(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Shift not found".to_string()))?;
+
+        let mut active_model: shift::ActiveModel = model.into();
+
+        if let Some(title) = dto.title {
+            active_model.title = Set(title);
+        }
+        if let Some(location) = dto.location {
+            active_model.location = Set(location);
+        }
+        if let Some(team_id) = dto.team_id {
+            active_model.team_id = Set(Some(team_id));
+        }
+        if let Some(location_id) = dto.location_id {
+            active_model.location_id = Set(Some(location_id));
+        }
+        if let Some(starts_at) = dto.starts_at {
+            active_model.starts_at = Set(starts_at);
+        }
+        if let Some(ends_at) = dto.ends_at {
+            active_model.ends_at = Set(ends_at);
+        }
+        if let Some(notes) = dto.notes {
+            active_model.notes = Set(notes);
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        let model = active_model
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if let Some(requirements) = &dto.requirements {
+            shift_requirement::Entity::delete_many()
+                .filter(shift_requirement::Column::ShiftId.eq(id))
+                .exec(&txn)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            insert_requirements(&txn, id, requirements).await?;
+        }
+        if let Some(skills) = &dto.required_skills {
+            shift_skill::Entity::delete_many()
+                .filter(shift_skill::Column::ShiftId.eq(id))
+                .exec(&txn)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            insert_skills(&txn, id, skills).await?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_staffing(vec![model])
+            .await?
+            .pop()
+            .ok_or(AppError::InternalServerError)
+    }
+
+    pub async fn delete_shift(&self, id: &str) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        shift_assignment::Entity::delete_many()
+            .filter(shift_assignment::Column::ShiftId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        shift_requirement::Entity::delete_many()
+            .filter(shift_requirement::Column::ShiftId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        shift_skill::Entity::delete_many()
+            .filter(shift_skill::Column::ShiftId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let result = shift::Entity::delete_by_id(id.to_string())
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Shift not found".to_string()));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn assign_shift(
+        &self,
+        shift_id: &str,
+        user_id: &str,
+        role: UserRole,
+    ) -> Result<ShiftAssignment, AppError> {
+        let assignment = shift_assignment::ActiveModel {
+            shift_id: Set(shift_id.to_string()),
+            user_id: Set(user_id.to_string()),
+            role: Set(role.into()),
+            assigned_at: Set(chrono::Utc::now()),
+        };
+
+        let model = assignment
+            .insert(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.into())
+    }
+
+    pub async fn unassign_shift(
+        &self,
+        event_id: &str,
+        shift_id: &str,
+        user_id: &str,
+    ) -> Result<(), AppError> {
+        let result = shift_assignment::Entity::delete_many()
+            .filter(shift_assignment::Column::ShiftId.eq(shift_id))
+            .filter(
+                shift_assignment::Column::ShiftId.in_subquery(
+                    sea_query::Query::select()
+                        .column(shift::Column::Id)
+                        .from(shift::Entity)
+                        .and_where(shift::Column::EventId.eq(event_id))
+                        .to_owned(),
+                ),
+            )
+            .filter(shift_assignment::Column::UserId.eq(user_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Assignment not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn find_rosters(&self, event_id: &str) -> Result<Vec<Roster>, AppError> {
+        let models = roster::Entity::find()
+            .filter(roster::Column::EventId.eq(event_id))
+            .order_by_desc(roster::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        // the list stays light, the proposals come with a single roster
+        Ok(models
+            .into_iter()
+            .map(|m| Roster::from_model(m, Vec::new()))
+            .collect())
+    }
+
+    // rosters proposing someone, each with just their own proposals
+    pub async fn find_rosters_proposing(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<Roster>, AppError> {
+        let rows = roster_assignment::Entity::find()
+            .filter(roster_assignment::Column::UserId.eq(user_id))
+            .filter(
+                roster_assignment::Column::RosterId.in_subquery(
+                    sea_query::Query::select()
+                        .column(roster::Column::Id)
+                        .from(roster::Entity)
+                        .and_where(roster::Column::EventId.eq(event_id))
+                        .to_owned(),
+                ),
+            )
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        if rows.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut proposals: HashMap<String, Vec<ProposedAssignment>> = HashMap::new();
+        for row in rows {
+            proposals
+                .entry(row.roster_id.clone())
+                .or_default()
+                .push(row.into());
+        }
+
+        let models = roster::Entity::find()
+            .filter(roster::Column::Id.is_in(proposals.keys().cloned()))
+            .order_by_desc(roster::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| {
+                let assignments = proposals.remove(&m.id).unwrap_or_default();
+                Roster::from_model(m, assignments)
+            })
+            .collect())
+    }
+
+    pub async fn find_roster(&self, event_id: &str, id: &str) -> Result<Option<Roster>, AppError> {
+        let model = roster::Entity::find_by_id(id.to_string())
+            .filter(roster::Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let Some(model) = model else {
+            return Ok(None);
+        };
+
+        let assignments = roster_assignment::Entity::find()
+            .filter(roster_assignment::Column::RosterId.eq(id))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(Some(Roster::from_model(model, assignments)))
+    }
+
+    pub async fn create_roster(
+        &self,
+        event_id: &str,
+        seed: u64,
+        shift_ids: &[String],
+        assignments: Vec<ProposedAssignment>,
+        unfilled: &[UnfilledSlot],
+        created_by: &str,
+    ) -> Result<Roster, AppError> {
+        let id = uuid::Uuid::now_v7().to_string();
+        let new_roster = roster::ActiveModel {
+            id: Set(id.clone()),
+            event_id: Set(event_id.to_string()),
+            status: Set(roster::Status::Draft),
+            seed: Set(seed as i64),
+            shift_ids: Set(to_json(&shift_ids)?),
+            unfilled: Set(to_json(&unfilled)?),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(chrono::Utc::now()),
+            published_at: Set(None),
+            published_by: Set(None),
+        };
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = new_roster
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        insert_proposals(&txn, &id, &assignments).await?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(Roster::from_model(model, assignments))
+    }
+
+    // adds or drops one proposal on a draft and stores the recomputed unfilled list with it
+    pub async fn update_proposal(
+        &self,
+        roster_id: &str,
+        add: Option<&ProposedAssignment>,
+        remove: Option<(&str, &str)>,
+        unfilled: &[UnfilledSlot],
+    ) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if let Some(assignment) = add {
+            insert_proposals(&txn, roster_id, std::slice::from_ref(assignment)).await?;
+        }
+        if let Some((shift_id, user_id)) = remove {
+            let result = roster_assignment::Entity::delete_many()
+                .filter(roster_assignment::Column::RosterId.eq(roster_id))
+                .filter(roster_assignment::Column::ShiftId.eq(shift_id))
+                .filter(roster_assignment::Column::UserId.eq(user_id))
+                .exec(&txn)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+
+            if result.rows_affected == 0 {
+                return Err(AppError::NotFound(
+                    "Proposed assignment not found".to_string(),
+                ));
+            }
+        }
+
+        let update = roster::ActiveModel {
+            id: Set(roster_id.to_string()),
+            unfilled: Set(to_json(&unfilled)?),
+            ..Default::default()
+        };
+        update
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    // writes the accepted proposals as real shift assignments and closes the draft
+    pub async fn publish_roster(
+        &self,
+        roster_id: &str,
+        assignments: &[ProposedAssignment],
+        published_by: &str,
+    ) -> Result<(), AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if !assignments.is_empty() {
+            shift_assignment::Entity::insert_many(assignments.iter().map(|a| {
+                shift_assignment::ActiveModel {
+                    shift_id: Set(a.shift_id.clone()),
+                    user_id: Set(a.user_id.clone()),
+                    role: Set(a.role.clone().into()),
+                    assigned_at: Set(now),
+                }
+            }))
+            .exec_without_returning(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        }
+
+        let update = roster::ActiveModel {
+            id: Set(roster_id.to_string()),
+            status: Set(roster::Status::Published),
+            published_at: Set(Some(now)),
+            published_by: Set(Some(published_by.to_string())),
+            ..Default::default()
+        };
+        update
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn set_roster_status(&self, id: &str, status: RosterStatus) -> Result<(), AppError> {
+        let update = roster::ActiveModel {
+            id: Set(id.to_string()),
+            status: Set(status.into()),
+            ..Default::default()
+        };
+
+        update
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn create_series(
+        &self,
+        event_id: &str,
+        kind: SeriesKind,
+        rule: &str,
+        starts_at: DateTime<Utc>,
+        template: &serde_json::Value,
+        created_by: &str,
+    ) -> Result<Series, AppError> {
+        let now = chrono::Utc::now();
+        let new_series = series::ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            kind: Set(kind.into()),
+            rule: Set(rule.to_string()),
+            starts_at: Set(starts_at),
+            exdates: Set("[]".to_string()),
+            template: Set(to_json(template)?),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        let model = new_series
+            .insert(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.into())
+    }
+
+    pub async fn find_series(&self, event_id: &str, id: &str) -> Result<Option<Series>, AppError> {
+        let model = series::Entity::find_by_id(id.to_string())
+            .filter(series::Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(Into::into))
+    }
+
+    pub async fn update_series(&self, updated: &Series) -> Result<Series, AppError> {
+        let active_model = series::ActiveModel {
+            id: Set(updated.id.clone()),
+            rule: Set(updated.recurrence.clone()),
+            starts_at: Set(updated.starts_at),
+            exdates: Set(to_json(&updated.exdates)?),
+            template: Set(to_json(&updated.template)?),
+            updated_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        };
+
+        let model = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.into())
+    }
+
+    pub async fn delete_series(&self, id: &str) -> Result<(), AppError> {
+        series::Entity::delete_by_id(id.to_string())
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn find_series_shifts(&self, series_id: &str) -> Result<Vec<Shift>, AppError> {
+        let models = shift::Entity::find()
+            .filter(shift::Column::SeriesId.eq(series_id))
+            .order_by_asc(shift::Column::OccurrenceAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_staffing(models).await
+    }
+
+    pub async fn find_series_tasks(&self, series_id: &str) -> Result<Vec<Task>, AppError> {
+        let models = task::Entity::find()
+            .filter(task::Column::SeriesId.eq(series_id))
+            .order_by_asc(task::Column::OccurrenceAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_assignees(models).await
+    }
+
+    // one shift per slot, each as long as the template
+    pub async fn create_shift_occurrences(
+        &self,
+        event_id: &str,
+        series_id: &str,
+        template: &CreateShiftRequest,
+        slots: &[DateTime<Utc>],
+        created_by: &str,
+    ) -> Result<Vec<Shift>, AppError> {
+        let length = template.ends_at - template.starts_at;
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut shifts = Vec::with_capacity(slots.len());
+        for slot in slots {
+            let dto = CreateShiftRequest {
+                starts_at: *slot,
+                ends_at: *slot + length,
+                ..template.clone()
+            };
+            let occurrence = Occurrence {
+                series_id: series_id.to_string(),
+                occurrence_at: *slot,
+                is_exception: false,
+            };
+            let model = insert_shift(&txn, event_id, &dto, created_by, Some(&occurrence)).await?;
+            shifts.push(Shift::from_model(
+                model,
+                dto.requirements,
+                dto.required_skills,
+                Vec::new(),
+            ));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(shifts)
+    }
+
+    pub async fn create_task_occurrences(
+        &self,
+        event_id: &str,
+        series_id: &str,
+        template: &CreateTaskRequest,
+        slots: &[DateTime<Utc>],
+        created_by: &str,
+    ) -> Result<Vec<Task>, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut tasks = Vec::with_capacity(slots.len());
+        for slot in slots {
+            let dto = CreateTaskRequest {
+                due_at: Some(*slot),
+                ..template.clone()
+            };
+            let occurrence = Occurrence {
+                series_id: series_id.to_string(),
+                occurrence_at: *slot,
+                is_exception: false,
+            };
+            let model = insert_task(&txn, event_id, &dto, created_by, Some(&occurrence)).await?;
+            tasks.push(Task::from_model(model, dto.assignees));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(tasks)
+    }
+
+    pub async fn set_occurrence(
+        &self,
+        kind: SeriesKind,
+        id: &str,
+        occurrence: &Occurrence,
+    ) -> Result<(), AppError> {
+        let result = match kind {
+            SeriesKind::Shift => shift::ActiveModel {
+                id: Set(id.to_string()),
+                series_id: Set(Some(occurrence.series_id.clone())),
+                occurrence_at: Set(Some(occurrence.occurrence_at)),
+                is_exception: Set(occurrence.is_exception),
+                ..Default::default()
+            }
+            .update(self.db.as_ref())
+            .await
+            .map(|_| ()),
+            SeriesKind::Task => task::ActiveModel {
+                id: Set(id.to_string()),
+                series_id: Set(Some(occurrence.series_id.clone())),
+                occurrence_at: Set(Some(occurrence.occurrence_at)),
+                is_exception: Set(occurrence.is_exception),
+                ..Default::default()
+            }
+            .update(self.db.as_ref())
+            .await
+            .map(|_| ()),
+        };
+        result.map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    async fn task_ids_assigned_to(&self, user_id: &str) -> Result<Vec<String>, AppError> {
+        let rows = task_assignee::Entity::find()
+            .filter(task_assignee::Column::UserId.eq(user_id))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(rows.into_iter().map(|r| r.task_id).collect())
+    }
+
+    async fn with_assignees(&self, models: Vec<task::Model>) -> Result<Vec<Task>, AppError> {
+        if models.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = task_assignee::Entity::find()
+            .filter(task_assignee::Column::TaskId.is_in(models.iter().map(|m| m.id.clone())))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut assignees: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            assignees.entry(row.task_id).or_default().push(row.user_id);
+        }
+
+        Ok(models
+            .into_iter()
+            .map(|m| {
+                let task_assignees = assignees.remove(&m.id).unwrap_or_default();
+                Task::from_model(m, task_assignees)
+            })
+            .collect())
+    }
+
+    async fn shift_ids_assigned_to(&self, user_id: &str) -> Result<Vec<String>, AppError> {
+        let rows = shift_assignment::Entity::find()
+            .filter(shift_assignment::Column::UserId.eq(user_id))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(rows.into_iter().map(|r| r.shift_id).collect())
+    }
+
+    async fn with_staffing(&self, models: Vec<shift::Model>) -> Result<Vec<Shift>, AppError> {
+        if models.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<String> = models.iter().map(|m| m.id.clone()).collect();
+
+        let requirement_rows = shift_requirement::Entity::find()
+            .filter(shift_requirement::Column::ShiftId.is_in(ids.clone()))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let skill_rows = shift_skill::Entity::find()
+            .filter(shift_skill::Column::ShiftId.is_in(ids.clone()))
+            .order_by_asc(shift_skill::Column::Skill)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let assignment_rows = shift_assignment::Entity::find()
+            .filter(shift_assignment::Column::ShiftId.is_in(ids))
+            .order_by_asc(shift_assignment::Column::AssignedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut requirements: HashMap<String, Vec<ShiftRequirement>> = HashMap::new();
+        for row in requirement_rows {
+            requirements
+                .entry(row.shift_id.clone())
+                .or_default()
+                .push(row.into());
+        }
+        let mut skills: HashMap<String, Vec<String>> = HashMap::new();
+        for row in skill_rows {
+            skills.entry(row.shift_id).or_default().push(row.skill);
+        }
+        let mut assignments: HashMap<String, Vec<ShiftAssignment>> = HashMap::new();
+        for row in assignment_rows {
+            assignments
+                .entry(row.shift_id.clone())
+                .or_default()
+                .push(row.into());
+        }
+
+        Ok(models
+            .into_iter()
+            .map(|m| {
+                let shift_requirements = requirements.remove(&m.id).unwrap_or_default();
+                let shift_skills = skills.remove(&m.id).unwrap_or_default();
+                let shift_assignments = assignments.remove(&m.id).unwrap_or_default();
+                Shift::from_model(m, shift_requirements, shift_skills, shift_assignments)
+            })
+            .collect())
+    }
+}
+
+async fn insert_task(
+    txn: &DatabaseTransaction,
+    event_id: &str,
+    dto: &CreateTaskRequest,
+    created_by: &str,
+    occurrence: Option<&Occurrence>,
+) -> Result<task::Model, AppError> {
+    let now = chrono::Utc::now();
+    let id = uuid::Uuid::now_v7().to_string();
+
+    let new_task = task::ActiveModel {
+        id: Set(id.clone()),
+        event_id: Set(event_id.to_string()),
+        title: Set(dto.title.clone()),
+        description: Set(dto.description.clone()),
+        priority: Set(dto
+            .priority
+            .clone()
+            .map(Into::into)
+            .unwrap_or(task::Priority::Medium)),
+        status: Set(task::Status::Todo),
+        due_at: Set(dto.due_at),
+        created_by: Set(created_by.to_string()),
+        created_at: Set(now),
+        updated_at: Set(now),
+        completed_at: Set(None),
+        completed_by: Set(None),
+        series_id: Set(occurrence.map(|o| o.series_id.clone())),
+        occurrence_at: Set(occurrence.map(|o| o.occurrence_at)),
+        is_exception: Set(false),
+        team_id: Set(dto.team_id.clone()),
+        location_id: Set(dto.location_id.clone()),
+    };
+
+    let model = new_task
+        .insert(txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    insert_assignees(txn, &id, &dto.assignees).await?;
+
+    Ok(model)
+}
+
+async fn insert_shift(
+    txn: &DatabaseTransaction,
+    event_id: &str,
+    dto: &CreateShiftRequest,
+    created_by: &str,
+    occurrence: Option<&Occurrence>,
+) -> Result<shift::Model, AppError> {
+    let now = chrono::Utc::now();
+    let id = uuid::Uuid::now_v7().to_string();
+
+    let new_shift = shift::ActiveModel {
+        id: Set(id.clone()),
+        event_id: Set(event_id.to_string()),
+        title: Set(dto.title.clone()),
+        location: Set(dto.location.clone()),
+        starts_at: Set(dto.starts_at),
+        ends_at: Set(dto.ends_at),
+        notes: Set(dto.notes.clone()),
+        created_by: Set(created_by.to_string()),
+        created_at: Set(now),
+        updated_at: Set(now),
+        series_id: Set(occurrence.map(|o| o.series_id.clone())),
+        occurrence_at: Set(occurrence.map(|o| o.occurrence_at)),
+        is_exception: Set(false),
+        team_id: Set(dto.team_id.clone()),
+        location_id: Set(dto.location_id.clone()),
+    };
+
+    let model = new_shift
+        .insert(txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    insert_requirements(txn, &id, &dto.requirements).await?;
+    insert_skills(txn, &id, &dto.required_skills).await?;
+
+    Ok(model)
+}
+
+async fn insert_assignees(
+    txn: &DatabaseTransaction,
+    task_id: &str,
+    user_ids: &[String],
+) -> Result<(), AppError> {
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    task_assignee::Entity::insert_many(user_ids.iter().map(|user_id| task_assignee::ActiveModel {
+        task_id: Set(task_id.to_string()),
+        user_id: Set(user_id.clone()),
+    }))
+    .exec_without_returning(txn)
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(())
+}
+
+async fn insert_requirements(
+    txn: &DatabaseTransaction,
+    shift_id: &str,
+    requirements: &[ShiftRequirement],
+) -> Result<(), AppError> {
+    if requirements.is_empty() {
+        return Ok(());
+    }
+
+    shift_requirement::Entity::insert_many(requirements.iter().map(|r| {
+        shift_requirement::ActiveModel {
+            shift_id: Set(shift_id.to_string()),
+            role: Set(r.role.clone().into()),
+            headcount: Set(r.headcount as i32),
+        }
+    }))
+    .exec_without_returning(txn)
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(())
+}
+
+async fn insert_skills(
+    txn: &DatabaseTransaction,
+    shift_id: &str,
+    skills: &[String],
+) -> Result<(), AppError> {
+    if skills.is_empty() {
+        return Ok(());
+    }
+
+    shift_skill::Entity::insert_many(skills.iter().map(|skill| shift_skill::ActiveModel {
+        shift_id: Set(shift_id.to_string()),
+        skill: Set(skill.clone()),
+    }))
+    .exec_without_returning(txn)
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(())
+}
+
+async fn insert_proposals(
+    txn: &DatabaseTransaction,
+    roster_id: &str,
+    assignments: &[ProposedAssignment],
+) -> Result<(), AppError> {
+    if assignments.is_empty() {
+        return Ok(());
+    }
+
+    roster_assignment::Entity::insert_many(assignments.iter().map(|a| {
+        roster_assignment::ActiveModel {
+            roster_id: Set(roster_id.to_string()),
+            shift_id: Set(a.shift_id.clone()),
+            user_id: Set(a.user_id.clone()),
+            role: Set(a.role.clone().into()),
+        }
+    }))
+    .exec_without_returning(txn)
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(())
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, AppError> {
+    serde_json::to_string(value).map_err(|_| AppError::InternalServerError)
+}
@@ -0,0 +1,226 @@
+use super::conflicts::find_conflicts;
+use super::models::{ProposedAssignment, Shift, ShiftLimits, UnfilledSlot};
+use crate::user::models::{User, UserRole, UserStatus};
+use std::collections::HashMap;
+
+pub struct RosterPlan {
+    pub assignments: Vec<ProposedAssignment>,
+    pub unfilled: Vec<UnfilledSlot>,
+}
+
+// greedy fill, earliest shift first. every open slot goes to the eligible person with the
+// fewest scheduled minutes so far, preferred area breaks ties, then a hash of the seed.
+// people need their profile loaded, schedule holds every shift around the window with its
+// current assignments and may include the shifts being rostered
+pub fn generate(
+    shifts: &[Shift],
+    people: &[User],
+    schedule: &[Shift],
+    limits: &ShiftLimits,
+    seed: u64,
+) -> RosterPlan {
+    let mut per_person: HashMap<&str, Vec<Shift>> = HashMap::new();
+    for shift in schedule {
+        for assignment in &shift.assignments {
+            per_person
+                .entry(assignment.user_id.as_str())
+                .or_default()
+                .push(shift.clone());
+        }
+    }
+    let mut minutes: HashMap<&str, i64> = per_person
+        .iter()
+        .map(|(id, shifts)| (*id, shifts.iter().map(duration_minutes).sum()))
+        .collect();
+
+    let mut ordered: Vec<&Shift> = shifts.iter().collect();
+    ordered.sort_by(|a, b| a.starts_at.cmp(&b.starts_at).then(a.id.cmp(&b.id)));
+
+    let mut assignments = Vec::new();
+    let mut unfilled = Vec::new();
+
+    for shift in ordered {
+        for slot in shift.open_slots() {
+            let mut tally = Tally::default();
+            let mut eligible: Vec<(&User, bool)> = Vec::new();
+
+            for person in people {
+                if person.role != slot.role || person.status != UserStatus::Active {
+                    continue;
+                }
+                tally.with_role += 1;
+
+                let on_shift = shift.is_assigned_to(&person.id)
+                    || assignments.iter().any(|a: &ProposedAssignment| {
+                        a.shift_id == shift.id && a.user_id == person.id
+                    });
+                if on_shift {
+                    tally.on_shift += 1;
+                    continue;
+                }
+
+                let profile = person.profile.clone().unwrap_or_default();
+                if !profile.is_available(shift.starts_at, shift.ends_at) {
+                    tally.unavailable += 1;
+                    continue;
+                }
+
+                let missing = profile.missing_skills(&shift.required_skills);
+                if !missing.is_empty() {
+                    tally.missing_skills += 1;
+                    for skill in missing {
+                        if !tally.skills.contains(&skill) {
+                            tally.skills.push(skill);
+                        }
+                    }
+                    continue;
+                }
+
+                let own = per_person
+                    .get(person.id.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                if !find_conflicts(shift, own, limits).is_empty() {
+                    tally.conflicting += 1;
+                    continue;
+                }
+
+                eligible.push((person, profile.prefers(&shift.location)));
+            }
+
+            eligible.sort_by_key(|(person, prefers)| {
+                (
+                    minutes.get(person.id.as_str()).copied().unwrap_or(0),
+                    !prefers,
+                    tiebreak(seed, &shift.id, &person.id),
+                )
+            });
+
+            let taken = eligible.len().min(slot.open as usize);
+            for (person, _) in eligible.iter().take(taken) {
+                assignments.push(ProposedAssignment {
+                    shift_id: shift.id.clone(),
+                    user_id: person.id.clone(),
+                    role: slot.role.clone(),
+                });
+                per_person
+                    .entry(person.id.as_str())
+                    .or_default()
+                    .push(shift.clone());
+                *minutes.entry(person.id.as_str()).or_default() += duration_minutes(shift);
+            }
+
+            if taken < slot.open as usize {
+                unfilled.push(UnfilledSlot {
+                    shift_id: shift.id.clone(),
+                    role: slot.role.clone(),
+                    missing: slot.open - taken as u32,
+                    reasons: tally.reasons(slot.role.as_str(), taken),
+                });
+            }
+        }
+    }
+
+    RosterPlan {
+        assignments,
+        unfilled,
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    with_role: usize,
+    on_shift: usize,
+    unavailable: usize,
+    missing_skills: usize,
+    skills: Vec<String>,
+    conflicting: usize,
+}
+
+impl Tally {
+    fn reasons(&self, role: &str, eligible: usize) -> Vec<String> {
+        if self.with_role == 0 {
+            return vec![format!("Nobody active has the {} role", role)];
+        }
+
+        let mut reasons = vec![format!(
+            "Only {} of {} {}(s) could take it",
+            eligible, self.with_role, role
+        )];
+        if self.on_shift > 0 {
+            reasons.push(format!("{} already on this shift", self.on_shift));
+        }
+        if self.unavailable > 0 {
+            reasons.push(format!("{} not available", self.unavailable));
+        }
+        if self.missing_skills > 0 {
+            reasons.push(format!(
+                "{} missing skills: {}",
+                self.missing_skills,
+                self.skills.join(", ")
+            ));
+        }
+        if self.conflicting > 0 {
+            reasons.push(format!(
+                "{} would overlap, run short on rest or go over daily hours",
+                self.conflicting
+            ));
+        }
+
+        reasons
+    }
+}
+
+fn duration_minutes(shift: &Shift) -> i64 {
+    (shift.ends_at - shift.starts_at).num_minutes()
+}
+
+// FNV-1a over the inputs, stable across runs and platforms unlike the std hasher
+fn tiebreak(seed: u64, shift_id: &str, user_id: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325 ^ seed;
+    for byte in shift_id
+        .bytes()
+        .chain(std::iter::once(0))
+        .chain(user_id.bytes())
+    {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+// keeps the unfilled list of a draft in step with hand edits
+pub fn fill_slot(unfilled: &mut Vec<UnfilledSlot>, shift_id: &str, role: &UserRole) {
+    if let Some(pos) = unfilled
+        .iter()
+        .position(|u| u.shift_id == shift_id && &u.role == role)
+    {
+        unfilled[pos].missing -= 1;
+        if unfilled[pos].missing == 0 {
+            unfilled.remove(pos);
+        }
+    }
+}
+
+pub fn reopen_slot(unfilled: &mut Vec<UnfilledSlot>, shift_id: &str, role: &UserRole) {
+    let reason = "Removed by hand".to_string();
+
+    match unfilled
+        .iter_mut()
+        .find(|u| u.shift_id == shift_id && &u.role == role)
+    {
+        Some(slot) => {
+            slot.missing += 1;
+            if !slot.reasons.contains(&reason) {
+                slot.reasons.push(reason);
+            }
+        }
+        None => unfilled.push(UnfilledSlot {
+            shift_id: shift_id.to_string(),
+            role: role.clone(),
+            missing: 1,
+            reasons: vec![reason],
+        }),
+    }
+}
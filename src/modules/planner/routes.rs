@@ -0,0 +1,413 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::planner::models::{
+    AssignShiftQuery, AssignShiftRequest, CandidateQuery, CreateShiftRequest, CreateTaskRequest,
+    FreeQuery, GenerateRosterRequest, RecurringShiftRequest, RecurringTaskRequest,
+    RosterAssignmentRequest, ScopeQuery, ShiftFilter, TaskFilter, UpdateSeriesRequest,
+    UpdateShiftQuery, UpdateShiftRequest, UpdateTaskRequest,
+};
+use crate::modules::planner::service::PlannerService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/planner")
+            .wrap(auth_middleware)
+            .route("/tasks", web::get().to(get_tasks))
+            .route("/tasks", web::post().to(create_task))
+            .route("/tasks/recurring", web::post().to(create_recurring_task))
+            .route("/tasks/{id}", web::get().to(get_task))
+            .route("/tasks/{id}", web::patch().to(update_task))
+            .route("/tasks/{id}", web::delete().to(delete_task))
+            .route("/tasks/{id}/complete", web::post().to(complete_task))
+            .route("/free", web::get().to(get_free))
+            .route("/shifts", web::get().to(get_shifts))
+            .route("/shifts", web::post().to(create_shift))
+            .route("/shifts/recurring", web::post().to(create_recurring_shift))
+            .route("/shifts/open", web::get().to(get_open_slots))
+            .route("/shifts/coverage", web::get().to(get_coverage))
+            .route("/shifts/{id}", web::get().to(get_shift))
+            .route("/shifts/{id}", web::patch().to(update_shift))
+            .route("/shifts/{id}", web::delete().to(delete_shift))
+            .route("/shifts/{id}/candidates", web::get().to(get_candidates))
+            .route("/shifts/{id}/assignments", web::post().to(assign_shift))
+            .route(
+                "/shifts/{id}/assignments/{user_id}",
+                web::delete().to(unassign_shift),
+            )
+            .route("/series/{id}", web::get().to(get_series))
+            .route("/series/{id}", web::patch().to(update_series))
+            .route("/rosters", web::get().to(get_rosters))
+            .route("/rosters", web::post().to(generate_roster))
+            .route("/rosters/{id}", web::get().to(get_roster))
+            .route("/rosters/{id}", web::delete().to(discard_roster))
+            .route("/rosters/{id}/publish", web::post().to(publish_roster))
+            .route(
+                "/rosters/{id}/assignments",
+                web::post().to(add_roster_assignment),
+            )
+            .route(
+                "/rosters/{id}/assignments/{shift_id}/{user_id}",
+                web::delete().to(remove_roster_assignment),
+            ),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_tasks(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    query: web::Query<TaskFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let tasks = service.get_tasks(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(tasks))
+}
+
+async fn create_task(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    body: web::Json<CreateTaskRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let task = service.create_task(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(task))
+}
+
+async fn get_task(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let task = service.get_task(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(task))
+}
+
+async fn update_task(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    query: web::Query<ScopeQuery>,
+    body: web::Json<UpdateTaskRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let task = service
+        .update_task(&path.into_inner(), body.into_inner(), query.scope, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(task))
+}
+
+async fn complete_task(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let task = service.complete_task(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(task))
+}
+
+async fn delete_task(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    query: web::Query<ScopeQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service
+        .delete_task(&path.into_inner(), query.scope, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().body("Task deleted successfully"))
+}
+
+async fn get_shifts(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    query: web::Query<ShiftFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let shifts = service.get_shifts(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(shifts))
+}
+
+async fn get_open_slots(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    query: web::Query<ShiftFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let slots = service.get_open_slots(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(slots))
+}
+
+async fn get_coverage(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    query: web::Query<ShiftFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let report = service.get_coverage(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+async fn create_shift(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    body: web::Json<CreateShiftRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let shift = service.create_shift(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(shift))
+}
+
+async fn get_shift(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let shift = service.get_shift(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(shift))
+}
+
+async fn update_shift(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    query: web::Query<UpdateShiftQuery>,
+    body: web::Json<UpdateShiftRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let shift = service
+        .update_shift(
+            &path.into_inner(),
+            body.into_inner(),
+            query.scope,
+            query.force,
+            &claims,
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(shift))
+}
+
+async fn delete_shift(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    query: web::Query<ScopeQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service
+        .delete_shift(&path.into_inner(), query.scope, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().body("Shift deleted successfully"))
+}
+
+async fn assign_shift(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    query: web::Query<AssignShiftQuery>,
+    body: web::Json<AssignShiftRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let assignment = service
+        .assign_shift(&path.into_inner(), body.into_inner(), query.force, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(assignment))
+}
+
+async fn unassign_shift(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+    let (shift_id, user_id) = path.into_inner();
+
+    service.unassign_shift(&shift_id, &user_id, &claims).await?;
+    Ok(HttpResponse::Ok().body("Assignment removed successfully"))
+}
+
+async fn get_candidates(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    query: web::Query<CandidateQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let candidates = service
+        .get_candidates(&path.into_inner(), query.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(candidates))
+}
+
+async fn get_free(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    query: web::Query<FreeQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let users = service.get_free(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(users))
+}
+
+async fn get_rosters(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let rosters = service.get_rosters(&claims).await?;
+    Ok(HttpResponse::Ok().json(rosters))
+}
+
+async fn generate_roster(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    body: web::Json<GenerateRosterRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let roster = service.generate_roster(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(roster))
+}
+
+async fn get_roster(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let roster = service.get_roster(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(roster))
+}
+
+async fn discard_roster(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service.discard_roster(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("Roster discarded"))
+}
+
+async fn publish_roster(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let report = service.publish_roster(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+async fn add_roster_assignment(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    body: web::Json<RosterAssignmentRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let roster = service
+        .add_roster_assignment(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(roster))
+}
+
+async fn remove_roster_assignment(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<(String, String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+    let (id, shift_id, user_id) = path.into_inner();
+
+    let roster = service
+        .remove_roster_assignment(&id, &shift_id, &user_id, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(roster))
+}
+
+async fn create_recurring_task(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    body: web::Json<RecurringTaskRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let series = service
+        .create_recurring_task(body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(series))
+}
+
+async fn create_recurring_shift(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    body: web::Json<RecurringShiftRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let series = service
+        .create_recurring_shift(body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(series))
+}
+
+async fn get_series(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let series = service.get_series(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(series))
+}
+
+async fn update_series(
+    req: HttpRequest,
+    service: web::Data<PlannerService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateSeriesRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let series = service
+        .update_series(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(series))
+}
@@ -0,0 +1,1666 @@
+use super::conflicts::find_conflicts;
+use super::models::{
+    AssignShiftRequest, CandidateQuery, CoverageReport, CreateShiftRequest, CreateTaskRequest,
+    EditScope, FreeQuery, GenerateRosterRequest, Occurrence, ProposedAssignment, PublishReport,
+    RecurringShiftRequest, RecurringTaskRequest, Roster, RosterAssignmentRequest, RosterStatus,
+    Series, SeriesKind, SeriesOccurrences, Shift, ShiftAssignment, ShiftCandidate, ShiftFilter,
+    ShiftLimits, ShiftRequirement, ShiftSlots, SkippedAssignment, Task, TaskFilter,
+    UpdateSeriesRequest, UpdateShiftRequest, UpdateTaskRequest,
+};
+use super::recurrence::RecurrenceRule;
+use super::repository::PlannerRepository;
+use super::roster::{self, fill_slot, reopen_slot};
+use crate::audit::models::NewAuditEntry;
+use crate::audit::service::AuditService;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::location::repository::LocationRepository;
+use crate::team::repository::TeamRepository;
+use crate::user::models::{User, UserFilter, UserStatus, normalize_tags};
+use crate::user::repository::UserRepository;
+use chrono::{DateTime, Duration, Utc};
+
+pub struct PlannerService {
+    repository: PlannerRepository,
+    users: UserRepository,
+    teams: TeamRepository,
+    locations: LocationRepository,
+    audit: AuditService,
+    limits: ShiftLimits,
+}
+
+fn require_manager(claims: &Claims) -> Result<(), AppError> {
+    if !claims.is_manager() {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(())
+}
+
+// staff may lead a team, so they get past the first check and are narrowed down per item
+fn require_lead_role(claims: &Claims) -> Result<(), AppError> {
+    if !claims.is_staff() {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(())
+}
+
+impl PlannerService {
+    pub fn new(
+        repository: PlannerRepository,
+        users: UserRepository,
+        teams: TeamRepository,
+        locations: LocationRepository,
+        audit: AuditService,
+        limits: ShiftLimits,
+    ) -> Self {
+        Self {
+            repository,
+            users,
+            teams,
+            locations,
+            audit,
+            limits,
+        }
+    }
+
+    pub async fn get_tasks(
+        &self,
+        filter: TaskFilter,
+        claims: &Claims,
+    ) -> Result<Vec<Task>, AppError> {
+        // volunteers only ever see their own schedule
+        let filter = if claims.role == "volunteer" {
+            TaskFilter {
+                assignee: Some(claims.sub.clone()),
+                ..filter
+            }
+        } else {
+            filter
+        };
+
+        self.repository.find_tasks(&claims.event, &filter).await
+    }
+
+    pub async fn get_task(&self, id: &str, claims: &Claims) -> Result<Task, AppError> {
+        let task = self
+            .repository
+            .find_task(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+        if claims.role == "volunteer" && !task.is_assigned_to(&claims.sub) {
+            return Err(AppError::NotFound("Task not found".to_string()));
+        }
+
+        Ok(task)
+    }
+
+    pub async fn create_task(
+        &self,
+        req: CreateTaskRequest,
+        claims: &Claims,
+    ) -> Result<Task, AppError> {
+        self.require_team_manager(req.team_id.as_deref(), claims)
+            .await?;
+
+        if req.title.trim().is_empty() {
+            return Err(AppError::BadRequest("Title is required".to_string()));
+        }
+        self.check_team(req.team_id.as_deref(), claims).await?;
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+        self.check_assignees(&req.assignees, claims).await?;
+
+        self.repository
+            .create_task(&claims.event, req, &claims.sub)
+            .await
+    }
+
+    pub async fn update_task(
+        &self,
+        id: &str,
+        req: UpdateTaskRequest,
+        scope: EditScope,
+        claims: &Claims,
+    ) -> Result<Task, AppError> {
+        require_lead_role(claims)?;
+
+        if req.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            return Err(AppError::BadRequest("Title is required".to_string()));
+        }
+
+        let current = self.find_task(id, claims).await?;
+        self.require_team_manager(current.team_id.as_deref(), claims)
+            .await?;
+        self.check_move(req.team_id.as_deref(), claims).await?;
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+        if let Some(assignees) = &req.assignees {
+            self.check_assignees(assignees, claims).await?;
+        }
+        let occurrence = match (&current.occurrence, scope) {
+            (Some(occurrence), EditScope::Following | EditScope::All) => occurrence.clone(),
+            _ => {
+                let task = self.repository.update_task(id, req).await?;
+                return self
+                    .mark_exception(SeriesKind::Task, id, current.occurrence)
+                    .await
+                    .map(|occurrence| Task { occurrence, ..task });
+            }
+        };
+
+        let mut series = self.find_series(&occurrence.series_id, claims).await?;
+        let members = self.repository.find_series_tasks(&series.id).await?;
+        let from = (scope == EditScope::Following).then_some(occurrence.occurrence_at);
+        let shift_by = match (req.due_at, current.due_at) {
+            (Some(due_at), Some(current)) => due_at - current,
+            _ => Duration::zero(),
+        };
+
+        for member in &members {
+            let is_exception = member.occurrence.as_ref().is_some_and(|o| o.is_exception);
+            if !reaches(member.occurrence.as_ref(), from) || (is_exception && member.id != id) {
+                continue;
+            }
+
+            let update = UpdateTaskRequest {
+                title: req.title.clone(),
+                description: req.description.clone(),
+                priority: req.priority.clone(),
+                status: req.status.clone(),
+                due_at: req.due_at.and(member.due_at.map(|d| d + shift_by)),
+                assignees: req.assignees.clone(),
+                team_id: req.team_id.clone(),
+                location_id: req.location_id.clone(),
+            };
+            self.repository.update_task(&member.id, update).await?;
+        }
+
+        let mut template: CreateTaskRequest = from_template(&series)?;
+        template.title = req.title.unwrap_or(template.title);
+        template.description = req.description.unwrap_or(template.description);
+        template.priority = req.priority.or(template.priority);
+        template.assignees = req.assignees.unwrap_or(template.assignees);
+        template.team_id = req.team_id.or(template.team_id);
+        template.location_id = req.location_id.or(template.location_id);
+        template.due_at = template.due_at.map(|d| d + shift_by);
+        series.template = to_template(&template)?;
+
+        let occurrences = members
+            .into_iter()
+            .filter_map(|m| Some((m.id, m.occurrence?)));
+        self.reshape_series(series, from, shift_by, occurrences.collect(), claims)
+            .await?;
+
+        self.find_task(id, claims).await
+    }
+
+    pub async fn complete_task(&self, id: &str, claims: &Claims) -> Result<Task, AppError> {
+        if !claims.is_manager() {
+            if claims.role != "staff" {
+                return Err(AppError::Forbidden);
+            }
+
+            let task = self
+                .repository
+                .find_task(&claims.event, id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+            if !task.is_assigned_to(&claims.sub) {
+                self.require_team_manager(task.team_id.as_deref(), claims)
+                    .await?;
+            }
+        }
+
+        self.repository
+            .complete_task(&claims.event, id, &claims.sub)
+            .await
+    }
+
+    pub async fn delete_task(
+        &self,
+        id: &str,
+        scope: EditScope,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        require_lead_role(claims)?;
+
+        let current = self.find_task(id, claims).await?;
+        self.require_team_manager(current.team_id.as_deref(), claims)
+            .await?;
+        match (current.occurrence, scope) {
+            (Some(occurrence), EditScope::Following | EditScope::All) => {
+                let from = (scope == EditScope::Following).then_some(occurrence.occurrence_at);
+                let members = self
+                    .repository
+                    .find_series_tasks(&occurrence.series_id)
+                    .await?;
+                for member in members {
+                    if reaches(member.occurrence.as_ref(), from) {
+                        self.repository.delete_task(&member.id).await?;
+                    }
+                }
+                self.trim_series(&occurrence.series_id, from, claims).await
+            }
+            (occurrence, _) => {
+                self.repository.delete_task(id).await?;
+                self.skip_occurrence(occurrence, claims).await
+            }
+        }
+    }
+
+    pub async fn get_shifts(
+        &self,
+        filter: ShiftFilter,
+        claims: &Claims,
+    ) -> Result<Vec<Shift>, AppError> {
+        let filter = if claims.role == "volunteer" {
+            ShiftFilter {
+                assignee: Some(claims.sub.clone()),
+                ..filter
+            }
+        } else {
+            filter
+        };
+
+        self.repository.find_shifts(&claims.event, &filter).await
+    }
+
+    pub async fn get_shift(&self, id: &str, claims: &Claims) -> Result<Shift, AppError> {
+        let shift = self.find_shift(id, claims).await?;
+
+        if claims.role == "volunteer" && !shift.is_assigned_to(&claims.sub) {
+            return Err(AppError::NotFound("Shift not found".to_string()));
+        }
+
+        Ok(shift)
+    }
+
+    // upcoming shifts that still need people, unless the filter says otherwise
+    pub async fn get_open_slots(
+        &self,
+        filter: ShiftFilter,
+        claims: &Claims,
+    ) -> Result<Vec<ShiftSlots>, AppError> {
+        if claims.role == "volunteer" {
+            return Err(AppError::Forbidden);
+        }
+
+        let shifts = self.upcoming_shifts(filter, claims).await?;
+        Ok(shifts.iter().filter_map(ShiftSlots::of).collect())
+    }
+
+    pub async fn get_coverage(
+        &self,
+        filter: ShiftFilter,
+        claims: &Claims,
+    ) -> Result<CoverageReport, AppError> {
+        require_manager(claims)?;
+
+        let shifts = self.upcoming_shifts(filter, claims).await?;
+        let understaffed: Vec<ShiftSlots> = shifts.iter().filter_map(ShiftSlots::of).collect();
+
+        Ok(CoverageReport {
+            total_shifts: shifts.len(),
+            understaffed_shifts: understaffed.len(),
+            open_slots: understaffed
+                .iter()
+                .flat_map(|s| s.slots.iter())
+                .map(|slot| slot.open)
+                .sum(),
+            shifts: understaffed,
+        })
+    }
+
+    pub async fn create_shift(
+        &self,
+        req: CreateShiftRequest,
+        claims: &Claims,
+    ) -> Result<Shift, AppError> {
+        self.require_team_manager(req.team_id.as_deref(), claims)
+            .await?;
+
+        if req.title.trim().is_empty() {
+            return Err(AppError::BadRequest("Title is required".to_string()));
+        }
+        validate_times(req.starts_at, req.ends_at)?;
+        validate_requirements(&req.requirements)?;
+        self.check_team(req.team_id.as_deref(), claims).await?;
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+
+        let req = CreateShiftRequest {
+            required_skills: normalize_tags(&req.required_skills),
+            ..req
+        };
+        self.repository
+            .create_shift(&claims.event, req, &claims.sub)
+            .await
+    }
+
+    pub async fn update_shift(
+        &self,
+        id: &str,
+        req: UpdateShiftRequest,
+        scope: EditScope,
+        force: bool,
+        claims: &Claims,
+    ) -> Result<Shift, AppError> {
+        require_lead_role(claims)?;
+        if force {
+            require_manager(claims)?;
+        }
+
+        if req.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            return Err(AppError::BadRequest("Title is required".to_string()));
+        }
+        if let Some(requirements) = &req.requirements {
+            validate_requirements(requirements)?;
+        }
+
+        let current = self.find_shift(id, claims).await?;
+        self.require_team_manager(current.team_id.as_deref(), claims)
+            .await?;
+        self.check_move(req.team_id.as_deref(), claims).await?;
+        self.check_location(req.location_id.as_deref(), claims)
+            .await?;
+        validate_times(
+            req.starts_at.unwrap_or(current.starts_at),
+            req.ends_at.unwrap_or(current.ends_at),
+        )?;
+
+        let req = UpdateShiftRequest {
+            required_skills: req.required_skills.as_deref().map(normalize_tags),
+            ..req
+        };
+        let occurrence = match (&current.occurrence, scope) {
+            (Some(occurrence), EditScope::Following | EditScope::All) => occurrence.clone(),
+            _ => {
+                let moved = Shift {
+                    starts_at: req.starts_at.unwrap_or(current.starts_at),
+                    ends_at: req.ends_at.unwrap_or(current.ends_at),
+                    ..current.clone()
+                };
+                self.check_moves(std::slice::from_ref(&current), &[moved], force, claims)
+                    .await?;
+                let shift = self.repository.update_shift(id, req).await?;
+                return self
+                    .mark_exception(SeriesKind::Shift, id, current.occurrence)
+                    .await
+                    .map(|occurrence| Shift {
+                        occurrence,
+                        ..shift
+                    });
+            }
+        };
+
+        let mut series = self.find_series(&occurrence.series_id, claims).await?;
+        let members = self.repository.find_series_shifts(&series.id).await?;
+        let from = (scope == EditScope::Following).then_some(occurrence.occurrence_at);
+        // moves are relative, so "start an hour later" carries over to every occurrence
+        let shift_by = req
+            .starts_at
+            .map(|s| s - current.starts_at)
+            .unwrap_or_default();
+        let stretch_by = req.ends_at.map(|e| e - current.ends_at).unwrap_or_default();
+
+        let targets: Vec<&Shift> = members
+            .iter()
+            .filter(|m| reaches(m.occurrence.as_ref(), from))
+            .filter(|m| !m.occurrence.as_ref().is_some_and(|o| o.is_exception) || m.id == id)
+            .collect();
+        for target in &targets {
+            validate_times(target.starts_at + shift_by, target.ends_at + stretch_by)?;
+        }
+        let moved: Vec<Shift> = targets
+            .iter()
+            .map(|t| Shift {
+                starts_at: t.starts_at + shift_by,
+                ends_at: t.ends_at + stretch_by,
+                ..(*t).clone()
+            })
+            .collect();
+        let before: Vec<Shift> = targets.iter().map(|t| (*t).clone()).collect();
+        self.check_moves(&before, &moved, force, claims).await?;
+        for target in targets {
+            let update = UpdateShiftRequest {
+                title: req.title.clone(),
+                location: req.location.clone(),
+                starts_at: req.starts_at.map(|_| target.starts_at + shift_by),
+                ends_at: req.ends_at.map(|_| target.ends_at + stretch_by),
+                notes: req.notes.clone(),
+                requirements: req.requirements.clone(),
+                required_skills: req.required_skills.clone(),
+                team_id: req.team_id.clone(),
+                location_id: req.location_id.clone(),
+            };
+            self.repository.update_shift(&target.id, update).await?;
+        }
+
+        let mut template: CreateShiftRequest = from_template(&series)?;
+        template.title = req.title.unwrap_or(template.title);
+        template.location = req.location.unwrap_or(template.location);
+        template.notes = req.notes.unwrap_or(template.notes);
+        template.requirements = req.requirements.unwrap_or(template.requirements);
+        template.required_skills = req.required_skills.unwrap_or(template.required_skills);
+        template.team_id = req.team_id.or(template.team_id);
+        template.location_id = req.location_id.or(template.location_id);
+        template.starts_at += shift_by;
+        template.ends_at += stretch_by;
+        series.template = to_template(&template)?;
+
+        let occurrences = members
+            .into_iter()
+            .filter_map(|m| Some((m.id, m.occurrence?)));
+        self.reshape_series(series, from, shift_by, occurrences.collect(), claims)
+            .await?;
+
+        self.find_shift(id, claims).await
+    }
+
+    pub async fn delete_shift(
+        &self,
+        id: &str,
+        scope: EditScope,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        require_lead_role(claims)?;
+
+        let current = self.find_shift(id, claims).await?;
+        self.require_team_manager(current.team_id.as_deref(), claims)
+            .await?;
+        match (current.occurrence, scope) {
+            (Some(occurrence), EditScope::Following | EditScope::All) => {
+                let from = (scope == EditScope::Following).then_some(occurrence.occurrence_at);
+                let members = self
+                    .repository
+                    .find_series_shifts(&occurrence.series_id)
+                    .await?;
+                for member in members {
+                    if reaches(member.occurrence.as_ref(), from) {
+                        self.repository.delete_shift(&member.id).await?;
+                    }
+                }
+                self.trim_series(&occurrence.series_id, from, claims).await
+            }
+            (occurrence, _) => {
+                self.repository.delete_shift(id).await?;
+                self.skip_occurrence(occurrence, claims).await
+            }
+        }
+    }
+
+    pub async fn assign_shift(
+        &self,
+        shift_id: &str,
+        req: AssignShiftRequest,
+        force: bool,
+        claims: &Claims,
+    ) -> Result<ShiftAssignment, AppError> {
+        require_lead_role(claims)?;
+        // team leads staff their shifts, but only managers override the rules
+        if force {
+            require_manager(claims)?;
+        }
+
+        let shift = self.find_shift(shift_id, claims).await?;
+        self.require_team_manager(shift.team_id.as_deref(), claims)
+            .await?;
+        let user = self
+            .users
+            .find_by_id(&claims.event, &req.user_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown user '{}'", req.user_id)))?;
+
+        if user.status == UserStatus::Inactive {
+            return Err(AppError::BadRequest(
+                "Inactive users can't be put on shifts".to_string(),
+            ));
+        }
+        if shift.is_assigned_to(&user.id) {
+            return Err(AppError::BadRequest(
+                "User is already on this shift".to_string(),
+            ));
+        }
+
+        let role = req.role.unwrap_or(user.role);
+        if !shift.open_slots().iter().any(|slot| slot.role == role) {
+            return Err(AppError::BadRequest(format!(
+                "No open {} slot on this shift",
+                role.as_str()
+            )));
+        }
+
+        let schedule = self.schedule_of(&claims.event, &user.id, &shift).await?;
+
+        let conflicts = find_conflicts(&shift, &schedule, &self.limits);
+        if !conflicts.is_empty() {
+            let details =
+                serde_json::to_value(&conflicts).map_err(|_| AppError::InternalServerError)?;
+
+            if !force {
+                return Err(AppError::Conflict(
+                    format!(
+                        "Shift clashes with {} rule(s) on this person's schedule",
+                        conflicts.len()
+                    ),
+                    details,
+                ));
+            }
+
+            self.audit
+                .record(NewAuditEntry {
+                    event_id: Some(claims.event.clone()),
+                    actor_id: claims.sub.clone(),
+                    action: "planner.assign_forced".to_string(),
+                    target_type: "shift".to_string(),
+                    target_id: Some(shift.id.clone()),
+                    details: Some(serde_json::json!({
+                        "user_id": user.id,
+                        "conflicts": details,
+                    })),
+                })
+                .await?;
+        }
+
+        self.repository.assign_shift(shift_id, &user.id, role).await
+    }
+
+    pub async fn unassign_shift(
+        &self,
+        shift_id: &str,
+        user_id: &str,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        require_lead_role(claims)?;
+
+        if !claims.is_manager() {
+            let shift = self.find_shift(shift_id, claims).await?;
+            self.require_team_manager(shift.team_id.as_deref(), claims)
+                .await?;
+        }
+
+        self.repository
+            .unassign_shift(&claims.event, shift_id, user_id)
+            .await
+    }
+
+    // active people who are available for the whole shift and have every required skill
+    pub async fn get_candidates(
+        &self,
+        shift_id: &str,
+        query: CandidateQuery,
+        claims: &Claims,
+    ) -> Result<Vec<ShiftCandidate>, AppError> {
+        require_lead_role(claims)?;
+
+        let shift = self.find_shift(shift_id, claims).await?;
+        self.require_team_manager(shift.team_id.as_deref(), claims)
+            .await?;
+        let available = self
+            .users
+            .find_available_ids(shift.starts_at, shift.ends_at)
+            .await?;
+        let mut profiles = self.users.find_profiles(&available).await?;
+
+        let mut candidates: Vec<ShiftCandidate> = self
+            .users
+            .find_by_ids(&claims.event, &available)
+            .await?
+            .into_iter()
+            .filter(|u| u.status == UserStatus::Active && !shift.is_assigned_to(&u.id))
+            .filter(|u| query.role.as_ref().is_none_or(|role| &u.role == role))
+            .filter_map(|mut user| {
+                let profile = profiles.remove(&user.id).unwrap_or_default();
+                if !profile.missing_skills(&shift.required_skills).is_empty() {
+                    return None;
+                }
+
+                let prefers_area = profile.prefers(&shift.location);
+                user.profile = Some(profile);
+                Some(ShiftCandidate { user, prefers_area })
+            })
+            .collect();
+
+        // people who asked for this area first, then alphabetical
+        candidates.sort_by(|a, b| {
+            b.prefers_area
+                .cmp(&a.prefers_area)
+                .then_with(|| a.user.surname.cmp(&b.user.surname))
+                .then_with(|| a.user.name.cmp(&b.user.name))
+        });
+
+        Ok(candidates)
+    }
+
+    // available at that moment and not already on a shift
+    pub async fn get_free(&self, query: FreeQuery, claims: &Claims) -> Result<Vec<User>, AppError> {
+        if claims.role == "volunteer" {
+            return Err(AppError::Forbidden);
+        }
+
+        let at = query.at.unwrap_or_else(Utc::now);
+        let skill = query.skill.as_deref().map(|s| s.trim().to_lowercase());
+
+        let busy: Vec<String> = self
+            .repository
+            .find_shifts(
+                &claims.event,
+                &ShiftFilter {
+                    from: Some(at),
+                    to: Some(at + Duration::seconds(1)),
+                    ..Default::default()
+                },
+            )
+            .await?
+            .into_iter()
+            .flat_map(|s| s.assignments.into_iter().map(|a| a.user_id))
+            .collect();
+
+        let free: Vec<String> = self
+            .users
+            .find_available_ids(at, at)
+            .await?
+            .into_iter()
+            .filter(|id| !busy.contains(id))
+            .collect();
+        let mut profiles = self.users.find_profiles(&free).await?;
+
+        Ok(self
+            .users
+            .find_by_ids(&claims.event, &free)
+            .await?
+            .into_iter()
+            .filter(|u| u.status == UserStatus::Active)
+            .filter_map(|mut user| {
+                let profile = profiles.remove(&user.id).unwrap_or_default();
+                if skill.as_ref().is_some_and(|s| !profile.skills.contains(s)) {
+                    return None;
+                }
+
+                user.profile = Some(profile);
+                Some(user)
+            })
+            .collect())
+    }
+
+    pub async fn generate_roster(
+        &self,
+        req: GenerateRosterRequest,
+        claims: &Claims,
+    ) -> Result<Roster, AppError> {
+        require_manager(claims)?;
+
+        let shifts = if req.shift_ids.is_empty() {
+            self.upcoming_shifts(
+                ShiftFilter {
+                    location: req.location,
+                    from: req.from,
+                    to: req.to,
+                    ..Default::default()
+                },
+                claims,
+            )
+            .await?
+        } else {
+            let shifts = self
+                .repository
+                .find_shifts_by_ids(&claims.event, &req.shift_ids)
+                .await?;
+            if let Some(missing) = req
+                .shift_ids
+                .iter()
+                .find(|id| !shifts.iter().any(|s| &s.id == *id))
+            {
+                return Err(AppError::BadRequest(format!("Unknown shift '{}'", missing)));
+            }
+            shifts
+        };
+
+        if shifts.is_empty() {
+            return Err(AppError::BadRequest("No shifts to roster".to_string()));
+        }
+
+        let schedule = self.schedule_around(&shifts, claims).await?;
+        let people = self.active_people(claims).await?;
+        let seed = req
+            .seed
+            .unwrap_or_else(|| uuid::Uuid::now_v7().as_u64_pair().1);
+
+        let plan = roster::generate(&shifts, &people, &schedule, &self.limits, seed);
+        let shift_ids: Vec<String> = shifts.into_iter().map(|s| s.id).collect();
+
+        self.repository
+            .create_roster(
+                &claims.event,
+                seed,
+                &shift_ids,
+                plan.assignments,
+                &plan.unfilled,
+                &claims.sub,
+            )
+            .await
+    }
+
+    pub async fn get_rosters(&self, claims: &Claims) -> Result<Vec<Roster>, AppError> {
+        require_manager(claims)?;
+
+        self.repository.find_rosters(&claims.event).await
+    }
+
+    pub async fn get_roster(&self, id: &str, claims: &Claims) -> Result<Roster, AppError> {
+        require_manager(claims)?;
+
+        self.find_roster(id, claims).await
+    }
+
+    pub async fn add_roster_assignment(
+        &self,
+        id: &str,
+        req: RosterAssignmentRequest,
+        claims: &Claims,
+    ) -> Result<Roster, AppError> {
+        require_manager(claims)?;
+
+        let mut draft = self.find_draft(id, claims).await?;
+        if !draft.shift_ids.contains(&req.shift_id) {
+            return Err(AppError::BadRequest(
+                "Shift is not part of this roster".to_string(),
+            ));
+        }
+
+        let shift = self.find_shift(&req.shift_id, claims).await?;
+        let user = self
+            .users
+            .find_by_id(&claims.event, &req.user_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown user '{}'", req.user_id)))?;
+
+        if user.status == UserStatus::Inactive {
+            return Err(AppError::BadRequest(
+                "Inactive users can't be put on shifts".to_string(),
+            ));
+        }
+        let proposed_here = |a: &&ProposedAssignment| a.shift_id == shift.id;
+        if shift.is_assigned_to(&user.id)
+            || draft
+                .assignments
+                .iter()
+                .filter(proposed_here)
+                .any(|a| a.user_id == user.id)
+        {
+            return Err(AppError::BadRequest(
+                "User is already on this shift".to_string(),
+            ));
+        }
+
+        let role = req.role.unwrap_or(user.role);
+        let drafted = draft
+            .assignments
+            .iter()
+            .filter(proposed_here)
+            .filter(|a| a.role == role)
+            .count() as u32;
+        if !shift
+            .open_slots()
+            .iter()
+            .any(|slot| slot.role == role && slot.open > drafted)
+        {
+            return Err(AppError::BadRequest(format!(
+                "No open {} slot on this shift",
+                role.as_str()
+            )));
+        }
+
+        // their live schedule plus whatever this draft already gives them
+        let mut schedule = self.schedule_of(&claims.event, &user.id, &shift).await?;
+        let drafted_ids: Vec<String> = draft
+            .assignments
+            .iter()
+            .filter(|a| a.user_id == user.id)
+            .map(|a| a.shift_id.clone())
+            .collect();
+        schedule.extend(
+            self.repository
+                .find_shifts_by_ids(&claims.event, &drafted_ids)
+                .await?,
+        );
+
+        let conflicts = find_conflicts(&shift, &schedule, &self.limits);
+        if !conflicts.is_empty() {
+            return Err(AppError::Conflict(
+                format!(
+                    "Shift clashes with {} rule(s) on this person's schedule",
+                    conflicts.len()
+                ),
+                serde_json::to_value(&conflicts).map_err(|_| AppError::InternalServerError)?,
+            ));
+        }
+
+        let assignment = ProposedAssignment {
+            shift_id: shift.id.clone(),
+            user_id: user.id,
+            role,
+        };
+        fill_slot(&mut draft.unfilled, &shift.id, &assignment.role);
+        self.repository
+            .update_proposal(id, Some(&assignment), None, &draft.unfilled)
+            .await?;
+
+        self.find_roster(id, claims).await
+    }
+
+    pub async fn remove_roster_assignment(
+        &self,
+        id: &str,
+        shift_id: &str,
+        user_id: &str,
+        claims: &Claims,
+    ) -> Result<Roster, AppError> {
+        require_manager(claims)?;
+
+        let mut draft = self.find_draft(id, claims).await?;
+        let assignment = draft
+            .assignments
+            .iter()
+            .find(|a| a.shift_id == shift_id && a.user_id == user_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Proposed assignment not found".to_string()))?;
+
+        reopen_slot(&mut draft.unfilled, shift_id, &assignment.role);
+        self.repository
+            .update_proposal(id, None, Some((shift_id, user_id)), &draft.unfilled)
+            .await?;
+
+        self.find_roster(id, claims).await
+    }
+
+    // shifts may have changed since drafting, anything that no longer fits is skipped and reported
+    pub async fn publish_roster(
+        &self,
+        id: &str,
+        claims: &Claims,
+    ) -> Result<PublishReport, AppError> {
+        require_manager(claims)?;
+
+        let draft = self.find_draft(id, claims).await?;
+        let shifts = self
+            .repository
+            .find_shifts_by_ids(&claims.event, &draft.shift_ids)
+            .await?;
+
+        let mut accepted: Vec<ProposedAssignment> = Vec::new();
+        let mut skipped = Vec::new();
+        for assignment in draft.assignments {
+            let reason = match shifts.iter().find(|s| s.id == assignment.shift_id) {
+                None => Some("Shift no longer exists".to_string()),
+                Some(shift) if shift.is_assigned_to(&assignment.user_id) => {
+                    Some("Already on the shift".to_string())
+                }
+                Some(shift) => {
+                    let taken = accepted
+                        .iter()
+                        .filter(|a| a.shift_id == shift.id && a.role == assignment.role)
+                        .count() as u32;
+                    let open = shift
+                        .open_slots()
+                        .iter()
+                        .find(|slot| slot.role == assignment.role)
+                        .map(|slot| slot.open)
+                        .unwrap_or(0);
+
+                    (taken >= open)
+                        .then(|| format!("No open {} slot left", assignment.role.as_str()))
+                }
+            };
+
+            // people and schedules may have changed since the draft was made
+            let reason = match (reason, shifts.iter().find(|s| s.id == assignment.shift_id)) {
+                (None, Some(shift)) => {
+                    self.publish_clash(shift, &assignment.user_id, &accepted, &shifts, claims)
+                        .await?
+                }
+                (reason, _) => reason,
+            };
+
+            match reason {
+                Some(reason) => skipped.push(SkippedAssignment { assignment, reason }),
+                None => accepted.push(assignment),
+            }
+        }
+
+        self.repository
+            .publish_roster(id, &accepted, &claims.sub)
+            .await?;
+        self.audit
+            .record(NewAuditEntry {
+                event_id: Some(claims.event.clone()),
+                actor_id: claims.sub.clone(),
+                action: "planner.roster_publish".to_string(),
+                target_type: "roster".to_string(),
+                target_id: Some(id.to_string()),
+                details: Some(serde_json::json!({
+                    "published": accepted.len(),
+                    "skipped": skipped.len(),
+                })),
+            })
+            .await?;
+
+        Ok(PublishReport {
+            roster: self.find_roster(id, claims).await?,
+            published: accepted.len(),
+            skipped,
+        })
+    }
+
+    // why a drafted assignment can't go live anymore, if it can't
+    async fn publish_clash(
+        &self,
+        shift: &Shift,
+        user_id: &str,
+        accepted: &[ProposedAssignment],
+        shifts: &[Shift],
+        claims: &Claims,
+    ) -> Result<Option<String>, AppError> {
+        match self.users.find_by_id(&claims.event, user_id).await? {
+            None => return Ok(Some("No longer part of the event".to_string())),
+            Some(user) if user.status == UserStatus::Inactive => {
+                return Ok(Some("User is inactive".to_string()));
+            }
+            Some(_) => {}
+        }
+
+        // their live schedule plus what this publish already gives them
+        let mut schedule = self.schedule_of(&claims.event, user_id, shift).await?;
+        schedule.extend(
+            shifts
+                .iter()
+                .filter(|s| {
+                    accepted
+                        .iter()
+                        .any(|a| a.user_id == user_id && a.shift_id == s.id)
+                })
+                .cloned(),
+        );
+
+        let conflicts = find_conflicts(shift, &schedule, &self.limits);
+        Ok((!conflicts.is_empty())
+            .then(|| format!("Clashes with {} rule(s) on their schedule", conflicts.len())))
+    }
+
+    pub async fn discard_roster(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        require_manager(claims)?;
+
+        self.find_draft(id, claims).await?;
+        self.repository
+            .set_roster_status(id, RosterStatus::Discarded)
+            .await
+    }
+
+    pub async fn create_recurring_shift(
+        &self,
+        req: RecurringShiftRequest,
+        claims: &Claims,
+    ) -> Result<SeriesOccurrences<Shift>, AppError> {
+        let shift = req.shift;
+        self.require_team_manager(shift.team_id.as_deref(), claims)
+            .await?;
+
+        if shift.title.trim().is_empty() {
+            return Err(AppError::BadRequest("Title is required".to_string()));
+        }
+        validate_times(shift.starts_at, shift.ends_at)?;
+        validate_requirements(&shift.requirements)?;
+        self.check_team(shift.team_id.as_deref(), claims).await?;
+        self.check_location(shift.location_id.as_deref(), claims)
+            .await?;
+        let shift = CreateShiftRequest {
+            required_skills: normalize_tags(&shift.required_skills),
+            ..shift
+        };
+
+        let rule = parse_rule(&req.recurrence)?;
+        let slots = expand(&rule, shift.starts_at, &[])?;
+
+        let series = self
+            .repository
+            .create_series(
+                &claims.event,
+                SeriesKind::Shift,
+                &rule.to_string(),
+                shift.starts_at,
+                &to_template(&shift)?,
+                &claims.sub,
+            )
+            .await?;
+        let occurrences = self
+            .repository
+            .create_shift_occurrences(&claims.event, &series.id, &shift, &slots, &claims.sub)
+            .await?;
+
+        Ok(SeriesOccurrences {
+            series,
+            occurrences,
+        })
+    }
+
+    pub async fn create_recurring_task(
+        &self,
+        req: RecurringTaskRequest,
+        claims: &Claims,
+    ) -> Result<SeriesOccurrences<Task>, AppError> {
+        let task = req.task;
+        self.require_team_manager(task.team_id.as_deref(), claims)
+            .await?;
+
+        if task.title.trim().is_empty() {
+            return Err(AppError::BadRequest("Title is required".to_string()));
+        }
+        let due_at = task.due_at.ok_or_else(|| {
+            AppError::BadRequest("Recurring tasks need a due_at to start from".to_string())
+        })?;
+        self.check_team(task.team_id.as_deref(), claims).await?;
+        self.check_location(task.location_id.as_deref(), claims)
+            .await?;
+        self.check_assignees(&task.assignees, claims).await?;
+
+        let rule = parse_rule(&req.recurrence)?;
+        let slots = expand(&rule, due_at, &[])?;
+
+        let series = self
+            .repository
+            .create_series(
+                &claims.event,
+                SeriesKind::Task,
+                &rule.to_string(),
+                due_at,
+                &to_template(&task)?,
+                &claims.sub,
+            )
+            .await?;
+        let occurrences = self
+            .repository
+            .create_task_occurrences(&claims.event, &series.id, &task, &slots, &claims.sub)
+            .await?;
+
+        Ok(SeriesOccurrences {
+            series,
+            occurrences,
+        })
+    }
+
+    pub async fn get_series(&self, id: &str, claims: &Claims) -> Result<Series, AppError> {
+        require_lead_role(claims)?;
+
+        let series = self.find_series(id, claims).await?;
+        self.require_team_manager(series.team_id(), claims).await?;
+
+        Ok(series)
+    }
+
+    // re-expands the series: occurrences the new rule still produces stay as they are, so do
+    // exceptions, the rest is deleted and missing ones are created from the template. Nobody's
+    // shift moves and new ones start empty, so unlike update_shift there are no schedules to check
+    pub async fn update_series(
+        &self,
+        id: &str,
+        req: UpdateSeriesRequest,
+        claims: &Claims,
+    ) -> Result<Series, AppError> {
+        require_lead_role(claims)?;
+
+        let mut series = self.find_series(id, claims).await?;
+        self.require_team_manager(series.team_id(), claims).await?;
+        let rule = parse_rule(&req.recurrence)?;
+        let slots = expand(&rule, series.starts_at, &series.exdates)?;
+
+        match series.kind {
+            SeriesKind::Shift => {
+                let members = self.repository.find_series_shifts(id).await?;
+                for member in &members {
+                    if !survives(&member.occurrence, &slots) {
+                        self.repository.delete_shift(&member.id).await?;
+                    }
+                }
+                let missing = missing_slots(&slots, members.iter().map(|m| &m.occurrence));
+                let template: CreateShiftRequest = from_template(&series)?;
+                self.repository
+                    .create_shift_occurrences(
+                        &claims.event,
+                        id,
+                        &template,
+                        &missing,
+                        &series.created_by,
+                    )
+                    .await?;
+            }
+            SeriesKind::Task => {
+                let members = self.repository.find_series_tasks(id).await?;
+                for member in &members {
+                    if !survives(&member.occurrence, &slots) {
+                        self.repository.delete_task(&member.id).await?;
+                    }
+                }
+                let missing = missing_slots(&slots, members.iter().map(|m| &m.occurrence));
+                let template: CreateTaskRequest = from_template(&series)?;
+                self.repository
+                    .create_task_occurrences(
+                        &claims.event,
+                        id,
+                        &template,
+                        &missing,
+                        &series.created_by,
+                    )
+                    .await?;
+            }
+        }
+
+        series.recurrence = rule.to_string();
+        self.repository.update_series(&series).await
+    }
+
+    async fn find_task(&self, id: &str, claims: &Claims) -> Result<Task, AppError> {
+        self.repository
+            .find_task(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))
+    }
+
+    async fn find_series(&self, id: &str, claims: &Claims) -> Result<Series, AppError> {
+        self.repository
+            .find_series(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Series not found".to_string()))
+    }
+
+    // an occurrence edited on its own keeps its changes when the rest of the series changes
+    async fn mark_exception(
+        &self,
+        kind: SeriesKind,
+        id: &str,
+        occurrence: Option<Occurrence>,
+    ) -> Result<Option<Occurrence>, AppError> {
+        let Some(occurrence) = occurrence else {
+            return Ok(None);
+        };
+        if occurrence.is_exception {
+            return Ok(Some(occurrence));
+        }
+
+        let occurrence = Occurrence {
+            is_exception: true,
+            ..occurrence
+        };
+        self.repository
+            .set_occurrence(kind, id, &occurrence)
+            .await?;
+
+        Ok(Some(occurrence))
+    }
+
+    // a deleted occurrence becomes an exdate so re-expanding never brings it back
+    async fn skip_occurrence(
+        &self,
+        occurrence: Option<Occurrence>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let Some(occurrence) = occurrence else {
+            return Ok(());
+        };
+
+        let mut series = self.find_series(&occurrence.series_id, claims).await?;
+        if !series.exdates.contains(&occurrence.occurrence_at) {
+            series.exdates.push(occurrence.occurrence_at);
+            series.exdates.sort();
+        }
+        self.repository.update_series(&series).await?;
+
+        Ok(())
+    }
+
+    // after "this and following" or "all" was deleted, cut the rule short or drop the series
+    async fn trim_series(
+        &self,
+        id: &str,
+        from: Option<DateTime<Utc>>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let mut series = self.find_series(id, claims).await?;
+
+        match from.filter(|f| *f > series.starts_at) {
+            Some(from) => {
+                series.recurrence = parse_rule(&series.recurrence)?
+                    .ending_before(from)
+                    .to_string();
+                series.exdates.retain(|d| *d < from);
+                self.repository.update_series(&series).await?;
+            }
+            None => self.repository.delete_series(id).await?,
+        }
+
+        Ok(())
+    }
+
+    // after an edit to "all" the series moves along; after "this and following" it splits
+    // in two at the edited occurrence, the tail keeping the members from there on
+    async fn reshape_series(
+        &self,
+        mut series: Series,
+        from: Option<DateTime<Utc>>,
+        shift_by: Duration,
+        members: Vec<(String, Occurrence)>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let rule = parse_rule(&series.recurrence)?;
+
+        let Some(from) = from.filter(|f| *f > series.starts_at) else {
+            series.starts_at += shift_by;
+            series.exdates = series.exdates.iter().map(|d| *d + shift_by).collect();
+            self.repository.update_series(&series).await?;
+
+            if shift_by != Duration::zero() {
+                for (id, occurrence) in members {
+                    let moved = Occurrence {
+                        occurrence_at: occurrence.occurrence_at + shift_by,
+                        ..occurrence
+                    };
+                    self.repository
+                        .set_occurrence(series.kind.clone(), &id, &moved)
+                        .await?;
+                }
+            }
+            return Ok(());
+        };
+
+        let index = rule
+            .occurrences(series.starts_at)
+            .map_err(AppError::BadRequest)?
+            .iter()
+            .filter(|slot| **slot < from)
+            .count();
+
+        let mut tail = self
+            .repository
+            .create_series(
+                &claims.event,
+                series.kind.clone(),
+                &rule.continuing_from(index).to_string(),
+                from + shift_by,
+                &series.template,
+                &claims.sub,
+            )
+            .await?;
+        tail.exdates = series
+            .exdates
+            .iter()
+            .filter(|d| **d >= from)
+            .map(|d| *d + shift_by)
+            .collect();
+        self.repository.update_series(&tail).await?;
+
+        // the head keeps the old template, only the tail gets the edit
+        let mut head = self.find_series(&series.id, claims).await?;
+        head.recurrence = rule.ending_before(from).to_string();
+        head.exdates.retain(|d| *d < from);
+        self.repository.update_series(&head).await?;
+
+        for (id, occurrence) in members {
+            if occurrence.occurrence_at < from {
+                continue;
+            }
+            let moved = Occurrence {
+                series_id: tail.id.clone(),
+                occurrence_at: occurrence.occurrence_at + shift_by,
+                is_exception: occurrence.is_exception,
+            };
+            self.repository
+                .set_occurrence(series.kind.clone(), &id, &moved)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn find_roster(&self, id: &str, claims: &Claims) -> Result<Roster, AppError> {
+        self.repository
+            .find_roster(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Roster not found".to_string()))
+    }
+
+    async fn find_draft(&self, id: &str, claims: &Claims) -> Result<Roster, AppError> {
+        let roster = self.find_roster(id, claims).await?;
+        if roster.status != RosterStatus::Draft {
+            return Err(AppError::BadRequest(format!(
+                "Roster is {}, only drafts can change",
+                roster.status.to_string().to_lowercase()
+            )));
+        }
+
+        Ok(roster)
+    }
+
+    // every shift near the ones being rostered, so rest and daily caps see the whole picture
+    async fn schedule_around(
+        &self,
+        shifts: &[Shift],
+        claims: &Claims,
+    ) -> Result<Vec<Shift>, AppError> {
+        let window = Duration::days(1).max(Duration::minutes(self.limits.min_rest_minutes as i64));
+        let from = shifts.iter().map(|s| s.starts_at).min();
+        let to = shifts.iter().map(|s| s.ends_at).max();
+
+        self.repository
+            .find_shifts(
+                &claims.event,
+                &ShiftFilter {
+                    from: from.map(|f| f - window),
+                    to: to.map(|t| t + window),
+                    ..Default::default()
+                },
+            )
+            .await
+    }
+
+    async fn active_people(&self, claims: &Claims) -> Result<Vec<User>, AppError> {
+        let users = self
+            .users
+            .find_filtered(
+                &claims.event,
+                &UserFilter {
+                    status: Some(UserStatus::Active),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let ids: Vec<String> = users.iter().map(|u| u.id.clone()).collect();
+        let mut profiles = self.users.find_profiles(&ids).await?;
+
+        Ok(users
+            .into_iter()
+            .map(|mut user| {
+                user.profile = Some(profiles.remove(&user.id).unwrap_or_default());
+                user
+            })
+            .collect())
+    }
+
+    // organizers and admins manage everything, staff manage the work of teams they lead
+    async fn require_team_manager(
+        &self,
+        team_id: Option<&str>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if claims.is_manager() {
+            return Ok(());
+        }
+
+        let leads = match team_id {
+            Some(team_id) if claims.role == "staff" => {
+                self.teams.is_lead(team_id, &claims.sub).await?
+            }
+            _ => false,
+        };
+        if !leads {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    async fn check_team(&self, team_id: Option<&str>, claims: &Claims) -> Result<(), AppError> {
+        if let Some(team_id) = team_id
+            && !self.teams.exists(&claims.event, team_id).await?
+        {
+            return Err(AppError::BadRequest(format!("Unknown team '{}'", team_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn check_location(
+        &self,
+        location_id: Option<&str>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if let Some(location_id) = location_id
+            && !self.locations.exists(&claims.event, location_id).await?
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unknown location '{}'",
+                location_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    // handing work over to another team needs a say in that team too
+    async fn check_move(&self, team_id: Option<&str>, claims: &Claims) -> Result<(), AppError> {
+        if team_id.is_some() {
+            self.require_team_manager(team_id, claims).await?;
+            self.check_team(team_id, claims).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn find_shift(&self, id: &str, claims: &Claims) -> Result<Shift, AppError> {
+        self.repository
+            .find_shift(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Shift not found".to_string()))
+    }
+
+    async fn upcoming_shifts(
+        &self,
+        filter: ShiftFilter,
+        claims: &Claims,
+    ) -> Result<Vec<Shift>, AppError> {
+        let filter = ShiftFilter {
+            from: filter.from.or_else(|| Some(Utc::now())),
+            ..filter
+        };
+
+        self.repository.find_shifts(&claims.event, &filter).await
+    }
+
+    // the people on a shift stay on it when it moves, so its new times have to fit their schedules
+    async fn check_moves(
+        &self,
+        before: &[Shift],
+        moved: &[Shift],
+        force: bool,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let mut clashes = Vec::new();
+        for (old, shift) in before.iter().zip(moved) {
+            if (old.starts_at, old.ends_at) == (shift.starts_at, shift.ends_at) {
+                continue;
+            }
+            for assignment in &shift.assignments {
+                let user_id = &assignment.user_id;
+                // the moving shifts count at their new times
+                let mut schedule: Vec<Shift> = self
+                    .schedule_of(&claims.event, user_id, shift)
+                    .await?
+                    .into_iter()
+                    .filter(|s| !moved.iter().any(|m| m.id == s.id))
+                    .collect();
+                schedule.extend(
+                    moved
+                        .iter()
+                        .filter(|m| m.id != shift.id && m.is_assigned_to(user_id))
+                        .cloned(),
+                );
+
+                let conflicts = find_conflicts(shift, &schedule, &self.limits);
+                if !conflicts.is_empty() {
+                    clashes.push(serde_json::json!({
+                        "shift_id": shift.id,
+                        "user_id": user_id,
+                        "conflicts": conflicts,
+                    }));
+                }
+            }
+        }
+        if clashes.is_empty() {
+            return Ok(());
+        }
+
+        let count = clashes.len();
+        let details = serde_json::Value::Array(clashes);
+        if !force {
+            return Err(AppError::Conflict(
+                format!(
+                    "New times clash with the schedule of {} assigned person(s)",
+                    count
+                ),
+                details,
+            ));
+        }
+
+        self.audit
+            .record(NewAuditEntry {
+                event_id: Some(claims.event.clone()),
+                actor_id: claims.sub.clone(),
+                action: "planner.move_forced".to_string(),
+                target_type: "shift".to_string(),
+                target_id: moved.first().map(|s| s.id.clone()),
+                details: Some(serde_json::json!({ "clashes": details })),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    // someone's shifts within reach of the rest rule or a day cap
+    async fn schedule_of(
+        &self,
+        event_id: &str,
+        user_id: &str,
+        shift: &Shift,
+    ) -> Result<Vec<Shift>, AppError> {
+        let window = Duration::days(1).max(Duration::minutes(self.limits.min_rest_minutes as i64));
+        self.repository
+            .find_shifts(
+                event_id,
+                &ShiftFilter {
+                    assignee: Some(user_id.to_string()),
+                    from: Some(shift.starts_at - window),
+                    to: Some(shift.ends_at + window),
+                    ..Default::default()
+                },
+            )
+            .await
+    }
+
+    async fn check_assignees(&self, assignees: &[String], claims: &Claims) -> Result<(), AppError> {
+        if assignees.is_empty() {
+            return Ok(());
+        }
+
+        let found = self.users.find_by_ids(&claims.event, assignees).await?;
+        if let Some(missing) = assignees
+            .iter()
+            .find(|id| !found.iter().any(|u| &u.id == *id))
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unknown assignee '{}'",
+                missing
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_times(starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> Result<(), AppError> {
+    if ends_at <= starts_at {
+        return Err(AppError::BadRequest(
+            "Shift must end after it starts".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_requirements(requirements: &[ShiftRequirement]) -> Result<(), AppError> {
+    for (i, requirement) in requirements.iter().enumerate() {
+        if requirement.headcount == 0 {
+            return Err(AppError::BadRequest(format!(
+                "Headcount for {} must be at least 1",
+                requirement.role.as_str()
+            )));
+        }
+        if requirements[..i].iter().any(|r| r.role == requirement.role) {
+            return Err(AppError::BadRequest(format!(
+                "Role {} is listed more than once",
+                requirement.role.as_str()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_rule(recurrence: &str) -> Result<RecurrenceRule, AppError> {
+    RecurrenceRule::parse(recurrence).map_err(AppError::BadRequest)
+}
+
+fn expand(
+    rule: &RecurrenceRule,
+    starts_at: DateTime<Utc>,
+    exdates: &[DateTime<Utc>],
+) -> Result<Vec<DateTime<Utc>>, AppError> {
+    let mut slots = rule.occurrences(starts_at).map_err(AppError::BadRequest)?;
+    slots.retain(|slot| !exdates.contains(slot));
+
+    if slots.is_empty() {
+        return Err(AppError::BadRequest(
+            "The rule makes no occurrences".to_string(),
+        ));
+    }
+
+    Ok(slots)
+}
+
+// whether an edit or delete starting at `from` reaches this occurrence, None means all of them
+fn reaches(occurrence: Option<&Occurrence>, from: Option<DateTime<Utc>>) -> bool {
+    match (occurrence, from) {
+        (Some(occurrence), Some(from)) => occurrence.occurrence_at >= from,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn survives(occurrence: &Option<Occurrence>, slots: &[DateTime<Utc>]) -> bool {
+    occurrence
+        .as_ref()
+        .is_some_and(|o| o.is_exception || slots.contains(&o.occurrence_at))
+}
+
+fn missing_slots<'a>(
+    slots: &[DateTime<Utc>],
+    occurrences: impl Iterator<Item = &'a Option<Occurrence>>,
+) -> Vec<DateTime<Utc>> {
+    let taken: Vec<DateTime<Utc>> = occurrences.flatten().map(|o| o.occurrence_at).collect();
+
+    slots
+        .iter()
+        .filter(|slot| !taken.contains(slot))
+        .copied()
+        .collect()
+}
+
+fn to_template<T: serde::Serialize>(template: &T) -> Result<serde_json::Value, AppError> {
+    serde_json::to_value(template).map_err(|_| AppError::InternalServerError)
+}
+
+fn from_template<T: serde::de::DeserializeOwned>(series: &Series) -> Result<T, AppError> {
+    serde_json::from_value(series.template.clone()).map_err(|_| AppError::InternalServerError)
+}
@@ -0,0 +1,47 @@
+use crate::audit::models::AuditEntry;
+use crate::calendar::models::CalendarFeed;
+use crate::consumable::models::StockMovement;
+use crate::event::models::EventMember;
+use crate::export::models::ExportJob;
+use crate::file::models::StoredFile;
+use crate::logistics::models::{AssetStatusChange, Checkout};
+use crate::planner::models::{Roster, Shift, Task};
+use crate::social::models::SocialPost;
+use crate::team::models::Team;
+use crate::user::models::User;
+use crate::vendor::models::Delivery;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// everything stored about one person, modules holding personal data add a field here
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PersonalDataExport {
+    pub generated_at: DateTime<Utc>,
+    pub user: User,
+    // every event they are part of, tasks and shifts below cover all of them
+    pub memberships: Vec<EventMember>,
+    pub audit_entries: Vec<AuditEntry>,
+    pub export_jobs: Vec<ExportJob>,
+    pub assigned_tasks: Vec<Task>,
+    pub shifts: Vec<Shift>,
+    // drafts included, with only their own proposals
+    pub roster_proposals: Vec<Roster>,
+    pub calendar_feeds: Vec<CalendarFeed>,
+    // with only their own membership, lead or not
+    pub teams: Vec<Team>,
+    // equipment they held, returned or not
+    pub checkouts: Vec<Checkout>,
+    pub asset_status_changes: Vec<AssetStatusChange>,
+    // down to receive or signed for
+    pub deliveries: Vec<Delivery>,
+    pub stock_movements: Vec<StockMovement>,
+    pub uploaded_files: Vec<StoredFile>,
+    // posts they wrote or reviewed
+    pub social_posts: Vec<SocialPost>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct EraseQuery {
+    #[serde(default)]
+    pub confirm: bool,
+}
@@ -0,0 +1,245 @@
+use super::models::PersonalDataExport;
+use crate::audit::models::NewAuditEntry;
+use crate::audit::repository::AuditRepository;
+use crate::auth::models::Claims;
+use crate::calendar::repository::CalendarRepository;
+use crate::consumable::repository::ConsumableRepository;
+use crate::error::AppError;
+use crate::event::repository::EventRepository;
+use crate::export::repository::ExportRepository;
+use crate::file::service::FileService;
+use crate::logistics::models::CheckoutFilter;
+use crate::logistics::repository::LogisticsRepository;
+use crate::planner::models::{ShiftFilter, TaskFilter};
+use crate::planner::repository::PlannerRepository;
+use crate::social::repository::SocialRepository;
+use crate::team::repository::TeamRepository;
+use crate::user::models::User;
+use crate::user::repository::UserRepository;
+use crate::vendor::repository::VendorRepository;
+use std::sync::Arc;
+
+pub struct PrivacyService {
+    users: UserRepository,
+    events: EventRepository,
+    audit: AuditRepository,
+    exports: ExportRepository,
+    planner: PlannerRepository,
+    calendars: CalendarRepository,
+    logistics: LogisticsRepository,
+    socials: SocialRepository,
+    teams: TeamRepository,
+    vendors: VendorRepository,
+    consumables: ConsumableRepository,
+    files: Arc<FileService>,
+    system_admins: Vec<String>,
+}
+
+impl PrivacyService {
+    // reads from every module holding personal data, so it grows with them
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        users: UserRepository,
+        events: EventRepository,
+        audit: AuditRepository,
+        exports: ExportRepository,
+        planner: PlannerRepository,
+        calendars: CalendarRepository,
+        logistics: LogisticsRepository,
+        socials: SocialRepository,
+        teams: TeamRepository,
+        vendors: VendorRepository,
+        consumables: ConsumableRepository,
+        files: Arc<FileService>,
+        system_admins: Vec<String>,
+    ) -> Self {
+        Self {
+            users,
+            events,
+            audit,
+            exports,
+            planner,
+            calendars,
+            logistics,
+            socials,
+            teams,
+            vendors,
+            consumables,
+            files,
+            system_admins,
+        }
+    }
+
+    pub async fn export_user_data(
+        &self,
+        id: &str,
+        claims: &Claims,
+    ) -> Result<PersonalDataExport, AppError> {
+        self.require_self_or_system_admin(id, claims)?;
+
+        let mut user = self
+            .users
+            .find_by_id(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        user.profile = Some(self.users.find_profile(id).await?);
+
+        let memberships = self.events.find_memberships(id).await?;
+        let audit_entries = self.audit.find_involving(id).await?;
+        let export_jobs = self.exports.find_by_requester(id).await?;
+        let mut assigned_tasks = Vec::new();
+        let mut shifts = Vec::new();
+        let mut roster_proposals = Vec::new();
+        let mut calendar_feeds = Vec::new();
+        let mut teams = Vec::new();
+        let mut checkouts = Vec::new();
+        let mut asset_status_changes = Vec::new();
+        let mut deliveries = Vec::new();
+        let mut stock_movements = Vec::new();
+        let mut uploaded_files = Vec::new();
+        let mut social_posts = Vec::new();
+        for membership in &memberships {
+            assigned_tasks.extend(
+                self.planner
+                    .find_tasks(
+                        &membership.event_id,
+                        &TaskFilter {
+                            assignee: Some(id.to_string()),
+                            ..Default::default()
+                        },
+                    )
+                    .await?,
+            );
+            shifts.extend(
+                self.planner
+                    .find_shifts(
+                        &membership.event_id,
+                        &ShiftFilter {
+                            assignee: Some(id.to_string()),
+                            ..Default::default()
+                        },
+                    )
+                    .await?,
+            );
+            roster_proposals.extend(
+                self.planner
+                    .find_rosters_proposing(&membership.event_id, id)
+                    .await?,
+            );
+            calendar_feeds.extend(
+                self.calendars
+                    .find_by_user(&membership.event_id, id)
+                    .await?,
+            );
+            for mut team in self.teams.find_by_member(&membership.event_id, id).await? {
+                team.members.retain(|m| m.user_id == id);
+                teams.push(team);
+            }
+            checkouts.extend(
+                self.logistics
+                    .find_checkouts(
+                        &membership.event_id,
+                        &CheckoutFilter {
+                            user_id: Some(id.to_string()),
+                            include_returned: true,
+                            ..Default::default()
+                        },
+                    )
+                    .await?,
+            );
+            asset_status_changes.extend(
+                self.logistics
+                    .find_changes_by(&membership.event_id, id)
+                    .await?,
+            );
+            deliveries.extend(
+                self.vendors
+                    .find_deliveries_involving(&membership.event_id, id)
+                    .await?,
+            );
+            stock_movements.extend(
+                self.consumables
+                    .find_movements_by(&membership.event_id, id)
+                    .await?,
+            );
+            uploaded_files.extend(self.files.get_uploads(&membership.event_id, id).await?);
+            social_posts.extend(
+                self.socials
+                    .find_involving(&membership.event_id, id)
+                    .await?,
+            );
+        }
+
+        self.record(claims, "privacy.data_export", id).await?;
+
+        Ok(PersonalDataExport {
+            generated_at: chrono::Utc::now(),
+            user,
+            memberships,
+            audit_entries,
+            export_jobs,
+            assigned_tasks,
+            shifts,
+            roster_proposals,
+            calendar_feeds,
+            teams,
+            checkouts,
+            asset_status_changes,
+            deliveries,
+            stock_movements,
+            uploaded_files,
+            social_posts,
+        })
+    }
+
+    pub async fn erase_user(
+        &self,
+        id: &str,
+        confirm: bool,
+        claims: &Claims,
+    ) -> Result<User, AppError> {
+        self.require_self_or_system_admin(id, claims)?;
+
+        if !confirm {
+            return Err(AppError::BadRequest(
+                "Erasure cannot be undone, pass confirm=true to continue".to_string(),
+            ));
+        }
+
+        let user = self.users.anonymize(&claims.event, id).await?;
+        // a picture of them is personal data too, not just the link to it
+        let avatar = self.users.find_avatar(id).await?;
+        self.users.clear_profile(id).await?;
+        if let Some(file_id) = avatar {
+            self.files.erase_file(&file_id).await?;
+        }
+        self.calendars.revoke_all(id).await?;
+        self.record(claims, "privacy.erase", id).await?;
+
+        Ok(user)
+    }
+
+    // both reach every event the account is in, so an event role isn't enough for someone else's
+    fn require_self_or_system_admin(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        if claims.sub != id && !self.system_admins.contains(&claims.sub) {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    async fn record(&self, claims: &Claims, action: &str, id: &str) -> Result<(), AppError> {
+        self.audit
+            .create(NewAuditEntry {
+                event_id: Some(claims.event.clone()),
+                actor_id: claims.sub.clone(),
+                action: action.to_string(),
+                target_type: "user".to_string(),
+                target_id: Some(id.to_string()),
+                details: None,
+            })
+            .await?;
+
+        Ok(())
+    }
+}
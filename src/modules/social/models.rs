@@ -0,0 +1,250 @@
+use super::entity;
+use crate::file::models::{ImageLinks, ImageStatus, SignedUrl, StoredFile};
+use chrono::{DateTime, NaiveDate, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    #[display("Draft")]
+    Draft,
+    #[display("In review")]
+    InReview,
+    #[display("Approved")]
+    Approved,
+    #[display("Scheduled")]
+    Scheduled,
+    #[display("Published")]
+    Published,
+    #[display("Failed")]
+    Failed,
+}
+
+impl PostStatus {
+    // draft → in_review → approved → scheduled → published or failed,
+    // rejected posts go back to draft and failed ones can be scheduled again
+    pub fn can_become(&self, next: &PostStatus) -> bool {
+        matches!(
+            (self, next),
+            (PostStatus::Draft, PostStatus::InReview)
+                | (PostStatus::InReview, PostStatus::Approved)
+                | (PostStatus::InReview, PostStatus::Draft)
+                | (PostStatus::Approved, PostStatus::Scheduled)
+                | (PostStatus::Failed, PostStatus::Scheduled)
+                | (PostStatus::Scheduled, PostStatus::Published)
+                | (PostStatus::Scheduled, PostStatus::Failed)
+        )
+    }
+}
+
+impl From<PostStatus> for entity::Status {
+    fn from(item: PostStatus) -> Self {
+        match item {
+            PostStatus::Draft => entity::Status::Draft,
+            PostStatus::InReview => entity::Status::InReview,
+            PostStatus::Approved => entity::Status::Approved,
+            PostStatus::Scheduled => entity::Status::Scheduled,
+            PostStatus::Published => entity::Status::Published,
+            PostStatus::Failed => entity::Status::Failed,
+        }
+    }
+}
+
+impl From<entity::Status> for PostStatus {
+    fn from(item: entity::Status) -> Self {
+        match item {
+            entity::Status::Draft => PostStatus::Draft,
+            entity::Status::InReview => PostStatus::InReview,
+            entity::Status::Approved => PostStatus::Approved,
+            entity::Status::Scheduled => PostStatus::Scheduled,
+            entity::Status::Published => PostStatus::Published,
+            entity::Status::Failed => PostStatus::Failed,
+        }
+    }
+}
+
+// an uploaded file attached to a post, links are signed when the post is read
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Media {
+    pub file_id: String,
+    pub name: String,
+    pub content_type: String,
+    // None for anything that isn't an image
+    #[serde(skip)]
+    pub image_status: Option<ImageStatus>,
+    // documents and other non-images
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<SignedUrl>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageLinks>,
+}
+
+impl From<StoredFile> for Media {
+    fn from(file: StoredFile) -> Self {
+        Self {
+            file_id: file.id,
+            name: file.name,
+            content_type: file.content_type,
+            image_status: file.image_status,
+            url: None,
+            image: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SocialPost {
+    pub id: String,
+    pub text: String,
+    pub channels: Vec<String>,
+    pub media: Vec<Media>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub status: PostStatus,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub published_url: Option<String>,
+    pub failure_reason: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SocialPost {
+    pub fn from_model(model: entity::Model, channels: Vec<String>, media: Vec<Media>) -> Self {
+        Self {
+            id: model.id,
+            text: model.text,
+            channels,
+            media,
+            scheduled_at: model.scheduled_at,
+            status: model.status.into(),
+            reviewed_by: model.reviewed_by,
+            reviewed_at: model.reviewed_at,
+            review_note: model.review_note,
+            published_at: model.published_at,
+            published_url: model.published_url,
+            failure_reason: model.failure_reason,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarChannel {
+    // None for posts that don't have a channel yet
+    pub channel: Option<String>,
+    pub posts: Vec<SocialPost>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarDay {
+    // UTC calendar day of scheduled_at
+    pub day: NaiveDate,
+    pub channels: Vec<CalendarChannel>,
+}
+
+// unscheduled posts are left out, a post on two channels shows up under both
+pub fn calendar(posts: Vec<SocialPost>) -> Vec<CalendarDay> {
+    let mut days: BTreeMap<NaiveDate, BTreeMap<Option<String>, Vec<SocialPost>>> = BTreeMap::new();
+
+    for post in posts {
+        let Some(scheduled_at) = post.scheduled_at else {
+            continue;
+        };
+        let channels = days.entry(scheduled_at.date_naive()).or_default();
+
+        if post.channels.is_empty() {
+            channels.entry(None).or_default().push(post);
+        } else {
+            for channel in &post.channels {
+                channels
+                    .entry(Some(channel.clone()))
+                    .or_default()
+                    .push(post.clone());
+            }
+        }
+    }
+
+    days.into_iter()
+        .map(|(day, channels)| CalendarDay {
+            day,
+            channels: channels
+                .into_iter()
+                .map(|(channel, mut posts)| {
+                    posts.sort_by_key(|p| p.scheduled_at);
+                    CalendarChannel { channel, posts }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CreatePostRequest {
+    pub text: String,
+    #[serde(default)]
+    pub channels: Vec<String>,
+    // file ids from /files
+    #[serde(default)]
+    pub media: Vec<String>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdatePostRequest {
+    pub text: Option<String>,
+    // each list replaces the stored one when present
+    pub channels: Option<Vec<String>>,
+    pub media: Option<Vec<String>>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ReviewRequest {
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ScheduleRequest {
+    // keeps the post's own time when left out
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct PublishRequest {
+    // link to the live post
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct FailRequest {
+    #[serde(default)]
+    pub reason: String,
+}
+
+// what changes along with the status, anything left out stays as it is
+#[derive(Debug, Clone, Default)]
+pub struct StatusChange {
+    pub reviewed_by: Option<String>,
+    pub review_note: Option<String>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub published_url: Option<String>,
+    pub failure_reason: Option<String>,
+}
+
+// shared by the list and the calendar
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct PostFilter {
+    pub status: Option<PostStatus>,
+    pub channel: Option<String>,
+    // only posts scheduled within the range
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,387 @@
+use super::entity::{self, ActiveModel, Column, Entity as PostEntity, Status, channel, media};
+use super::models::{
+    CreatePostRequest, Media, PostFilter, PostStatus, SocialPost, StatusChange, UpdatePostRequest,
+};
+use crate::error::AppError;
+use crate::file::entity as file;
+use crate::file::models::StoredFile;
+use sea_orm::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct SocialRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl SocialRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_posts(
+        &self,
+        event_id: &str,
+        filter: &PostFilter,
+    ) -> Result<Vec<SocialPost>, AppError> {
+        let mut select = PostEntity::find().filter(Column::EventId.eq(event_id));
+
+        if let Some(status) = &filter.status {
+            select = select.filter(Column::Status.eq(Status::from(status.clone())));
+        }
+        if let Some(name) = filter.channel.as_deref().filter(|c| !c.is_empty()) {
+            select = select.filter(
+                Column::Id.in_subquery(
+                    sea_query::Query::select()
+                        .column(channel::Column::PostId)
+                        .from(channel::Entity)
+                        .and_where(channel::Column::Channel.eq(name.trim().to_lowercase()))
+                        .to_owned(),
+                ),
+            );
+        }
+        if let Some(from) = filter.from {
+            select = select.filter(Column::ScheduledAt.gte(from));
+        }
+        if let Some(to) = filter.to {
+            select = select.filter(Column::ScheduledAt.lt(to));
+        }
+
+        let models = select
+            .order_by_asc(Column::ScheduledAt)
+            .order_by_asc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_details(models).await
+    }
+
+    pub async fn find_post(
+        &self,
+        event_id: &str,
+        id: &str,
+    ) -> Result<Option<SocialPost>, AppError> {
+        let model = PostEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(self.with_details(model.into_iter().collect()).await?.pop())
+    }
+
+    // posts past draft showing the file, which would lose it if it went away
+    pub async fn find_using_file(
+        &self,
+        event_id: &str,
+        file_id: &str,
+    ) -> Result<Vec<SocialPost>, AppError> {
+        let models = PostEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::Status.ne(Status::Draft))
+            .filter(
+                Column::Id.in_subquery(
+                    sea_query::Query::select()
+                        .column(media::Column::PostId)
+                        .from(media::Entity)
+                        .and_where(media::Column::FileId.eq(file_id))
+                        .to_owned(),
+                ),
+            )
+            .order_by_asc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_details(models).await
+    }
+
+    // posts someone wrote or reviewed
+    pub async fn find_involving(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<SocialPost>, AppError> {
+        let models = PostEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(
+                Condition::any()
+                    .add(Column::CreatedBy.eq(user_id))
+                    .add(Column::ReviewedBy.eq(user_id)),
+            )
+            .order_by_asc(Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_details(models).await
+    }
+
+    pub async fn create_post(
+        &self,
+        event_id: &str,
+        dto: CreatePostRequest,
+        created_by: &str,
+    ) -> Result<SocialPost, AppError> {
+        let now = chrono::Utc::now();
+        let id = uuid::Uuid::now_v7().to_string();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        ActiveModel {
+            id: Set(id.clone()),
+            event_id: Set(event_id.to_string()),
+            text: Set(dto.text),
+            scheduled_at: Set(dto.scheduled_at),
+            status: Set(Status::Draft),
+            reviewed_by: Set(None),
+            reviewed_at: Set(None),
+            review_note: Set(None),
+            published_at: Set(None),
+            published_url: Set(None),
+            failure_reason: Set(None),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        replace_channels(&txn, &id, dto.channels).await?;
+        replace_media(&txn, &id, dto.media).await?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.find_post(event_id, &id)
+            .await?
+            .ok_or(AppError::InternalServerError)
+    }
+
+    // any edit sends the post back to draft, it has to be approved again
+    pub async fn update_post(
+        &self,
+        event_id: &str,
+        id: &str,
+        dto: UpdatePostRequest,
+    ) -> Result<SocialPost, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let model = PostEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(text) = dto.text {
+            active_model.text = Set(text);
+        }
+        if let Some(scheduled_at) = dto.scheduled_at {
+            active_model.scheduled_at = Set(Some(scheduled_at));
+        }
+        active_model.status = Set(Status::Draft);
+        active_model.failure_reason = Set(None);
+        active_model.updated_at = Set(chrono::Utc::now());
+        active_model
+            .update(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if let Some(channels) = dto.channels {
+            replace_channels(&txn, id, channels).await?;
+        }
+        if let Some(media) = dto.media {
+            replace_media(&txn, id, media).await?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.find_post(event_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+    }
+
+    pub async fn set_status(
+        &self,
+        post: &SocialPost,
+        status: PostStatus,
+        change: StatusChange,
+    ) -> Result<SocialPost, AppError> {
+        let now = chrono::Utc::now();
+        let model = PostEntity::find_by_id(post.id.clone())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(reviewed_by) = change.reviewed_by {
+            active_model.reviewed_by = Set(Some(reviewed_by));
+            active_model.reviewed_at = Set(Some(now));
+            active_model.review_note = Set(change.review_note);
+        }
+        if let Some(scheduled_at) = change.scheduled_at {
+            active_model.scheduled_at = Set(Some(scheduled_at));
+        }
+        match status {
+            PostStatus::Published => {
+                active_model.published_at = Set(Some(now));
+                active_model.published_url = Set(change.published_url);
+            }
+            PostStatus::Failed => active_model.failure_reason = Set(change.failure_reason),
+            _ => {}
+        }
+        active_model.status = Set(status.into());
+        active_model.updated_at = Set(now);
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(SocialPost::from_model(
+            result,
+            post.channels.clone(),
+            post.media.clone(),
+        ))
+    }
+
+    // channels and media go with it
+    pub async fn delete_post(&self, event_id: &str, id: &str) -> Result<(), AppError> {
+        let result = PostEntity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::EventId.eq(event_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Post not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn with_details(&self, models: Vec<entity::Model>) -> Result<Vec<SocialPost>, AppError> {
+        if models.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let ids: Vec<String> = models.iter().map(|m| m.id.clone()).collect();
+        let channels = channel::Entity::find()
+            .filter(channel::Column::PostId.is_in(ids.iter().cloned()))
+            .order_by_asc(channel::Column::Channel)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let media = media::Entity::find()
+            .filter(media::Column::PostId.is_in(ids.iter().cloned()))
+            .order_by_asc(media::Column::Position)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let files: HashMap<String, StoredFile> = if media.is_empty() {
+            HashMap::new()
+        } else {
+            file::Entity::find()
+                .filter(file::Column::Id.is_in(media.iter().map(|m| m.file_id.clone())))
+                .all(self.db.as_ref())
+                .await
+                .map_err(|_| AppError::InternalServerError)?
+                .into_iter()
+                .map(|f| (f.id.clone(), f.into()))
+                .collect()
+        };
+
+        let mut channels_by_post: HashMap<String, Vec<String>> = HashMap::new();
+        for row in channels {
+            channels_by_post
+                .entry(row.post_id)
+                .or_default()
+                .push(row.channel);
+        }
+        let mut media_by_post: HashMap<String, Vec<Media>> = HashMap::new();
+        for row in media {
+            if let Some(file) = files.get(&row.file_id) {
+                media_by_post
+                    .entry(row.post_id)
+                    .or_default()
+                    .push(file.clone().into());
+            }
+        }
+
+        Ok(models
+            .into_iter()
+            .map(|m| {
+                let channels = channels_by_post.remove(&m.id).unwrap_or_default();
+                let media = media_by_post.remove(&m.id).unwrap_or_default();
+                SocialPost::from_model(m, channels, media)
+            })
+            .collect())
+    }
+}
+
+async fn replace_channels(
+    txn: &DatabaseTransaction,
+    post_id: &str,
+    channels: Vec<String>,
+) -> Result<(), AppError> {
+    channel::Entity::delete_many()
+        .filter(channel::Column::PostId.eq(post_id))
+        .exec(txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    if !channels.is_empty() {
+        channel::Entity::insert_many(channels.into_iter().map(|c| channel::ActiveModel {
+            post_id: Set(post_id.to_string()),
+            channel: Set(c),
+        }))
+        .exec(txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    }
+
+    Ok(())
+}
+
+async fn replace_media(
+    txn: &DatabaseTransaction,
+    post_id: &str,
+    file_ids: Vec<String>,
+) -> Result<(), AppError> {
+    media::Entity::delete_many()
+        .filter(media::Column::PostId.eq(post_id))
+        .exec(txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    if !file_ids.is_empty() {
+        media::Entity::insert_many(file_ids.into_iter().enumerate().map(|(i, file_id)| {
+            media::ActiveModel {
+                post_id: Set(post_id.to_string()),
+                file_id: Set(file_id),
+                position: Set(i as i32),
+            }
+        }))
+        .exec(txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::social::models::{
+    CreatePostRequest, FailRequest, PostFilter, PublishRequest, ReviewRequest, ScheduleRequest,
+    UpdatePostRequest,
+};
+use crate::modules::social::service::SocialService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/socials")
+            .wrap(auth_middleware)
+            .route("/calendar", web::get().to(get_calendar))
+            .route("/posts", web::get().to(get_posts))
+            .route("/posts", web::post().to(create_post))
+            .route("/posts/{id}", web::get().to(get_post))
+            .route("/posts/{id}", web::patch().to(update_post))
+            .route("/posts/{id}", web::delete().to(delete_post))
+            .route("/posts/{id}/submit", web::post().to(submit_post))
+            .route("/posts/{id}/approve", web::post().to(approve_post))
+            .route("/posts/{id}/reject", web::post().to(reject_post))
+            .route("/posts/{id}/schedule", web::post().to(schedule_post))
+            .route("/posts/{id}/publish", web::post().to(publish_post))
+            .route("/posts/{id}/fail", web::post().to(fail_post)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_calendar(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    query: web::Query<PostFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let days = service.get_calendar(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(days))
+}
+
+async fn get_posts(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    query: web::Query<PostFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let posts = service.get_posts(query.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(posts))
+}
+
+async fn get_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service.get_post(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn create_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    body: web::Json<CreatePostRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service.create_post(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn update_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+    body: web::Json<UpdatePostRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service
+        .update_post(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn delete_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service.delete_post(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("Post deleted successfully"))
+}
+
+async fn submit_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service.submit_post(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn approve_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+    body: web::Json<ReviewRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service
+        .approve_post(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn reject_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+    body: web::Json<ReviewRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service
+        .reject_post(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn schedule_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+    body: web::Json<ScheduleRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service
+        .schedule_post(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn publish_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+    body: web::Json<PublishRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service
+        .publish_post(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+async fn fail_post(
+    req: HttpRequest,
+    service: web::Data<SocialService>,
+    path: web::Path<String>,
+    body: web::Json<FailRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let post = service
+        .fail_post(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(post))
+}
@@ -0,0 +1,359 @@
+use super::models::{
+    CalendarDay, CreatePostRequest, FailRequest, PostFilter, PostStatus, PublishRequest,
+    ReviewRequest, ScheduleRequest, SocialPost, StatusChange, UpdatePostRequest, calendar,
+};
+use super::repository::SocialRepository;
+use crate::audit::models::NewAuditEntry;
+use crate::audit::service::AuditService;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::file::repository::FileRepository;
+use crate::file::signer::UrlSigner;
+use crate::user::models::normalize_tags;
+use chrono::Utc;
+
+pub struct SocialService {
+    repository: SocialRepository,
+    files: FileRepository,
+    signer: UrlSigner,
+    audit: AuditService,
+}
+
+impl SocialService {
+    pub fn new(
+        repository: SocialRepository,
+        files: FileRepository,
+        signer: UrlSigner,
+        audit: AuditService,
+    ) -> Self {
+        Self {
+            repository,
+            files,
+            signer,
+            audit,
+        }
+    }
+
+    pub async fn get_posts(
+        &self,
+        filter: PostFilter,
+        claims: &Claims,
+    ) -> Result<Vec<SocialPost>, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        let posts = self.repository.find_posts(&claims.event, &filter).await?;
+        Ok(posts.into_iter().map(|p| self.with_links(p)).collect())
+    }
+
+    pub async fn get_post(&self, id: &str, claims: &Claims) -> Result<SocialPost, AppError> {
+        if !claims.is_staff() {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(self.with_links(self.find_post(id, claims).await?))
+    }
+
+    pub async fn get_calendar(
+        &self,
+        filter: PostFilter,
+        claims: &Claims,
+    ) -> Result<Vec<CalendarDay>, AppError> {
+        let channel = filter.channel.as_deref().map(|c| c.trim().to_lowercase());
+        let mut days = calendar(self.get_posts(filter, claims).await?);
+
+        // a post on other channels too only shows under the one asked for
+        if let Some(channel) = channel.filter(|c| !c.is_empty()) {
+            for day in &mut days {
+                day.channels
+                    .retain(|c| c.channel.as_deref() == Some(channel.as_str()));
+            }
+        }
+
+        Ok(days)
+    }
+
+    pub async fn create_post(
+        &self,
+        req: CreatePostRequest,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let text = req.text.trim().to_string();
+        if text.is_empty() {
+            return Err(AppError::BadRequest("Text is required".to_string()));
+        }
+        self.check_media(&req.media, claims).await?;
+
+        let req = CreatePostRequest {
+            text,
+            channels: normalize_tags(&req.channels),
+            ..req
+        };
+        let post = self
+            .repository
+            .create_post(&claims.event, req, &claims.sub)
+            .await?;
+        Ok(self.with_links(post))
+    }
+
+    // published posts are history, anything else goes back to draft
+    pub async fn update_post(
+        &self,
+        id: &str,
+        req: UpdatePostRequest,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let post = self.find_post(id, claims).await?;
+        if post.status == PostStatus::Published {
+            return Err(AppError::BadRequest(
+                "Published posts can't be changed".to_string(),
+            ));
+        }
+        let text = req.text.map(|t| t.trim().to_string());
+        if text.as_deref() == Some("") {
+            return Err(AppError::BadRequest("Text is required".to_string()));
+        }
+        if let Some(media) = &req.media {
+            self.check_media(media, claims).await?;
+        }
+
+        let req = UpdatePostRequest {
+            text,
+            channels: req.channels.as_deref().map(normalize_tags),
+            ..req
+        };
+        let post = self.repository.update_post(&claims.event, id, req).await?;
+        Ok(self.with_links(post))
+    }
+
+    pub async fn delete_post(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.delete_post(&claims.event, id).await
+    }
+
+    pub async fn submit_post(&self, id: &str, claims: &Claims) -> Result<SocialPost, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let post = self.find_post(id, claims).await?;
+        if post.channels.is_empty() {
+            return Err(AppError::BadRequest(
+                "Pick at least one channel before sending it for review".to_string(),
+            ));
+        }
+
+        self.move_post(post, PostStatus::InReview, StatusChange::default())
+            .await
+    }
+
+    pub async fn approve_post(
+        &self,
+        id: &str,
+        req: ReviewRequest,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        self.review_post(id, req, PostStatus::Approved, claims)
+            .await
+    }
+
+    // back to draft with a note on what to change
+    pub async fn reject_post(
+        &self,
+        id: &str,
+        req: ReviewRequest,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        self.review_post(id, req, PostStatus::Draft, claims).await
+    }
+
+    pub async fn schedule_post(
+        &self,
+        id: &str,
+        req: ScheduleRequest,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let post = self.find_post(id, claims).await?;
+        let scheduled_at = req
+            .scheduled_at
+            .or(post.scheduled_at)
+            .ok_or_else(|| AppError::BadRequest("Pick a time to publish it".to_string()))?;
+        if scheduled_at <= Utc::now() {
+            return Err(AppError::BadRequest(
+                "Publish time must be in the future".to_string(),
+            ));
+        }
+
+        let change = StatusChange {
+            scheduled_at: Some(scheduled_at),
+            ..StatusChange::default()
+        };
+        self.move_post(post, PostStatus::Scheduled, change).await
+    }
+
+    // nothing posts on its own yet, whoever posted it marks how it went
+    pub async fn publish_post(
+        &self,
+        id: &str,
+        req: PublishRequest,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let post = self.find_post(id, claims).await?;
+        let url = req
+            .url
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty());
+        if let Some(url) = &url
+            && !url.starts_with("https://")
+            && !url.starts_with("http://")
+        {
+            return Err(AppError::BadRequest(
+                "Post link must be an http(s) URL".to_string(),
+            ));
+        }
+
+        let change = StatusChange {
+            published_url: url,
+            ..StatusChange::default()
+        };
+        self.move_post(post, PostStatus::Published, change).await
+    }
+
+    pub async fn fail_post(
+        &self,
+        id: &str,
+        req: FailRequest,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let post = self.find_post(id, claims).await?;
+        let reason = req.reason.trim().to_string();
+
+        let change = StatusChange {
+            failure_reason: Some(reason).filter(|r| !r.is_empty()),
+            ..StatusChange::default()
+        };
+        self.move_post(post, PostStatus::Failed, change).await
+    }
+
+    async fn review_post(
+        &self,
+        id: &str,
+        req: ReviewRequest,
+        status: PostStatus,
+        claims: &Claims,
+    ) -> Result<SocialPost, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let post = self.find_post(id, claims).await?;
+        // a second pair of eyes, unless there's an admin to answer for it
+        if status == PostStatus::Approved && post.created_by == claims.sub && claims.role != "admin"
+        {
+            return Err(AppError::Forbidden);
+        }
+        let note = req.note.trim().to_string();
+
+        let change = StatusChange {
+            reviewed_by: Some(claims.sub.clone()),
+            review_note: Some(note.clone()).filter(|n| !n.is_empty()),
+            ..StatusChange::default()
+        };
+        let action = if status == PostStatus::Approved {
+            "social.approve"
+        } else {
+            "social.reject"
+        };
+        let post = self.move_post(post, status, change).await?;
+
+        self.audit
+            .record(NewAuditEntry {
+                event_id: Some(claims.event.clone()),
+                actor_id: claims.sub.clone(),
+                action: action.to_string(),
+                target_type: "social_post".to_string(),
+                target_id: Some(post.id.clone()),
+                details: Some(serde_json::json!({ "note": note })),
+            })
+            .await?;
+
+        Ok(post)
+    }
+
+    // every step of the workflow is for organizers and admins, checked by the callers
+    async fn move_post(
+        &self,
+        post: SocialPost,
+        status: PostStatus,
+        change: StatusChange,
+    ) -> Result<SocialPost, AppError> {
+        if !post.status.can_become(&status) {
+            return Err(AppError::BadRequest(format!(
+                "Post can't go from {} to {}",
+                post.status, status
+            )));
+        }
+
+        let post = self.repository.set_status(&post, status, change).await?;
+        Ok(self.with_links(post))
+    }
+
+    async fn check_media(&self, file_ids: &[String], claims: &Claims) -> Result<(), AppError> {
+        for file_id in file_ids {
+            if self
+                .files
+                .find_file(&claims.event, file_id)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::BadRequest(format!("Unknown file '{}'", file_id)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn with_links(&self, mut post: SocialPost) -> SocialPost {
+        for media in &mut post.media {
+            match media.image_status.clone() {
+                Some(status) => {
+                    media.image = Some(self.signer.image_links_for(&media.file_id, status))
+                }
+                None => media.url = Some(self.signer.sign(&media.file_id, None)),
+            }
+        }
+
+        post
+    }
+
+    async fn find_post(&self, id: &str, claims: &Claims) -> Result<SocialPost, AppError> {
+        self.repository
+            .find_post(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+    }
+}
@@ -0,0 +1,89 @@
+use super::entity;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TeamMember {
+    pub user_id: String,
+    pub is_lead: bool,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl From<entity::member::Model> for TeamMember {
+    fn from(model: entity::member::Model) -> Self {
+        Self {
+            user_id: model.user_id,
+            is_lead: model.is_lead,
+            joined_at: model.joined_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Team {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub members: Vec<TeamMember>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Team {
+    pub fn from_model(model: entity::Model, members: Vec<TeamMember>) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            description: model.description,
+            members,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+
+    pub fn leads(&self) -> Vec<&str> {
+        self.members
+            .iter()
+            .filter(|m| m.is_lead)
+            .map(|m| m.user_id.as_str())
+            .collect()
+    }
+
+    pub fn is_lead(&self, user_id: &str) -> bool {
+        self.members
+            .iter()
+            .any(|m| m.is_lead && m.user_id == user_id)
+    }
+
+    pub fn is_member(&self, user_id: &str) -> bool {
+        self.members.iter().any(|m| m.user_id == user_id)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateTeamRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    // user ids, they join as members right away
+    #[serde(default)]
+    pub leads: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateTeamRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AddMemberRequest {
+    pub user_id: String,
+    #[serde(default)]
+    pub is_lead: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateMemberRequest {
+    pub is_lead: bool,
+}
@@ -0,0 +1,250 @@
+use super::entity::{ActiveModel, Column, Entity as TeamEntity, member};
+use super::models::{CreateTeamRequest, Team, TeamMember, UpdateTeamRequest};
+use crate::error::AppError;
+use sea_orm::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct TeamRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl TeamRepository {
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn find_all(&self, event_id: &str) -> Result<Vec<Team>, AppError> {
+        let models = TeamEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .order_by_asc(Column::Name)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_members(models).await
+    }
+
+    pub async fn find_by_id(&self, event_id: &str, id: &str) -> Result<Option<Team>, AppError> {
+        let model = TeamEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(self.with_members(model.into_iter().collect()).await?.pop())
+    }
+
+    pub async fn find_by_name(&self, event_id: &str, name: &str) -> Result<Option<Team>, AppError> {
+        let model = TeamEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(Column::Name.eq(name))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.map(|m| Team::from_model(m, vec![])))
+    }
+
+    // teams someone is in, whether they lead them or not
+    pub async fn find_by_member(
+        &self,
+        event_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<Team>, AppError> {
+        let models = TeamEntity::find()
+            .filter(Column::EventId.eq(event_id))
+            .filter(
+                Column::Id.in_subquery(
+                    sea_query::Query::select()
+                        .column(member::Column::TeamId)
+                        .from(member::Entity)
+                        .and_where(member::Column::UserId.eq(user_id))
+                        .to_owned(),
+                ),
+            )
+            .order_by_asc(Column::Name)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        self.with_members(models).await
+    }
+
+    // leads join together with the team, so it is never left without one by accident
+    pub async fn create(&self, event_id: &str, dto: CreateTeamRequest) -> Result<Team, AppError> {
+        let now = chrono::Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let team = ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            event_id: Set(event_id.to_string()),
+            name: Set(dto.name),
+            description: Set(dto.description),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        let mut members = Vec::with_capacity(dto.leads.len());
+        for user_id in dto.leads {
+            let row = member::ActiveModel {
+                team_id: Set(team.id.clone()),
+                user_id: Set(user_id),
+                is_lead: Set(true),
+                joined_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+            members.push(row.into());
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(Team::from_model(team, members))
+    }
+
+    pub async fn update(&self, id: &str, dto: UpdateTeamRequest) -> Result<(), AppError> {
+        let model = TeamEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("Team not found".to_string()))?;
+
+        let mut active_model: ActiveModel = model.into();
+        if let Some(name) = dto.name {
+            active_model.name = Set(name);
+        }
+        if let Some(description) = dto.description {
+            active_model.description = Set(description);
+        }
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    // memberships go with it, shifts and tasks of the team are kept without one
+    pub async fn delete(&self, event_id: &str, id: &str) -> Result<(), AppError> {
+        let result = TeamEntity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::EventId.eq(event_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Team not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // adds the member or changes their lead flag if they are already in
+    pub async fn save_member(
+        &self,
+        team_id: &str,
+        user_id: &str,
+        is_lead: bool,
+    ) -> Result<TeamMember, AppError> {
+        let existing = member::Entity::find_by_id((team_id.to_string(), user_id.to_string()))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let result = match existing {
+            Some(model) => {
+                let mut active_model: member::ActiveModel = model.into();
+                active_model.is_lead = Set(is_lead);
+                active_model.update(self.db.as_ref()).await
+            }
+            None => {
+                member::ActiveModel {
+                    team_id: Set(team_id.to_string()),
+                    user_id: Set(user_id.to_string()),
+                    is_lead: Set(is_lead),
+                    joined_at: Set(chrono::Utc::now()),
+                }
+                .insert(self.db.as_ref())
+                .await
+            }
+        };
+
+        Ok(result.map_err(|_| AppError::InternalServerError)?.into())
+    }
+
+    pub async fn remove_member(&self, team_id: &str, user_id: &str) -> Result<(), AppError> {
+        let result = member::Entity::delete_by_id((team_id.to_string(), user_id.to_string()))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("Team member not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn exists(&self, event_id: &str, id: &str) -> Result<bool, AppError> {
+        let model = TeamEntity::find_by_id(id.to_string())
+            .filter(Column::EventId.eq(event_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(model.is_some())
+    }
+
+    pub async fn is_lead(&self, team_id: &str, user_id: &str) -> Result<bool, AppError> {
+        let membership = member::Entity::find_by_id((team_id.to_string(), user_id.to_string()))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(membership.is_some_and(|m| m.is_lead))
+    }
+
+    async fn with_members(&self, models: Vec<super::entity::Model>) -> Result<Vec<Team>, AppError> {
+        if models.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let rows = member::Entity::find()
+            .filter(member::Column::TeamId.is_in(models.iter().map(|m| m.id.clone())))
+            .order_by_desc(member::Column::IsLead)
+            .order_by_asc(member::Column::JoinedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut members: HashMap<String, Vec<TeamMember>> = HashMap::new();
+        for row in rows {
+            members
+                .entry(row.team_id.clone())
+                .or_default()
+                .push(row.into());
+        }
+
+        Ok(models
+            .into_iter()
+            .map(|m| {
+                let team_members = members.remove(&m.id).unwrap_or_default();
+                Team::from_model(m, team_members)
+            })
+            .collect())
+    }
+}
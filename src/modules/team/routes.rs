@@ -0,0 +1,131 @@
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::modules::auth::middleware::jwt_validator;
+use crate::modules::team::models::{
+    AddMemberRequest, CreateTeamRequest, UpdateMemberRequest, UpdateTeamRequest,
+};
+use crate::modules::team::service::TeamService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(jwt_validator);
+
+    cfg.service(
+        web::scope("/teams")
+            .wrap(auth_middleware)
+            .route("", web::get().to(get_teams))
+            .route("", web::post().to(create_team))
+            .route("/{id}", web::get().to(get_team))
+            .route("/{id}", web::patch().to(update_team))
+            .route("/{id}", web::delete().to(delete_team))
+            .route("/{id}/members", web::post().to(add_member))
+            .route("/{id}/members/{user_id}", web::patch().to(update_member))
+            .route("/{id}/members/{user_id}", web::delete().to(remove_member)),
+    );
+}
+
+fn claims_of(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn get_teams(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let teams = service.get_teams(&claims).await?;
+    Ok(HttpResponse::Ok().json(teams))
+}
+
+async fn get_team(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let team = service.get_team(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(team))
+}
+
+async fn create_team(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+    body: web::Json<CreateTeamRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let team = service.create_team(body.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().json(team))
+}
+
+async fn update_team(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateTeamRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let team = service
+        .update_team(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(team))
+}
+
+async fn delete_team(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    service.delete_team(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("Team deleted successfully"))
+}
+
+async fn add_member(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+    path: web::Path<String>,
+    body: web::Json<AddMemberRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+
+    let member = service
+        .add_member(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(member))
+}
+
+async fn update_member(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+    path: web::Path<(String, String)>,
+    body: web::Json<UpdateMemberRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+    let (id, user_id) = path.into_inner();
+
+    let member = service
+        .update_member(&id, &user_id, body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(member))
+}
+
+async fn remove_member(
+    req: HttpRequest,
+    service: web::Data<TeamService>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_of(&req)?;
+    let (id, user_id) = path.into_inner();
+
+    service.remove_member(&id, &user_id, &claims).await?;
+    Ok(HttpResponse::Ok().body("Team member removed"))
+}
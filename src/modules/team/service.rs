@@ -0,0 +1,205 @@
+use super::models::{
+    AddMemberRequest, CreateTeamRequest, Team, TeamMember, UpdateMemberRequest, UpdateTeamRequest,
+};
+use super::repository::TeamRepository;
+use crate::auth::models::Claims;
+use crate::error::AppError;
+use crate::user::models::{UserRole, UserStatus};
+use crate::user::repository::UserRepository;
+
+pub struct TeamService {
+    repository: TeamRepository,
+    users: UserRepository,
+}
+
+// organizers and admins run every team, staff leads run their own
+fn can_run(team: &Team, claims: &Claims) -> bool {
+    claims.is_manager() || (claims.role == "staff" && team.is_lead(&claims.sub))
+}
+
+impl TeamService {
+    pub fn new(repository: TeamRepository, users: UserRepository) -> Self {
+        Self { repository, users }
+    }
+
+    pub async fn get_teams(&self, claims: &Claims) -> Result<Vec<Team>, AppError> {
+        self.repository.find_all(&claims.event).await
+    }
+
+    pub async fn get_team(&self, id: &str, claims: &Claims) -> Result<Team, AppError> {
+        self.find_team(id, claims).await
+    }
+
+    pub async fn create_team(
+        &self,
+        req: CreateTeamRequest,
+        claims: &Claims,
+    ) -> Result<Team, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let name = req.name.trim().to_string();
+        self.check_name(&name, None, claims).await?;
+
+        let mut leads: Vec<String> = Vec::new();
+        for user_id in req.leads {
+            if !leads.contains(&user_id) {
+                self.check_member(&user_id, true, claims).await?;
+                leads.push(user_id);
+            }
+        }
+
+        self.repository
+            .create(&claims.event, CreateTeamRequest { name, leads, ..req })
+            .await
+    }
+
+    pub async fn update_team(
+        &self,
+        id: &str,
+        req: UpdateTeamRequest,
+        claims: &Claims,
+    ) -> Result<Team, AppError> {
+        let team = self.find_team(id, claims).await?;
+        if !can_run(&team, claims) {
+            return Err(AppError::Forbidden);
+        }
+
+        let name = req.name.map(|n| n.trim().to_string());
+        if let Some(name) = &name {
+            self.check_name(name, Some(id), claims).await?;
+        }
+
+        self.repository
+            .update(id, UpdateTeamRequest { name, ..req })
+            .await?;
+        self.find_team(id, claims).await
+    }
+
+    pub async fn delete_team(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.delete(&claims.event, id).await
+    }
+
+    // leads can bring people in, only organizers and admins hand out the lead
+    pub async fn add_member(
+        &self,
+        team_id: &str,
+        req: AddMemberRequest,
+        claims: &Claims,
+    ) -> Result<TeamMember, AppError> {
+        let team = self.find_team(team_id, claims).await?;
+        if !can_run(&team, claims) || (req.is_lead && !claims.is_manager()) {
+            return Err(AppError::Forbidden);
+        }
+        if team.is_member(&req.user_id) {
+            return Err(AppError::BadRequest(
+                "User is already in this team".to_string(),
+            ));
+        }
+        self.check_member(&req.user_id, req.is_lead, claims).await?;
+
+        self.repository
+            .save_member(team_id, &req.user_id, req.is_lead)
+            .await
+    }
+
+    pub async fn update_member(
+        &self,
+        team_id: &str,
+        user_id: &str,
+        req: UpdateMemberRequest,
+        claims: &Claims,
+    ) -> Result<TeamMember, AppError> {
+        if !claims.is_manager() {
+            return Err(AppError::Forbidden);
+        }
+
+        let team = self.find_team(team_id, claims).await?;
+        if !team.is_member(user_id) {
+            return Err(AppError::NotFound("Team member not found".to_string()));
+        }
+        if req.is_lead {
+            self.check_member(user_id, true, claims).await?;
+        }
+
+        self.repository
+            .save_member(team_id, user_id, req.is_lead)
+            .await
+    }
+
+    // anyone can leave, leads can remove members but not other leads
+    pub async fn remove_member(
+        &self,
+        team_id: &str,
+        user_id: &str,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let team = self.find_team(team_id, claims).await?;
+        let allowed = claims.sub == user_id
+            || claims.is_manager()
+            || (can_run(&team, claims) && !team.is_lead(user_id));
+        if !allowed {
+            return Err(AppError::Forbidden);
+        }
+
+        self.repository.remove_member(team_id, user_id).await
+    }
+
+    async fn find_team(&self, id: &str, claims: &Claims) -> Result<Team, AppError> {
+        self.repository
+            .find_by_id(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Team not found".to_string()))
+    }
+
+    async fn check_name(
+        &self,
+        name: &str,
+        id: Option<&str>,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if name.is_empty() {
+            return Err(AppError::BadRequest("Name is required".to_string()));
+        }
+
+        let taken = self.repository.find_by_name(&claims.event, name).await?;
+        if taken.is_some_and(|t| Some(t.id.as_str()) != id) {
+            return Err(AppError::BadRequest(
+                "A team with this name already exists".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn check_member(
+        &self,
+        user_id: &str,
+        is_lead: bool,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let user = self
+            .users
+            .find_by_id(&claims.event, user_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown user '{}'", user_id)))?;
+
+        if is_lead && user.status == UserStatus::Inactive {
+            return Err(AppError::BadRequest(
+                "Inactive users can't lead a team".to_string(),
+            ));
+        }
+        if is_lead && user.role == UserRole::Volunteer {
+            return Err(AppError::BadRequest(
+                "Volunteers can't lead a team".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,15 @@
+// profile data lives in its own tables, keyed by user id
+pub mod availability;
+pub mod avatar;
+pub mod preferred_area;
+pub mod skill;
+
 use derive_more::Display;
 use sea_orm::entity::prelude::*;
 use sea_orm::sea_query::StringLen;
 use serde::{Deserialize, Serialize};
 
+// stored on event memberships, see the event module
 #[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
 pub enum Role {
@@ -34,7 +41,6 @@ pub struct Model {
     pub surname: String,
     pub email: String,
     pub phone: String,
-    pub role: Role,
     pub status: Status,
 }
 
@@ -1,6 +1,9 @@
 use super::entity;
+use crate::file::models::ImageLinks;
+use chrono::{DateTime, Utc};
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -24,6 +27,41 @@ impl UserRole {
             UserRole::Volunteer => "volunteer",
         }
     }
+
+    // one step up the ladder, admins stay admins
+    pub fn promoted(&self) -> Self {
+        match self {
+            UserRole::Admin | UserRole::Organizer => UserRole::Admin,
+            UserRole::Staff => UserRole::Organizer,
+            UserRole::Volunteer => UserRole::Staff,
+        }
+    }
+
+    // one step down the ladder, volunteers stay volunteers
+    pub fn demoted(&self) -> Self {
+        match self {
+            UserRole::Admin => UserRole::Organizer,
+            UserRole::Organizer => UserRole::Staff,
+            UserRole::Staff | UserRole::Volunteer => UserRole::Volunteer,
+        }
+    }
+}
+
+impl FromStr for UserRole {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "admin" => Ok(UserRole::Admin),
+            "organizer" => Ok(UserRole::Organizer),
+            "staff" => Ok(UserRole::Staff),
+            "volunteer" => Ok(UserRole::Volunteer),
+            other => Err(format!(
+                "Unknown role '{}', expected admin, organizer, staff or volunteer",
+                other
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
@@ -82,22 +120,118 @@ pub struct User {
     pub surname: String,
     pub email: String,
     pub phone: String,
+    // role at the event the user was looked up in
     pub role: UserRole,
     pub status: UserStatus,
+    // only loaded where it's needed, absent otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<UserProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<ImageLinks>,
 }
 
-impl From<entity::Model> for User {
-    fn from(model: entity::Model) -> Self {
+impl User {
+    pub fn from_model(model: entity::Model, role: UserRole) -> Self {
         Self {
             id: model.id,
             name: model.name,
             surname: model.surname,
             email: model.email,
             phone: model.phone,
-            role: model.role.into(),
+            role,
             status: model.status.into(),
+            profile: None,
+            avatar: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Availability {
+    pub id: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub note: String,
+}
+
+impl From<entity::availability::Model> for Availability {
+    fn from(model: entity::availability::Model) -> Self {
+        Self {
+            id: model.id,
+            starts_at: model.starts_at,
+            ends_at: model.ends_at,
+            note: model.note,
+        }
+    }
+}
+
+// skills and areas are stored normalized, see normalize_tags
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct UserProfile {
+    pub skills: Vec<String>,
+    pub preferred_areas: Vec<String>,
+    pub availability: Vec<Availability>,
+}
+
+impl UserProfile {
+    pub fn missing_skills(&self, required: &[String]) -> Vec<String> {
+        required
+            .iter()
+            .filter(|skill| !self.skills.contains(skill))
+            .cloned()
+            .collect()
+    }
+
+    pub fn prefers(&self, area: &str) -> bool {
+        let area = area.trim().to_lowercase();
+        self.preferred_areas.contains(&area)
+    }
+
+    // a single window has to cover the whole range
+    pub fn is_available(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
+        self.availability
+            .iter()
+            .any(|w| w.starts_at <= from && w.ends_at >= to)
+    }
+}
+
+// emails are stored and looked up like this, so "Alice@Circa.local " is the same account as "alice@circa.local"
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+// trimmed, lowercase and without duplicates, so "First aid " and "first aid" match
+pub fn normalize_tags(values: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for value in values {
+        let tag = value.trim().to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
         }
     }
+
+    tags
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateProfileRequest {
+    // each list replaces the stored one when present
+    pub skills: Option<Vec<String>>,
+    pub preferred_areas: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SetAvatarRequest {
+    // an image uploaded through /files
+    pub file_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateAvailabilityRequest {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default)]
+    pub note: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -118,3 +252,66 @@ pub struct UpdateUserRequest {
     pub role: Option<UserRole>,
     pub status: Option<UserStatus>,
 }
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdateUserQuery {
+    // deactivate even if the user still holds checked out equipment
+    #[serde(default)]
+    pub force: bool,
+}
+
+// shared by the list endpoint and exports, so both filter the same way
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct UserFilter {
+    pub role: Option<UserRole>,
+    pub status: Option<UserStatus>,
+    // matches name, surname or email
+    pub q: Option<String>,
+    // team id, only members of that team
+    pub team: Option<String>,
+}
+
+// column names default to the field names, override them to match whatever the spreadsheet uses
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ImportUsersQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub skip_invalid: bool,
+    pub name_column: Option<String>,
+    pub surname_column: Option<String>,
+    pub email_column: Option<String>,
+    pub phone_column: Option<String>,
+    pub role_column: Option<String>,
+    pub default_role: Option<UserRole>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Valid,
+    Created,
+    Invalid,
+    Duplicate,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportRowResult {
+    // line in the csv file, header is line 1
+    pub line: usize,
+    pub email: String,
+    pub status: ImportRowStatus,
+    pub errors: Vec<String>,
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub committed: bool,
+    pub total: usize,
+    pub created: usize,
+    pub failed: usize,
+    pub rows: Vec<ImportRowResult>,
+}
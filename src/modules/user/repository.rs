@@ -1,116 +1,704 @@
 use super::entity::{ActiveModel, Entity as UserEntity};
-use super::models::{CreateUserRequest, UpdateUserRequest, User};
+use super::entity::{Column, Role};
+use super::entity::{availability, avatar, preferred_area, skill};
+use super::models::{
+    Availability, CreateAvailabilityRequest, CreateUserRequest, UpdateUserRequest, User,
+    UserFilter, UserProfile, UserRole, normalize_email,
+};
 use crate::error::AppError;
+use crate::event::entity::member as event_member;
+use crate::logistics::entity::checkout;
+use crate::team::entity::member;
+use chrono::{DateTime, Utc};
 use sea_orm::*;
+use std::collections::HashMap;
+use std::sync::Arc;
 use uuid;
 
 pub struct UserRepository {
-    db: DatabaseConnection,
+    db: Arc<DatabaseConnection>,
 }
 
 impl UserRepository {
-    pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+    // takes either an owned connection or a shared one, so several repositories can use one pool
+    pub fn new(db: impl Into<Arc<DatabaseConnection>>) -> Self {
+        Self { db: db.into() }
     }
 
-    pub async fn find_all(&self) -> Result<Vec<User>, AppError> {
-        let models = UserEntity::find()
-            .all(&self.db)
+    pub async fn find_all(&self, event_id: &str) -> Result<Vec<User>, AppError> {
+        self.find_filtered(event_id, &UserFilter::default()).await
+    }
+
+    pub async fn find_filtered(
+        &self,
+        event_id: &str,
+        filter: &UserFilter,
+    ) -> Result<Vec<User>, AppError> {
+        let models = filtered(event_id, filter)
+            .all(self.db.as_ref())
             .await
             .map_err(|_| AppError::InternalServerError)?;
 
-        Ok(models.into_iter().map(|m| m.into()).collect())
+        self.with_roles(event_id, models).await
     }
 
-    pub async fn find_by_id(&self, id: &str) -> Result<Option<User>, AppError> {
+    pub async fn count_filtered(
+        &self,
+        event_id: &str,
+        filter: &UserFilter,
+    ) -> Result<u64, AppError> {
+        filtered(event_id, filter)
+            .count(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)
+    }
+
+    // people outside the event are not found, even if they have an account
+    pub async fn find_by_id(&self, event_id: &str, id: &str) -> Result<Option<User>, AppError> {
         let model = UserEntity::find_by_id(id.to_string())
-            .one(&self.db)
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        match model {
+            Some(model) => Ok(self.with_roles(event_id, vec![model]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn find_by_ids(&self, event_id: &str, ids: &[String]) -> Result<Vec<User>, AppError> {
+        let models = UserEntity::find()
+            .filter(Column::Id.is_in(ids.iter().cloned()))
+            .all(self.db.as_ref())
             .await
             .map_err(|_| AppError::InternalServerError)?;
 
-        Ok(model.map(|m| m.into()))
+        self.with_roles(event_id, models).await
     }
 
-    pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+    pub async fn find_by_email(
+        &self,
+        event_id: &str,
+        email: &str,
+    ) -> Result<Option<User>, AppError> {
         let model = UserEntity::find()
-            .filter(super::entity::Column::Email.eq(email))
-            .one(&self.db)
+            .filter(Column::Email.eq(normalize_email(email)))
+            .one(self.db.as_ref())
             .await
             .map_err(|_| AppError::InternalServerError)?;
 
-        Ok(model.map(|m| m.into()))
+        match model {
+            Some(model) => Ok(self.with_roles(event_id, vec![model]).await?.pop()),
+            None => Ok(None),
+        }
     }
 
-    pub async fn create(&self, dto: CreateUserRequest) -> Result<User, AppError> {
-        let id = uuid::Uuid::now_v7().to_string();
+    // the account behind an email, whichever events it is part of
+    pub async fn find_id_by_email(&self, email: &str) -> Result<Option<String>, AppError> {
+        let model = UserEntity::find()
+            .filter(Column::Email.eq(normalize_email(email)))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
 
-        let new_user = ActiveModel {
-            id: Set(id),
-            name: Set(dto.name),
-            surname: Set(dto.surname),
-            email: Set(dto.email),
-            phone: Set(dto.phone),
-            role: Set(dto.role.into()),
+        Ok(model.map(|m| m.id))
+    }
+
+    fn new_active_model(dto: &CreateUserRequest) -> ActiveModel {
+        ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            name: Set(dto.name.clone()),
+            surname: Set(dto.surname.clone()),
+            email: Set(normalize_email(&dto.email)),
+            phone: Set(dto.phone.clone()),
             status: Set(super::entity::Status::Active),
-        };
+        }
+    }
+
+    fn new_membership(event_id: &str, user_id: &str, role: UserRole) -> event_member::ActiveModel {
+        event_member::ActiveModel {
+            event_id: Set(event_id.to_string()),
+            user_id: Set(user_id.to_string()),
+            role: Set(role.into()),
+            joined_at: Set(chrono::Utc::now()),
+        }
+    }
+
+    // emails of people already in the event, accounts elsewhere don't count
+    pub async fn find_existing_emails(
+        &self,
+        event_id: &str,
+        emails: &[String],
+    ) -> Result<Vec<String>, AppError> {
+        let models = UserEntity::find()
+            .filter(Column::Email.is_in(emails.iter().map(|e| normalize_email(e))))
+            .filter(
+                Column::Id.in_subquery(
+                    sea_query::Query::select()
+                        .column(event_member::Column::UserId)
+                        .from(event_member::Entity)
+                        .and_where(event_member::Column::EventId.eq(event_id))
+                        .to_owned(),
+                ),
+            )
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
 
-        let result = new_user
-            .insert(&self.db)
+        Ok(models.into_iter().map(|m| m.email).collect())
+    }
+
+    pub async fn create(&self, event_id: &str, dto: CreateUserRequest) -> Result<User, AppError> {
+        let txn = self
+            .db
+            .begin()
             .await
             .map_err(|_| AppError::InternalServerError)?;
 
-        Ok(result.into())
+        let result = Self::new_active_model(&dto)
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        Self::new_membership(event_id, &result.id, dto.role.clone())
+            .insert(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(User::from_model(result, dto.role))
     }
 
-    pub async fn update(&self, id: &str, dto: UpdateUserRequest) -> Result<User, AppError> {
+    // brings an existing account into the event
+    pub async fn add_member(
+        &self,
+        event_id: &str,
+        id: &str,
+        role: UserRole,
+    ) -> Result<User, AppError> {
         let model = UserEntity::find_by_id(id.to_string())
-            .one(&self.db)
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        Self::new_membership(event_id, id, role.clone())
+            .insert(self.db.as_ref())
             .await
             .map_err(|_| AppError::InternalServerError)?;
 
-        if let Some(model) = model {
-            let mut active_model: ActiveModel = model.into();
+        Ok(User::from_model(model, role))
+    }
 
-            if let Some(name) = dto.name {
-                active_model.name = Set(name);
-            }
-            if let Some(surname) = dto.surname {
-                active_model.surname = Set(surname);
-            }
-            if let Some(email) = dto.email {
-                active_model.email = Set(email);
-            }
-            if let Some(phone) = dto.phone {
-                active_model.phone = Set(phone);
-            }
-            if let Some(role) = dto.role {
-                active_model.role = Set(role.into());
+    // all or nothing, a failed insert rolls back the whole batch. people who already have an
+    // account only join the event
+    pub async fn create_many(
+        &self,
+        event_id: &str,
+        dtos: Vec<CreateUserRequest>,
+    ) -> Result<Vec<User>, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut accounts: HashMap<String, super::entity::Model> = UserEntity::find()
+            .filter(Column::Email.is_in(dtos.iter().map(|d| normalize_email(&d.email))))
+            .all(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .into_iter()
+            .map(|m| (m.email.clone(), m))
+            .collect();
+
+        let mut users = Vec::with_capacity(dtos.len());
+        for dto in dtos {
+            let model = match accounts.remove(&normalize_email(&dto.email)) {
+                Some(model) => model,
+                None => Self::new_active_model(&dto)
+                    .insert(&txn)
+                    .await
+                    .map_err(|_| AppError::InternalServerError)?,
+            };
+            Self::new_membership(event_id, &model.id, dto.role.clone())
+                .insert(&txn)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            users.push(User::from_model(model, dto.role));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(users)
+    }
+
+    // the role changes for this event only, everything else is the same everywhere
+    pub async fn update(
+        &self,
+        event_id: &str,
+        id: &str,
+        dto: UpdateUserRequest,
+    ) -> Result<User, AppError> {
+        let not_found = || AppError::NotFound("User not found".to_string());
+
+        let model = UserEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(not_found)?;
+        let membership = event_member::Entity::find_by_id((event_id.to_string(), id.to_string()))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(not_found)?;
+
+        let mut active_model: ActiveModel = model.clone().into();
+        if let Some(name) = dto.name {
+            active_model.name = Set(name);
+        }
+        if let Some(surname) = dto.surname {
+            active_model.surname = Set(surname);
+        }
+        if let Some(email) = dto.email {
+            active_model.email = Set(normalize_email(&email));
+        }
+        if let Some(phone) = dto.phone {
+            active_model.phone = Set(phone);
+        }
+        if let Some(status) = dto.status {
+            active_model.status = Set(status.into());
+        }
+
+        let model = if active_model.is_changed() {
+            // taken meanwhile by another account
+            active_model
+                .update(self.db.as_ref())
+                .await
+                .map_err(|err| match err.sql_err() {
+                    Some(SqlErr::UniqueConstraintViolation(_)) => {
+                        AppError::BadRequest("Email already in use".to_string())
+                    }
+                    _ => AppError::InternalServerError,
+                })?
+        } else {
+            model
+        };
+
+        let role = match dto.role {
+            Some(role) => {
+                let mut active_membership: event_member::ActiveModel = membership.into();
+                active_membership.role = Set(role.clone().into());
+                active_membership
+                    .update(self.db.as_ref())
+                    .await
+                    .map_err(|_| AppError::InternalServerError)?;
+                role
             }
-            if let Some(status) = dto.status {
-                active_model.status = Set(status.into());
+            None => membership.role.into(),
+        };
+
+        Ok(User::from_model(model, role))
+    }
+
+    // the account is the same in every event, the role returned is the one at event_id
+    pub async fn anonymize(&self, event_id: &str, id: &str) -> Result<User, AppError> {
+        let not_found = || AppError::NotFound("User not found".to_string());
+
+        let model = UserEntity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(not_found)?;
+        let membership = event_member::Entity::find_by_id((event_id.to_string(), id.to_string()))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(not_found)?;
+
+        let mut active_model: ActiveModel = model.into();
+        active_model.name = Set("Erased".to_string());
+        active_model.surname = Set("User".to_string());
+        active_model.email = Set(format!("erased-{}@erased.invalid", id));
+        active_model.phone = Set(String::new());
+        active_model.status = Set(super::entity::Status::Inactive);
+
+        let result = active_model
+            .update(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(User::from_model(result, membership.role.into()))
+    }
+
+    // takes the person out of the event, the account goes too once they are in none
+    pub async fn delete(&self, event_id: &str, id: &str) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let result = event_member::Entity::delete_by_id((event_id.to_string(), id.to_string()))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+
+        // the custody log keeps pointing at the account, so it stays once there is one
+        UserEntity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(
+                Column::Id.not_in_subquery(
+                    sea_query::Query::select()
+                        .column(event_member::Column::UserId)
+                        .from(event_member::Entity)
+                        .to_owned(),
+                ),
+            )
+            .filter(
+                Column::Id.not_in_subquery(
+                    sea_query::Query::select()
+                        .column(checkout::Column::UserId)
+                        .from(checkout::Entity)
+                        .to_owned(),
+                ),
+            )
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)
+    }
+
+    pub async fn find_profile(&self, id: &str) -> Result<UserProfile, AppError> {
+        let mut profiles = self.find_profiles(&[id.to_string()]).await?;
+        Ok(profiles.remove(id).unwrap_or_default())
+    }
+
+    // users without any profile data are simply missing from the map
+    pub async fn find_profiles(
+        &self,
+        ids: &[String],
+    ) -> Result<HashMap<String, UserProfile>, AppError> {
+        let mut profiles: HashMap<String, UserProfile> = HashMap::new();
+        if ids.is_empty() {
+            return Ok(profiles);
+        }
+
+        let skills = skill::Entity::find()
+            .filter(skill::Column::UserId.is_in(ids.iter().cloned()))
+            .order_by_asc(skill::Column::Skill)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let areas = preferred_area::Entity::find()
+            .filter(preferred_area::Column::UserId.is_in(ids.iter().cloned()))
+            .order_by_asc(preferred_area::Column::Area)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        let windows = availability::Entity::find()
+            .filter(availability::Column::UserId.is_in(ids.iter().cloned()))
+            .order_by_asc(availability::Column::StartsAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        for row in skills {
+            profiles
+                .entry(row.user_id)
+                .or_default()
+                .skills
+                .push(row.skill);
+        }
+        for row in areas {
+            profiles
+                .entry(row.user_id)
+                .or_default()
+                .preferred_areas
+                .push(row.area);
+        }
+        for row in windows {
+            profiles
+                .entry(row.user_id.clone())
+                .or_default()
+                .availability
+                .push(row.into());
+        }
+
+        Ok(profiles)
+    }
+
+    // expects already normalized lists, None leaves the stored one alone
+    pub async fn update_profile(
+        &self,
+        id: &str,
+        skills: Option<Vec<String>>,
+        preferred_areas: Option<Vec<String>>,
+    ) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        if let Some(skills) = skills {
+            skill::Entity::delete_many()
+                .filter(skill::Column::UserId.eq(id))
+                .exec(&txn)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+
+            if !skills.is_empty() {
+                skill::Entity::insert_many(skills.into_iter().map(|s| skill::ActiveModel {
+                    user_id: Set(id.to_string()),
+                    skill: Set(s),
+                }))
+                .exec_without_returning(&txn)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
             }
+        }
 
-            let result = active_model
-                .update(&self.db)
+        if let Some(areas) = preferred_areas {
+            preferred_area::Entity::delete_many()
+                .filter(preferred_area::Column::UserId.eq(id))
+                .exec(&txn)
                 .await
                 .map_err(|_| AppError::InternalServerError)?;
 
-            Ok(result.into())
-        } else {
-            Err(AppError::NotFound("User not found".to_string()))
+            if !areas.is_empty() {
+                preferred_area::Entity::insert_many(areas.into_iter().map(|a| {
+                    preferred_area::ActiveModel {
+                        user_id: Set(id.to_string()),
+                        area: Set(a),
+                    }
+                }))
+                .exec_without_returning(&txn)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            }
         }
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn create_availability(
+        &self,
+        user_id: &str,
+        dto: CreateAvailabilityRequest,
+    ) -> Result<Availability, AppError> {
+        let window = availability::ActiveModel {
+            id: Set(uuid::Uuid::now_v7().to_string()),
+            user_id: Set(user_id.to_string()),
+            starts_at: Set(dto.starts_at),
+            ends_at: Set(dto.ends_at),
+            note: Set(dto.note),
+        };
+
+        let result = window
+            .insert(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(result.into())
     }
 
-    pub async fn delete(&self, id: &str) -> Result<(), AppError> {
-        let result = UserEntity::delete_by_id(id.to_string())
-            .exec(&self.db)
+    pub async fn delete_availability(&self, user_id: &str, id: &str) -> Result<(), AppError> {
+        let result = availability::Entity::delete_many()
+            .filter(availability::Column::Id.eq(id))
+            .filter(availability::Column::UserId.eq(user_id))
+            .exec(self.db.as_ref())
             .await
             .map_err(|_| AppError::InternalServerError)?;
 
         if result.rows_affected == 0 {
-            Err(AppError::NotFound("User not found".to_string()))
-        } else {
-            Ok(())
+            return Err(AppError::NotFound(
+                "Availability window not found".to_string(),
+            ));
         }
+
+        Ok(())
     }
+
+    // ids of users with a single window covering the whole range
+    pub async fn find_available_ids(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<String>, AppError> {
+        let rows = availability::Entity::find()
+            .filter(availability::Column::StartsAt.lte(from))
+            .filter(availability::Column::EndsAt.gte(to))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut ids: Vec<String> = rows.into_iter().map(|r| r.user_id).collect();
+        ids.sort();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    pub async fn find_avatar(&self, user_id: &str) -> Result<Option<String>, AppError> {
+        let row = avatar::Entity::find_by_id(user_id)
+            .one(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(row.map(|r| r.file_id))
+    }
+
+    pub async fn set_avatar(&self, user_id: &str, file_id: &str) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        avatar::Entity::delete_many()
+            .filter(avatar::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        avatar::ActiveModel {
+            user_id: Set(user_id.to_string()),
+            file_id: Set(file_id.to_string()),
+            updated_at: Set(Utc::now()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn delete_avatar(&self, user_id: &str) -> Result<(), AppError> {
+        avatar::Entity::delete_many()
+            .filter(avatar::Column::UserId.eq(user_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    pub async fn clear_profile(&self, id: &str) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        skill::Entity::delete_many()
+            .filter(skill::Column::UserId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        preferred_area::Entity::delete_many()
+            .filter(preferred_area::Column::UserId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        availability::Entity::delete_many()
+            .filter(availability::Column::UserId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        avatar::Entity::delete_many()
+            .filter(avatar::Column::UserId.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        txn.commit()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        Ok(())
+    }
+
+    // pairs users with their role at the event, anyone who isn't part of it is dropped
+    async fn with_roles(
+        &self,
+        event_id: &str,
+        models: Vec<super::entity::Model>,
+    ) -> Result<Vec<User>, AppError> {
+        if models.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let roles: HashMap<String, Role> = event_member::Entity::find()
+            .filter(event_member::Column::EventId.eq(event_id))
+            .filter(event_member::Column::UserId.is_in(models.iter().map(|m| m.id.clone())))
+            .all(self.db.as_ref())
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .into_iter()
+            .map(|m| (m.user_id, m.role))
+            .collect();
+
+        Ok(models
+            .into_iter()
+            .filter_map(|m| {
+                let role = roles.get(&m.id)?.clone();
+                Some(User::from_model(m, role.into()))
+            })
+            .collect())
+    }
+}
+
+// the members of an event matching a list filter
+fn filtered(event_id: &str, filter: &UserFilter) -> Select<UserEntity> {
+    let mut members = sea_query::Query::select()
+        .column(event_member::Column::UserId)
+        .from(event_member::Entity)
+        .and_where(event_member::Column::EventId.eq(event_id))
+        .to_owned();
+    if let Some(role) = &filter.role {
+        members.and_where(event_member::Column::Role.eq(Role::from(role.clone())));
+    }
+    let mut select = UserEntity::find().filter(Column::Id.in_subquery(members));
+
+    if let Some(status) = &filter.status {
+        select = select.filter(Column::Status.eq(super::entity::Status::from(status.clone())));
+    }
+    if let Some(q) = filter.q.as_deref().filter(|q| !q.is_empty()) {
+        select = select.filter(
+            Condition::any()
+                .add(Column::Name.contains(q))
+                .add(Column::Surname.contains(q))
+                .add(Column::Email.contains(q)),
+        );
+    }
+    if let Some(team) = filter.team.as_deref().filter(|t| !t.is_empty()) {
+        select = select.filter(
+            Column::Id.in_subquery(
+                sea_query::Query::select()
+                    .column(member::Column::UserId)
+                    .from(member::Entity)
+                    .and_where(member::Column::TeamId.eq(team))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    select
 }
@@ -1,8 +1,14 @@
 use crate::auth::models::Claims;
 use crate::error::AppError;
 use crate::modules::auth::middleware::jwt_validator;
-use crate::modules::user::models::{CreateUserRequest, UpdateUserRequest};
+use crate::modules::privacy::models::EraseQuery;
+use crate::modules::privacy::service::PrivacyService;
+use crate::modules::user::models::{
+    CreateAvailabilityRequest, CreateUserRequest, ImportUsersQuery, SetAvatarRequest,
+    UpdateProfileRequest, UpdateUserQuery, UpdateUserRequest, UserFilter,
+};
 use crate::modules::user::service::UserService;
+use actix_web::http::header::ContentDisposition;
 use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
 use actix_web_httpauth::middleware::HttpAuthentication;
 
@@ -14,30 +20,83 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .wrap(auth_middleware)
             .route("", web::get().to(get_users))
             .route("", web::post().to(create_user))
+            .route("/import", web::post().to(import_users))
             .route("/{id}", web::get().to(get_user))
             .route("/{id}", web::patch().to(update_user))
-            .route("/{id}", web::delete().to(delete_user)),
+            .route("/{id}", web::delete().to(delete_user))
+            .route("/{id}/profile", web::patch().to(update_profile))
+            .route("/{id}/avatar", web::put().to(set_avatar))
+            .route("/{id}/avatar", web::delete().to(remove_avatar))
+            .route("/{id}/availability", web::post().to(add_availability))
+            .route(
+                "/{id}/availability/{window_id}",
+                web::delete().to(remove_availability),
+            )
+            .route("/{id}/data-export", web::get().to(export_user_data))
+            .route("/{id}/erase", web::post().to(erase_user)),
     );
 }
 
-async fn get_users(service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
-    let users = service.get_users().await?;
+async fn get_users(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    query: web::Query<UserFilter>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let users = service.list_users(&query, &claims).await?;
     Ok(HttpResponse::Ok().json(users))
 }
 
 async fn create_user(
+    req: HttpRequest,
     service: web::Data<UserService>,
     body: web::Json<CreateUserRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let user = service.create_user(body.into_inner()).await?;
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user = service.create_user(body.into_inner(), &claims).await?;
     Ok(HttpResponse::Ok().json(user))
 }
 
+async fn import_users(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    query: web::Query<ImportUsersQuery>,
+    body: String,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let report = service
+        .import_users(&body, query.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
 async fn get_user(
+    req: HttpRequest,
     service: web::Data<UserService>,
     path: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
-    let user = service.get_user(&path.into_inner()).await?;
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user = service.get_user(&path.into_inner(), &claims).await?;
     Ok(HttpResponse::Ok().json(user))
 }
 
@@ -45,20 +104,108 @@ async fn update_user(
     req: HttpRequest,
     service: web::Data<UserService>,
     path: web::Path<String>,
+    query: web::Query<UpdateUserQuery>,
     body: web::Json<UpdateUserRequest>,
 ) -> Result<HttpResponse, AppError> {
     let claims = req
         .extensions()
         .get::<Claims>()
         .cloned()
-        .ok_or_else(|| AppError::Unauthorized)?;
+        .ok_or(AppError::Unauthorized)?;
+
+    let user = service
+        .update_user(&path.into_inner(), body.into_inner(), query.force, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+async fn update_profile(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    path: web::Path<String>,
+    body: web::Json<UpdateProfileRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user = service
+        .update_profile(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+async fn set_avatar(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    path: web::Path<String>,
+    body: web::Json<SetAvatarRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
 
     let user = service
-        .update_user(&path.into_inner(), body.into_inner(), &claims)
+        .set_avatar(&path.into_inner(), body.into_inner(), &claims)
         .await?;
     Ok(HttpResponse::Ok().json(user))
 }
 
+async fn remove_avatar(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    service.remove_avatar(&path.into_inner(), &claims).await?;
+    Ok(HttpResponse::Ok().body("Avatar removed successfully"))
+}
+
+async fn add_availability(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    path: web::Path<String>,
+    body: web::Json<CreateAvailabilityRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let window = service
+        .add_availability(&path.into_inner(), body.into_inner(), &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(window))
+}
+
+async fn remove_availability(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+    let (id, window_id) = path.into_inner();
+
+    service
+        .remove_availability(&id, &window_id, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().body("Availability removed successfully"))
+}
+
 async fn delete_user(
     req: HttpRequest,
     service: web::Data<UserService>,
@@ -68,8 +215,47 @@ async fn delete_user(
         .extensions()
         .get::<Claims>()
         .cloned()
-        .ok_or_else(|| AppError::Unauthorized)?;
+        .ok_or(AppError::Unauthorized)?;
 
     service.delete_user(&path.into_inner(), &claims).await?;
     Ok(HttpResponse::Ok().body("User deleted successfully"))
 }
+
+async fn export_user_data(
+    req: HttpRequest,
+    service: web::Data<PrivacyService>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let id = path.into_inner();
+    let data = service.export_user_data(&id, &claims).await?;
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentDisposition::attachment(format!(
+            "circa-user-{}.json",
+            id
+        )))
+        .json(data))
+}
+
+async fn erase_user(
+    req: HttpRequest,
+    service: web::Data<PrivacyService>,
+    path: web::Path<String>,
+    query: web::Query<EraseQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user = service
+        .erase_user(&path.into_inner(), query.confirm, &claims)
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
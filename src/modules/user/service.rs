@@ -1,58 +1,501 @@
-use super::models::User;
+use super::models::{
+    Availability, CreateAvailabilityRequest, SetAvatarRequest, UpdateProfileRequest, User,
+    normalize_email, normalize_tags,
+};
 use super::repository::UserRepository;
 use crate::auth::models::Claims;
 use crate::error::AppError;
-use crate::user::models::{CreateUserRequest, UpdateUserRequest};
+use crate::file::models::ImageLinks;
+use crate::file::repository::FileRepository;
+use crate::file::signer::UrlSigner;
+use crate::logistics::repository::LogisticsRepository;
+use crate::user::models::{
+    CreateUserRequest, ImportReport, ImportRowResult, ImportRowStatus, ImportUsersQuery,
+    UpdateUserRequest, UserFilter, UserRole, UserStatus,
+};
+use std::collections::HashSet;
 
 pub struct UserService {
     repository: UserRepository,
+    logistics: LogisticsRepository,
+    files: FileRepository,
+    signer: UrlSigner,
 }
 
 impl UserService {
-    pub fn new(repository: UserRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: UserRepository,
+        logistics: LogisticsRepository,
+        files: FileRepository,
+        signer: UrlSigner,
+    ) -> Self {
+        Self {
+            repository,
+            logistics,
+            files,
+            signer,
+        }
     }
 
-    pub async fn get_users(&self) -> Result<Vec<User>, AppError> {
-        self.repository.find_all().await
+    pub async fn get_users(&self, claims: &Claims) -> Result<Vec<User>, AppError> {
+        self.repository.find_all(&claims.event).await
     }
 
-    pub async fn get_user(&self, id: &str) -> Result<User, AppError> {
-        let user = self.repository.find_by_id(id).await?;
-        user.ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    pub async fn list_users(
+        &self,
+        filter: &UserFilter,
+        claims: &Claims,
+    ) -> Result<Vec<User>, AppError> {
+        self.repository.find_filtered(&claims.event, filter).await
     }
 
-    pub async fn get_user_by_email(&self, email: &str) -> Result<User, AppError> {
-        let user = self.repository.find_by_email(email).await?;
+    pub async fn get_user(&self, id: &str, claims: &Claims) -> Result<User, AppError> {
+        let mut user = self
+            .repository
+            .find_by_id(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        user.profile = Some(self.repository.find_profile(id).await?);
+        user.avatar = self.find_avatar(id).await?;
+        Ok(user)
+    }
+
+    pub async fn get_user_by_email(&self, email: &str, claims: &Claims) -> Result<User, AppError> {
+        let user = self.repository.find_by_email(&claims.event, email).await?;
         user.ok_or_else(|| AppError::NotFound("User not found".to_string()))
     }
 
-    pub async fn create_user(&self, req: CreateUserRequest) -> Result<User, AppError> {
-        if req.email.is_empty() {
-            return Err(AppError::BadRequest("Email is required".to_string()));
+    // someone with an account from another event only joins this one, with the given role
+    pub async fn create_user(
+        &self,
+        req: CreateUserRequest,
+        claims: &Claims,
+    ) -> Result<User, AppError> {
+        if claims.role != "admin" && claims.role != "organizer" {
+            return Err(AppError::Forbidden);
+        }
+        if req.role == UserRole::Admin && claims.role != "admin" {
+            return Err(AppError::Forbidden);
         }
+        validate_create_request(&req)?;
 
-        self.repository.create(req).await
+        match self.repository.find_id_by_email(&req.email).await? {
+            Some(id) => {
+                if self
+                    .repository
+                    .find_by_id(&claims.event, &id)
+                    .await?
+                    .is_some()
+                {
+                    return Err(AppError::BadRequest(
+                        "User is already part of this event".to_string(),
+                    ));
+                }
+                self.repository
+                    .add_member(&claims.event, &id, req.role)
+                    .await
+            }
+            None => self.repository.create(&claims.event, req).await,
+        }
     }
 
+    pub async fn import_users(
+        &self,
+        csv: &str,
+        query: ImportUsersQuery,
+        claims: &Claims,
+    ) -> Result<ImportReport, AppError> {
+        if claims.role != "admin" && claims.role != "organizer" {
+            return Err(AppError::Forbidden);
+        }
+
+        let parsed = parse_import_csv(csv, &query)?;
+
+        let mut rows = Vec::with_capacity(parsed.len());
+        let mut requests = Vec::with_capacity(parsed.len());
+        let mut seen = HashSet::new();
+
+        for (line, result) in parsed {
+            let (email, mut errors, req) = match result {
+                Ok(req) => (req.email.clone(), Vec::new(), Some(req)),
+                Err((email, errors)) => (email, errors, None),
+            };
+
+            if let Some(req) = &req {
+                if let Err(err) = validate_create_request(req) {
+                    errors.push(error_message(err));
+                }
+                if req.role == UserRole::Admin && claims.role != "admin" {
+                    errors.push("Only admins can import admins".to_string());
+                }
+            }
+
+            let mut status = if errors.is_empty() {
+                ImportRowStatus::Valid
+            } else {
+                ImportRowStatus::Invalid
+            };
+
+            if !email.is_empty() && !seen.insert(email.clone()) {
+                errors.push("Email appears more than once in the file".to_string());
+                status = ImportRowStatus::Duplicate;
+            }
+
+            rows.push(ImportRowResult {
+                line,
+                email,
+                status,
+                errors,
+                user_id: None,
+            });
+            requests.push(req);
+        }
+
+        let candidates: Vec<String> = rows
+            .iter()
+            .filter(|r| r.status == ImportRowStatus::Valid)
+            .map(|r| r.email.clone())
+            .collect();
+
+        if !candidates.is_empty() {
+            let existing: HashSet<String> = self
+                .repository
+                .find_existing_emails(&claims.event, &candidates)
+                .await?
+                .into_iter()
+                .collect();
+
+            for row in rows.iter_mut().filter(|r| existing.contains(&r.email)) {
+                row.status = ImportRowStatus::Duplicate;
+                row.errors
+                    .push("A user with this email is already part of this event".to_string());
+            }
+        }
+
+        let failed = rows
+            .iter()
+            .filter(|r| r.status != ImportRowStatus::Valid)
+            .count();
+
+        let mut report = ImportReport {
+            dry_run: query.dry_run,
+            committed: false,
+            total: rows.len(),
+            created: 0,
+            failed,
+            rows,
+        };
+
+        if query.dry_run || (failed > 0 && !query.skip_invalid) {
+            return Ok(report);
+        }
+
+        let to_create: Vec<(usize, CreateUserRequest)> = report
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.status == ImportRowStatus::Valid)
+            .filter_map(|(i, _)| requests[i].take().map(|req| (i, req)))
+            .collect();
+
+        if to_create.is_empty() {
+            return Ok(report);
+        }
+
+        let (indices, reqs): (Vec<usize>, Vec<CreateUserRequest>) = to_create.into_iter().unzip();
+        let created = self.repository.create_many(&claims.event, reqs).await?;
+
+        for (i, user) in indices.into_iter().zip(created) {
+            let row = &mut report.rows[i];
+            row.status = ImportRowStatus::Created;
+            row.user_id = Some(user.id);
+        }
+
+        report.created = report
+            .rows
+            .iter()
+            .filter(|r| r.status == ImportRowStatus::Created)
+            .count();
+        report.committed = true;
+
+        Ok(report)
+    }
+
+    // deactivating someone who still holds equipment lists it first, force goes ahead anyway
     pub async fn update_user(
         &self,
         id: &str,
         req: UpdateUserRequest,
+        force: bool,
         claims: &Claims,
     ) -> Result<User, AppError> {
-        if claims.sub != id && claims.role != "admin" && claims.role != "organizer" {
+        require_self_or_manager(id, claims)?;
+
+        // people can edit their own details, but only managers change roles and status
+        let is_manager = claims.role == "admin" || claims.role == "organizer";
+        if !is_manager && (req.role.is_some() || req.status.is_some()) {
+            return Err(AppError::Forbidden);
+        }
+        if req.role == Some(UserRole::Admin) && claims.role != "admin" {
+            return Err(AppError::Forbidden);
+        }
+        // nor do they touch an admin's account, just as they can't make one
+        if is_manager
+            && claims.role != "admin"
+            && claims.sub != id
+            && self
+                .repository
+                .find_by_id(&claims.event, id)
+                .await?
+                .is_some_and(|user| user.role == UserRole::Admin)
+        {
             return Err(AppError::Forbidden);
         }
+        if let Some(email) = &req.email
+            && let Some(owner) = self.repository.find_id_by_email(email).await?
+            && owner != id
+        {
+            return Err(AppError::BadRequest("Email already in use".to_string()));
+        }
+
+        if req.status == Some(UserStatus::Inactive) && !force {
+            let held = self.logistics.find_held_items(&claims.event, id).await?;
+            if !held.is_empty() {
+                return Err(AppError::Conflict(
+                    format!(
+                        "User still holds {} checked out item(s), pass force=true to deactivate anyway",
+                        held.len()
+                    ),
+                    serde_json::to_value(&held).map_err(|_| AppError::InternalServerError)?,
+                ));
+            }
+        }
+
+        self.repository.update(&claims.event, id, req).await
+    }
+
+    pub async fn update_profile(
+        &self,
+        id: &str,
+        req: UpdateProfileRequest,
+        claims: &Claims,
+    ) -> Result<User, AppError> {
+        require_self_or_manager(id, claims)?;
+
+        let skills = req.skills.as_deref().map(normalize_tags);
+        let preferred_areas = req.preferred_areas.as_deref().map(normalize_tags);
+
+        self.ensure_exists(id, claims).await?;
+        self.repository
+            .update_profile(id, skills, preferred_areas)
+            .await?;
 
-        self.repository.update(id, req).await
+        self.get_user(id, claims).await
+    }
+
+    pub async fn add_availability(
+        &self,
+        id: &str,
+        req: CreateAvailabilityRequest,
+        claims: &Claims,
+    ) -> Result<Availability, AppError> {
+        require_self_or_manager(id, claims)?;
+
+        if req.ends_at <= req.starts_at {
+            return Err(AppError::BadRequest(
+                "Availability must end after it starts".to_string(),
+            ));
+        }
+
+        self.ensure_exists(id, claims).await?;
+        self.repository.create_availability(id, req).await
+    }
+
+    pub async fn remove_availability(
+        &self,
+        id: &str,
+        window_id: &str,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        require_self_or_manager(id, claims)?;
+
+        self.repository.delete_availability(id, window_id).await
+    }
+
+    // any image uploaded to the event, the variants show up once the worker is done with it
+    pub async fn set_avatar(
+        &self,
+        id: &str,
+        req: SetAvatarRequest,
+        claims: &Claims,
+    ) -> Result<User, AppError> {
+        require_self_or_manager(id, claims)?;
+
+        self.ensure_exists(id, claims).await?;
+        let file = self
+            .files
+            .find_file(&claims.event, &req.file_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+        if file.image_status.is_none() {
+            return Err(AppError::BadRequest("Avatars must be images".to_string()));
+        }
+
+        self.repository.set_avatar(id, &file.id).await?;
+        self.get_user(id, claims).await
+    }
+
+    pub async fn remove_avatar(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        require_self_or_manager(id, claims)?;
+
+        self.ensure_exists(id, claims).await?;
+        self.repository.delete_avatar(id).await
     }
 
     pub async fn delete_user(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
         if claims.sub != id && claims.role != "admin" {
             return Err(AppError::Forbidden);
         }
+        let held = self.logistics.find_held_items(&claims.event, id).await?;
+        if !held.is_empty() {
+            return Err(AppError::Conflict(
+                format!(
+                    "User still holds {} checked out item(s), check them in first",
+                    held.len()
+                ),
+                serde_json::to_value(&held).map_err(|_| AppError::InternalServerError)?,
+            ));
+        }
+
+        self.repository.delete(&claims.event, id).await
+    }
+
+    // the avatar file can be gone by now, which just means no avatar
+    async fn find_avatar(&self, id: &str) -> Result<Option<ImageLinks>, AppError> {
+        let Some(file_id) = self.repository.find_avatar(id).await? else {
+            return Ok(None);
+        };
 
-        self.repository.delete(id).await
+        Ok(self
+            .files
+            .find_stored(&file_id)
+            .await?
+            .and_then(|(file, _)| self.signer.image_links(&file)))
     }
+
+    async fn ensure_exists(&self, id: &str, claims: &Claims) -> Result<(), AppError> {
+        self.repository
+            .find_by_id(&claims.event, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        Ok(())
+    }
+}
+
+// people manage their own profile, organizers and admins manage everyone's
+fn require_self_or_manager(id: &str, claims: &Claims) -> Result<(), AppError> {
+    if claims.sub != id && claims.role != "admin" && claims.role != "organizer" {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(())
+}
+
+fn validate_create_request(req: &CreateUserRequest) -> Result<(), AppError> {
+    if req.email.trim().is_empty() {
+        return Err(AppError::BadRequest("Email is required".to_string()));
+    }
+
+    Ok(())
+}
+
+fn error_message(err: AppError) -> String {
+    match err {
+        AppError::BadRequest(msg) | AppError::NotFound(msg) => msg,
+        other => other.to_string(),
+    }
+}
+
+type ParsedRow = Result<CreateUserRequest, (String, Vec<String>)>;
+
+// turns the csv into create requests, rows that can't even be read come back with their errors
+fn parse_import_csv(
+    csv: &str,
+    query: &ImportUsersQuery,
+) -> Result<Vec<(usize, ParsedRow)>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(csv.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|err| AppError::BadRequest(format!("Invalid CSV header: {}", err)))?
+        .clone();
+
+    let column = |mapped: &Option<String>, default: &str| -> Option<usize> {
+        let name = mapped.as_deref().unwrap_or(default);
+        headers.iter().position(|h| h.eq_ignore_ascii_case(name))
+    };
+
+    let required = |mapped: &Option<String>, default: &str| -> Result<usize, AppError> {
+        column(mapped, default).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Missing column '{}'",
+                mapped.as_deref().unwrap_or(default)
+            ))
+        })
+    };
+
+    let name_idx = required(&query.name_column, "name")?;
+    let surname_idx = required(&query.surname_column, "surname")?;
+    let email_idx = required(&query.email_column, "email")?;
+    let phone_idx = column(&query.phone_column, "phone");
+    let role_idx = column(&query.role_column, "role");
+
+    let default_role = query.default_role.clone().unwrap_or(UserRole::Volunteer);
+    let mut rows = Vec::new();
+
+    for (i, record) in reader.records().enumerate() {
+        // header is line 1, so the first record sits on line 2
+        let line = record
+            .as_ref()
+            .ok()
+            .and_then(|r| r.position())
+            .map(|p| p.line() as usize)
+            .unwrap_or(i + 2);
+
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                rows.push((line, Err((String::new(), vec![err.to_string()]))));
+                continue;
+            }
+        };
+
+        let field = |idx: usize| record.get(idx).unwrap_or("").to_string();
+        let email = normalize_email(&field(email_idx));
+
+        let role = match role_idx.map(field).filter(|r| !r.is_empty()) {
+            Some(role) => role.parse::<UserRole>(),
+            None => Ok(default_role.clone()),
+        };
+
+        let row = match role {
+            Ok(role) => Ok(CreateUserRequest {
+                name: field(name_idx),
+                surname: field(surname_idx),
+                email,
+                phone: phone_idx.map(field).unwrap_or_default(),
+                role,
+            }),
+            Err(err) => Err((email, vec![err])),
+        };
+
+        rows.push((line, row));
+    }
+
+    Ok(rows)
 }
@@ -0,0 +1,81 @@
+use chrono::Utc;
+use circa_backend::audit::{
+    entity::Model,
+    models::{AuditQuery, NewAuditEntry},
+    repository::AuditRepository,
+    service::AuditService,
+};
+use circa_backend::auth::models::Claims;
+use sea_orm::{DatabaseBackend, MockDatabase};
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_entry(id: &str) -> Model {
+    Model {
+        id: id.to_string(),
+        event_id: Some("event-1".to_string()),
+        actor_id: "admin-id".to_string(),
+        action: "export.stream".to_string(),
+        target_type: "users".to_string(),
+        target_id: None,
+        details: Some(r#"{"rows":2}"#.to_string()),
+        created_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_record_entry() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_entry("1")]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+    let service = AuditService::new(AuditRepository::new(db));
+
+    let result = service
+        .record(NewAuditEntry {
+            event_id: Some("event-1".to_string()),
+            actor_id: "admin-id".to_string(),
+            action: "export.stream".to_string(),
+            target_type: "users".to_string(),
+            target_id: None,
+            details: Some(serde_json::json!({ "rows": 2 })),
+        })
+        .await;
+
+    assert!(result.is_ok());
+    let entry = result.unwrap();
+    assert_eq!(entry.action, "export.stream");
+    assert_eq!(entry.details, Some(serde_json::json!({ "rows": 2 })));
+}
+
+#[tokio::test]
+async fn test_get_entries_as_admin() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_entry("1"), make_entry("2")]])
+        .into_connection();
+    let service = AuditService::new(AuditRepository::new(db));
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service.get_entries(&AuditQuery::default(), &claims).await;
+    assert_eq!(result.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_get_entries_forbidden_for_organizer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = AuditService::new(AuditRepository::new(db));
+    let claims = make_claims("org-id", "organizer");
+
+    let result = service.get_entries(&AuditQuery::default(), &claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
@@ -1,10 +1,16 @@
 use actix_web::{App, http::StatusCode, test, web};
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
 use circa_backend::auth;
+use circa_backend::auth::models::Claims;
 use circa_backend::auth::service::generate_jwt;
-use circa_backend::modules::user::entity::{Model, Role, Status};
-use circa_backend::user::repository::UserRepository;
-use circa_backend::user::service::UserService;
-use sea_orm::{DatabaseBackend, MockDatabase};
+use circa_backend::event::entity::member;
+use circa_backend::event::{repository::EventRepository, service::EventService};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::user::entity::Role;
+use jsonwebtoken::{DecodingKey, Validation, decode};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
 
 const JWT_SECRET: &str = "test_secret";
 
@@ -12,35 +18,74 @@ fn make_jwt_secret() -> web::Data<String> {
     web::Data::new(JWT_SECRET.to_string())
 }
 
-fn setup_user_service_with_user() -> web::Data<UserService> {
+fn make_member(event_id: &str, role: Role, days_ago: i64) -> member::Model {
+    member::Model {
+        event_id: event_id.to_string(),
+        user_id: "1".to_string(),
+        role,
+        joined_at: chrono::Utc::now() - chrono::Duration::days(days_ago),
+    }
+}
+
+// volunteer at the newest event, admin at an older one
+fn setup_event_service_with_user() -> web::Data<EventService> {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_query_results([vec![Model {
-            id: "1".to_string(),
-            name: "John".to_string(),
-            surname: "Doe".to_string(),
-            email: "john@example.com".to_string(),
-            phone: "123".to_string(),
-            role: Role::Admin,
-            status: Status::Active,
-        }]])
+        .append_query_results([vec![
+            make_member("event-2", Role::Volunteer, 1),
+            make_member("event-1", Role::Admin, 30),
+        ]])
         .into_connection();
 
-    web::Data::new(UserService::new(UserRepository::new(db)))
+    make_event_service(db)
 }
 
-fn setup_user_service_no_user() -> web::Data<UserService> {
+fn setup_event_service_no_user() -> web::Data<EventService> {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_query_results([Vec::<Model>::new()])
+        .append_query_results([Vec::<member::Model>::new()])
         .into_connection();
 
-    web::Data::new(UserService::new(UserRepository::new(db)))
+    make_event_service(db)
+}
+
+fn make_event_service(db: DatabaseConnection) -> web::Data<EventService> {
+    let db = Arc::new(db);
+    web::Data::new(EventService::new(
+        EventRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        TeamRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+    ))
+}
+
+async fn login_claims(body: serde_json::Value) -> Claims {
+    let app = test::init_service(
+        App::new()
+            .app_data(setup_event_service_with_user())
+            .app_data(make_jwt_secret())
+            .configure(auth::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/auth/login")
+        .set_json(body)
+        .to_request();
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    decode::<Claims>(
+        body["token"].as_str().unwrap(),
+        &DecodingKey::from_secret(JWT_SECRET.as_bytes()),
+        &Validation::default(),
+    )
+    .unwrap()
+    .claims
 }
 
 #[actix_web::test]
 async fn test_login_success() {
     let app = test::init_service(
         App::new()
-            .app_data(setup_user_service_with_user())
+            .app_data(setup_event_service_with_user())
             .app_data(make_jwt_secret())
             .configure(auth::routes::config),
     )
@@ -55,11 +100,38 @@ async fn test_login_success() {
     assert_eq!(resp.status(), StatusCode::OK);
 }
 
+#[actix_web::test]
+async fn test_login_token_subject_is_user_id() {
+    let claims = login_claims(serde_json::json!({ "email": "john@example.com" })).await;
+
+    assert_eq!(claims.sub, "1");
+}
+
+#[actix_web::test]
+async fn test_login_defaults_to_latest_event() {
+    let claims = login_claims(serde_json::json!({ "email": "john@example.com" })).await;
+
+    assert_eq!(claims.event, "event-2");
+    assert_eq!(claims.role, "volunteer");
+}
+
+#[actix_web::test]
+async fn test_login_to_chosen_event_carries_role_there() {
+    let claims = login_claims(serde_json::json!({
+        "email": "john@example.com",
+        "event_id": "event-1",
+    }))
+    .await;
+
+    assert_eq!(claims.event, "event-1");
+    assert_eq!(claims.role, "admin");
+}
+
 #[actix_web::test]
 async fn test_login_user_not_found() {
     let app = test::init_service(
         App::new()
-            .app_data(setup_user_service_no_user())
+            .app_data(setup_event_service_no_user())
             .app_data(make_jwt_secret())
             .configure(auth::routes::config),
     )
@@ -76,7 +148,7 @@ async fn test_login_user_not_found() {
 
 #[actix_web::test]
 async fn test_get_current_user_with_valid_token() {
-    let token_response = generate_jwt("john@example.com", "admin", JWT_SECRET)
+    let token_response = generate_jwt("john@example.com", "event-1", "admin", JWT_SECRET)
         .await
         .unwrap();
 
@@ -130,7 +202,7 @@ async fn test_get_current_user_with_invalid_token() {
 
 #[actix_web::test]
 async fn test_get_current_user_with_wrong_secret_token() {
-    let token_response = generate_jwt("john@example.com", "admin", "wrong_secret")
+    let token_response = generate_jwt("john@example.com", "event-1", "admin", "wrong_secret")
         .await
         .unwrap();
 
@@ -149,3 +221,26 @@ async fn test_get_current_user_with_wrong_secret_token() {
     let resp = test::call_service(&app, req).await;
     assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
 }
+
+#[actix_web::test]
+async fn test_token_for_another_event_is_rejected() {
+    let token_response = generate_jwt("1", "event-1", "admin", JWT_SECRET)
+        .await
+        .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_jwt_secret())
+            .configure(auth::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/me")
+        .insert_header(("Authorization", format!("Bearer {}", token_response.token)))
+        .insert_header(("X-Event-Id", "event-2"))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
@@ -5,7 +5,7 @@ use jsonwebtoken::{DecodingKey, Validation, decode};
 #[tokio::test]
 async fn test_generate_jwt_success() {
     let secret = "test_secret";
-    let result = generate_jwt("user@example.com", "admin", secret).await;
+    let result = generate_jwt("user@example.com", "event-1", "admin", secret).await;
 
     assert!(result.is_ok());
     let token_response = result.unwrap();
@@ -18,7 +18,7 @@ async fn test_generated_jwt_contains_correct_claims() {
     let email = "dave@example.com";
     let role = "organizer";
 
-    let token_response = generate_jwt(email, role, secret).await.unwrap();
+    let token_response = generate_jwt(email, "event-1", role, secret).await.unwrap();
 
     let token_data = decode::<Claims>(
         &token_response.token,
@@ -28,6 +28,7 @@ async fn test_generated_jwt_contains_correct_claims() {
     .unwrap();
 
     assert_eq!(token_data.claims.sub, email);
+    assert_eq!(token_data.claims.event, "event-1");
     assert_eq!(token_data.claims.role, role);
     assert!(token_data.claims.exp > 0);
 }
@@ -35,7 +36,7 @@ async fn test_generated_jwt_contains_correct_claims() {
 #[tokio::test]
 async fn test_generated_jwt_invalid_with_wrong_secret() {
     let secret = "correct_secret";
-    let token_response = generate_jwt("user@example.com", "admin", secret)
+    let token_response = generate_jwt("user@example.com", "event-1", "admin", secret)
         .await
         .unwrap();
 
@@ -53,7 +54,7 @@ async fn test_generate_jwt_different_roles() {
     let secret = "test_secret";
 
     for role in &["admin", "organizer", "staff", "volunteer"] {
-        let result = generate_jwt("user@example.com", role, secret).await;
+        let result = generate_jwt("user@example.com", "event-1", role, secret).await;
         assert!(result.is_ok());
 
         let token_data = decode::<Claims>(
@@ -0,0 +1,2 @@
+mod models_test;
+mod service_test;
@@ -0,0 +1,41 @@
+use chrono::{TimeZone, Utc};
+use circa_backend::backup::models::BackupKind;
+use circa_backend::backup::service::{backup_file_name, is_valid_backup_name};
+
+#[test]
+fn test_backup_file_name() {
+    let at = Utc.with_ymd_and_hms(2026, 7, 4, 18, 30, 5).unwrap();
+
+    assert_eq!(
+        backup_file_name(at, BackupKind::Scheduled),
+        "circa-20260704T183005000Z-scheduled.db"
+    );
+}
+
+#[test]
+fn test_kind_from_file_name() {
+    assert_eq!(
+        BackupKind::from_file_name("circa-20260704T183005000Z-manual.db"),
+        Some(BackupKind::Manual)
+    );
+    assert_eq!(
+        BackupKind::from_file_name("circa-20260704T183005000Z-pre_restore.db"),
+        Some(BackupKind::PreRestore)
+    );
+    assert_eq!(
+        BackupKind::from_file_name("circa-20260704T183005000Z.db"),
+        None
+    );
+    assert_eq!(BackupKind::from_file_name("notes.txt"), None);
+}
+
+#[test]
+fn test_valid_backup_names() {
+    assert!(is_valid_backup_name("circa-20260704T183005000Z-manual.db"));
+    assert!(!is_valid_backup_name(
+        "../circa-20260704T183005000Z-manual.db"
+    ));
+    assert!(!is_valid_backup_name("data.db"));
+    assert!(!is_valid_backup_name("circa-..-manual.db"));
+    assert!(!is_valid_backup_name("circa-x/y-manual.db"));
+}
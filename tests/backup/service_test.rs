@@ -0,0 +1,129 @@
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::backup::{repository::BackupRepository, service::BackupService};
+use sea_orm::{DatabaseBackend, MockDatabase};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(dir: &Path) -> BackupService {
+    let db = Arc::new(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    BackupService::new(
+        BackupRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+        dir,
+        vec!["admin-id".to_string()],
+    )
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("circa-backup-test-{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_get_backups_forbidden_for_organizer() {
+    let service = make_service(&temp_dir("forbidden"));
+    let claims = make_claims("org-id", "organizer");
+
+    let result = service.get_backups(&claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
+
+// being admin of some event isn't enough, backups hold all of them
+#[tokio::test]
+async fn test_get_backups_forbidden_for_event_admin() {
+    let service = make_service(&temp_dir("event-admin"));
+    let claims = make_claims("other-admin-id", "admin");
+
+    let result = service.get_backups(&claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
+
+#[tokio::test]
+async fn test_get_backups_lists_newest_first() {
+    let dir = temp_dir("list");
+    std::fs::write(dir.join("circa-20260701T000000000Z-manual.db"), b"a").unwrap();
+    std::fs::write(dir.join("circa-20260702T000000000Z-scheduled.db"), b"bb").unwrap();
+    std::fs::write(dir.join("unrelated.txt"), b"c").unwrap();
+
+    let service = make_service(&dir);
+    let claims = make_claims("admin-id", "admin");
+
+    let backups = service.get_backups(&claims).await.unwrap();
+    assert_eq!(backups.len(), 2);
+    assert_eq!(backups[0].name, "circa-20260702T000000000Z-scheduled.db");
+    assert_eq!(backups[0].size_bytes, 2);
+}
+
+#[tokio::test]
+async fn test_restore_requires_confirmation() {
+    let service = make_service(&temp_dir("confirm"));
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service
+        .restore_backup("circa-20260701T000000000Z-manual.db", false, &claims)
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Restoring replaces all data, pass confirm=true to continue"
+    );
+}
+
+#[tokio::test]
+async fn test_restore_rejects_invalid_names() {
+    let service = make_service(&temp_dir("names"));
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service.restore_backup("../data.db", true, &claims).await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Invalid backup name"
+    );
+}
+
+#[tokio::test]
+async fn test_restore_missing_backup() {
+    let service = make_service(&temp_dir("missing"));
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service
+        .restore_backup("circa-20260701T000000000Z-manual.db", true, &claims)
+        .await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Not found: Backup not found"
+    );
+}
+
+#[tokio::test]
+async fn test_prune_keeps_manual_and_newest_scheduled() {
+    let dir = temp_dir("prune");
+    for name in [
+        "circa-20260701T000000000Z-scheduled.db",
+        "circa-20260702T000000000Z-scheduled.db",
+        "circa-20260703T000000000Z-scheduled.db",
+        "circa-20260601T000000000Z-manual.db",
+    ] {
+        std::fs::write(dir.join(name), b"x").unwrap();
+    }
+
+    let service = make_service(&dir);
+    let removed = service.prune(2).await.unwrap();
+
+    assert_eq!(removed, 1);
+    assert!(!dir.join("circa-20260701T000000000Z-scheduled.db").exists());
+    assert!(dir.join("circa-20260703T000000000Z-scheduled.db").exists());
+    assert!(dir.join("circa-20260601T000000000Z-manual.db").exists());
+}
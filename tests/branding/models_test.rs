@@ -0,0 +1,77 @@
+use chrono::Utc;
+use circa_backend::branding::entity::{Model, Status};
+use circa_backend::branding::models::{
+    BrandingConfig, BrandingStatus, BrandingVersion, PublicBranding, is_hex_color,
+};
+use std::collections::BTreeMap;
+
+fn tokens(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_hex_colors() {
+    assert!(is_hex_color("#fff"));
+    assert!(is_hex_color("#FF6600"));
+    assert!(is_hex_color("#ff660080"));
+    assert!(!is_hex_color("ff6600"));
+    assert!(!is_hex_color("#ff66"));
+    assert!(!is_hex_color("#gggggg"));
+}
+
+#[test]
+fn test_config_problems() {
+    let config = BrandingConfig {
+        colors: tokens(&[("primary", "#ff6600"), ("Accent", "red")]),
+        typography: tokens(&[("heading_font", "Inter"), ("body_font", " ")]),
+        logos: tokens(&[("primary", "/files/logo.png"), ("dark", "ftp://logo")]),
+        social_images: tokens(&[("og_image", "https://cdn.example.com/og.png")]),
+    };
+
+    let problems = config.problems();
+
+    assert_eq!(problems.len(), 4);
+    assert!(problems.iter().any(|p| p.contains("'Accent'")));
+    assert!(
+        problems
+            .iter()
+            .any(|p| p == "typography.body_font is empty")
+    );
+    assert!(
+        problems
+            .iter()
+            .any(|p| p.contains("'red' isn't a hex color"))
+    );
+    assert!(problems.iter().any(|p| p.starts_with("logos.dark")));
+    assert!(BrandingConfig::default().problems().is_empty());
+}
+
+#[test]
+fn test_public_branding_flattens_config() {
+    let now = Utc::now();
+    let version = BrandingVersion::from_model(Model {
+        id: "b1".to_string(),
+        event_id: "event-1".to_string(),
+        version: 3,
+        status: Status::Published,
+        config: r##"{"colors":{"primary":"#ff6600"}}"##.to_string(),
+        note: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        published_by: Some("1".to_string()),
+        published_at: Some(now),
+    })
+    .unwrap();
+    assert_eq!(version.status, BrandingStatus::Published);
+
+    let body = serde_json::to_value(PublicBranding::from(version)).unwrap();
+
+    assert_eq!(body["version"], 3);
+    assert_eq!(body["colors"]["primary"], "#ff6600");
+    assert_eq!(body["typography"], serde_json::json!({}));
+    assert!(body.get("published_by").is_none());
+}
@@ -0,0 +1,115 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::branding;
+use circa_backend::branding::entity::{Model, Status, settings};
+use circa_backend::branding::repository::BrandingRepository;
+use circa_backend::branding::service::BrandingService;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<BrandingService> {
+    let db = Arc::new(db);
+    web::Data::new(BrandingService::new(
+        BrandingRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+    ))
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+#[actix_web::test]
+async fn test_public_branding_needs_no_token() {
+    let now = Utc::now();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![Model {
+            id: "b1".to_string(),
+            event_id: "event-1".to_string(),
+            version: 1,
+            status: Status::Published,
+            config: r##"{"colors":{"primary":"#ff6600"}}"##.to_string(),
+            note: "".to_string(),
+            created_by: "1".to_string(),
+            created_at: now,
+            updated_at: now,
+            published_by: Some("1".to_string()),
+            published_at: Some(now),
+        }]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(branding::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/branding/public/event-1")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().contains_key("cache-control"));
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["colors"]["primary"], "#ff6600");
+}
+
+#[actix_web::test]
+async fn test_public_branding_not_published() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(branding::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/branding/public/event-1")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_volunteer_forbidden_without_access() {
+    let token = make_token("volunteer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<settings::Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(branding::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/branding")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
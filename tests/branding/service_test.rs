@@ -0,0 +1,253 @@
+use chrono::Utc;
+use circa_backend::audit::entity::Model as AuditModel;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::branding::entity::{Model, Status, settings};
+use circa_backend::branding::models::{
+    BrandingConfig, CreateDraftRequest, UpdateDraftRequest, UpdateSettingsRequest,
+};
+use circa_backend::branding::repository::BrandingRepository;
+use circa_backend::branding::service::BrandingService;
+use circa_backend::error::AppError;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: Arc<DatabaseConnection>) -> BrandingService {
+    BrandingService::new(
+        BrandingRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+    )
+}
+
+fn make_version(id: &str, version: i32, status: Status) -> Model {
+    let now = Utc::now();
+    let published = status == Status::Published;
+    Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        version,
+        status,
+        config: r##"{"colors":{"primary":"#ff6600"}}"##.to_string(),
+        note: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        published_by: published.then(|| "1".to_string()),
+        published_at: published.then_some(now),
+    }
+}
+
+fn make_settings(volunteer_access: bool) -> settings::Model {
+    settings::Model {
+        event_id: "event-1".to_string(),
+        volunteer_access,
+        updated_by: "1".to_string(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn make_audit_entry() -> AuditModel {
+    AuditModel {
+        id: "a".to_string(),
+        event_id: Some("event-1".to_string()),
+        actor_id: "1".to_string(),
+        action: "branding.publish".to_string(),
+        target_type: "branding_version".to_string(),
+        target_id: Some("b2".to_string()),
+        details: None,
+        created_at: Utc::now(),
+    }
+}
+
+fn exec_ok() -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+#[tokio::test]
+async fn test_volunteers_kept_out_by_default() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<settings::Model>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service.get_current(&make_claims("3", "volunteer")).await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_volunteers_see_branding_when_allowed() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_settings(true)]])
+        .append_query_results([vec![make_version("b1", 1, Status::Published)]])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let current = service
+        .get_current(&make_claims("3", "volunteer"))
+        .await
+        .unwrap();
+
+    assert_eq!(current.version, 1);
+    assert_eq!(current.config.colors["primary"], "#ff6600");
+}
+
+#[tokio::test]
+async fn test_staff_cannot_create_draft() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .create_draft(CreateDraftRequest::default(), &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_new_draft_starts_from_published_version() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_version("b2", 2, Status::Published)]])
+            .append_query_results([vec![make_version("b2", 2, Status::Published)]])
+            .append_query_results([vec![make_version("b3", 3, Status::Draft)]])
+            .append_exec_results([exec_ok()])
+            .into_connection(),
+    );
+    let service = make_service(db.clone());
+
+    let draft = service
+        .create_draft(
+            CreateDraftRequest {
+                config: None,
+                note: " Autumn refresh ".to_string(),
+            },
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(draft.version, 3);
+
+    drop(service);
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains("Int(Some(3))"));
+    assert!(log.contains("#ff6600"));
+    assert!(log.contains("\"Autumn refresh\""));
+}
+
+#[tokio::test]
+async fn test_create_draft_rejects_bad_colors() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .create_draft(
+            CreateDraftRequest {
+                config: Some(BrandingConfig {
+                    colors: BTreeMap::from([("primary".to_string(), "orange".to_string())]),
+                    ..Default::default()
+                }),
+                note: "".to_string(),
+            },
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    match result {
+        Err(AppError::BadRequest(message)) => {
+            assert_eq!(message, "colors.primary: 'orange' isn't a hex color")
+        }
+        other => panic!("expected bad request, got {:?}", other.map(|v| v.id)),
+    }
+}
+
+#[tokio::test]
+async fn test_published_versions_cannot_be_edited() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_version("b1", 1, Status::Published)]])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .update_draft(
+            "b1",
+            UpdateDraftRequest {
+                note: Some("tweak".to_string()),
+                ..Default::default()
+            },
+            &make_claims("1", "organizer"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_publish_records_audit_entry() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_version("b2", 2, Status::Draft)]])
+            .append_query_results([vec![make_version("b1", 1, Status::Published)]])
+            .append_query_results([vec![make_version("b2", 2, Status::Draft)]])
+            .append_query_results([vec![make_version("b2", 2, Status::Published)]])
+            .append_query_results([vec![make_audit_entry()]])
+            .append_exec_results([exec_ok(), exec_ok()])
+            .into_connection(),
+    );
+    let service = make_service(db.clone());
+
+    let published = service
+        .publish("b2", &make_claims("1", "admin"))
+        .await
+        .unwrap();
+    assert!(!published.is_draft());
+
+    drop(service);
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains("branding.publish"));
+    assert!(log.contains("previous"));
+}
+
+#[tokio::test]
+async fn test_published_versions_cannot_be_deleted() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_version("b1", 1, Status::Published)]])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .delete_draft("b1", &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_staff_cannot_change_settings() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .update_settings(
+            UpdateSettingsRequest {
+                volunteer_access: true,
+            },
+            &make_claims("2", "staff"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
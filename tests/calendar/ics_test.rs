@@ -0,0 +1,48 @@
+use chrono::{TimeZone, Utc};
+use circa_backend::calendar::ics::render;
+use circa_backend::calendar::models::CalendarEvent;
+
+fn make_event(summary: &str) -> CalendarEvent {
+    CalendarEvent {
+        uid: "shift-s1@circa".to_string(),
+        summary: summary.to_string(),
+        location: Some("bar".to_string()),
+        description: None,
+        starts_at: Utc.with_ymd_and_hms(2030, 6, 1, 8, 0, 0).unwrap(),
+        ends_at: Some(Utc.with_ymd_and_hms(2030, 6, 1, 12, 30, 0).unwrap()),
+        updated_at: Utc.with_ymd_and_hms(2030, 5, 1, 9, 0, 0).unwrap(),
+        category: "Shift".to_string(),
+    }
+}
+
+#[test]
+fn test_render_event() {
+    let ics = render("Circa", &[make_event("Bar")]);
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains("UID:shift-s1@circa\r\n"));
+    assert!(ics.contains("DTSTART:20300601T080000Z\r\n"));
+    assert!(ics.contains("DTEND:20300601T123000Z\r\n"));
+    assert!(ics.contains("DTSTAMP:20300501T090000Z\r\n"));
+    assert!(ics.contains("LOCATION:bar\r\n"));
+    assert!(!ics.contains("DESCRIPTION"));
+}
+
+#[test]
+fn test_render_escapes_text() {
+    let ics = render("Circa", &[make_event("Bar; doors, stage\nback\\side")]);
+
+    assert!(ics.contains("SUMMARY:Bar\\; doors\\, stage\\nback\\\\side\r\n"));
+}
+
+#[test]
+fn test_render_folds_long_lines() {
+    let ics = render("Circa", &[make_event(&"ż".repeat(60))]);
+
+    for line in ics.split("\r\n") {
+        assert!(line.len() <= 75, "line too long: {}", line);
+    }
+    let unfolded = ics.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!("SUMMARY:{}\r\n", "ż".repeat(60))));
+}
@@ -0,0 +1,89 @@
+use chrono::Utc;
+use circa_backend::calendar::entity::{Kind, Model};
+use circa_backend::calendar::models::{CalendarEvent, CalendarFeed, FeedKind};
+use circa_backend::planner::entity::{shift, task};
+use circa_backend::planner::models::{Shift, Task, TaskStatus};
+
+fn make_task(due_at: Option<chrono::DateTime<Utc>>) -> Task {
+    let now = Utc::now();
+    let model = task::Model {
+        id: "t1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Order ice".to_string(),
+        description: "".to_string(),
+        priority: task::Priority::Medium,
+        status: task::Status::Todo,
+        due_at,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+        completed_by: None,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    Task::from_model(model, vec![])
+}
+
+#[test]
+fn test_feed_url_from_token() {
+    let feed: CalendarFeed = Model {
+        event_id: "event-1".to_string(),
+        user_id: "1".to_string(),
+        kind: Kind::Personal,
+        token: "abc".to_string(),
+        created_at: Utc::now(),
+    }
+    .into();
+
+    assert_eq!(feed.kind, FeedKind::Personal);
+    assert_eq!(feed.url, "/calendar/abc.ics");
+}
+
+#[test]
+fn test_shift_event_has_stable_uid() {
+    let now = Utc::now();
+    let model = shift::Model {
+        id: "s1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Bar".to_string(),
+        location: "bar".to_string(),
+        starts_at: now,
+        ends_at: now,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    let shift = Shift::from_model(model, vec![], vec![], vec![]);
+
+    let event = CalendarEvent::from(&shift);
+
+    assert_eq!(event.uid, "shift-s1@circa");
+    assert_eq!(event.description, None);
+}
+
+#[test]
+fn test_task_without_due_date_is_skipped() {
+    assert!(CalendarEvent::from_task(&make_task(None)).is_none());
+}
+
+#[test]
+fn test_done_task_is_marked() {
+    let mut task = make_task(Some(Utc::now()));
+    task.status = TaskStatus::Done;
+
+    let event = CalendarEvent::from_task(&task).unwrap();
+
+    assert_eq!(event.uid, "task-t1@circa");
+    assert_eq!(event.summary, "Done: Order ice");
+    assert_eq!(event.ends_at, None);
+}
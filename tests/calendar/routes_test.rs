@@ -0,0 +1,125 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::calendar;
+use circa_backend::calendar::entity::{Kind, Model};
+use circa_backend::calendar::repository::CalendarRepository;
+use circa_backend::calendar::service::CalendarService;
+use circa_backend::event::entity::member;
+use circa_backend::modules::user::entity::{Model as UserModel, Role, Status};
+use circa_backend::planner::entity::{shift::Model as ShiftModel, task::Model as TaskModel};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<CalendarService> {
+    let db = Arc::new(db);
+    web::Data::new(CalendarService::new(
+        CalendarRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        UserRepository::new(db),
+    ))
+}
+
+#[actix_web::test]
+async fn test_feed_is_public_calendar() {
+    let feed = Model {
+        event_id: "event-1".to_string(),
+        user_id: "1".to_string(),
+        kind: Kind::Event,
+        token: "secret".to_string(),
+        created_at: Utc::now(),
+    };
+    let owner = UserModel {
+        id: "1".to_string(),
+        name: "Ann".to_string(),
+        surname: "Lee".to_string(),
+        email: "ann@example.com".to_string(),
+        phone: "123".to_string(),
+        status: Status::Active,
+    };
+    let membership = member::Model {
+        event_id: "event-1".to_string(),
+        user_id: "1".to_string(),
+        role: Role::Organizer,
+        joined_at: Utc::now(),
+    };
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![feed]])
+        .append_query_results([vec![owner]])
+        .append_query_results([vec![membership]])
+        .append_query_results([Vec::<ShiftModel>::new()])
+        .append_query_results([Vec::<TaskModel>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(calendar::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/calendar/secret.ics")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/calendar; charset=utf-8"
+    );
+    let body = test::read_body(resp).await;
+    assert!(body.starts_with(b"BEGIN:VCALENDAR\r\n"));
+}
+
+#[actix_web::test]
+async fn test_feeds_require_auth() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(calendar::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/calendar/feeds").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_event_feed_forbidden_for_volunteer() {
+    let token = generate_jwt("1", "event-1", "volunteer", JWT_SECRET)
+        .await
+        .unwrap()
+        .token;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(calendar::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/calendar/feeds/event")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,230 @@
+use chrono::{Duration, Utc};
+use circa_backend::auth::models::Claims;
+use circa_backend::calendar::entity::{Kind, Model};
+use circa_backend::calendar::models::FeedKind;
+use circa_backend::calendar::repository::CalendarRepository;
+use circa_backend::calendar::service::CalendarService;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::member;
+use circa_backend::modules::user::entity::{Model as UserModel, Role, Status};
+use circa_backend::planner::entity::{
+    shift::Model as ShiftModel, shift_assignment::Model as ShiftAssignmentModel,
+    shift_requirement::Model as RequirementModel, shift_skill::Model as SkillModel,
+    task::Model as TaskModel, task::Priority, task::Status as TaskStatus,
+    task_assignee::Model as AssigneeModel,
+};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> CalendarService {
+    let db = Arc::new(db);
+    CalendarService::new(
+        CalendarRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        UserRepository::new(db),
+    )
+}
+
+fn make_feed(kind: Kind) -> Model {
+    Model {
+        event_id: "event-1".to_string(),
+        user_id: "1".to_string(),
+        kind,
+        token: "secret".to_string(),
+        created_at: Utc::now(),
+    }
+}
+
+fn make_user(status: Status) -> UserModel {
+    UserModel {
+        id: "1".to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        status,
+    }
+}
+
+fn make_member(role: Role) -> member::Model {
+    member::Model {
+        event_id: "event-1".to_string(),
+        user_id: "1".to_string(),
+        role,
+        joined_at: Utc::now(),
+    }
+}
+
+fn exec_result(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected,
+    }
+}
+
+#[tokio::test]
+async fn test_event_feed_forbidden_for_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .regenerate_feed(FeedKind::Event, &make_claims("1", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_regenerate_personal_feed() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_feed(Kind::Personal)]])
+        .append_exec_results([exec_result(1), exec_result(1)])
+        .into_connection();
+    let service = make_service(db);
+
+    let feed = service
+        .regenerate_feed(FeedKind::Personal, &make_claims("1", "volunteer"))
+        .await
+        .unwrap();
+
+    assert_eq!(feed.kind, FeedKind::Personal);
+    assert_eq!(feed.url, "/calendar/secret.ics");
+}
+
+#[tokio::test]
+async fn test_revoke_missing_feed() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_exec_results([exec_result(0)])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .revoke_feed(FeedKind::Personal, &make_claims("1", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_render_unknown_token() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.render_feed("nope").await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_render_feed_of_inactive_user() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_feed(Kind::Personal)]])
+        .append_query_results([vec![make_user(Status::Inactive)]])
+        .append_query_results([vec![make_member(Role::Staff)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.render_feed("secret").await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_event_feed_stops_after_demotion() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_feed(Kind::Event)]])
+        .append_query_results([vec![make_user(Status::Active)]])
+        .append_query_results([vec![make_member(Role::Staff)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.render_feed("secret").await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_render_personal_feed() {
+    let now = Utc::now();
+    let shift = ShiftModel {
+        id: "s1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Bar".to_string(),
+        location: "bar".to_string(),
+        starts_at: now + Duration::days(1),
+        ends_at: now + Duration::days(1) + Duration::hours(4),
+        notes: "".to_string(),
+        created_by: "2".to_string(),
+        created_at: now,
+        updated_at: now,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    let task = TaskModel {
+        id: "t1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Order ice".to_string(),
+        description: "".to_string(),
+        priority: Priority::Medium,
+        status: TaskStatus::Todo,
+        due_at: Some(now + Duration::days(2)),
+        created_by: "2".to_string(),
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+        completed_by: None,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    let assignment = ShiftAssignmentModel {
+        shift_id: "s1".to_string(),
+        user_id: "1".to_string(),
+        role: Role::Staff,
+        assigned_at: now,
+    };
+    let assignee = AssigneeModel {
+        task_id: "t1".to_string(),
+        user_id: "1".to_string(),
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_feed(Kind::Personal)]])
+        .append_query_results([vec![make_user(Status::Active)]])
+        .append_query_results([vec![make_member(Role::Staff)]])
+        .append_query_results([vec![assignment.clone()]])
+        .append_query_results([vec![shift]])
+        .append_query_results([Vec::<RequirementModel>::new()])
+        .append_query_results([Vec::<SkillModel>::new()])
+        .append_query_results([vec![assignment]])
+        .append_query_results([vec![assignee.clone()]])
+        .append_query_results([vec![task]])
+        .append_query_results([vec![assignee]])
+        .into_connection();
+    let service = make_service(db);
+
+    let ics = service.render_feed("secret").await.unwrap();
+
+    assert!(ics.contains("X-WR-CALNAME:Circa: John Doe\r\n"));
+    assert!(ics.contains("UID:shift-s1@circa\r\n"));
+    assert!(ics.contains("UID:task-t1@circa\r\n"));
+    assert!(ics.contains("SUMMARY:Due: Order ice\r\n"));
+}
@@ -0,0 +1,53 @@
+use chrono::Utc;
+use circa_backend::consumable::entity::{Model, movement};
+use circa_backend::consumable::models::{Consumable, MovementKind};
+
+fn make_consumable(stock: i32, reorder_threshold: i32) -> Model {
+    Model {
+        id: "c1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Water".to_string(),
+        category: "drinks".to_string(),
+        unit: "bottles".to_string(),
+        stock,
+        reorder_threshold,
+        location_id: None,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn test_change_for_kind() {
+    assert_eq!(MovementKind::Received.change_for(12), 12);
+    assert_eq!(MovementKind::Issued.change_for(12), -12);
+    assert_eq!(MovementKind::Adjusted.change_for(-3), -3);
+}
+
+#[test]
+fn test_low_stock_at_or_below_threshold() {
+    assert!(!Consumable::from(make_consumable(50, 20)).low_stock);
+    assert!(Consumable::from(make_consumable(20, 20)).low_stock);
+    assert!(Consumable::from(make_consumable(0, 0)).low_stock);
+}
+
+#[test]
+fn test_stock_is_not_clamped() {
+    let consumable = Consumable::from(make_consumable(-2, 0));
+    assert_eq!(consumable.stock, -2);
+    assert!(consumable.low_stock);
+}
+
+#[test]
+fn test_kind_conversion() {
+    assert_eq!(
+        MovementKind::from(movement::Kind::Adjusted),
+        MovementKind::Adjusted
+    );
+    assert_eq!(
+        movement::Kind::from(MovementKind::Issued),
+        movement::Kind::Issued
+    );
+}
@@ -0,0 +1,95 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::consumable;
+use circa_backend::consumable::entity::Model;
+use circa_backend::consumable::repository::ConsumableRepository;
+use circa_backend::consumable::service::ConsumableService;
+use circa_backend::location::repository::LocationRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<ConsumableService> {
+    let db = Arc::new(db);
+    web::Data::new(ConsumableService::new(
+        ConsumableRepository::new(db.clone()),
+        LocationRepository::new(db),
+    ))
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+#[actix_web::test]
+async fn test_get_low_stock() {
+    let token = make_token("organizer").await;
+    let now = Utc::now();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![Model {
+            id: "c1".to_string(),
+            event_id: "event-1".to_string(),
+            name: "Cable ties".to_string(),
+            category: "tools".to_string(),
+            unit: "packs".to_string(),
+            stock: 2,
+            reorder_threshold: 5,
+            location_id: None,
+            notes: "".to_string(),
+            created_by: "1".to_string(),
+            created_at: now,
+            updated_at: now,
+        }]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(consumable::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/consumables/low-stock")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body[0]["name"], "Cable ties");
+    assert_eq!(body[0]["low_stock"], true);
+}
+
+#[actix_web::test]
+async fn test_record_movement_rejects_unknown_kind() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(consumable::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/consumables/c1/movements")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(serde_json::json!({"kind": "stolen", "quantity": 3}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,253 @@
+use chrono::Utc;
+use circa_backend::auth::models::Claims;
+use circa_backend::consumable::entity::{Model, movement};
+use circa_backend::consumable::models::{
+    CreateConsumableRequest, MovementKind, RecordMovementRequest,
+};
+use circa_backend::consumable::repository::ConsumableRepository;
+use circa_backend::consumable::service::ConsumableService;
+use circa_backend::error::AppError;
+use circa_backend::location::repository::LocationRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> ConsumableService {
+    let db = Arc::new(db);
+    ConsumableService::new(
+        ConsumableRepository::new(db.clone()),
+        LocationRepository::new(db),
+    )
+}
+
+fn make_consumable(stock: i32) -> Model {
+    let now = Utc::now();
+    Model {
+        id: "c1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Water".to_string(),
+        category: "drinks".to_string(),
+        unit: "bottles".to_string(),
+        stock,
+        reorder_threshold: 20,
+        location_id: None,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn make_movement(kind: movement::Kind, change: i32, stock_after: i32) -> movement::Model {
+    movement::Model {
+        id: "m1".to_string(),
+        consumable_id: "c1".to_string(),
+        kind,
+        change,
+        stock_after,
+        reason: "".to_string(),
+        moved_by: "2".to_string(),
+        moved_at: Utc::now(),
+    }
+}
+
+fn exec_ok() -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+fn movement_request(kind: MovementKind, quantity: i32, reason: &str) -> RecordMovementRequest {
+    RecordMovementRequest {
+        kind,
+        quantity,
+        reason: reason.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_create_consumable_records_starting_stock() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_exec_results([exec_ok(), exec_ok()])
+        .append_query_results([vec![make_consumable(100)]])
+        .append_query_results([vec![make_movement(movement::Kind::Received, 100, 100)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CreateConsumableRequest {
+        name: " Water ".to_string(),
+        category: "drinks".to_string(),
+        unit: "bottles".to_string(),
+        stock: 100,
+        reorder_threshold: 20,
+        location_id: None,
+        notes: "".to_string(),
+    };
+    let consumable = service
+        .create_consumable(req, &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(consumable.stock, 100);
+    assert!(!consumable.low_stock);
+}
+
+#[tokio::test]
+async fn test_issue_stock() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_consumable(30)]])
+        .append_query_results([vec![make_consumable(30)]])
+        .append_query_results([vec![make_consumable(18)]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .append_query_results([vec![make_movement(movement::Kind::Issued, -12, 18)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let movement = service
+        .record_movement(
+            "c1",
+            movement_request(MovementKind::Issued, 12, ""),
+            &make_claims("2", "staff"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(movement.change, -12);
+    assert_eq!(movement.stock_after, 18);
+    assert_eq!(movement.moved_by, "2");
+}
+
+// someone issued 3 between the lookup and the movement, the level read in the transaction wins
+#[tokio::test]
+async fn test_issue_more_than_in_stock() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_consumable(8)]])
+            .append_query_results([vec![make_consumable(5)]])
+            .into_connection(),
+    );
+    let service = ConsumableService::new(
+        ConsumableRepository::new(db.clone()),
+        LocationRepository::new(db.clone()),
+    );
+
+    let result = service
+        .record_movement(
+            "c1",
+            movement_request(MovementKind::Issued, 6, ""),
+            &make_claims("2", "staff"),
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Only 5 left in stock"
+    );
+    drop(service);
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(!log.contains("UPDATE"));
+    assert!(!log.contains("INSERT"));
+}
+
+#[tokio::test]
+async fn test_receive_past_the_largest_count() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_consumable(i32::MAX - 2)]])
+        .append_query_results([vec![make_consumable(i32::MAX - 2)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .record_movement(
+            "c1",
+            movement_request(MovementKind::Received, 5, ""),
+            &make_claims("2", "staff"),
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        format!("Bad request: Stock can't go above {}", i32::MAX)
+    );
+}
+
+#[tokio::test]
+async fn test_adjustment_needs_a_reason() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_consumable(30)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .record_movement(
+            "c1",
+            movement_request(MovementKind::Adjusted, -4, "  "),
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Adjustments need a reason"
+    );
+}
+
+#[tokio::test]
+async fn test_received_quantity_must_be_positive() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_consumable(30)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .record_movement(
+            "c1",
+            movement_request(MovementKind::Received, -4, ""),
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_volunteers_cant_move_stock() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .record_movement(
+            "c1",
+            movement_request(MovementKind::Issued, 1, ""),
+            &make_claims("3", "volunteer"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_low_stock_report_for_organizers_only() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_consumable(12)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let staff = service.get_low_stock(&make_claims("2", "staff")).await;
+    let report = service
+        .get_low_stock(&make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert!(matches!(staff, Err(AppError::Forbidden)));
+    assert_eq!(report.len(), 1);
+    assert!(report[0].low_stock);
+}
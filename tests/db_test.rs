@@ -0,0 +1,198 @@
+use circa_backend::db::{establish_connection, migrate};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+// the shape of users before roles moved into events
+const OLD_SCHEMA: &str = "
+    CREATE TABLE users (
+        id TEXT PRIMARY KEY NOT NULL,
+        name TEXT NOT NULL,
+        surname TEXT NOT NULL,
+        email TEXT NOT NULL,
+        phone TEXT NOT NULL,
+        role TEXT NOT NULL,
+        status TEXT NOT NULL
+    );
+    INSERT INTO users VALUES ('1', 'Alice', 'Lovelace', 'alice@circa.local', '', 'admin', 'active');
+    INSERT INTO users VALUES ('2', 'Bob', 'Birkenstock', 'bob@circa.local', '', 'organizer', 'inactive');
+";
+
+#[tokio::test]
+async fn test_migrate_moves_roles_into_an_event() {
+    let db = establish_connection("sqlite::memory:").await.unwrap();
+    db.execute_unprepared(OLD_SCHEMA).await.unwrap();
+
+    migrate(&db).await.unwrap();
+    // running it again changes nothing
+    migrate(&db).await.unwrap();
+
+    let rows = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT m.user_id, m.role FROM event_members m
+             JOIN events e ON e.id = m.event_id ORDER BY m.user_id",
+        ))
+        .await
+        .unwrap();
+    let roles: Vec<(String, String)> = rows
+        .iter()
+        .map(|r| {
+            (
+                r.try_get_by_index(0).unwrap(),
+                r.try_get_by_index(1).unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        roles,
+        vec![
+            ("1".to_string(), "admin".to_string()),
+            ("2".to_string(), "organizer".to_string()),
+        ]
+    );
+
+    let role_column = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT COUNT(*) FROM pragma_table_info('users') WHERE name = 'role'",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get_by_index::<i64>(0)
+        .unwrap();
+    assert_eq!(role_column, 0);
+}
+
+#[tokio::test]
+async fn test_migrate_leaves_new_databases_alone() {
+    let db = establish_connection("sqlite::memory:").await.unwrap();
+    db.execute_unprepared(include_str!("../seed.sql"))
+        .await
+        .unwrap();
+
+    migrate(&db).await.unwrap();
+
+    let events = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT COUNT(*) FROM events",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get_by_index::<i64>(0)
+        .unwrap();
+    assert_eq!(events, 1);
+}
+
+// the users table as of version 20, before emails were made unique
+const VERSION_20_USERS: &str = "
+    PRAGMA user_version = 20;
+    CREATE TABLE users (
+        id TEXT PRIMARY KEY NOT NULL,
+        name TEXT NOT NULL,
+        surname TEXT NOT NULL,
+        email TEXT NOT NULL,
+        phone TEXT NOT NULL,
+        status TEXT NOT NULL
+    );
+";
+
+#[tokio::test]
+async fn test_migrate_lowercases_emails() {
+    let db = establish_connection("sqlite::memory:").await.unwrap();
+    db.execute_unprepared(VERSION_20_USERS).await.unwrap();
+    db.execute_unprepared(
+        "INSERT INTO users VALUES ('1', 'Alice', 'Lovelace', ' Alice@Circa.local', '', 'active')",
+    )
+    .await
+    .unwrap();
+
+    migrate(&db).await.unwrap();
+
+    let email = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT email FROM users WHERE id = '1'",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get_by_index::<String>(0)
+        .unwrap();
+    assert_eq!(email, "alice@circa.local");
+
+    let duplicate = db
+        .execute_unprepared(
+            "INSERT INTO users VALUES ('2', 'Alice', 'L', 'ALICE@circa.local', '', 'active')",
+        )
+        .await;
+    assert!(duplicate.is_err());
+}
+
+#[tokio::test]
+async fn test_migrate_refuses_emails_that_only_differ_by_case() {
+    let db = establish_connection("sqlite::memory:").await.unwrap();
+    db.execute_unprepared(VERSION_20_USERS).await.unwrap();
+    db.execute_unprepared(
+        "INSERT INTO users VALUES ('1', 'Alice', 'Lovelace', 'Alice@circa.local', '', 'active');
+         INSERT INTO users VALUES ('2', 'Alice', 'L', 'alice@circa.local', '', 'active');",
+    )
+    .await
+    .unwrap();
+
+    let err = migrate(&db).await.unwrap_err();
+
+    assert!(err.to_string().contains("alice@circa.local"));
+}
+
+#[tokio::test]
+async fn test_migrate_keeps_checkouts_when_accounts_go() {
+    let db = establish_connection("sqlite::memory:").await.unwrap();
+    db.execute_unprepared(
+        "PRAGMA user_version = 21;
+        CREATE TABLE users (id TEXT PRIMARY KEY NOT NULL, email TEXT NOT NULL);
+        CREATE TABLE events (id TEXT PRIMARY KEY NOT NULL);
+        CREATE TABLE assets (id TEXT PRIMARY KEY NOT NULL);
+        CREATE TABLE asset_checkouts (
+            id TEXT PRIMARY KEY NOT NULL,
+            event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+            asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+            quantity INTEGER NOT NULL,
+            note TEXT NOT NULL,
+            checked_out_by TEXT NOT NULL,
+            checked_out_at TEXT NOT NULL,
+            due_at TEXT NOT NULL,
+            checked_in_by TEXT,
+            checked_in_at TEXT,
+            condition TEXT,
+            return_note TEXT
+        );
+        INSERT INTO users VALUES ('1', 'alice@circa.local');
+        INSERT INTO events VALUES ('e1');
+        INSERT INTO assets VALUES ('a1');
+        INSERT INTO asset_checkouts VALUES ('c1', 'e1', 'a1', '1', 1, '', '2', 't', 't', NULL, NULL, NULL, NULL);",
+    )
+    .await
+    .unwrap();
+
+    migrate(&db).await.unwrap();
+
+    assert!(
+        db.execute_unprepared("DELETE FROM users WHERE id = '1'")
+            .await
+            .is_err()
+    );
+    let checkouts = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT COUNT(*) FROM asset_checkouts",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get_by_index::<i64>(0)
+        .unwrap();
+    assert_eq!(checkouts, 1);
+}
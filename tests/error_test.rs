@@ -84,3 +84,10 @@ fn test_internal_server_error_response() {
     let response = err.error_response();
     assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
 }
+
+#[test]
+fn test_conflict_status() {
+    let err = AppError::Conflict("overlap".to_string(), serde_json::json!([]));
+    assert_eq!(err.status_code(), StatusCode::CONFLICT);
+    assert_eq!(err.to_string(), "Conflict: overlap");
+}
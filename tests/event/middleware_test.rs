@@ -0,0 +1,37 @@
+use circa_backend::event::middleware::{is_archive_exempt, split_event_path};
+
+#[test]
+fn test_split_event_path() {
+    assert_eq!(
+        split_event_path("/events/e1/planner/shifts"),
+        Some(("e1".to_string(), "/planner/shifts".to_string()))
+    );
+    assert_eq!(
+        split_event_path("/events/e1/users"),
+        Some(("e1".to_string(), "/users".to_string()))
+    );
+}
+
+#[test]
+fn test_split_event_path_leaves_event_routes_alone() {
+    assert_eq!(split_event_path("/events"), None);
+    assert_eq!(split_event_path("/events/e1"), None);
+    assert_eq!(split_event_path("/events/e1/"), None);
+    assert_eq!(split_event_path("/events/e1/token"), None);
+    assert_eq!(split_event_path("/users"), None);
+}
+
+#[test]
+fn test_archive_exempt_paths() {
+    assert!(is_archive_exempt("/events"));
+    assert!(is_archive_exempt("/events/e1/status"));
+    assert!(is_archive_exempt("/backups/restore"));
+    assert!(is_archive_exempt("/users/u1/erase"));
+    assert!(is_archive_exempt("/users/u1/data-export"));
+
+    assert!(!is_archive_exempt("/eventsfoo"));
+    assert!(!is_archive_exempt("/events-archive/x"));
+    assert!(!is_archive_exempt("/users/u1"));
+    assert!(!is_archive_exempt("/users/erase"));
+    assert!(!is_archive_exempt("/planner/shifts"));
+}
@@ -0,0 +1,85 @@
+use chrono::Utc;
+use circa_backend::event::entity::{Model, Status, member};
+use circa_backend::event::models::{Event, EventMember, EventStatus};
+use circa_backend::user::entity::Role;
+use circa_backend::user::models::UserRole;
+
+#[test]
+fn test_model_to_event_conversion() {
+    let now = Utc::now();
+    let event: Event = Model {
+        id: "e1".to_string(),
+        name: "Summer Camp".to_string(),
+        starts_at: now,
+        ends_at: now,
+        status: Status::Live,
+        report: None,
+        created_at: now,
+        updated_at: now,
+    }
+    .into();
+
+    assert_eq!(event.id, "e1");
+    assert_eq!(event.name, "Summer Camp");
+    assert_eq!(event.status, EventStatus::Live);
+    assert_eq!(event.role, None);
+}
+
+#[test]
+fn test_event_role_only_serialized_when_set() {
+    let now = Utc::now();
+    let event: Event = Model {
+        id: "e1".to_string(),
+        name: "Summer Camp".to_string(),
+        starts_at: now,
+        ends_at: now,
+        status: Status::Live,
+        report: None,
+        created_at: now,
+        updated_at: now,
+    }
+    .into();
+
+    let json = serde_json::to_value(&event).unwrap();
+    assert!(json.get("role").is_none());
+
+    let json = serde_json::to_value(Event {
+        role: Some(UserRole::Staff),
+        ..event
+    })
+    .unwrap();
+    assert_eq!(json["role"], "staff");
+}
+
+#[test]
+fn test_member_conversion() {
+    let member: EventMember = member::Model {
+        event_id: "e1".to_string(),
+        user_id: "1".to_string(),
+        role: Role::Organizer,
+        joined_at: Utc::now(),
+    }
+    .into();
+
+    assert_eq!(member.event_id, "e1");
+    assert_eq!(member.role, UserRole::Organizer);
+}
+
+#[test]
+fn test_status_only_moves_forward() {
+    assert_eq!(EventStatus::Draft.next(), Some(EventStatus::Published));
+    assert_eq!(EventStatus::Published.next(), Some(EventStatus::Live));
+    assert_eq!(EventStatus::Live.next(), Some(EventStatus::WrappedUp));
+    assert_eq!(EventStatus::WrappedUp.next(), Some(EventStatus::Archived));
+    assert_eq!(EventStatus::Archived.next(), None);
+}
+
+#[test]
+fn test_status_conversion() {
+    assert_eq!(Status::from(EventStatus::WrappedUp), Status::WrappedUp);
+    assert_eq!(EventStatus::from(Status::Archived), EventStatus::Archived);
+    assert_eq!(
+        serde_json::to_value(EventStatus::WrappedUp).unwrap(),
+        "wrapped_up"
+    );
+}
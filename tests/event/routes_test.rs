@@ -0,0 +1,242 @@
+use actix_web::middleware::from_fn;
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::event;
+use circa_backend::event::entity::{Model as EventModel, Status, member};
+use circa_backend::event::middleware::event_path;
+use circa_backend::event::{repository::EventRepository, service::EventService};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::team;
+use circa_backend::team::entity::{Model as TeamModel, member as team_member};
+use circa_backend::team::{repository::TeamRepository, service::TeamService};
+use circa_backend::user::entity::Role;
+use circa_backend::user::repository::UserRepository;
+use jsonwebtoken::{DecodingKey, Validation, decode};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_event_service(db: DatabaseConnection) -> web::Data<EventService> {
+    let db = Arc::new(db);
+    web::Data::new(EventService::new(
+        EventRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        TeamRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+    ))
+}
+
+fn make_team_service(db: DatabaseConnection) -> web::Data<TeamService> {
+    let db = Arc::new(db);
+    web::Data::new(TeamService::new(
+        TeamRepository::new(db.clone()),
+        UserRepository::new(db),
+    ))
+}
+
+async fn make_token(event_id: &str, role: &str) -> String {
+    generate_jwt("1", event_id, role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+#[actix_web::test]
+async fn test_switch_event_carries_role_there() {
+    let token = make_token("e1", "admin").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![member::Model {
+            event_id: "e2".to_string(),
+            user_id: "1".to_string(),
+            role: Role::Volunteer,
+            joined_at: Utc::now(),
+        }]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(event_path))
+            .app_data(make_event_service(db))
+            .app_data(make_jwt_secret())
+            .configure(event::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/events/e2/token")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    let claims = decode::<Claims>(
+        body["token"].as_str().unwrap(),
+        &DecodingKey::from_secret(JWT_SECRET.as_bytes()),
+        &Validation::default(),
+    )
+    .unwrap()
+    .claims;
+
+    assert_eq!(claims.event, "e2");
+    assert_eq!(claims.role, "volunteer");
+}
+
+#[actix_web::test]
+async fn test_switch_to_event_user_is_not_part_of() {
+    let token = make_token("e1", "admin").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<member::Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(event_path))
+            .app_data(make_event_service(db))
+            .app_data(make_jwt_secret())
+            .configure(event::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/events/e2/token")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_event_path_reaches_module_routes() {
+    let token = make_token("e1", "volunteer").await;
+    let now = Utc::now();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![TeamModel {
+            id: "t1".to_string(),
+            event_id: "e1".to_string(),
+            name: "Bar".to_string(),
+            description: "".to_string(),
+            created_at: now,
+            updated_at: now,
+        }]])
+        .append_query_results([Vec::<team_member::Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(event_path))
+            .app_data(make_team_service(db))
+            .app_data(make_jwt_secret())
+            .configure(team::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/events/e1/teams")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body[0]["name"], "Bar");
+}
+
+#[actix_web::test]
+async fn test_event_path_for_another_event_is_forbidden() {
+    let token = make_token("e1", "admin").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(event_path))
+            .app_data(make_team_service(db))
+            .app_data(make_jwt_secret())
+            .configure(team::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/events/e2/teams")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_archived_event_is_read_only() {
+    let token = make_token("e1", "admin").await;
+    let now = Utc::now();
+    let events = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![EventModel {
+            id: "e1".to_string(),
+            name: "Camp".to_string(),
+            starts_at: now,
+            ends_at: now,
+            status: Status::Archived,
+            report: None,
+            created_at: now,
+            updated_at: now,
+        }]])
+        .into_connection();
+    let teams = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<TeamModel>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(event_path))
+            .app_data(make_event_service(events))
+            .app_data(make_team_service(teams))
+            .app_data(make_jwt_secret())
+            .configure(team::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/events/e1/teams")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(serde_json::json!({ "name": "Bar" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "Bad request: Archived events are read-only");
+
+    let req = test::TestRequest::get()
+        .uri("/events/e1/teams")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_delete_event_needs_confirmation() {
+    let token = make_token("e1", "admin").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_event_service(db))
+            .app_data(make_jwt_secret())
+            .configure(event::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::delete()
+        .uri("/events/e1")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,512 @@
+use chrono::{Duration, Utc};
+use circa_backend::audit::entity::Model as AuditModel;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::{Model, Status, member};
+use circa_backend::event::models::{
+    ChangeStatusRequest, CreateEventRequest, EventStatus, UpdateEventRequest,
+};
+use circa_backend::event::repository::EventRepository;
+use circa_backend::event::service::EventService;
+use circa_backend::planner::entity::{
+    shift::Model as ShiftModel, shift_assignment::Model as AssignmentModel,
+    shift_requirement::Model as RequirementModel, shift_skill::Model as SkillModel,
+    task::Model as TaskModel, task::Priority, task::Status as TaskStatus,
+    task_assignee::Model as AssigneeModel,
+};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::team::entity::Model as TeamModel;
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::user::entity::Role;
+use circa_backend::user::models::UserRole;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "e1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> EventService {
+    let db = Arc::new(db);
+    EventService::new(
+        EventRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        TeamRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+    )
+}
+
+fn make_event(id: &str, name: &str) -> Model {
+    let now = Utc::now();
+    Model {
+        id: id.to_string(),
+        name: name.to_string(),
+        starts_at: now,
+        ends_at: now + Duration::days(3),
+        status: Status::Draft,
+        report: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn make_event_in(status: Status) -> Model {
+    Model {
+        status,
+        ..make_event("e1", "Camp")
+    }
+}
+
+fn make_audit_entry() -> AuditModel {
+    AuditModel {
+        id: "a".to_string(),
+        event_id: Some("e1".to_string()),
+        actor_id: "1".to_string(),
+        action: "event.status".to_string(),
+        target_type: "event".to_string(),
+        target_id: Some("e1".to_string()),
+        details: None,
+        created_at: Utc::now(),
+    }
+}
+
+fn make_member(event_id: &str, role: Role) -> member::Model {
+    member::Model {
+        event_id: event_id.to_string(),
+        user_id: "1".to_string(),
+        role,
+        joined_at: Utc::now(),
+    }
+}
+
+fn create_request(name: &str) -> CreateEventRequest {
+    let now = Utc::now();
+    CreateEventRequest {
+        name: name.to_string(),
+        starts_at: now,
+        ends_at: now + Duration::days(3),
+    }
+}
+
+fn exec_ok() -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+#[tokio::test]
+async fn test_get_events_lists_own_with_role() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![
+            make_member("e2", Role::Volunteer),
+            make_member("e1", Role::Organizer),
+        ]])
+        .append_query_results([vec![make_event("e1", "Camp"), make_event("e2", "Fair")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let events = service
+        .get_events(&make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].role, Some(UserRole::Organizer));
+    assert_eq!(events[1].role, Some(UserRole::Volunteer));
+}
+
+#[tokio::test]
+async fn test_get_event_hidden_from_non_members() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<member::Model>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.get_event("e2", &make_claims("1", "admin")).await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Not found: Event not found"
+    );
+}
+
+#[tokio::test]
+async fn test_create_event_makes_creator_admin() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .append_query_results([vec![make_event("e2", "Fair")]])
+        .append_query_results([vec![make_member("e2", Role::Admin)]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let event = service
+        .create_event(create_request(" Fair "), &make_claims("1", "admin"))
+        .await
+        .unwrap();
+
+    assert_eq!(event.name, "Fair");
+    assert_eq!(event.role, Some(UserRole::Admin));
+}
+
+#[tokio::test]
+async fn test_create_event_forbidden_for_organizer() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .create_event(create_request("Fair"), &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_create_event_rejects_taken_name() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_event("e1", "Fair")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .create_event(create_request("Fair"), &make_claims("1", "admin"))
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: An event with this name already exists"
+    );
+}
+
+#[tokio::test]
+async fn test_create_event_rejects_inverted_dates() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let now = Utc::now();
+
+    let req = CreateEventRequest {
+        name: "Fair".to_string(),
+        starts_at: now,
+        ends_at: now - Duration::hours(1),
+    };
+    let result = service.create_event(req, &make_claims("1", "admin")).await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Event must end after it starts"
+    );
+}
+
+#[tokio::test]
+async fn test_update_event_needs_token_for_that_event() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .update_event(
+            "e2",
+            UpdateEventRequest::default(),
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_sign_in_to_requested_event() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![
+            make_member("e2", Role::Volunteer),
+            make_member("e1", Role::Organizer),
+        ]])
+        .into_connection();
+    let service = make_service(db);
+
+    let membership = service
+        .sign_in("john@example.com", Some("e1"))
+        .await
+        .unwrap();
+
+    assert_eq!(membership.event_id, "e1");
+    assert_eq!(membership.role, UserRole::Organizer);
+}
+
+#[tokio::test]
+async fn test_sign_in_to_event_user_is_not_part_of() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_member("e1", Role::Organizer)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.sign_in("john@example.com", Some("e3")).await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+fn status_request(status: EventStatus) -> ChangeStatusRequest {
+    ChangeStatusRequest { status }
+}
+
+#[tokio::test]
+async fn test_publish_draft_as_organizer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_event_in(Status::Draft)]])
+        .append_query_results([vec![make_event_in(Status::Draft)]])
+        .append_query_results([vec![make_event_in(Status::Published)]])
+        .append_query_results([vec![make_audit_entry()]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let event = service
+        .change_status(
+            "e1",
+            status_request(EventStatus::Published),
+            false,
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(event.status, EventStatus::Published);
+}
+
+#[tokio::test]
+async fn test_status_cant_skip_steps() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_event_in(Status::Draft)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .change_status(
+            "e1",
+            status_request(EventStatus::Live),
+            false,
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Draft events can't become live"
+    );
+}
+
+#[tokio::test]
+async fn test_archive_is_admin_only() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .change_status(
+            "e1",
+            status_request(EventStatus::Archived),
+            true,
+            &make_claims("1", "organizer"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_archive_requires_confirmation() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .change_status(
+            "e1",
+            status_request(EventStatus::Archived),
+            false,
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_wrap_up_writes_report() {
+    let now = Utc::now();
+    let shift = ShiftModel {
+        id: "s1".to_string(),
+        event_id: "e1".to_string(),
+        title: "Bar".to_string(),
+        location: "bar".to_string(),
+        starts_at: now,
+        ends_at: now + Duration::hours(4),
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    let task = TaskModel {
+        id: "t1".to_string(),
+        event_id: "e1".to_string(),
+        title: "Clean up".to_string(),
+        description: "".to_string(),
+        priority: Priority::Low,
+        status: TaskStatus::Done,
+        due_at: None,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        completed_at: Some(now),
+        completed_by: Some("1".to_string()),
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_event_in(Status::Live)]])
+            .append_query_results([vec![
+                make_member("e1", Role::Organizer),
+                make_member("e1", Role::Staff),
+            ]])
+            .append_query_results([vec![shift]])
+            .append_query_results([vec![RequirementModel {
+                shift_id: "s1".to_string(),
+                role: Role::Staff,
+                headcount: 2,
+            }]])
+            .append_query_results([Vec::<SkillModel>::new()])
+            .append_query_results([vec![AssignmentModel {
+                shift_id: "s1".to_string(),
+                user_id: "1".to_string(),
+                role: Role::Staff,
+                assigned_at: now,
+            }]])
+            .append_query_results([vec![task]])
+            .append_query_results([Vec::<AssigneeModel>::new()])
+            .append_query_results([Vec::<TeamModel>::new()])
+            .append_query_results([vec![make_event_in(Status::Live)]])
+            .append_query_results([vec![make_event_in(Status::WrappedUp)]])
+            .append_query_results([vec![make_audit_entry()]])
+            .append_exec_results([exec_ok(), exec_ok()])
+            .into_connection(),
+    );
+    let service = EventService::new(
+        EventRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        TeamRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db.clone())),
+    );
+
+    let event = service
+        .change_status(
+            "e1",
+            status_request(EventStatus::WrappedUp),
+            false,
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(event.status, EventStatus::WrappedUp);
+
+    drop(service);
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"\"organizers\":1,\"staff\":1"#));
+    assert!(log.contains(r#"\"slots\":2,\"filled\":1,\"staffed_hours\":4.0"#));
+    assert!(log.contains(r#"\"total\":1,\"done\":1,\"open\":0"#));
+}
+
+#[tokio::test]
+async fn test_update_archived_event_is_refused() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_event_in(Status::Archived)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .update_event(
+            "e1",
+            UpdateEventRequest::default(),
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Archived events are read-only"
+    );
+}
+
+#[tokio::test]
+async fn test_ensure_writable() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_event_in(Status::WrappedUp)]])
+        .append_query_results([vec![make_event_in(Status::Archived)]])
+        .into_connection();
+    let service = make_service(db);
+
+    assert!(service.ensure_writable("e1").await.is_ok());
+    assert!(service.ensure_writable("e1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_report_missing_before_wrap_up() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_event_in(Status::Live)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .get_report("e1", &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_delete_event_as_admin() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_event_in(Status::Archived)]])
+        .append_query_results([vec![make_audit_entry()]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .delete_event("e1", true, &make_claims("1", "admin"))
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_delete_event_forbidden_for_organizer() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .delete_event("e1", true, &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_delete_event_requires_confirmation() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .delete_event("e1", false, &make_claims("1", "admin"))
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Deleting an event removes all of its data, pass confirm=true to continue"
+    );
+}
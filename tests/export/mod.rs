@@ -0,0 +1,4 @@
+mod models_test;
+mod routes_test;
+mod service_test;
+mod writer_test;
@@ -0,0 +1,78 @@
+use circa_backend::export::models::{ExportEntity, ExportFormat, ExportParams, ExportTable};
+use circa_backend::user::models::{User, UserRole, UserStatus};
+use serde_json::json;
+
+fn make_user() -> User {
+    User {
+        id: "1".to_string(),
+        name: "Dave".to_string(),
+        surname: "Strider".to_string(),
+        email: "dave@example.com".to_string(),
+        phone: "123".to_string(),
+        role: UserRole::Staff,
+        status: UserStatus::Active,
+        profile: None,
+        avatar: None,
+    }
+}
+
+#[test]
+fn test_format_from_str() {
+    assert_eq!("csv".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+    assert_eq!(
+        "jsonl".parse::<ExportFormat>().unwrap(),
+        ExportFormat::Jsonl
+    );
+    assert_eq!("xlsx".parse::<ExportFormat>().unwrap(), ExportFormat::Xlsx);
+    assert!("pdf".parse::<ExportFormat>().is_err());
+}
+
+#[test]
+fn test_entity_round_trip() {
+    let entity: ExportEntity = ExportEntity::Users.to_string().parse().unwrap();
+    assert_eq!(entity, ExportEntity::Users);
+}
+
+#[test]
+fn test_params_column_list() {
+    let params = ExportParams {
+        columns: Some(" name, email ,,".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(params.column_list(), vec!["name", "email"]);
+    assert_eq!(params.format(), ExportFormat::Csv);
+}
+
+#[test]
+fn test_table_defaults_to_all_columns() {
+    let table = ExportTable::from_items(&[make_user()], &[]).unwrap();
+
+    assert_eq!(
+        table.columns,
+        vec!["id", "name", "surname", "email", "phone", "role", "status"]
+    );
+    assert_eq!(table.rows[0][5], json!("staff"));
+}
+
+#[test]
+fn test_table_selects_columns_in_order() {
+    let columns = vec!["email".to_string(), "name".to_string()];
+    let table = ExportTable::from_items(&[make_user()], &columns).unwrap();
+
+    assert_eq!(table.columns, columns);
+    assert_eq!(
+        table.rows[0],
+        vec![json!("dave@example.com"), json!("Dave")]
+    );
+}
+
+#[test]
+fn test_table_rejects_unknown_columns() {
+    let result = ExportTable::from_items(&[make_user()], &["password".to_string()]);
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Unknown column 'password'"
+    );
+}
@@ -0,0 +1,154 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::event::entity::member;
+use circa_backend::event::{repository::EventRepository, service::EventService};
+use circa_backend::export;
+use circa_backend::export::{repository::ExportRepository, service::ExportService};
+use circa_backend::modules::audit::entity::Model as AuditModel;
+use circa_backend::modules::user::entity::{Model, Role, Status};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("someone", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+fn make_service(db: DatabaseConnection) -> web::Data<ExportService> {
+    let db = Arc::new(db);
+    web::Data::new(ExportService::new(
+        ExportRepository::new(db.clone()),
+        UserRepository::new(db.clone()),
+        Arc::new(EventService::new(
+            EventRepository::new(db.clone()),
+            PlannerRepository::new(db.clone()),
+            TeamRepository::new(db.clone()),
+            AuditService::new(AuditRepository::new(db.clone())),
+        )),
+        AuditService::new(AuditRepository::new(db)),
+        std::env::temp_dir().join("circa-export-test"),
+    ))
+}
+
+#[actix_web::test]
+async fn test_export_users_route() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(1)))])]])
+        .append_query_results([vec![Model {
+            id: "1".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![member::Model {
+            event_id: "event-1".to_string(),
+            user_id: "1".to_string(),
+            role: Role::Admin,
+            joined_at: Utc::now(),
+        }]])
+        .append_query_results([vec![AuditModel {
+            id: "a".to_string(),
+            event_id: Some("event-1".to_string()),
+            actor_id: "someone".to_string(),
+            action: "export.stream".to_string(),
+            target_type: "users".to_string(),
+            target_id: None,
+            details: None,
+            created_at: Utc::now(),
+        }]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_service(db))
+            .app_data(make_jwt_secret())
+            .configure(export::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/export/users?format=jsonl&columns=name,role")
+        .insert_header((
+            "Authorization",
+            format!("Bearer {}", make_token("admin").await),
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/x-ndjson"
+    );
+
+    let body = test::read_body(resp).await;
+    assert_eq!(body, "{\"name\":\"John\",\"role\":\"admin\"}\n");
+}
+
+#[actix_web::test]
+async fn test_export_route_forbidden_for_organizer() {
+    let app = test::init_service(
+        App::new()
+            .app_data(make_service(
+                MockDatabase::new(DatabaseBackend::Sqlite).into_connection(),
+            ))
+            .app_data(make_jwt_secret())
+            .configure(export::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/export/users")
+        .insert_header((
+            "Authorization",
+            format!("Bearer {}", make_token("organizer").await),
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_export_route_unknown_entity() {
+    let app = test::init_service(
+        App::new()
+            .app_data(make_service(
+                MockDatabase::new(DatabaseBackend::Sqlite).into_connection(),
+            ))
+            .app_data(make_jwt_secret())
+            .configure(export::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/export/secrets")
+        .insert_header((
+            "Authorization",
+            format!("Bearer {}", make_token("admin").await),
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
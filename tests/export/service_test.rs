@@ -0,0 +1,302 @@
+use chrono::Utc;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::event::entity::{Model as EventModel, Status as EventStatus, member};
+use circa_backend::event::{repository::EventRepository, service::EventService};
+use circa_backend::export::service::SYNC_ROW_LIMIT;
+use circa_backend::export::{
+    entity::{JobStatus, Model as JobModel},
+    models::{ExportEntity, ExportFormat, ExportOutcome, ExportParams},
+    repository::ExportRepository,
+    service::ExportService,
+};
+use circa_backend::modules::audit::entity::Model as AuditModel;
+use circa_backend::modules::user::entity::{Model, Role, Status};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> Arc<ExportService> {
+    make_service_arc(Arc::new(db))
+}
+
+fn make_service_arc(db: Arc<DatabaseConnection>) -> Arc<ExportService> {
+    Arc::new(ExportService::new(
+        ExportRepository::new(db.clone()),
+        UserRepository::new(db.clone()),
+        Arc::new(EventService::new(
+            EventRepository::new(db.clone()),
+            PlannerRepository::new(db.clone()),
+            TeamRepository::new(db.clone()),
+            AuditService::new(AuditRepository::new(db.clone())),
+        )),
+        AuditService::new(AuditRepository::new(db)),
+        std::env::temp_dir().join("circa-export-test"),
+    ))
+}
+
+fn make_event(status: EventStatus) -> EventModel {
+    let now = Utc::now();
+    EventModel {
+        id: "event-1".to_string(),
+        name: "Camp".to_string(),
+        starts_at: now,
+        ends_at: now,
+        status,
+        report: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn make_user(id: &str) -> Model {
+    Model {
+        id: id.to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: format!("{}@example.com", id),
+        phone: "123".to_string(),
+        status: Status::Active,
+    }
+}
+
+fn make_member(user_id: &str) -> member::Model {
+    member::Model {
+        event_id: "event-1".to_string(),
+        user_id: user_id.to_string(),
+        role: Role::Staff,
+        joined_at: Utc::now(),
+    }
+}
+
+fn make_audit_entry() -> AuditModel {
+    AuditModel {
+        id: "a".to_string(),
+        event_id: Some("event-1".to_string()),
+        actor_id: "admin-id".to_string(),
+        action: "export.stream".to_string(),
+        target_type: "users".to_string(),
+        target_id: None,
+        details: None,
+        created_at: Utc::now(),
+    }
+}
+
+fn count_row(count: usize) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([("num_items", Value::Int(Some(count as i32)))])
+}
+
+fn make_job(status: JobStatus) -> JobModel {
+    JobModel {
+        id: "job-1".to_string(),
+        event_id: "event-1".to_string(),
+        requested_by: "admin-id".to_string(),
+        entity: "users".to_string(),
+        format: "csv".to_string(),
+        columns: None,
+        filter: String::new(),
+        status,
+        row_count: None,
+        file_name: None,
+        error: None,
+        created_at: Utc::now(),
+        finished_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_export_users_csv() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![count_row(2)]])
+        .append_query_results([vec![make_user("john"), make_user("jane")]])
+        .append_query_results([vec![make_member("john"), make_member("jane")]])
+        .append_query_results([vec![make_audit_entry()]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let params = ExportParams {
+        format: Some(ExportFormat::Csv),
+        columns: Some("email".to_string()),
+        background: false,
+    };
+
+    let outcome = service
+        .export(ExportEntity::Users, params, "columns=email", &claims)
+        .await
+        .unwrap();
+
+    match outcome {
+        ExportOutcome::Ready(file) => {
+            assert_eq!(file.file_name, "users.csv");
+            assert_eq!(
+                String::from_utf8(file.chunks.concat()).unwrap(),
+                "email\njohn@example.com\njane@example.com\n"
+            );
+        }
+        ExportOutcome::Queued(_) => panic!("small exports should not be queued"),
+    }
+}
+
+// the mock hands out results in order, so loading the users here would have eaten the job row
+#[tokio::test]
+async fn test_export_over_limit_is_queued_without_loading() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![count_row(SYNC_ROW_LIMIT + 1)]])
+        .append_query_results([vec![make_event(EventStatus::Live)]])
+        .append_query_results([vec![make_job(JobStatus::Pending)]])
+        .append_query_results([vec![make_audit_entry()]])
+        .append_exec_results([
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let outcome = service
+        .export(ExportEntity::Users, ExportParams::default(), "", &claims)
+        .await
+        .unwrap();
+
+    match outcome {
+        ExportOutcome::Queued(job) => assert_eq!(job.id, "job-1"),
+        ExportOutcome::Ready(_) => panic!("big exports should be queued"),
+    }
+}
+
+// reading an archived event is fine, queueing a job in it is a write
+#[tokio::test]
+async fn test_export_over_limit_in_archived_event() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![count_row(SYNC_ROW_LIMIT + 1)]])
+            .append_query_results([vec![make_event(EventStatus::Archived)]])
+            .into_connection(),
+    );
+    let service = make_service_arc(db.clone());
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service
+        .clone()
+        .export(ExportEntity::Users, ExportParams::default(), "", &claims)
+        .await;
+
+    assert_eq!(
+        result.err().unwrap().to_string(),
+        "Bad request: Archived events are read-only"
+    );
+    drop(service);
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(!log.contains("INSERT"));
+}
+
+#[tokio::test]
+async fn test_export_forbidden_for_organizer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("org-id", "organizer");
+
+    let result = service
+        .export(ExportEntity::Users, ExportParams::default(), "", &claims)
+        .await;
+
+    assert_eq!(result.err().unwrap().to_string(), "Forbidden");
+}
+
+#[tokio::test]
+async fn test_export_invalid_filter() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service
+        .export(
+            ExportEntity::Users,
+            ExportParams::default(),
+            "role=overlord",
+            &claims,
+        )
+        .await;
+
+    assert!(
+        result
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("Bad request: Invalid filter")
+    );
+}
+
+#[tokio::test]
+async fn test_start_job_rejects_unknown_columns() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let params = ExportParams {
+        columns: Some("password".to_string()),
+        ..Default::default()
+    };
+
+    let result = service
+        .start_job(ExportEntity::Users, params, "", &claims)
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Unknown column 'password'"
+    );
+}
+
+#[tokio::test]
+async fn test_get_job_not_found() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<JobModel>::new()])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service.get_job("missing", &claims).await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Not found: Export job not found"
+    );
+}
+
+#[tokio::test]
+async fn test_download_unfinished_job() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_job(JobStatus::Running)]])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service.download_job("job-1", &claims).await;
+    assert_eq!(
+        result.err().unwrap().to_string(),
+        "Bad request: Export job has not finished yet"
+    );
+}
@@ -0,0 +1,45 @@
+use circa_backend::export::models::{ExportFormat, ExportTable};
+use circa_backend::export::writer::encode;
+use serde_json::json;
+
+fn make_table() -> ExportTable {
+    ExportTable {
+        columns: vec!["name".to_string(), "note".to_string(), "count".to_string()],
+        rows: vec![
+            vec![json!("Dave"), json!("likes, commas"), json!(3)],
+            vec![json!("Rose"), json!(null), json!(1)],
+        ],
+    }
+}
+
+#[test]
+fn test_encode_csv() {
+    let chunks = encode(&make_table(), ExportFormat::Csv).unwrap();
+    let content = String::from_utf8(chunks.concat()).unwrap();
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(
+        content,
+        "name,note,count\nDave,\"likes, commas\",3\nRose,,1\n"
+    );
+}
+
+#[test]
+fn test_encode_jsonl() {
+    let chunks = encode(&make_table(), ExportFormat::Jsonl).unwrap();
+    let content = String::from_utf8(chunks.concat()).unwrap();
+
+    assert_eq!(
+        content,
+        "{\"name\":\"Dave\",\"note\":\"likes, commas\",\"count\":3}\n{\"name\":\"Rose\",\"note\":null,\"count\":1}\n"
+    );
+}
+
+#[test]
+fn test_encode_xlsx() {
+    let chunks = encode(&make_table(), ExportFormat::Xlsx).unwrap();
+
+    assert_eq!(chunks.len(), 1);
+    // xlsx files are zip archives
+    assert!(chunks[0].starts_with(b"PK"));
+}
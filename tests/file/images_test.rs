@@ -0,0 +1,78 @@
+use circa_backend::file::images::{MEDIUM_SIZE, THUMBNAIL_SIZE, process};
+use circa_backend::file::models::VariantKind;
+use image::{ImageFormat, RgbImage};
+use std::io::Cursor;
+
+fn encode(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+    });
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), format)
+        .unwrap();
+    bytes
+}
+
+// an APP1 segment with just the orientation tag, 6 means turn it clockwise
+fn with_orientation(jpeg: &[u8], orientation: u16) -> Vec<u8> {
+    let mut exif = b"Exif\0\0II*\0\x08\0\0\0\x01\0".to_vec();
+    exif.extend_from_slice(&0x0112u16.to_le_bytes());
+    exif.extend_from_slice(&3u16.to_le_bytes());
+    exif.extend_from_slice(&1u32.to_le_bytes());
+    exif.extend_from_slice(&orientation.to_le_bytes());
+    exif.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+    let mut bytes = jpeg[..2].to_vec();
+    bytes.extend_from_slice(&[0xFF, 0xE1]);
+    bytes.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+    bytes.extend_from_slice(&exif);
+    bytes.extend_from_slice(&jpeg[2..]);
+    bytes
+}
+
+fn is_webp(bytes: &[u8]) -> bool {
+    bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP"
+}
+
+#[test]
+fn test_process_makes_webp_variants() {
+    let variants = process(&encode(2000, 1000, ImageFormat::Png)).unwrap();
+
+    let sizes: Vec<(VariantKind, u32, u32)> = variants
+        .iter()
+        .map(|v| (v.kind, v.width, v.height))
+        .collect();
+    assert_eq!(
+        sizes,
+        vec![
+            (VariantKind::Thumbnail, THUMBNAIL_SIZE, THUMBNAIL_SIZE),
+            (VariantKind::Medium, MEDIUM_SIZE, MEDIUM_SIZE / 2),
+            (VariantKind::Original, 2000, 1000),
+        ]
+    );
+    assert!(variants.iter().all(|v| is_webp(&v.bytes)));
+}
+
+#[test]
+fn test_process_keeps_small_images_as_they_are() {
+    let variants = process(&encode(300, 200, ImageFormat::Png)).unwrap();
+
+    assert_eq!((variants[1].width, variants[1].height), (300, 200));
+}
+
+#[test]
+fn test_process_turns_upright_and_drops_exif() {
+    let jpeg = with_orientation(&encode(400, 200, ImageFormat::Jpeg), 6);
+
+    let variants = process(&jpeg).unwrap();
+
+    let original = &variants[2];
+    assert_eq!((original.width, original.height), (200, 400));
+    assert!(!original.bytes.windows(4).any(|w| w == b"Exif"));
+}
+
+#[test]
+fn test_process_rejects_broken_images() {
+    assert!(process(b"\x89PNG\r\n\x1a\nnot really a picture").is_err());
+}
@@ -0,0 +1,4 @@
+mod images_test;
+mod models_test;
+mod routes_test;
+mod service_test;
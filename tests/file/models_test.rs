@@ -0,0 +1,30 @@
+use circa_backend::file::models::{clean_file_name, sniff_content_type};
+
+#[test]
+fn test_sniff_content_type() {
+    assert_eq!(
+        sniff_content_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0, 0]),
+        Some("image/jpeg")
+    );
+    assert_eq!(
+        sniff_content_type(b"\x89PNG\r\n\x1a\n\0\0"),
+        Some("image/png")
+    );
+    assert_eq!(
+        sniff_content_type(b"RIFF\0\0\0\0WEBPVP8 "),
+        Some("image/webp")
+    );
+    assert_eq!(sniff_content_type(b"%PDF-1.7\n"), Some("application/pdf"));
+    assert_eq!(sniff_content_type(b"<html><script>"), None);
+    assert_eq!(sniff_content_type(b"RIFF\0\0\0\0WAVE"), None);
+}
+
+#[test]
+fn test_clean_file_name() {
+    assert_eq!(clean_file_name("C:\\Users\\me\\logo.png"), "logo.png");
+    assert_eq!(clean_file_name("../../etc/passwd"), "passwd");
+    assert_eq!(clean_file_name(" photo\n.jpg "), "photo.jpg");
+    assert_eq!(clean_file_name(""), "upload");
+    assert_eq!(clean_file_name("dir/"), "upload");
+    assert_eq!(clean_file_name(&"a".repeat(300)).len(), 255);
+}
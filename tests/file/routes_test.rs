@@ -0,0 +1,189 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::{Duration, Utc};
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::file;
+use circa_backend::file::entity::Model;
+use circa_backend::file::repository::FileRepository;
+use circa_backend::file::service::FileService;
+use circa_backend::file::signer::UrlSigner;
+use circa_backend::file::storage::MemoryStorage;
+use circa_backend::social::repository::SocialRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+const BOUNDARY: &str = "circa-boundary";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<FileService> {
+    let db = Arc::new(db);
+    web::Data::new(FileService::new(
+        FileRepository::new(db.clone()),
+        SocialRepository::new(db),
+        Arc::new(MemoryStorage::new()),
+        1024,
+        UrlSigner::new(JWT_SECRET, Duration::minutes(15)),
+    ))
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+fn multipart_body(field: &str, file_name: &str, content_type: &str, bytes: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        BOUNDARY, field, file_name, content_type
+    )
+    .into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+#[actix_web::test]
+async fn test_upload_multipart() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .append_query_results([vec![Model {
+            id: "f1".to_string(),
+            event_id: "event-1".to_string(),
+            name: "flyer.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            size: 9,
+            sha256: "abc".to_string(),
+            storage_key: "abc".to_string(),
+            image_status: None,
+            uploaded_by: "1".to_string(),
+            created_at: Utc::now(),
+        }]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(file::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/files")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        ))
+        .set_payload(multipart_body(
+            "file",
+            "flyer.pdf",
+            "application/pdf",
+            b"%PDF-1.7\n",
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["name"], "flyer.pdf");
+}
+
+#[actix_web::test]
+async fn test_upload_over_limit() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(file::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/files")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        ))
+        .set_payload(multipart_body(
+            "file",
+            "huge.pdf",
+            "application/pdf",
+            &[b'a'; 4096],
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_upload_with_oversized_form_field() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(file::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/files")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        ))
+        .set_payload(multipart_body(
+            "note",
+            "note.txt",
+            "text/plain",
+            &[b'a'; 100 * 1024],
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(
+        body["error"],
+        "Bad request: Form fields other than 'file' can't be larger than 64 KB"
+    );
+}
+
+#[actix_web::test]
+async fn test_download_with_bad_signature() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(file::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/files/f1/download?expires=9999999999&signature=deadbeef")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
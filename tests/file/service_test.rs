@@ -0,0 +1,533 @@
+use chrono::{Duration, Utc};
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::file::entity::{ImageStatus, Model, variant};
+use circa_backend::file::models::{DownloadQuery, Upload, UrlQuery, VariantKind};
+use circa_backend::file::repository::FileRepository;
+use circa_backend::file::service::FileService;
+use circa_backend::file::signer::UrlSigner;
+use circa_backend::file::storage::{MemoryStorage, Storage};
+use circa_backend::social::entity::{self as post, media};
+use circa_backend::social::repository::SocialRepository;
+use image::{ImageFormat, RgbImage};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::io::Cursor;
+use std::sync::Arc;
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\nnot really a picture";
+const PNG_SHA256: &str = "a010b05bd441d6c7d4c3ce317e81256e100141aaeef4b648f47b4986a51594af";
+const PDF: &[u8] = b"%PDF-1.7 not really a document";
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection, storage: Arc<MemoryStorage>) -> FileService {
+    make_service_arc(Arc::new(db), storage)
+}
+
+fn make_service_arc(db: Arc<DatabaseConnection>, storage: Arc<MemoryStorage>) -> FileService {
+    FileService::new(
+        FileRepository::new(db.clone()),
+        SocialRepository::new(db),
+        storage,
+        64,
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+    )
+}
+
+fn make_file(uploaded_by: &str) -> Model {
+    Model {
+        id: "f1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "logo.png".to_string(),
+        content_type: "image/png".to_string(),
+        size: PNG.len() as i64,
+        sha256: PNG_SHA256.to_string(),
+        storage_key: PNG_SHA256.to_string(),
+        image_status: Some(ImageStatus::Pending),
+        uploaded_by: uploaded_by.to_string(),
+        created_at: Utc::now(),
+    }
+}
+
+fn make_document() -> Model {
+    Model {
+        name: "map.pdf".to_string(),
+        content_type: "application/pdf".to_string(),
+        size: PDF.len() as i64,
+        image_status: None,
+        ..make_file("1")
+    }
+}
+
+fn make_variant(kind: variant::Kind) -> variant::Model {
+    variant::Model {
+        id: format!("v-{}", kind),
+        file_id: "f1".to_string(),
+        storage_key: format!("{}-{}.webp", PNG_SHA256, kind.to_string().to_lowercase()),
+        kind,
+        content_type: "image/webp".to_string(),
+        width: 256,
+        height: 256,
+        size: 100,
+        created_at: Utc::now(),
+    }
+}
+
+fn png_upload(content_type: Option<&str>) -> Upload {
+    Upload {
+        name: "logo.png".to_string(),
+        content_type: content_type.map(|c| c.to_string()),
+        bytes: PNG.to_vec(),
+    }
+}
+
+fn exec_ok() -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+#[tokio::test]
+async fn test_upload_stores_content_under_its_hash() {
+    let storage = Arc::new(MemoryStorage::new());
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .append_query_results([vec![make_file("3")]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db, storage.clone());
+
+    let file = service
+        .upload(
+            png_upload(Some("image/png")),
+            &make_claims("3", "volunteer"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(file.content_type, "image/png");
+    assert_eq!(storage.get(&file.sha256).await.unwrap().unwrap(), PNG);
+}
+
+// the content is shared, the record is the uploader's own
+fn make_copy(uploaded_by: &str, status: ImageStatus) -> Model {
+    Model {
+        id: "f2".to_string(),
+        name: "our-logo.png".to_string(),
+        image_status: Some(status),
+        ..make_file(uploaded_by)
+    }
+}
+
+#[tokio::test]
+async fn test_duplicate_upload_gets_its_own_record() {
+    let storage = Arc::new(MemoryStorage::new());
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_file("2")]])
+        .append_query_results([vec![make_copy("3", ImageStatus::Pending)]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db, storage.clone());
+
+    let upload = Upload {
+        name: "our-logo.png".to_string(),
+        ..png_upload(None)
+    };
+    let file = service
+        .upload(upload, &make_claims("3", "volunteer"))
+        .await
+        .unwrap();
+
+    assert_eq!(file.id, "f2");
+    assert_eq!(file.name, "our-logo.png");
+    assert_eq!(file.uploaded_by, "3");
+    assert_eq!(file.sha256, PNG_SHA256);
+    assert!(storage.is_empty());
+}
+
+// the original of a processed image is gone, its variants are what the copy gets
+#[tokio::test]
+async fn test_duplicate_of_processed_image_shares_its_variants() {
+    let storage = Arc::new(MemoryStorage::new());
+    let mut first = make_file("2");
+    first.image_status = Some(ImageStatus::Ready);
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![first]])
+            .append_query_results([vec![make_copy("3", ImageStatus::Ready)]])
+            .append_query_results([vec![make_variant(variant::Kind::Original)]])
+            .append_query_results([vec![make_copy("3", ImageStatus::Ready)]])
+            .append_query_results([vec![make_variant(variant::Kind::Original)]])
+            .append_query_results([vec![make_copy("3", ImageStatus::Ready)]])
+            .append_exec_results([exec_ok(), exec_ok(), exec_ok(), exec_ok()])
+            .into_connection(),
+    );
+    let service = make_service_arc(db.clone(), storage.clone());
+
+    let file = service
+        .upload(png_upload(None), &make_claims("3", "volunteer"))
+        .await
+        .unwrap();
+    drop(service);
+
+    assert_eq!(file.id, "f2");
+    assert_eq!(file.image_status, Some(ImageStatus::Ready.into()));
+    assert!(storage.is_empty());
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"INSERT INTO \"file_variants\""#));
+    assert!(log.contains(r#"String(Some("f2")), String(Some("original"))"#));
+}
+
+#[tokio::test]
+async fn test_upload_rejects_disallowed_types() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db, Arc::new(MemoryStorage::new()));
+
+    let result = service
+        .upload(
+            Upload {
+                name: "page.html".to_string(),
+                content_type: Some("text/html".to_string()),
+                bytes: b"<script>alert(1)</script>".to_vec(),
+            },
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_upload_rejects_mismatched_type() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db, Arc::new(MemoryStorage::new()));
+
+    let result = service
+        .upload(png_upload(Some("image/jpeg")), &make_claims("1", "admin"))
+        .await;
+
+    match result {
+        Err(AppError::BadRequest(message)) => {
+            assert_eq!(message, "File content doesn't match its type 'image/jpeg'")
+        }
+        other => panic!("expected bad request, got {:?}", other.map(|f| f.id)),
+    }
+}
+
+#[tokio::test]
+async fn test_upload_rejects_large_files() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db, Arc::new(MemoryStorage::new()));
+
+    let mut upload = png_upload(None);
+    upload.bytes.extend_from_slice(&[0; 64]);
+    let result = service.upload(upload, &make_claims("1", "admin")).await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_signed_url_downloads_file() {
+    let storage = Arc::new(MemoryStorage::new());
+    storage
+        .put(PNG_SHA256, PDF.to_vec(), "application/pdf")
+        .await
+        .unwrap();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_document()]])
+        .append_query_results([vec![make_document()]])
+        .into_connection();
+    let service = make_service(db, storage);
+
+    let signed = service
+        .get_download_url("f1", UrlQuery::default(), &make_claims("3", "volunteer"))
+        .await
+        .unwrap();
+    assert!(signed.url.starts_with("/files/f1/download?expires="));
+    let query: DownloadQuery =
+        serde_urlencoded::from_str(signed.url.split_once('?').unwrap().1).unwrap();
+
+    let file = service.download("f1", &query).await.unwrap();
+
+    assert_eq!(file.name, "map.pdf");
+    assert_eq!(file.content_type, "application/pdf");
+    assert_eq!(file.bytes, PDF);
+}
+
+#[tokio::test]
+async fn test_signed_url_downloads_image_variant() {
+    let storage = Arc::new(MemoryStorage::new());
+    let key = format!("{}-medium.webp", PNG_SHA256);
+    storage
+        .put(&key, b"webp bytes".to_vec(), "image/webp")
+        .await
+        .unwrap();
+    let ready = Model {
+        image_status: Some(ImageStatus::Ready),
+        ..make_file("1")
+    };
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![ready.clone()]])
+        .append_query_results([vec![ready]])
+        .append_query_results([vec![make_variant(variant::Kind::Medium)]])
+        .into_connection();
+    let service = make_service(db, storage);
+
+    let signed = service
+        .get_download_url(
+            "f1",
+            UrlQuery {
+                variant: Some(VariantKind::Medium),
+            },
+            &make_claims("3", "volunteer"),
+        )
+        .await
+        .unwrap();
+    assert!(signed.url.starts_with("/files/f1/download?variant=medium&"));
+    let query: DownloadQuery =
+        serde_urlencoded::from_str(signed.url.split_once('?').unwrap().1).unwrap();
+
+    let file = service.download("f1", &query).await.unwrap();
+
+    assert_eq!(file.name, "logo.webp");
+    assert_eq!(file.content_type, "image/webp");
+    assert_eq!(file.bytes, b"webp bytes");
+}
+
+#[tokio::test]
+async fn test_variant_link_needs_an_image() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_document()]])
+        .into_connection();
+    let service = make_service(db, Arc::new(MemoryStorage::new()));
+
+    let result = service
+        .get_download_url(
+            "f1",
+            UrlQuery {
+                variant: Some(VariantKind::Thumbnail),
+            },
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_pending_image_is_not_served() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_file("1")]])
+        .append_query_results([vec![make_file("1")]])
+        .into_connection();
+    let service = make_service(db, Arc::new(MemoryStorage::new()));
+
+    let signed = service
+        .get_download_url("f1", UrlQuery::default(), &make_claims("1", "admin"))
+        .await
+        .unwrap();
+    let query: DownloadQuery =
+        serde_urlencoded::from_str(signed.url.split_once('?').unwrap().1).unwrap();
+
+    match service.download("f1", &query).await {
+        Err(AppError::NotFound(message)) => assert_eq!(message, "Image is still being processed"),
+        other => panic!("expected not found, got {:?}", other.map(|f| f.name)),
+    }
+}
+
+#[tokio::test]
+async fn test_process_image_stores_variants_and_drops_upload() {
+    let mut png = Vec::new();
+    RgbImage::new(600, 400)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    let storage = Arc::new(MemoryStorage::new());
+    storage.put(PNG_SHA256, png, "image/png").await.unwrap();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_file("1")]])
+        .append_query_results([vec![make_file("1")]])
+        .append_query_results([vec![make_variant(variant::Kind::Thumbnail)]])
+        .append_query_results([vec![make_variant(variant::Kind::Medium)]])
+        .append_query_results([vec![make_variant(variant::Kind::Original)]])
+        .append_query_results([vec![Model {
+            image_status: Some(ImageStatus::Ready),
+            ..make_file("1")
+        }]])
+        .append_query_results([Vec::<Model>::new()])
+        .append_exec_results([exec_ok(), exec_ok(), exec_ok(), exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db, storage.clone());
+
+    service.process_image("f1").await.unwrap();
+
+    assert_eq!(storage.len(), 3);
+    assert!(!storage.exists(PNG_SHA256).await.unwrap());
+    let original = storage
+        .get(&format!("{}-original.webp", PNG_SHA256))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(&original[8..12], b"WEBP");
+}
+
+#[tokio::test]
+async fn test_process_image_marks_broken_images_failed() {
+    let storage = Arc::new(MemoryStorage::new());
+    storage
+        .put(PNG_SHA256, PNG.to_vec(), "image/png")
+        .await
+        .unwrap();
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_file("1")]])
+            .append_query_results([vec![make_file("1")]])
+            .append_query_results([vec![Model {
+                image_status: Some(ImageStatus::Failed),
+                ..make_file("1")
+            }]])
+            .append_exec_results([exec_ok()])
+            .into_connection(),
+    );
+    let service = FileService::new(
+        FileRepository::new(db.clone()),
+        SocialRepository::new(db.clone()),
+        storage.clone(),
+        64,
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+    );
+
+    service.process_image("f1").await.unwrap();
+    drop(service);
+
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"UPDATE \"files\" SET \"image_status\" = ?"#));
+    assert!(log.contains(r#"String(Some("failed"))"#));
+    assert_eq!(storage.len(), 1);
+}
+
+#[tokio::test]
+async fn test_tampered_and_expired_links_are_refused() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_file("1")]])
+        .into_connection();
+    let db = Arc::new(db);
+    let service = FileService::new(
+        FileRepository::new(db.clone()),
+        SocialRepository::new(db),
+        Arc::new(MemoryStorage::new()),
+        64,
+        UrlSigner::new("test_secret", Duration::seconds(-10)),
+    );
+
+    let signed = service
+        .get_download_url("f1", UrlQuery::default(), &make_claims("1", "admin"))
+        .await
+        .unwrap();
+    let expired: DownloadQuery =
+        serde_urlencoded::from_str(signed.url.split_once('?').unwrap().1).unwrap();
+    let tampered = DownloadQuery {
+        variant: None,
+        expires: expired.expires + 3600,
+        signature: expired.signature.clone(),
+    };
+
+    assert!(matches!(
+        service.download("f1", &expired).await,
+        Err(AppError::Forbidden)
+    ));
+    assert!(matches!(
+        service.download("f1", &tampered).await,
+        Err(AppError::Forbidden)
+    ));
+}
+
+#[tokio::test]
+async fn test_volunteer_cannot_delete_others_files() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_file("2")]])
+        .into_connection();
+    let service = make_service(db, Arc::new(MemoryStorage::new()));
+
+    let result = service
+        .delete_file("f1", &make_claims("3", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_delete_refused_while_a_post_uses_it() {
+    let now = Utc::now();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_file("3")]])
+        .append_query_results([vec![post::Model {
+            id: "p1".to_string(),
+            event_id: "event-1".to_string(),
+            text: "Gates open at 10".to_string(),
+            scheduled_at: Some(now),
+            status: post::Status::Scheduled,
+            reviewed_by: Some("2".to_string()),
+            reviewed_at: Some(now),
+            review_note: None,
+            published_at: None,
+            published_url: None,
+            failure_reason: None,
+            created_by: "1".to_string(),
+            created_at: now,
+            updated_at: now,
+        }]])
+        .append_query_results([Vec::<post::channel::Model>::new()])
+        .append_query_results([vec![media::Model {
+            post_id: "p1".to_string(),
+            file_id: "f1".to_string(),
+            position: 0,
+        }]])
+        .append_query_results([vec![make_file("3")]])
+        .into_connection();
+    let service = make_service(db, Arc::new(MemoryStorage::new()));
+
+    let result = service
+        .delete_file("f1", &make_claims("3", "volunteer"))
+        .await;
+
+    match result {
+        Err(AppError::Conflict(_, details)) => assert_eq!(details[0]["id"], "p1"),
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_delete_keeps_content_still_in_use() {
+    let storage = Arc::new(MemoryStorage::new());
+    storage
+        .put(PNG_SHA256, PNG.to_vec(), "image/png")
+        .await
+        .unwrap();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_file("3")]])
+        .append_query_results([Vec::<post::Model>::new()])
+        .append_query_results([vec![make_file("3")]])
+        .append_query_results([vec![Model {
+            id: "f2".to_string(),
+            event_id: "event-2".to_string(),
+            ..make_file("5")
+        }]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db, storage.clone());
+
+    service
+        .delete_file("f1", &make_claims("3", "volunteer"))
+        .await
+        .unwrap();
+
+    assert_eq!(storage.len(), 1);
+}
@@ -0,0 +1,70 @@
+use circa_backend::label::models::{
+    LabelKind, ScanAction, ScanResult, Scanned, normalize_code, short_code,
+};
+use circa_backend::user::models::{User, UserRole, UserStatus};
+
+#[test]
+fn test_short_code_is_stable() {
+    let id = "019c8555-7a32-719a-bbfc-289d208c2996";
+
+    assert_eq!(
+        short_code(LabelKind::User, id, 0),
+        short_code(LabelKind::User, id, 0)
+    );
+    assert_ne!(
+        short_code(LabelKind::User, id, 0),
+        short_code(LabelKind::User, "019c8555-7a32-7972-8961-f2c2b29ebd22", 0)
+    );
+    assert_ne!(
+        short_code(LabelKind::User, id, 0),
+        short_code(LabelKind::User, id, 1)
+    );
+}
+
+#[test]
+fn test_short_code_shape() {
+    let asset = short_code(LabelKind::Asset, "a1", 0);
+    let user = short_code(LabelKind::User, "a1", 0);
+
+    assert_eq!(asset.len(), 9);
+    assert!(asset.starts_with('A'));
+    assert!(user.starts_with('U'));
+    assert_eq!(asset[1..], user[1..]);
+    assert!(
+        asset[1..]
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+    );
+    assert!(!asset[1..].contains(['I', 'L', 'O', 'U']));
+}
+
+#[test]
+fn test_normalize_code() {
+    assert_eq!(normalize_code(" a3y2-w1jjd "), "A3Y2W1JJD");
+    assert_eq!(normalize_code("Ao1l"), "A011");
+    assert_eq!(normalize_code("UC18 698DP"), "UC18698DP");
+}
+
+#[test]
+fn test_scan_result_shape() {
+    let result = ScanResult {
+        code: "UC18698DP".to_string(),
+        target: Scanned::User(User {
+            id: "1".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            role: UserRole::Staff,
+            status: UserStatus::Active,
+            profile: None,
+            avatar: None,
+        }),
+        actions: vec![ScanAction::View, ScanAction::ViewItems],
+    };
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["kind"], "user");
+    assert_eq!(json["entity"]["email"], "john@example.com");
+    assert_eq!(json["actions"], serde_json::json!(["view", "view_items"]));
+}
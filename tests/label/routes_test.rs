@@ -0,0 +1,174 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::label;
+use circa_backend::label::entity::{self, Kind};
+use circa_backend::label::repository::LabelRepository;
+use circa_backend::label::service::LabelService;
+use circa_backend::logistics::entity::{Model as AssetModel, Ownership, Status, checkout};
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<LabelService> {
+    let db = Arc::new(db);
+    web::Data::new(LabelService::new(
+        LabelRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        UserRepository::new(db),
+    ))
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+fn make_asset() -> AssetModel {
+    let now = Utc::now();
+    AssetModel {
+        id: "a1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Van".to_string(),
+        category: "vehicles".to_string(),
+        quantity: 1,
+        location: "Parking".to_string(),
+        ownership: Ownership::Rented,
+        status: Status::Delivered,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        location_id: None,
+    }
+}
+
+fn code_model() -> entity::Model {
+    entity::Model {
+        code: "A00000001".to_string(),
+        kind: Kind::Asset,
+        target_id: "a1".to_string(),
+        created_at: Utc::now(),
+    }
+}
+
+#[actix_web::test]
+async fn test_get_asset_qr_png() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset()]])
+        .append_query_results([vec![code_model()]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(label::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/labels/assets/a1/qr?format=png&size=128")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "image/png");
+    let body = test::read_body(resp).await;
+    assert!(body.starts_with(b"\x89PNG"));
+}
+
+#[actix_web::test]
+async fn test_get_label_sheet() {
+    let token = make_token("organizer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset()]])
+        .append_query_results([vec![code_model()]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(label::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/labels/sheet")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(serde_json::json!({ "assets": ["a1"] }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/pdf"
+    );
+    let body = test::read_body(resp).await;
+    assert!(body.starts_with(b"%PDF"));
+}
+
+#[actix_web::test]
+async fn test_scan_asset() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![code_model()]])
+        .append_query_results([vec![make_asset()]])
+        .append_query_results([Vec::<checkout::Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(label::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/scan/a0000000l")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "A00000001");
+    assert_eq!(body["kind"], "asset");
+    assert_eq!(body["entity"]["name"], "Van");
+    assert_eq!(
+        body["actions"],
+        serde_json::json!(["view", "view_history", "check_out", "change_status"])
+    );
+}
+
+#[actix_web::test]
+async fn test_scan_requires_auth() {
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(
+                MockDatabase::new(DatabaseBackend::Sqlite).into_connection(),
+            ))
+            .app_data(make_jwt_secret())
+            .configure(label::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/scan/A00000001").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,382 @@
+use chrono::{Duration, Utc};
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::member;
+use circa_backend::label::entity::{self, Kind};
+use circa_backend::label::models::{
+    LabelKind, LabelSheetRequest, QrFormat, QrQuery, ScanAction, Scanned, short_code,
+};
+use circa_backend::label::repository::LabelRepository;
+use circa_backend::label::service::LabelService;
+use circa_backend::logistics::entity::{Model as AssetModel, Ownership, Status, checkout};
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::user::entity::{Model as UserModel, Role, Status as UserStatus};
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{
+    ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult,
+};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> LabelService {
+    let db = Arc::new(db);
+    LabelService::new(
+        LabelRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        UserRepository::new(db),
+    )
+}
+
+fn make_code(code: &str, kind: Kind, target_id: &str) -> entity::Model {
+    entity::Model {
+        code: code.to_string(),
+        kind,
+        target_id: target_id.to_string(),
+        created_at: Utc::now(),
+    }
+}
+
+fn make_asset(status: Status) -> AssetModel {
+    let now = Utc::now();
+    AssetModel {
+        id: "a1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Radio".to_string(),
+        category: "radios".to_string(),
+        quantity: 2,
+        location: "HQ".to_string(),
+        ownership: Ownership::Rented,
+        status,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        location_id: None,
+    }
+}
+
+fn make_checkout(quantity: i32) -> checkout::Model {
+    let now = Utc::now();
+    checkout::Model {
+        id: "c1".to_string(),
+        event_id: "event-1".to_string(),
+        asset_id: "a1".to_string(),
+        user_id: "3".to_string(),
+        quantity,
+        note: "".to_string(),
+        checked_out_by: "1".to_string(),
+        checked_out_at: now,
+        due_at: now + Duration::hours(4),
+        checked_in_by: None,
+        checked_in_at: None,
+        condition: None,
+        return_note: None,
+    }
+}
+
+fn make_user(status: UserStatus) -> UserModel {
+    UserModel {
+        id: "3".to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        status,
+    }
+}
+
+fn make_member(role: Role) -> member::Model {
+    member::Model {
+        event_id: "event-1".to_string(),
+        user_id: "3".to_string(),
+        role,
+        joined_at: Utc::now(),
+    }
+}
+
+// ── labels ───────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_label_reuses_stored_code() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Delivered)]])
+        .append_query_results([vec![make_code("A00000001", Kind::Asset, "a1")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let label = service
+        .get_label(LabelKind::Asset, "a1", &make_claims("1", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(label.code, "A00000001");
+    assert_eq!(label.title, "Radio");
+    assert_eq!(label.subtitle, "radios");
+}
+
+#[tokio::test]
+async fn test_get_label_saves_new_code() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_user(UserStatus::Active)]])
+        .append_query_results([vec![make_member(Role::Volunteer)]])
+        .append_query_results([Vec::<entity::Model>::new()])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![make_code("UC18698DP", Kind::User, "3")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let label = service
+        .get_label(LabelKind::User, "3", &make_claims("3", "volunteer"))
+        .await
+        .unwrap();
+
+    assert_eq!(label.code, "UC18698DP");
+    assert_eq!(label.title, "John Doe");
+    assert_eq!(label.subtitle, "Volunteer");
+}
+
+// the mock can't raise a unique violation, so this one runs on a real database
+#[tokio::test]
+async fn test_code_for_salts_a_taken_code() {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    db.execute_unprepared(
+        "CREATE TABLE scan_codes (
+            code TEXT PRIMARY KEY NOT NULL,
+            kind TEXT NOT NULL,
+            target_id TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            UNIQUE (kind, target_id)
+        )",
+    )
+    .await
+    .unwrap();
+    // something else already holds the code a1 would get
+    db.execute_unprepared(&format!(
+        "INSERT INTO scan_codes VALUES ('{}', 'asset', 'other', '2026-01-01T00:00:00Z')",
+        short_code(LabelKind::Asset, "a1", 0)
+    ))
+    .await
+    .unwrap();
+    let repository = LabelRepository::new(db);
+
+    let code = repository.code_for(LabelKind::Asset, "a1").await.unwrap();
+
+    assert_eq!(code, short_code(LabelKind::Asset, "a1", 1));
+    assert_eq!(
+        repository.code_for(LabelKind::Asset, "a1").await.unwrap(),
+        code
+    );
+    assert_eq!(
+        repository
+            .find_by_code(&code)
+            .await
+            .unwrap()
+            .unwrap()
+            .target_id,
+        "a1"
+    );
+}
+
+#[tokio::test]
+async fn test_get_label_permissions() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let asset = service
+        .get_label(LabelKind::Asset, "a1", &make_claims("1", "volunteer"))
+        .await;
+    let badge = service
+        .get_label(LabelKind::User, "3", &make_claims("1", "staff"))
+        .await;
+
+    assert!(matches!(asset, Err(AppError::Forbidden)));
+    assert!(matches!(badge, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_get_qr_size_out_of_range() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let query = QrQuery {
+        format: QrFormat::Png,
+        size: Some(10_000),
+    };
+
+    let result = service
+        .get_qr(LabelKind::Asset, "a1", query, &make_claims("1", "admin"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_get_sheet_needs_something_to_print() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .get_sheet(LabelSheetRequest::default(), &make_claims("1", "admin"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_get_sheet_renders_pdf() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Delivered)]])
+        .append_query_results([vec![make_code("A00000001", Kind::Asset, "a1")]])
+        .into_connection();
+    let service = make_service(db);
+    let req = LabelSheetRequest {
+        assets: vec!["a1".to_string()],
+        users: vec![],
+    };
+
+    let sheet = service
+        .get_sheet(req, &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(sheet.content_type, "application/pdf");
+    assert!(sheet.bytes.starts_with(b"%PDF"));
+}
+
+// ── scan ─────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_scan_unknown_code() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<entity::Model>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.scan("AZZZZZZZZ", &make_claims("1", "admin")).await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_scan_asset_as_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_code("A00000001", Kind::Asset, "a1")]])
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .append_query_results([vec![make_checkout(2)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .scan("a0000-000l", &make_claims("1", "staff"))
+        .await
+        .unwrap();
+
+    assert!(matches!(result.target, Scanned::Asset(_)));
+    // every unit is out, so nothing left to hand out
+    assert_eq!(
+        result.actions,
+        vec![
+            ScanAction::View,
+            ScanAction::ViewHistory,
+            ScanAction::CheckIn,
+            ScanAction::ChangeStatus,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_scan_asset_as_organizer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_code("A00000001", Kind::Asset, "a1")]])
+        .append_query_results([vec![make_asset(Status::Delivered)]])
+        .append_query_results([Vec::<checkout::Model>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .scan("A00000001", &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.actions,
+        vec![
+            ScanAction::View,
+            ScanAction::ViewHistory,
+            ScanAction::CheckOut,
+            ScanAction::ChangeStatus,
+            ScanAction::Edit,
+            ScanAction::Delete,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_scan_asset_as_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_code("A00000001", Kind::Asset, "a1")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .scan("A00000001", &make_claims("3", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_scan_own_badge_as_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_code("UC18698DP", Kind::User, "3")]])
+        .append_query_results([vec![make_user(UserStatus::Active)]])
+        .append_query_results([vec![make_member(Role::Volunteer)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .scan("UC18698DP", &make_claims("3", "volunteer"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.actions,
+        vec![ScanAction::View, ScanAction::ViewItems, ScanAction::Edit]
+    );
+}
+
+#[tokio::test]
+async fn test_scan_badge_as_organizer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_code("UC18698DP", Kind::User, "3")]])
+        .append_query_results([vec![make_user(UserStatus::Inactive)]])
+        .append_query_results([vec![make_member(Role::Staff)]])
+        .append_query_results([vec![make_checkout(1)]])
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .scan("UC18698DP", &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert!(matches!(result.target, Scanned::User(_)));
+    assert_eq!(
+        result.actions,
+        vec![
+            ScanAction::View,
+            ScanAction::ViewItems,
+            ScanAction::CheckIn,
+            ScanAction::Edit,
+            ScanAction::Activate,
+        ]
+    );
+}
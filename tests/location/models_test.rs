@@ -0,0 +1,74 @@
+use chrono::Utc;
+use circa_backend::location::models::{Location, LocationKind, build_tree, subtree_ids};
+
+fn make_location(id: &str, parent_id: Option<&str>, kind: LocationKind) -> Location {
+    Location {
+        id: id.to_string(),
+        parent_id: parent_id.map(str::to_string),
+        kind,
+        name: id.to_string(),
+        description: "".to_string(),
+        latitude: None,
+        longitude: None,
+        floor_plan: None,
+        floor_x: None,
+        floor_y: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn camp() -> Vec<Location> {
+    vec![
+        make_location("camp", None, LocationKind::Venue),
+        make_location("area-1", Some("camp"), LocationKind::Area),
+        make_location("area-2", Some("camp"), LocationKind::Area),
+        make_location("zone-a", Some("area-1"), LocationKind::Zone),
+        make_location("zone-b", Some("area-2"), LocationKind::Zone),
+        make_location("gate", Some("zone-b"), LocationKind::Point),
+    ]
+}
+
+#[test]
+fn test_kind_parent() {
+    assert_eq!(LocationKind::Venue.parent(), None);
+    assert_eq!(LocationKind::Area.parent(), Some(LocationKind::Venue));
+    assert_eq!(LocationKind::Zone.parent(), Some(LocationKind::Area));
+    assert_eq!(LocationKind::Point.parent(), Some(LocationKind::Zone));
+}
+
+#[test]
+fn test_build_tree() {
+    let tree = build_tree(camp());
+
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0].location.id, "camp");
+    assert_eq!(tree[0].children.len(), 2);
+    assert_eq!(tree[0].children[1].children[0].location.id, "zone-b");
+    assert_eq!(
+        tree[0].children[1].children[0].children[0].location.id,
+        "gate"
+    );
+}
+
+#[test]
+fn test_subtree_ids() {
+    let locations = camp();
+
+    assert_eq!(
+        subtree_ids(&locations, "area-2"),
+        vec!["area-2", "zone-b", "gate"]
+    );
+    assert_eq!(subtree_ids(&locations, "gate"), vec!["gate"]);
+    assert_eq!(subtree_ids(&locations, "camp").len(), 6);
+}
+
+#[test]
+fn test_node_flattens_location() {
+    let tree = build_tree(vec![make_location("camp", None, LocationKind::Venue)]);
+
+    let json = serde_json::to_value(&tree[0]).unwrap();
+    assert_eq!(json["id"], "camp");
+    assert_eq!(json["kind"], "venue");
+    assert_eq!(json["children"], serde_json::json!([]));
+}
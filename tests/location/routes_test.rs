@@ -0,0 +1,113 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::location;
+use circa_backend::location::entity::{Kind, Model};
+use circa_backend::location::repository::LocationRepository;
+use circa_backend::location::service::LocationService;
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<LocationService> {
+    let db = Arc::new(db);
+    web::Data::new(LocationService::new(
+        LocationRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        UserRepository::new(db),
+    ))
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+fn make_location(id: &str, parent_id: Option<&str>, kind: Kind) -> Model {
+    Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        parent_id: parent_id.map(str::to_string),
+        kind,
+        name: id.to_string(),
+        description: "".to_string(),
+        latitude: Some(50.06),
+        longitude: Some(19.94),
+        floor_plan: None,
+        floor_x: None,
+        floor_y: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[actix_web::test]
+async fn test_get_tree_as_volunteer() {
+    let token = make_token("volunteer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![
+            make_location("area-2", Some("camp"), Kind::Area),
+            make_location("camp", None, Kind::Venue),
+        ]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(location::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/locations")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body[0]["id"], "camp");
+    assert_eq!(body[0]["latitude"], 50.06);
+    assert_eq!(body[0]["children"][0]["id"], "area-2");
+}
+
+#[actix_web::test]
+async fn test_delete_location_conflict() {
+    let token = make_token("admin").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![
+            make_location("camp", None, Kind::Venue),
+            make_location("area-2", Some("camp"), Kind::Area),
+        ]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(location::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::delete()
+        .uri("/locations/camp")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["details"][0]["id"], "area-2");
+}
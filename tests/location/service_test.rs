@@ -0,0 +1,335 @@
+use chrono::{Duration, Utc};
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::member;
+use circa_backend::location::entity::{Kind, Model};
+use circa_backend::location::models::{CreateLocationRequest, LocationKind, UpdateLocationRequest};
+use circa_backend::location::repository::LocationRepository;
+use circa_backend::location::service::LocationService;
+use circa_backend::logistics::entity::Model as AssetModel;
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::planner::entity::shift::Model as ShiftModel;
+use circa_backend::planner::entity::shift_assignment::Model as ShiftAssignmentModel;
+use circa_backend::planner::entity::shift_requirement::Model as ShiftRequirementModel;
+use circa_backend::planner::entity::shift_skill::Model as ShiftSkillModel;
+use circa_backend::planner::entity::task::Model as TaskModel;
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::user::entity::{Model as UserModel, Role, Status as UserStatus};
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(role: &str) -> Claims {
+    Claims {
+        sub: "1".to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> LocationService {
+    let db = Arc::new(db);
+    LocationService::new(
+        LocationRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        UserRepository::new(db),
+    )
+}
+
+fn make_location(id: &str, parent_id: Option<&str>, kind: Kind) -> Model {
+    Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        parent_id: parent_id.map(str::to_string),
+        kind,
+        name: id.to_string(),
+        description: "".to_string(),
+        latitude: None,
+        longitude: None,
+        floor_plan: None,
+        floor_x: None,
+        floor_y: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn camp() -> Vec<Model> {
+    vec![
+        make_location("camp", None, Kind::Venue),
+        make_location("area-2", Some("camp"), Kind::Area),
+        make_location("zone-b", Some("area-2"), Kind::Zone),
+    ]
+}
+
+fn create_request(kind: LocationKind, parent_id: Option<&str>) -> CreateLocationRequest {
+    CreateLocationRequest {
+        name: " Zone B ".to_string(),
+        kind,
+        parent_id: parent_id.map(str::to_string),
+        description: "".to_string(),
+        latitude: None,
+        longitude: None,
+        floor_plan: None,
+        floor_x: None,
+        floor_y: None,
+    }
+}
+
+fn make_shift(location_id: &str) -> ShiftModel {
+    let now = Utc::now();
+    ShiftModel {
+        id: "s1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Gate".to_string(),
+        location: "".to_string(),
+        starts_at: now - Duration::hours(1),
+        ends_at: now + Duration::hours(2),
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: Some(location_id.to_string()),
+    }
+}
+
+// ── create / update / delete ─────────────────────────────────────────
+
+#[tokio::test]
+async fn test_create_location_under_the_right_level() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_location("area-2", Some("camp"), Kind::Area)]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![make_location("zone-b", Some("area-2"), Kind::Zone)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let location = service
+        .create_location(
+            create_request(LocationKind::Zone, Some("area-2")),
+            &make_claims("organizer"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(location.kind, LocationKind::Zone);
+    assert_eq!(location.parent_id.as_deref(), Some("area-2"));
+}
+
+#[tokio::test]
+async fn test_create_location_skipping_a_level() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_location("camp", None, Kind::Venue)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .create_location(
+            create_request(LocationKind::Zone, Some("camp")),
+            &make_claims("admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg == "Zones go inside areas"));
+}
+
+#[tokio::test]
+async fn test_create_location_parent_rules() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("admin");
+
+    let venue = service
+        .create_location(create_request(LocationKind::Venue, Some("camp")), &claims)
+        .await;
+    let orphan = service
+        .create_location(create_request(LocationKind::Point, None), &claims)
+        .await;
+
+    assert!(matches!(venue, Err(AppError::BadRequest(_))));
+    assert!(matches!(orphan, Err(AppError::BadRequest(msg)) if msg == "Points go inside zones"));
+}
+
+#[tokio::test]
+async fn test_create_location_coordinates() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("admin");
+
+    let half = CreateLocationRequest {
+        latitude: Some(50.0),
+        ..create_request(LocationKind::Venue, None)
+    };
+    let off_plan = CreateLocationRequest {
+        floor_x: Some(1.5),
+        floor_y: Some(0.5),
+        ..create_request(LocationKind::Venue, None)
+    };
+
+    assert!(matches!(
+        service.create_location(half, &claims).await,
+        Err(AppError::BadRequest(_))
+    ));
+    assert!(matches!(
+        service.create_location(off_plan, &claims).await,
+        Err(AppError::BadRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_manage_locations_forbidden_for_staff() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("staff");
+
+    let create = service
+        .create_location(create_request(LocationKind::Venue, None), &claims)
+        .await;
+    let update = service
+        .update_location("camp", UpdateLocationRequest::default(), &claims)
+        .await;
+    let delete = service.delete_location("camp", true, &claims).await;
+
+    assert!(matches!(create, Err(AppError::Forbidden)));
+    assert!(matches!(update, Err(AppError::Forbidden)));
+    assert!(matches!(delete, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_delete_location_with_sublocations_needs_force() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([camp()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .delete_location("camp", false, &make_claims("admin"))
+        .await;
+
+    match result {
+        Err(AppError::Conflict(msg, details)) => {
+            assert!(msg.contains("2 sublocation(s)"));
+            assert_eq!(details[0]["id"], "area-2");
+        }
+        other => panic!("expected a conflict, got {:?}", other.err()),
+    }
+}
+
+#[tokio::test]
+async fn test_delete_location_with_force() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([camp()])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 3,
+        }])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .delete_location("camp", true, &make_claims("admin"))
+        .await;
+
+    assert!(result.is_ok());
+}
+
+// ── queries ──────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_contents_includes_sublocations() {
+    let now = Utc::now();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([camp()])
+        .append_query_results([vec![make_shift("zone-b")]])
+        .append_query_results([Vec::<ShiftRequirementModel>::new()])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([Vec::<TaskModel>::new()])
+        .append_query_results([Vec::<AssetModel>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let contents = service
+        .get_contents("area-2", &make_claims("staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(contents.location.id, "area-2");
+    assert_eq!(contents.sublocations.len(), 1);
+    assert_eq!(contents.sublocations[0].id, "zone-b");
+    assert_eq!(contents.shifts[0].title, "Gate");
+    assert!(contents.shifts[0].starts_at < now);
+    assert!(contents.tasks.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_contents_forbidden_for_volunteers() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .get_contents("area-2", &make_claims("volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_get_on_shift() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([camp()])
+        .append_query_results([vec![make_shift("zone-b")]])
+        .append_query_results([Vec::<ShiftRequirementModel>::new()])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![ShiftAssignmentModel {
+            shift_id: "s1".to_string(),
+            user_id: "3".to_string(),
+            role: Role::Staff,
+            assigned_at: Utc::now(),
+        }]])
+        .append_query_results([vec![UserModel {
+            id: "3".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: UserStatus::Active,
+        }]])
+        .append_query_results([vec![member::Model {
+            event_id: "event-1".to_string(),
+            user_id: "3".to_string(),
+            role: Role::Staff,
+            joined_at: Utc::now(),
+        }]])
+        .into_connection();
+    let service = make_service(db);
+
+    let people = service
+        .get_on_shift("camp", None, &make_claims("organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(people.len(), 1);
+    assert_eq!(people[0].user.name, "John");
+    assert_eq!(people[0].shift_title, "Gate");
+    assert_eq!(people[0].location_id.as_deref(), Some("zone-b"));
+}
+
+#[tokio::test]
+async fn test_get_on_shift_unknown_location() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([camp()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .get_on_shift("nowhere", None, &make_claims("staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
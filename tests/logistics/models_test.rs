@@ -0,0 +1,56 @@
+use chrono::{Duration, Utc};
+use circa_backend::logistics::entity::{Status, checkout};
+use circa_backend::logistics::models::{AssetStatus, Checkout};
+
+#[test]
+fn test_status_workflow() {
+    assert!(AssetStatus::Requested.can_advance_to(&AssetStatus::Ordered));
+    assert!(AssetStatus::Ordered.can_advance_to(&AssetStatus::Delivered));
+    assert!(AssetStatus::Delivered.can_advance_to(&AssetStatus::InUse));
+    assert!(AssetStatus::InUse.can_advance_to(&AssetStatus::Returned));
+    assert!(AssetStatus::InUse.can_advance_to(&AssetStatus::Lost));
+
+    assert!(!AssetStatus::Requested.can_advance_to(&AssetStatus::Delivered));
+    assert!(!AssetStatus::Delivered.can_advance_to(&AssetStatus::Ordered));
+    assert!(!AssetStatus::Returned.can_advance_to(&AssetStatus::InUse));
+    assert!(!AssetStatus::Lost.can_advance_to(&AssetStatus::Returned));
+}
+
+#[test]
+fn test_status_conversion() {
+    assert_eq!(AssetStatus::from(Status::InUse), AssetStatus::InUse);
+    assert_eq!(Status::from(AssetStatus::Lost), Status::Lost);
+}
+
+#[test]
+fn test_checkout_overdue_only_while_out() {
+    let now = Utc::now();
+    let model = checkout::Model {
+        id: "c1".to_string(),
+        event_id: "event-1".to_string(),
+        asset_id: "a1".to_string(),
+        user_id: "3".to_string(),
+        quantity: 1,
+        note: "".to_string(),
+        checked_out_by: "1".to_string(),
+        checked_out_at: now - Duration::hours(5),
+        due_at: now - Duration::hours(1),
+        checked_in_by: None,
+        checked_in_at: None,
+        condition: None,
+        return_note: None,
+    };
+
+    let out = Checkout::from_model(model.clone(), "Radio".to_string(), now);
+    assert!(out.overdue);
+
+    let returned = Checkout::from_model(
+        checkout::Model {
+            checked_in_at: Some(now),
+            ..model
+        },
+        "Radio".to_string(),
+        now,
+    );
+    assert!(!returned.overdue);
+}
@@ -0,0 +1,128 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::location::repository::LocationRepository;
+use circa_backend::logistics;
+use circa_backend::logistics::entity::{Model, Ownership, Status, checkout};
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::logistics::service::LogisticsService;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<LogisticsService> {
+    let db = Arc::new(db);
+    web::Data::new(LogisticsService::new(
+        LogisticsRepository::new(db.clone()),
+        UserRepository::new(db.clone()),
+        LocationRepository::new(db),
+    ))
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+#[actix_web::test]
+async fn test_get_assets_by_status() {
+    let token = make_token("staff").await;
+    let now = Utc::now();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![Model {
+            id: "a1".to_string(),
+            event_id: "event-1".to_string(),
+            name: "Van".to_string(),
+            category: "vehicles".to_string(),
+            quantity: 1,
+            location: "Parking".to_string(),
+            ownership: Ownership::Rented,
+            status: Status::InUse,
+            notes: "".to_string(),
+            created_by: "1".to_string(),
+            created_at: now,
+            updated_at: now,
+            location_id: None,
+        }]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(logistics::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/logistics/assets?status=in_use")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body[0]["name"], "Van");
+    assert_eq!(body[0]["ownership"], "rented");
+    assert_eq!(body[0]["status"], "in_use");
+}
+
+#[actix_web::test]
+async fn test_volunteer_gets_forbidden() {
+    let token = make_token("volunteer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(logistics::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/logistics/assets")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_volunteer_sees_own_held_items() {
+    let token = make_token("volunteer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<checkout::Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(logistics::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/logistics/users/1/items")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::get()
+        .uri("/logistics/users/2/items")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,498 @@
+use chrono::{Duration, Utc};
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::member;
+use circa_backend::location::repository::LocationRepository;
+use circa_backend::logistics::entity::{Model, Ownership, Status, checkout, history};
+use circa_backend::logistics::models::{
+    AssetFilter, AssetStatus, ChangeAssetStatusRequest, CheckInRequest, CheckOutRequest,
+    CreateAssetRequest, ItemCondition, UpdateAssetRequest,
+};
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::logistics::service::LogisticsService;
+use circa_backend::user::entity::{Model as UserModel, Role, Status as UserStatus};
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> LogisticsService {
+    let db = Arc::new(db);
+    LogisticsService::new(
+        LogisticsRepository::new(db.clone()),
+        UserRepository::new(db.clone()),
+        LocationRepository::new(db),
+    )
+}
+
+fn make_asset(status: Status) -> Model {
+    let now = Utc::now();
+    Model {
+        id: "a1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Radio".to_string(),
+        category: "radios".to_string(),
+        quantity: 10,
+        location: "HQ".to_string(),
+        ownership: Ownership::Rented,
+        status,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        location_id: None,
+    }
+}
+
+fn make_change(from: Option<Status>, to: Status) -> history::Model {
+    history::Model {
+        id: "h1".to_string(),
+        asset_id: "a1".to_string(),
+        from_status: from,
+        to_status: to,
+        note: "".to_string(),
+        changed_by: "1".to_string(),
+        changed_at: Utc::now(),
+    }
+}
+
+fn exec_ok() -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+fn status_request(status: AssetStatus) -> ChangeAssetStatusRequest {
+    ChangeAssetStatusRequest {
+        status,
+        note: "".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_volunteers_have_no_access() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .get_assets(AssetFilter::default(), &make_claims("1", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_staff_can_view_assets() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Delivered)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let assets = service
+        .get_assets(AssetFilter::default(), &make_claims("1", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].status, AssetStatus::Delivered);
+}
+
+#[tokio::test]
+async fn test_create_asset_forbidden_for_staff() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let req = CreateAssetRequest {
+        name: "Radio".to_string(),
+        category: "".to_string(),
+        quantity: None,
+        location: "".to_string(),
+        ownership: None,
+        status: None,
+        notes: "".to_string(),
+        location_id: None,
+    };
+    let result = service.create_asset(req, &make_claims("1", "staff")).await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_create_asset_records_starting_status() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Requested)]])
+        .append_query_results([vec![make_change(None, Status::Requested)]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CreateAssetRequest {
+        name: " Radio ".to_string(),
+        category: "radios".to_string(),
+        quantity: Some(10),
+        location: "HQ".to_string(),
+        ownership: None,
+        status: None,
+        notes: "".to_string(),
+        location_id: None,
+    };
+    let asset = service
+        .create_asset(req, &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(asset.name, "Radio");
+    assert_eq!(asset.status, AssetStatus::Requested);
+}
+
+#[tokio::test]
+async fn test_create_asset_rejects_zero_quantity() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let req = CreateAssetRequest {
+        name: "Radio".to_string(),
+        category: "".to_string(),
+        quantity: Some(0),
+        location: "".to_string(),
+        ownership: None,
+        status: None,
+        notes: "".to_string(),
+        location_id: None,
+    };
+    let result = service.create_asset(req, &make_claims("1", "admin")).await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_update_asset_forbidden_for_staff() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .update_asset(
+            "a1",
+            UpdateAssetRequest::default(),
+            &make_claims("1", "staff"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_staff_advances_status() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Delivered)]])
+        .append_query_results([vec![make_asset(Status::Delivered)]])
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .append_query_results([vec![make_change(Some(Status::Delivered), Status::InUse)]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let asset = service
+        .change_status(
+            "a1",
+            status_request(AssetStatus::InUse),
+            &make_claims("2", "staff"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(asset.status, AssetStatus::InUse);
+}
+
+#[tokio::test]
+async fn test_staff_cant_move_status_back() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .change_status(
+            "a1",
+            status_request(AssetStatus::Delivered),
+            &make_claims("2", "staff"),
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: In use assets can't become delivered"
+    );
+}
+
+#[tokio::test]
+async fn test_organizer_can_correct_status() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Lost)]])
+        .append_query_results([vec![make_asset(Status::Lost)]])
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .append_query_results([vec![make_change(Some(Status::Lost), Status::InUse)]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let asset = service
+        .change_status(
+            "a1",
+            status_request(AssetStatus::InUse),
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(asset.status, AssetStatus::InUse);
+}
+
+#[tokio::test]
+async fn test_same_status_is_rejected() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Ordered)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .change_status(
+            "a1",
+            status_request(AssetStatus::Ordered),
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_get_history() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Ordered)]])
+        .append_query_results([vec![
+            make_change(None, Status::Requested),
+            make_change(Some(Status::Requested), Status::Ordered),
+        ]])
+        .into_connection();
+    let service = make_service(db);
+
+    let history = service
+        .get_history("a1", &make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].from, None);
+    assert_eq!(history[1].from, Some(AssetStatus::Requested));
+    assert_eq!(history[1].to, AssetStatus::Ordered);
+}
+
+#[tokio::test]
+async fn test_delete_missing_asset() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        }])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.delete_asset("a1", &make_claims("1", "admin")).await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+fn make_user(status: UserStatus) -> UserModel {
+    UserModel {
+        id: "3".to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        status,
+    }
+}
+
+fn make_event_member() -> member::Model {
+    member::Model {
+        event_id: "event-1".to_string(),
+        user_id: "3".to_string(),
+        role: Role::Staff,
+        joined_at: Utc::now(),
+    }
+}
+
+fn make_checkout(quantity: i32, returned: bool) -> checkout::Model {
+    let now = Utc::now();
+    checkout::Model {
+        id: "c1".to_string(),
+        event_id: "event-1".to_string(),
+        asset_id: "a1".to_string(),
+        user_id: "3".to_string(),
+        quantity,
+        note: "".to_string(),
+        checked_out_by: "1".to_string(),
+        checked_out_at: now,
+        due_at: now + Duration::hours(4),
+        checked_in_by: returned.then(|| "1".to_string()),
+        checked_in_at: returned.then_some(now),
+        condition: returned.then_some(checkout::Condition::Good),
+        return_note: returned.then(String::new),
+    }
+}
+
+fn check_out_request(quantity: u32) -> CheckOutRequest {
+    CheckOutRequest {
+        user_id: "3".to_string(),
+        quantity: Some(quantity),
+        due_at: Utc::now() + Duration::hours(4),
+        note: "".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_check_out() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .append_query_results([vec![make_user(UserStatus::Active)]])
+        .append_query_results([vec![make_event_member()]])
+        .append_query_results([vec![make_checkout(8, false)]])
+        .append_query_results([vec![make_checkout(2, false)]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let checkout = service
+        .check_out("a1", check_out_request(2), &make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(checkout.asset_name, "Radio");
+    assert_eq!(checkout.quantity, 2);
+    assert!(checkout.is_out());
+}
+
+#[tokio::test]
+async fn test_check_out_more_than_available() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .append_query_results([vec![make_user(UserStatus::Active)]])
+        .append_query_results([vec![make_event_member()]])
+        .append_query_results([vec![make_checkout(9, false)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .check_out("a1", check_out_request(2), &make_claims("2", "staff"))
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Only 1 of 10 left to check out"
+    );
+}
+
+#[tokio::test]
+async fn test_check_out_to_inactive_user() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Delivered)]])
+        .append_query_results([vec![make_user(UserStatus::Inactive)]])
+        .append_query_results([vec![make_event_member()]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .check_out("a1", check_out_request(1), &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_check_out_needs_delivered_asset() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_asset(Status::Ordered)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .check_out("a1", check_out_request(1), &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_check_in_notes_condition() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_checkout(1, false)]])
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .append_query_results([vec![make_checkout(1, false)]])
+        .append_query_results([vec![checkout::Model {
+            condition: Some(checkout::Condition::Damaged),
+            ..make_checkout(1, true)
+        }]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CheckInRequest {
+        condition: ItemCondition::Damaged,
+        note: " cracked antenna ".to_string(),
+    };
+    let checkout = service
+        .check_in("c1", req, &make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    assert!(!checkout.is_out());
+    assert_eq!(checkout.condition, Some(ItemCondition::Damaged));
+}
+
+#[tokio::test]
+async fn test_check_in_twice() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_checkout(1, true)]])
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CheckInRequest {
+        condition: ItemCondition::Good,
+        note: "".to_string(),
+    };
+    let result = service
+        .check_in("c1", req, &make_claims("2", "staff"))
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Item was already checked in"
+    );
+}
+
+#[tokio::test]
+async fn test_overdue_items_are_flagged() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![checkout::Model {
+            due_at: Utc::now() - Duration::hours(1),
+            ..make_checkout(1, false)
+        }]])
+        .append_query_results([vec![make_asset(Status::InUse)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let overdue = service
+        .get_overdue(&make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(overdue.len(), 1);
+    assert!(overdue[0].overdue);
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use circa_backend::planner::conflicts::find_conflicts;
+use circa_backend::planner::entity::shift;
+use circa_backend::planner::models::{Shift, ShiftConflict, ShiftLimits};
+
+const LIMITS: ShiftLimits = ShiftLimits {
+    max_hours_per_day: 10,
+    min_rest_minutes: 60,
+};
+
+fn at(hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2030, 6, 1, 0, 0, 0).unwrap() + Duration::hours(hour as i64)
+}
+
+fn make_shift(id: &str, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> Shift {
+    let model = shift::Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        title: format!("Shift {}", id),
+        location: "bar".to_string(),
+        starts_at,
+        ends_at,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    Shift::from_model(model, vec![], vec![], vec![])
+}
+
+#[test]
+fn test_no_conflicts_with_enough_rest() {
+    let candidate = make_shift("new", at(14), at(18));
+    let schedule = vec![make_shift("a", at(8), at(12))];
+
+    assert!(find_conflicts(&candidate, &schedule, &LIMITS).is_empty());
+}
+
+#[test]
+fn test_overlap_is_reported() {
+    let candidate = make_shift("new", at(10), at(14));
+    let schedule = vec![make_shift("a", at(12), at(16))];
+
+    let conflicts = find_conflicts(&candidate, &schedule, &LIMITS);
+
+    assert!(matches!(
+        &conflicts[0],
+        ShiftConflict::Overlap { shift_id, .. } if shift_id == "a"
+    ));
+}
+
+#[test]
+fn test_short_rest_is_reported() {
+    let candidate = make_shift("new", at(12), at(14));
+    let schedule = vec![make_shift("a", at(8), at(12))];
+
+    let conflicts = find_conflicts(&candidate, &schedule, &LIMITS);
+
+    assert_eq!(
+        conflicts,
+        vec![ShiftConflict::InsufficientRest {
+            shift_id: "a".to_string(),
+            title: "Shift a".to_string(),
+            rest_minutes: 0,
+            required_minutes: 60,
+        }]
+    );
+}
+
+#[test]
+fn test_daily_hours_cap() {
+    let candidate = make_shift("new", at(14), at(20));
+    let schedule = vec![make_shift("a", at(6), at(12))];
+
+    let conflicts = find_conflicts(&candidate, &schedule, &LIMITS);
+
+    assert_eq!(
+        conflicts,
+        vec![ShiftConflict::DailyHours {
+            day: NaiveDate::from_ymd_opt(2030, 6, 1).unwrap(),
+            scheduled_minutes: 720,
+            limit_minutes: 600,
+        }]
+    );
+}
+
+#[test]
+fn test_overnight_shift_counts_per_day() {
+    // 20:00 to 04:00 puts 4h on the first day and 4h on the next, 4h + 5h stays under the cap
+    let candidate = make_shift("new", at(20), at(28));
+    let schedule = vec![make_shift("a", at(30), at(35))];
+
+    assert!(find_conflicts(&candidate, &schedule, &LIMITS).is_empty());
+}
+
+#[test]
+fn test_candidate_is_ignored_in_schedule() {
+    let candidate = make_shift("new", at(10), at(14));
+    let schedule = vec![candidate.clone()];
+
+    assert!(find_conflicts(&candidate, &schedule, &LIMITS).is_empty());
+}
+
+#[test]
+fn test_zero_limits_turn_rules_off() {
+    let limits = ShiftLimits {
+        max_hours_per_day: 0,
+        min_rest_minutes: 0,
+    };
+    let candidate = make_shift("new", at(12), at(24));
+    let schedule = vec![make_shift("a", at(0), at(12))];
+
+    assert!(find_conflicts(&candidate, &schedule, &limits).is_empty());
+}
@@ -0,0 +1,6 @@
+mod conflicts_test;
+mod models_test;
+mod recurrence_test;
+mod roster_test;
+mod routes_test;
+mod service_test;
@@ -0,0 +1,135 @@
+use chrono::{Duration, Utc};
+use circa_backend::planner::entity::shift;
+use circa_backend::planner::entity::task::{Model, Priority, Status};
+use circa_backend::planner::models::{
+    Shift, ShiftAssignment, ShiftRequirement, ShiftSlots, Task, TaskPriority, TaskStatus,
+};
+use circa_backend::user::models::UserRole;
+
+fn make_model() -> Model {
+    let now = Utc::now();
+    Model {
+        id: "t1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Set up stage".to_string(),
+        description: "".to_string(),
+        priority: Priority::High,
+        status: Status::InProgress,
+        due_at: None,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+        completed_by: None,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    }
+}
+
+#[test]
+fn test_priority_conversion() {
+    assert_eq!(Priority::from(TaskPriority::Low), Priority::Low);
+    assert_eq!(Priority::from(TaskPriority::Urgent), Priority::Urgent);
+    assert_eq!(TaskPriority::from(Priority::Medium), TaskPriority::Medium);
+    assert_eq!(TaskPriority::from(Priority::High), TaskPriority::High);
+}
+
+#[test]
+fn test_status_conversion() {
+    assert_eq!(Status::from(TaskStatus::Todo), Status::Todo);
+    assert_eq!(Status::from(TaskStatus::Blocked), Status::Blocked);
+    assert_eq!(TaskStatus::from(Status::InProgress), TaskStatus::InProgress);
+    assert_eq!(TaskStatus::from(Status::Done), TaskStatus::Done);
+}
+
+#[test]
+fn test_task_from_model() {
+    let task = Task::from_model(make_model(), vec!["2".to_string()]);
+
+    assert_eq!(task.id, "t1");
+    assert_eq!(task.priority, TaskPriority::High);
+    assert_eq!(task.status, TaskStatus::InProgress);
+    assert!(task.is_assigned_to("2"));
+    assert!(!task.is_assigned_to("3"));
+}
+
+#[test]
+fn test_status_serializes_snake_case() {
+    let json = serde_json::to_string(&TaskStatus::InProgress).unwrap();
+    assert_eq!(json, "\"in_progress\"");
+}
+
+fn make_shift(requirements: Vec<ShiftRequirement>, assignments: Vec<ShiftAssignment>) -> Shift {
+    let now = Utc::now();
+    let model = shift::Model {
+        id: "s1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Bar".to_string(),
+        location: "bar".to_string(),
+        starts_at: now,
+        ends_at: now + Duration::hours(4),
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    Shift::from_model(model, requirements, vec![], assignments)
+}
+
+fn assignment(user_id: &str, role: UserRole) -> ShiftAssignment {
+    ShiftAssignment {
+        user_id: user_id.to_string(),
+        role,
+        assigned_at: Utc::now(),
+    }
+}
+
+#[test]
+fn test_shift_open_slots() {
+    let shift = make_shift(
+        vec![
+            ShiftRequirement {
+                role: UserRole::Volunteer,
+                headcount: 3,
+            },
+            ShiftRequirement {
+                role: UserRole::Staff,
+                headcount: 1,
+            },
+        ],
+        vec![
+            assignment("2", UserRole::Volunteer),
+            assignment("3", UserRole::Staff),
+        ],
+    );
+
+    let slots = shift.open_slots();
+
+    assert_eq!(slots.len(), 1);
+    assert_eq!(slots[0].role, UserRole::Volunteer);
+    assert_eq!(slots[0].assigned, 1);
+    assert_eq!(slots[0].open, 2);
+    assert!(shift.is_assigned_to("3"));
+}
+
+#[test]
+fn test_fully_staffed_shift_has_no_slots() {
+    let shift = make_shift(
+        vec![ShiftRequirement {
+            role: UserRole::Staff,
+            headcount: 1,
+        }],
+        vec![assignment("3", UserRole::Staff)],
+    );
+
+    assert!(shift.open_slots().is_empty());
+    assert!(ShiftSlots::of(&shift).is_none());
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, TimeZone, Utc};
+use circa_backend::planner::recurrence::{Frequency, MAX_OCCURRENCES, RecurrenceRule};
+
+fn at(day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2030, 6, day, hour, 0, 0).unwrap()
+}
+
+#[test]
+fn test_parse_rule() {
+    let rule = RecurrenceRule::parse("RRULE:FREQ=hourly;INTERVAL=2;COUNT=6").unwrap();
+
+    assert_eq!(rule.frequency, Frequency::Hourly);
+    assert_eq!(rule.interval, 2);
+    assert_eq!(rule.count, Some(6));
+    assert_eq!(rule.to_string(), "FREQ=HOURLY;INTERVAL=2;COUNT=6");
+}
+
+#[test]
+fn test_parse_rejects_unbounded_rules() {
+    let result = RecurrenceRule::parse("FREQ=DAILY");
+
+    assert_eq!(
+        result.unwrap_err(),
+        "The rule needs COUNT or UNTIL to end".to_string()
+    );
+}
+
+#[test]
+fn test_parse_rejects_unknown_parts() {
+    assert!(RecurrenceRule::parse("FREQ=MONTHLY;COUNT=2").is_err());
+    assert!(RecurrenceRule::parse("FREQ=DAILY;BYMONTH=6;COUNT=2").is_err());
+    assert!(RecurrenceRule::parse("FREQ=DAILY;INTERVAL=0;COUNT=2").is_err());
+    assert!(RecurrenceRule::parse("FREQ=DAILY;COUNT=2;UNTIL=20300601").is_err());
+}
+
+#[test]
+fn test_hourly_with_interval() {
+    let rule = RecurrenceRule::parse("FREQ=HOURLY;INTERVAL=2;COUNT=3").unwrap();
+
+    assert_eq!(
+        rule.occurrences(at(1, 8)).unwrap(),
+        vec![at(1, 8), at(1, 10), at(1, 12)]
+    );
+}
+
+#[test]
+fn test_daily_until_bare_date_is_inclusive() {
+    let rule = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20300603").unwrap();
+
+    assert_eq!(
+        rule.occurrences(at(1, 9)).unwrap(),
+        vec![at(1, 9), at(2, 9), at(3, 9)]
+    );
+}
+
+#[test]
+fn test_weekly_by_day() {
+    // 2030-06-03 is a monday
+    let rule = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=WE,MO;COUNT=4").unwrap();
+
+    assert_eq!(
+        rule.occurrences(at(4, 9)).unwrap(),
+        vec![at(5, 9), at(10, 9), at(12, 9), at(17, 9)]
+    );
+}
+
+#[test]
+fn test_too_many_occurrences() {
+    let rule = RecurrenceRule::parse(&format!("FREQ=HOURLY;COUNT={}", MAX_OCCURRENCES + 1));
+
+    assert!(rule.unwrap().occurrences(at(1, 0)).is_err());
+}
+
+#[test]
+fn test_split_keeps_the_total_count() {
+    let rule = RecurrenceRule::parse("FREQ=DAILY;COUNT=5").unwrap();
+
+    let head = rule.ending_before(at(3, 9));
+    let tail = rule.continuing_from(2);
+
+    assert_eq!(
+        head.occurrences(at(1, 9)).unwrap(),
+        vec![at(1, 9), at(2, 9)]
+    );
+    assert_eq!(
+        tail.occurrences(at(3, 9)).unwrap(),
+        vec![at(3, 9), at(4, 9), at(5, 9)]
+    );
+}
@@ -0,0 +1,247 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use circa_backend::planner::entity::shift;
+use circa_backend::planner::models::{
+    ProposedAssignment, Shift, ShiftAssignment, ShiftLimits, ShiftRequirement, UnfilledSlot,
+};
+use circa_backend::planner::roster::{fill_slot, generate, reopen_slot};
+use circa_backend::user::models::{Availability, User, UserProfile, UserRole, UserStatus};
+
+const LIMITS: ShiftLimits = ShiftLimits {
+    max_hours_per_day: 10,
+    min_rest_minutes: 60,
+};
+
+fn at(hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2030, 6, 1, 0, 0, 0).unwrap() + Duration::hours(hour as i64)
+}
+
+fn make_shift(id: &str, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>, staff: u32) -> Shift {
+    let model = shift::Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        title: format!("Shift {}", id),
+        location: "bar".to_string(),
+        starts_at,
+        ends_at,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    };
+    let requirements = vec![ShiftRequirement {
+        role: UserRole::Staff,
+        headcount: staff,
+    }];
+    Shift::from_model(model, requirements, vec![], vec![])
+}
+
+// available all week unless told otherwise
+fn make_person(id: &str) -> User {
+    User {
+        id: id.to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: format!("{}@example.com", id),
+        phone: "123".to_string(),
+        role: UserRole::Staff,
+        status: UserStatus::Active,
+        profile: Some(UserProfile {
+            skills: vec![],
+            preferred_areas: vec![],
+            availability: vec![Availability {
+                id: format!("w-{}", id),
+                starts_at: at(0),
+                ends_at: at(24 * 7),
+                note: "".to_string(),
+            }],
+        }),
+        avatar: None,
+    }
+}
+
+fn people(count: usize) -> Vec<User> {
+    (0..count)
+        .map(|i| make_person(&format!("u{}", i)))
+        .collect()
+}
+
+fn assigned_to<'a>(assignments: &'a [ProposedAssignment], shift_id: &str) -> Vec<&'a str> {
+    assignments
+        .iter()
+        .filter(|a| a.shift_id == shift_id)
+        .map(|a| a.user_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_same_seed_gives_same_roster() {
+    let shifts = vec![
+        make_shift("a", at(8), at(12), 2),
+        make_shift("b", at(14), at(18), 2),
+    ];
+    let people = people(6);
+
+    let first = generate(&shifts, &people, &[], &LIMITS, 42);
+    let second = generate(&shifts, &people, &[], &LIMITS, 42);
+
+    assert_eq!(first.assignments, second.assignments);
+    assert_eq!(first.assignments.len(), 4);
+    assert!(first.unfilled.is_empty());
+}
+
+#[test]
+fn test_different_seeds_can_break_ties_differently() {
+    let shifts = vec![make_shift("a", at(8), at(12), 1)];
+    let people = people(8);
+
+    let picks: Vec<String> = (0..16)
+        .map(|seed| {
+            generate(&shifts, &people, &[], &LIMITS, seed).assignments[0]
+                .user_id
+                .clone()
+        })
+        .collect();
+
+    assert!(picks.iter().any(|p| p != &picks[0]));
+}
+
+#[test]
+fn test_hours_are_spread_out() {
+    let shifts: Vec<Shift> = (0..4)
+        .map(|day| make_shift(&format!("s{}", day), at(day * 24 + 8), at(day * 24 + 12), 1))
+        .collect();
+    let people = people(2);
+
+    let plan = generate(&shifts, &people, &[], &LIMITS, 7);
+
+    let u0 = plan
+        .assignments
+        .iter()
+        .filter(|a| a.user_id == "u0")
+        .count();
+    let u1 = plan
+        .assignments
+        .iter()
+        .filter(|a| a.user_id == "u1")
+        .count();
+    assert_eq!((u0, u1), (2, 2));
+}
+
+#[test]
+fn test_existing_hours_count_towards_fairness() {
+    let mut busy = make_shift("earlier", at(0), at(4), 1);
+    busy.assignments.push(ShiftAssignment {
+        user_id: "u0".to_string(),
+        role: UserRole::Staff,
+        assigned_at: Utc::now(),
+    });
+    let shifts = vec![make_shift("a", at(10), at(14), 1)];
+
+    let plan = generate(&shifts, &people(2), &[busy], &LIMITS, 1);
+
+    assert_eq!(assigned_to(&plan.assignments, "a"), vec!["u1"]);
+}
+
+#[test]
+fn test_unavailable_people_are_skipped() {
+    let shifts = vec![make_shift("a", at(8), at(12), 1)];
+    let mut away = make_person("away");
+    away.profile.as_mut().unwrap().availability.clear();
+
+    let plan = generate(&shifts, &[away], &[], &LIMITS, 1);
+
+    assert!(plan.assignments.is_empty());
+    assert_eq!(plan.unfilled[0].missing, 1);
+    assert!(
+        plan.unfilled[0]
+            .reasons
+            .contains(&"1 not available".to_string())
+    );
+}
+
+#[test]
+fn test_required_skills_are_respected() {
+    let mut shift = make_shift("a", at(8), at(12), 1);
+    shift.required_skills = vec!["forklift".to_string()];
+    let mut driver = make_person("driver");
+    driver.profile.as_mut().unwrap().skills = vec!["forklift".to_string()];
+
+    let plan = generate(&[shift], &[make_person("walker"), driver], &[], &LIMITS, 3);
+
+    assert_eq!(assigned_to(&plan.assignments, "a"), vec!["driver"]);
+}
+
+#[test]
+fn test_rest_between_generated_shifts_is_kept() {
+    let shifts = vec![
+        make_shift("a", at(8), at(12), 1),
+        make_shift("b", at(12), at(14), 1),
+    ];
+
+    let plan = generate(&shifts, &people(1), &[], &LIMITS, 1);
+
+    assert_eq!(assigned_to(&plan.assignments, "a"), vec!["u0"]);
+    assert!(assigned_to(&plan.assignments, "b").is_empty());
+    assert!(
+        plan.unfilled[0]
+            .reasons
+            .iter()
+            .any(|r| r.starts_with("1 would overlap"))
+    );
+}
+
+#[test]
+fn test_existing_assignments_are_kept_and_not_doubled() {
+    let mut shift = make_shift("a", at(8), at(12), 2);
+    shift.assignments.push(ShiftAssignment {
+        user_id: "u0".to_string(),
+        role: UserRole::Staff,
+        assigned_at: Utc::now(),
+    });
+
+    let plan = generate(&[shift.clone()], &people(2), &[shift], &LIMITS, 1);
+
+    assert_eq!(assigned_to(&plan.assignments, "a"), vec!["u1"]);
+}
+
+#[test]
+fn test_missing_role_is_explained() {
+    let shifts = vec![make_shift("a", at(8), at(12), 1)];
+    let mut organizer = make_person("boss");
+    organizer.role = UserRole::Organizer;
+
+    let plan = generate(&shifts, &[organizer], &[], &LIMITS, 1);
+
+    assert_eq!(
+        plan.unfilled,
+        vec![UnfilledSlot {
+            shift_id: "a".to_string(),
+            role: UserRole::Staff,
+            missing: 1,
+            reasons: vec!["Nobody active has the staff role".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn test_fill_and_reopen_slot() {
+    let mut unfilled = vec![UnfilledSlot {
+        shift_id: "a".to_string(),
+        role: UserRole::Staff,
+        missing: 1,
+        reasons: vec!["1 not available".to_string()],
+    }];
+
+    fill_slot(&mut unfilled, "a", &UserRole::Staff);
+    assert!(unfilled.is_empty());
+
+    reopen_slot(&mut unfilled, "a", &UserRole::Staff);
+    reopen_slot(&mut unfilled, "a", &UserRole::Staff);
+    assert_eq!(unfilled[0].missing, 2);
+    assert_eq!(unfilled[0].reasons, vec!["Removed by hand".to_string()]);
+}
@@ -0,0 +1,164 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::location::repository::LocationRepository;
+use circa_backend::planner;
+use circa_backend::planner::entity::task::{Model, Priority, Status};
+use circa_backend::planner::entity::task_assignee::Model as AssigneeModel;
+use circa_backend::planner::models::{CreateTaskRequest, ShiftLimits};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::planner::service::PlannerService;
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<PlannerService> {
+    let db = Arc::new(db);
+    web::Data::new(PlannerService::new(
+        PlannerRepository::new(db.clone()),
+        UserRepository::new(db.clone()),
+        TeamRepository::new(db.clone()),
+        LocationRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+        ShiftLimits {
+            max_hours_per_day: 10,
+            min_rest_minutes: 60,
+        },
+    ))
+}
+
+fn make_task() -> Model {
+    let now = Utc::now();
+    Model {
+        id: "t1".to_string(),
+        event_id: "event-1".to_string(),
+        title: "Set up stage".to_string(),
+        description: "".to_string(),
+        priority: Priority::Medium,
+        status: Status::Todo,
+        due_at: None,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+        completed_by: None,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_get_tasks_route() {
+    let token = make_token("organizer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_task()]])
+        .append_query_results([Vec::<AssigneeModel>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(planner::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/planner/tasks?status=todo")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_get_tasks_route_unauthorized() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(planner::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/planner/tasks").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_create_task_route_forbidden_for_volunteer() {
+    let token = make_token("volunteer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(planner::routes::config),
+    )
+    .await;
+
+    let req_body = CreateTaskRequest {
+        title: "Set up stage".to_string(),
+        description: "".to_string(),
+        priority: None,
+        due_at: None,
+        assignees: vec![],
+        team_id: None,
+        location_id: None,
+    };
+
+    let req = test::TestRequest::post()
+        .uri("/planner/tasks")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req_body)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_coverage_route_forbidden_for_staff() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(planner::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/planner/shifts/coverage")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,1110 @@
+use chrono::{Duration, Utc};
+use circa_backend::audit::entity::Model as AuditModel;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::member::Model as EventMemberModel;
+use circa_backend::location::repository::LocationRepository;
+use circa_backend::modules::user::entity::{
+    Model as UserModel, Role, Status as UserStatus, availability, preferred_area, skill,
+};
+use circa_backend::planner::entity::roster::{Model as RosterModel, Status as RosterStatus};
+use circa_backend::planner::entity::roster_assignment::Model as RosterAssignmentModel;
+use circa_backend::planner::entity::shift::Model as ShiftModel;
+use circa_backend::planner::entity::shift_assignment::Model as ShiftAssignmentModel;
+use circa_backend::planner::entity::shift_requirement::Model as ShiftRequirementModel;
+use circa_backend::planner::entity::shift_skill::Model as ShiftSkillModel;
+use circa_backend::planner::entity::task::{Model, Priority, Status};
+use circa_backend::planner::entity::task_assignee::Model as AssigneeModel;
+use circa_backend::planner::models::{
+    AssignShiftRequest, CandidateQuery, CreateShiftRequest, CreateTaskRequest, EditScope,
+    FreeQuery, GenerateRosterRequest, RecurringShiftRequest, RecurringTaskRequest, ShiftFilter,
+    ShiftLimits, ShiftRequirement, TaskFilter, TaskStatus, UpdateShiftRequest, UpdateTaskRequest,
+};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::planner::service::PlannerService;
+use circa_backend::team::entity::member::Model as MemberModel;
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::user::models::UserRole;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> PlannerService {
+    make_service_arc(Arc::new(db))
+}
+
+fn make_service_arc(db: Arc<DatabaseConnection>) -> PlannerService {
+    PlannerService::new(
+        PlannerRepository::new(db.clone()),
+        UserRepository::new(db.clone()),
+        TeamRepository::new(db.clone()),
+        LocationRepository::new(db.clone()),
+        AuditService::new(AuditRepository::new(db)),
+        ShiftLimits {
+            max_hours_per_day: 10,
+            min_rest_minutes: 60,
+        },
+    )
+}
+
+fn make_task(id: &str, status: Status) -> Model {
+    let now = Utc::now();
+    Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        title: "Set up stage".to_string(),
+        description: "".to_string(),
+        priority: Priority::Medium,
+        status,
+        due_at: None,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+        completed_by: None,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    }
+}
+
+fn make_assignee(task_id: &str, user_id: &str) -> AssigneeModel {
+    AssigneeModel {
+        task_id: task_id.to_string(),
+        user_id: user_id.to_string(),
+    }
+}
+
+fn make_user(id: &str) -> UserModel {
+    UserModel {
+        id: id.to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: format!("{}@example.com", id),
+        phone: "123".to_string(),
+        status: UserStatus::Active,
+    }
+}
+
+fn make_member(user_id: &str) -> EventMemberModel {
+    EventMemberModel {
+        event_id: "event-1".to_string(),
+        user_id: user_id.to_string(),
+        role: Role::Staff,
+        joined_at: Utc::now(),
+    }
+}
+
+fn exec_ok() -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+fn create_request(assignees: Vec<String>) -> CreateTaskRequest {
+    CreateTaskRequest {
+        title: "Set up stage".to_string(),
+        description: "".to_string(),
+        priority: None,
+        due_at: None,
+        assignees,
+        team_id: None,
+        location_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_get_tasks_as_organizer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_task("t1", Status::Todo)]])
+        .append_query_results([vec![make_assignee("t1", "2")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let tasks = service
+        .get_tasks(TaskFilter::default(), &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].assignees, vec!["2".to_string()]);
+}
+
+#[tokio::test]
+async fn test_get_task_hidden_from_unassigned_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_task("t1", Status::Todo)]])
+        .append_query_results([vec![make_assignee("t1", "2")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service.get_task("t1", &make_claims("3", "volunteer")).await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_create_task() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_user("2")]])
+        .append_query_results([vec![make_member("2")]])
+        .append_query_results([vec![make_task("t1", Status::Todo)]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let task = service
+        .create_task(
+            create_request(vec!["2".to_string()]),
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(task.status, TaskStatus::Todo);
+    assert_eq!(task.assignees, vec!["2".to_string()]);
+}
+
+#[tokio::test]
+async fn test_create_task_unknown_assignee() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<UserModel>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .create_task(
+            create_request(vec!["9".to_string()]),
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    match result {
+        Err(AppError::BadRequest(msg)) => assert!(msg.contains("'9'")),
+        other => panic!("expected BadRequest, got {:?}", other.map(|t| t.id)),
+    }
+}
+
+#[tokio::test]
+async fn test_create_task_requires_title() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let mut req = create_request(vec![]);
+    req.title = "  ".to_string();
+    let result = service.create_task(req, &make_claims("1", "admin")).await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_create_task_forbidden_for_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .create_task(create_request(vec![]), &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_update_task_forbidden_for_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .update_task(
+            "t1",
+            UpdateTaskRequest::default(),
+            EditScope::This,
+            &make_claims("2", "volunteer"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_complete_task_as_assigned_staff() {
+    let mut done = make_task("t1", Status::Done);
+    done.completed_by = Some("2".to_string());
+    done.completed_at = Some(Utc::now());
+
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_task("t1", Status::Todo)]])
+        .append_query_results([vec![make_assignee("t1", "2")]])
+        .append_query_results([vec![make_task("t1", Status::Todo)]])
+        .append_query_results([vec![done]])
+        .append_query_results([vec![make_assignee("t1", "2")]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let task = service
+        .complete_task("t1", &make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(task.status, TaskStatus::Done);
+    assert_eq!(task.completed_by.as_deref(), Some("2"));
+}
+
+#[tokio::test]
+async fn test_complete_task_forbidden_for_unassigned_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_task("t1", Status::Todo)]])
+        .append_query_results([vec![make_assignee("t1", "2")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .complete_task("t1", &make_claims("3", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_complete_task_forbidden_for_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .complete_task("t1", &make_claims("2", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_delete_task_not_found() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .delete_task("t1", EditScope::This, &make_claims("1", "admin"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+fn make_shift(id: &str) -> ShiftModel {
+    let now = Utc::now();
+    ShiftModel {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        title: "Bar".to_string(),
+        location: "bar".to_string(),
+        starts_at: now + Duration::hours(1),
+        ends_at: now + Duration::hours(5),
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        series_id: None,
+        occurrence_at: None,
+        is_exception: false,
+        team_id: None,
+        location_id: None,
+    }
+}
+
+fn make_requirement(shift_id: &str, role: Role, headcount: i32) -> ShiftRequirementModel {
+    ShiftRequirementModel {
+        shift_id: shift_id.to_string(),
+        role,
+        headcount,
+    }
+}
+
+fn make_shift_assignment(shift_id: &str, user_id: &str, role: Role) -> ShiftAssignmentModel {
+    ShiftAssignmentModel {
+        shift_id: shift_id.to_string(),
+        user_id: user_id.to_string(),
+        role,
+        assigned_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_create_shift_rejects_inverted_times() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let now = Utc::now();
+
+    let req = CreateShiftRequest {
+        title: "Bar".to_string(),
+        location: "bar".to_string(),
+        starts_at: now,
+        ends_at: now - Duration::hours(1),
+        notes: "".to_string(),
+        requirements: vec![],
+        required_skills: vec![],
+        team_id: None,
+        location_id: None,
+    };
+    let result = service
+        .create_shift(req, &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_create_shift_rejects_duplicate_roles() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let now = Utc::now();
+
+    let requirement = ShiftRequirement {
+        role: UserRole::Volunteer,
+        headcount: 2,
+    };
+    let req = CreateShiftRequest {
+        title: "Bar".to_string(),
+        location: "bar".to_string(),
+        starts_at: now,
+        ends_at: now + Duration::hours(4),
+        notes: "".to_string(),
+        requirements: vec![requirement.clone(), requirement],
+        required_skills: vec![],
+        team_id: None,
+        location_id: None,
+    };
+    let result = service.create_shift(req, &make_claims("1", "admin")).await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_assign_shift_uses_user_role() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([vec![make_user("2")]])
+        .append_query_results([vec![make_member("2")]])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([Vec::<ShiftModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s1", "2", Role::Staff)]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = AssignShiftRequest {
+        user_id: "2".to_string(),
+        role: None,
+    };
+    let assignment = service
+        .assign_shift("s1", req, false, &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(assignment.role, UserRole::Staff);
+}
+
+#[tokio::test]
+async fn test_assign_shift_without_open_slot() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s1", "3", Role::Staff)]])
+        .append_query_results([vec![make_user("2")]])
+        .append_query_results([vec![make_member("2")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = AssignShiftRequest {
+        user_id: "2".to_string(),
+        role: None,
+    };
+    let result = service
+        .assign_shift("s1", req, false, &make_claims("1", "organizer"))
+        .await;
+
+    match result {
+        Err(AppError::BadRequest(msg)) => assert!(msg.contains("staff")),
+        other => panic!("expected BadRequest, got {:?}", other.map(|a| a.user_id)),
+    }
+}
+
+#[tokio::test]
+async fn test_coverage_lists_understaffed_shifts() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_shift("s1"), make_shift("s2")]])
+        .append_query_results([vec![
+            make_requirement("s1", Role::Volunteer, 3),
+            make_requirement("s2", Role::Staff, 1),
+        ]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![
+            make_shift_assignment("s1", "4", Role::Volunteer),
+            make_shift_assignment("s2", "3", Role::Staff),
+        ]])
+        .into_connection();
+    let service = make_service(db);
+
+    let report = service
+        .get_coverage(ShiftFilter::default(), &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(report.total_shifts, 2);
+    assert_eq!(report.understaffed_shifts, 1);
+    assert_eq!(report.open_slots, 2);
+    assert_eq!(report.shifts[0].shift_id, "s1");
+}
+
+#[tokio::test]
+async fn test_coverage_forbidden_for_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .get_coverage(ShiftFilter::default(), &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_open_slots_forbidden_for_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .get_open_slots(ShiftFilter::default(), &make_claims("2", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_get_shift_hidden_from_unassigned_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([Vec::<ShiftRequirementModel>::new()])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s1", "2", Role::Volunteer)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .get_shift("s1", &make_claims("3", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+// s1 runs 1h..5h from now, s2 overlaps it
+fn overlapping_shift_db(extra_queries: bool) -> DatabaseConnection {
+    let mut s2 = make_shift("s2");
+    s2.starts_at += Duration::hours(2);
+    s2.ends_at += Duration::hours(2);
+
+    let mut db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 2)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([vec![make_user("2")]])
+        .append_query_results([vec![make_member("2")]])
+        .append_query_results([vec![make_shift_assignment("s2", "2", Role::Staff)]])
+        .append_query_results([vec![s2]])
+        .append_query_results([vec![make_requirement("s2", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s2", "2", Role::Staff)]]);
+
+    if extra_queries {
+        db = db
+            .append_query_results([vec![AuditModel {
+                id: "a".to_string(),
+                event_id: Some("event-1".to_string()),
+                actor_id: "1".to_string(),
+                action: "planner.assign_forced".to_string(),
+                target_type: "shift".to_string(),
+                target_id: Some("s1".to_string()),
+                details: None,
+                created_at: Utc::now(),
+            }]])
+            .append_query_results([vec![make_shift_assignment("s1", "2", Role::Staff)]])
+            .append_exec_results([exec_ok(), exec_ok()]);
+    }
+
+    db.into_connection()
+}
+
+#[tokio::test]
+async fn test_assign_shift_reports_conflicts() {
+    let service = make_service(overlapping_shift_db(false));
+
+    let req = AssignShiftRequest {
+        user_id: "2".to_string(),
+        role: None,
+    };
+    let result = service
+        .assign_shift("s1", req, false, &make_claims("1", "organizer"))
+        .await;
+
+    match result {
+        Err(AppError::Conflict(_, details)) => {
+            assert_eq!(details[0]["kind"], "overlap");
+            assert_eq!(details[0]["shift_id"], "s2");
+        }
+        other => panic!("expected Conflict, got {:?}", other.map(|a| a.user_id)),
+    }
+}
+
+#[tokio::test]
+async fn test_assign_shift_forced_despite_conflicts() {
+    let service = make_service(overlapping_shift_db(true));
+
+    let req = AssignShiftRequest {
+        user_id: "2".to_string(),
+        role: None,
+    };
+    let assignment = service
+        .assign_shift("s1", req, true, &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(assignment.user_id, "2");
+}
+
+#[tokio::test]
+async fn test_team_lead_cant_force_assignment() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let req = AssignShiftRequest {
+        user_id: "3".to_string(),
+        role: None,
+    };
+    let result = service
+        .assign_shift("s1", req, true, &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_candidates_need_required_skills() {
+    let now = Utc::now();
+    let window = |user_id: &str| availability::Model {
+        id: format!("w{}", user_id),
+        user_id: user_id.to_string(),
+        starts_at: now,
+        ends_at: now + Duration::hours(12),
+        note: "".to_string(),
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 2)]])
+        .append_query_results([vec![ShiftSkillModel {
+            shift_id: "s1".to_string(),
+            skill: "first aid".to_string(),
+        }]])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([vec![window("2"), window("3")]])
+        .append_query_results([vec![skill::Model {
+            user_id: "2".to_string(),
+            skill: "first aid".to_string(),
+        }]])
+        .append_query_results([vec![preferred_area::Model {
+            user_id: "2".to_string(),
+            area: "bar".to_string(),
+        }]])
+        .append_query_results([vec![window("2"), window("3")]])
+        .append_query_results([vec![make_user("2"), make_user("3")]])
+        .append_query_results([vec![make_member("2"), make_member("3")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let candidates = service
+        .get_candidates(
+            "s1",
+            CandidateQuery::default(),
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].user.id, "2");
+    assert!(candidates[0].prefers_area);
+}
+
+#[tokio::test]
+async fn test_free_forbidden_for_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .get_free(FreeQuery::default(), &make_claims("2", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+fn make_roster(id: &str, status: RosterStatus) -> RosterModel {
+    RosterModel {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        status,
+        seed: 1,
+        shift_ids: r#"["s1"]"#.to_string(),
+        unfilled: "[]".to_string(),
+        created_by: "1".to_string(),
+        created_at: Utc::now(),
+        published_at: None,
+        published_by: None,
+    }
+}
+
+fn make_proposal(shift_id: &str, user_id: &str) -> RosterAssignmentModel {
+    RosterAssignmentModel {
+        roster_id: "r1".to_string(),
+        shift_id: shift_id.to_string(),
+        user_id: user_id.to_string(),
+        role: Role::Staff,
+    }
+}
+
+#[tokio::test]
+async fn test_generate_roster_forbidden_for_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .generate_roster(GenerateRosterRequest::default(), &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_discard_published_roster_rejected() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_roster("r1", RosterStatus::Published)]])
+        .append_query_results([Vec::<RosterAssignmentModel>::new()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .discard_roster("r1", &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_publish_roster_skips_filled_slots() {
+    let mut published = make_roster("r1", RosterStatus::Published);
+    published.published_at = Some(Utc::now());
+
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_roster("r1", RosterStatus::Draft)]])
+        .append_query_results([vec![make_proposal("s1", "3")]])
+        // someone took the only place after the draft was made
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s1", "2", Role::Staff)]])
+        .append_query_results([vec![published.clone()]])
+        .append_query_results([vec![AuditModel {
+            id: "a".to_string(),
+            event_id: Some("event-1".to_string()),
+            actor_id: "1".to_string(),
+            action: "planner.roster_publish".to_string(),
+            target_type: "roster".to_string(),
+            target_id: Some("r1".to_string()),
+            details: None,
+            created_at: Utc::now(),
+        }]])
+        .append_query_results([vec![published]])
+        .append_query_results([vec![make_proposal("s1", "3")]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let report = service
+        .publish_roster("r1", &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(report.published, 0);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].reason, "No open staff slot left");
+}
+
+#[tokio::test]
+async fn test_recurring_task_needs_due_at() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let req = RecurringTaskRequest {
+        task: create_request(vec![]),
+        recurrence: "FREQ=DAILY;COUNT=3".to_string(),
+    };
+    let result = service
+        .create_recurring_task(req, &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_recurring_shift_rejects_unbounded_rule() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let now = Utc::now();
+
+    let req = RecurringShiftRequest {
+        shift: CreateShiftRequest {
+            title: "Gate".to_string(),
+            location: "gate".to_string(),
+            starts_at: now,
+            ends_at: now + Duration::hours(2),
+            notes: "".to_string(),
+            requirements: vec![],
+            required_skills: vec![],
+            team_id: None,
+            location_id: None,
+        },
+        recurrence: "FREQ=HOURLY;INTERVAL=2".to_string(),
+    };
+    let result = service
+        .create_recurring_shift(req, &make_claims("1", "organizer"))
+        .await;
+
+    match result {
+        Err(AppError::BadRequest(msg)) => assert!(msg.contains("COUNT or UNTIL")),
+        other => panic!("expected BadRequest, got {:?}", other.map(|s| s.series.id)),
+    }
+}
+
+#[tokio::test]
+async fn test_recurring_task_forbidden_for_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let mut task = create_request(vec![]);
+    task.due_at = Some(Utc::now());
+    let req = RecurringTaskRequest {
+        task,
+        recurrence: "FREQ=DAILY;COUNT=3".to_string(),
+    };
+    let result = service
+        .create_recurring_task(req, &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_publish_roster_skips_assignments_that_now_clash() {
+    let mut published = make_roster("r1", RosterStatus::Published);
+    published.published_at = Some(Utc::now());
+    // put on an overlapping shift by hand after the draft was made
+    let mut s2 = make_shift("s2");
+    s2.starts_at += Duration::hours(2);
+    s2.ends_at += Duration::hours(2);
+
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_roster("r1", RosterStatus::Draft)]])
+        .append_query_results([vec![make_proposal("s1", "3")]])
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([vec![make_user("3")]])
+        .append_query_results([vec![make_member("3")]])
+        .append_query_results([vec![make_shift_assignment("s2", "3", Role::Staff)]])
+        .append_query_results([vec![s2]])
+        .append_query_results([vec![make_requirement("s2", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s2", "3", Role::Staff)]])
+        .append_query_results([vec![published.clone()]])
+        .append_query_results([vec![AuditModel {
+            id: "a".to_string(),
+            event_id: Some("event-1".to_string()),
+            actor_id: "1".to_string(),
+            action: "planner.roster_publish".to_string(),
+            target_type: "roster".to_string(),
+            target_id: Some("r1".to_string()),
+            details: None,
+            created_at: Utc::now(),
+        }]])
+        .append_query_results([vec![published]])
+        .append_query_results([vec![make_proposal("s1", "3")]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let report = service
+        .publish_roster("r1", &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(report.published, 0);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(
+        report.skipped[0].reason,
+        "Clashes with 1 rule(s) on their schedule"
+    );
+}
+
+#[tokio::test]
+async fn test_publish_roster_skips_inactive_people() {
+    let mut published = make_roster("r1", RosterStatus::Published);
+    published.published_at = Some(Utc::now());
+    let mut user = make_user("3");
+    user.status = UserStatus::Inactive;
+
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_roster("r1", RosterStatus::Draft)]])
+        .append_query_results([vec![make_proposal("s1", "3")]])
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([vec![user]])
+        .append_query_results([vec![make_member("3")]])
+        .append_query_results([vec![published.clone()]])
+        .append_query_results([vec![AuditModel {
+            id: "a".to_string(),
+            event_id: Some("event-1".to_string()),
+            actor_id: "1".to_string(),
+            action: "planner.roster_publish".to_string(),
+            target_type: "roster".to_string(),
+            target_id: Some("r1".to_string()),
+            details: None,
+            created_at: Utc::now(),
+        }]])
+        .append_query_results([vec![published]])
+        .append_query_results([vec![make_proposal("s1", "3")]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let report = service
+        .publish_roster("r1", &make_claims("1", "organizer"))
+        .await
+        .unwrap();
+
+    assert_eq!(report.published, 0);
+    assert_eq!(report.skipped[0].reason, "User is inactive");
+}
+
+fn make_membership(user_id: &str, is_lead: bool) -> MemberModel {
+    MemberModel {
+        team_id: "bar".to_string(),
+        user_id: user_id.to_string(),
+        is_lead,
+        joined_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_team_lead_deletes_team_task() {
+    let mut task = make_task("t1", Status::Todo);
+    task.team_id = Some("bar".to_string());
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![task]])
+        .append_query_results([Vec::<AssigneeModel>::new()])
+        .append_query_results([vec![make_membership("2", true)]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .delete_task("t1", EditScope::This, &make_claims("2", "staff"))
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_team_member_cant_edit_team_shift() {
+    let mut shift = make_shift("s1");
+    shift.team_id = Some("bar".to_string());
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![shift]])
+        .append_query_results([Vec::<ShiftRequirementModel>::new()])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([vec![make_membership("2", false)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = UpdateShiftRequest {
+        title: Some("Bar, late".to_string()),
+        ..Default::default()
+    };
+    let result = service
+        .update_shift(
+            "s1",
+            req,
+            EditScope::This,
+            false,
+            &make_claims("2", "staff"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+// s1 has user 2 on it, moving it four hours later runs into their s2
+fn moved_shift_db(forced: bool) -> DatabaseConnection {
+    let mut s2 = make_shift("s2");
+    s2.starts_at += Duration::hours(5);
+    s2.ends_at += Duration::hours(5);
+
+    let mut db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_shift("s1")]])
+        .append_query_results([vec![make_requirement("s1", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s1", "2", Role::Staff)]])
+        .append_query_results([vec![make_shift_assignment("s2", "2", Role::Staff)]])
+        .append_query_results([vec![s2]])
+        .append_query_results([vec![make_requirement("s2", Role::Staff, 1)]])
+        .append_query_results([Vec::<ShiftSkillModel>::new()])
+        .append_query_results([vec![make_shift_assignment("s2", "2", Role::Staff)]]);
+
+    if forced {
+        db = db
+            .append_query_results([vec![AuditModel {
+                id: "a".to_string(),
+                event_id: Some("event-1".to_string()),
+                actor_id: "1".to_string(),
+                action: "planner.move_forced".to_string(),
+                target_type: "shift".to_string(),
+                target_id: Some("s1".to_string()),
+                details: None,
+                created_at: Utc::now(),
+            }]])
+            .append_query_results([vec![make_shift("s1")]])
+            .append_query_results([vec![make_shift("s1")]])
+            .append_query_results([vec![make_requirement("s1", Role::Staff, 1)]])
+            .append_query_results([Vec::<ShiftSkillModel>::new()])
+            .append_query_results([vec![make_shift_assignment("s1", "2", Role::Staff)]])
+            .append_exec_results([exec_ok(), exec_ok()]);
+    }
+
+    db.into_connection()
+}
+
+fn move_request() -> UpdateShiftRequest {
+    let shift = make_shift("s1");
+    UpdateShiftRequest {
+        starts_at: Some(shift.starts_at + Duration::hours(4)),
+        ends_at: Some(shift.ends_at + Duration::hours(4)),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_update_shift_reports_conflicts_for_assigned_people() {
+    let service = make_service(moved_shift_db(false));
+
+    let result = service
+        .update_shift(
+            "s1",
+            move_request(),
+            EditScope::This,
+            false,
+            &make_claims("1", "organizer"),
+        )
+        .await;
+
+    match result {
+        Err(AppError::Conflict(_, details)) => {
+            assert_eq!(details[0]["user_id"], "2");
+            assert_eq!(details[0]["conflicts"][0]["kind"], "overlap");
+            assert_eq!(details[0]["conflicts"][0]["shift_id"], "s2");
+        }
+        other => panic!("expected Conflict, got {:?}", other.map(|s| s.id)),
+    }
+}
+
+#[tokio::test]
+async fn test_update_shift_forced_despite_conflicts() {
+    let db = Arc::new(moved_shift_db(true));
+    let service = make_service_arc(db.clone());
+
+    let shift = service
+        .update_shift(
+            "s1",
+            move_request(),
+            EditScope::This,
+            true,
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+    drop(service);
+
+    assert_eq!(shift.id, "s1");
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains("planner.move_forced"));
+}
+
+#[tokio::test]
+async fn test_team_lead_cant_force_move() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .update_shift(
+            "s1",
+            move_request(),
+            EditScope::This,
+            true,
+            &make_claims("2", "staff"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_lead_cant_create_shift_outside_team() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let now = Utc::now();
+
+    let req = CreateShiftRequest {
+        title: "Gate".to_string(),
+        location: "gate".to_string(),
+        starts_at: now,
+        ends_at: now + Duration::hours(2),
+        notes: "".to_string(),
+        requirements: vec![],
+        required_skills: vec![],
+        team_id: None,
+        location_id: None,
+    };
+    let result = service.create_shift(req, &make_claims("2", "staff")).await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
@@ -0,0 +1,556 @@
+use chrono::Utc;
+use circa_backend::audit::{entity::Model as AuditModel, repository::AuditRepository};
+use circa_backend::auth::models::Claims;
+use circa_backend::calendar::{entity as feed, repository::CalendarRepository};
+use circa_backend::consumable::{entity::movement, repository::ConsumableRepository};
+use circa_backend::event::{entity::member, repository::EventRepository};
+use circa_backend::export::{entity::Model as JobModel, repository::ExportRepository};
+use circa_backend::file::entity::{ImageStatus, Model as FileModel};
+use circa_backend::file::storage::{MemoryStorage, Storage};
+use circa_backend::file::{repository::FileRepository, service::FileService, signer::UrlSigner};
+use circa_backend::logistics::entity::{
+    Model as AssetModel, Ownership, Status as AssetStatus, checkout, history,
+};
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::modules::user::entity::{
+    Model, Role, Status, availability::Model as AvailabilityModel, avatar,
+    preferred_area::Model as AreaModel, skill::Model as SkillModel,
+};
+use circa_backend::planner::entity::{
+    roster, roster_assignment, shift::Model as ShiftModel,
+    shift_assignment::Model as ShiftAssignmentModel, task::Model as TaskModel,
+    task_assignee::Model as AssigneeModel,
+};
+use circa_backend::planner::repository::PlannerRepository;
+use circa_backend::privacy::service::PrivacyService;
+use circa_backend::social::entity::{
+    Model as PostModel, Status as PostStatus, channel::Model as ChannelModel,
+    media::Model as MediaModel,
+};
+use circa_backend::social::repository::SocialRepository;
+use circa_backend::team::{entity as team, repository::TeamRepository};
+use circa_backend::user::models::UserStatus;
+use circa_backend::user::repository::UserRepository;
+use circa_backend::vendor::{entity as vendor, repository::VendorRepository};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> PrivacyService {
+    make_service_with_storage(Arc::new(db), Arc::new(MemoryStorage::new()))
+}
+
+fn make_service_with_storage(
+    db: Arc<DatabaseConnection>,
+    storage: Arc<MemoryStorage>,
+) -> PrivacyService {
+    PrivacyService::new(
+        UserRepository::new(db.clone()),
+        EventRepository::new(db.clone()),
+        AuditRepository::new(db.clone()),
+        ExportRepository::new(db.clone()),
+        PlannerRepository::new(db.clone()),
+        CalendarRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        SocialRepository::new(db.clone()),
+        TeamRepository::new(db.clone()),
+        VendorRepository::new(db.clone()),
+        ConsumableRepository::new(db.clone()),
+        Arc::new(FileService::new(
+            FileRepository::new(db.clone()),
+            SocialRepository::new(db),
+            storage,
+            64,
+            UrlSigner::new("test_secret", chrono::Duration::minutes(15)),
+        )),
+        vec!["admin-id".to_string()],
+    )
+}
+
+fn make_user(name: &str, email: &str, status: Status) -> Model {
+    Model {
+        id: "1".to_string(),
+        name: name.to_string(),
+        surname: "Doe".to_string(),
+        email: email.to_string(),
+        phone: "123".to_string(),
+        status,
+    }
+}
+
+fn make_member(event_id: &str) -> member::Model {
+    member::Model {
+        event_id: event_id.to_string(),
+        user_id: "1".to_string(),
+        role: Role::Volunteer,
+        joined_at: Utc::now(),
+    }
+}
+
+fn make_audit_entry(action: &str) -> AuditModel {
+    AuditModel {
+        id: "a".to_string(),
+        event_id: Some("event-1".to_string()),
+        actor_id: "1".to_string(),
+        action: action.to_string(),
+        target_type: "user".to_string(),
+        target_id: Some("1".to_string()),
+        details: None,
+        created_at: Utc::now(),
+    }
+}
+
+fn make_checkout() -> checkout::Model {
+    let now = Utc::now();
+    checkout::Model {
+        id: "c1".to_string(),
+        event_id: "event-1".to_string(),
+        asset_id: "a1".to_string(),
+        user_id: "1".to_string(),
+        quantity: 1,
+        note: "".to_string(),
+        checked_out_by: "2".to_string(),
+        checked_out_at: now,
+        due_at: now,
+        checked_in_by: Some("2".to_string()),
+        checked_in_at: Some(now),
+        condition: None,
+        return_note: None,
+    }
+}
+
+fn make_asset() -> AssetModel {
+    let now = Utc::now();
+    AssetModel {
+        id: "a1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Van keys".to_string(),
+        category: "keys".to_string(),
+        quantity: 1,
+        location: "".to_string(),
+        ownership: Ownership::Owned,
+        status: AssetStatus::InUse,
+        notes: "".to_string(),
+        created_by: "2".to_string(),
+        created_at: now,
+        updated_at: now,
+        location_id: None,
+    }
+}
+
+fn make_file(id: &str) -> FileModel {
+    FileModel {
+        id: id.to_string(),
+        event_id: "event-2".to_string(),
+        name: "me.png".to_string(),
+        content_type: "image/png".to_string(),
+        size: 3,
+        sha256: "abc".to_string(),
+        storage_key: "abc".to_string(),
+        image_status: Some(ImageStatus::Ready),
+        uploaded_by: "1".to_string(),
+        created_at: Utc::now(),
+    }
+}
+
+// written by someone else and reviewed by user 1
+fn make_post() -> PostModel {
+    PostModel {
+        id: "p1".to_string(),
+        event_id: "event-1".to_string(),
+        text: "Gates open at 10".to_string(),
+        scheduled_at: None,
+        status: PostStatus::Approved,
+        reviewed_by: Some("1".to_string()),
+        reviewed_at: Some(Utc::now()),
+        review_note: None,
+        published_at: None,
+        published_url: None,
+        failure_reason: None,
+        created_by: "2".to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn make_roster() -> roster::Model {
+    roster::Model {
+        id: "r1".to_string(),
+        event_id: "event-1".to_string(),
+        status: roster::Status::Draft,
+        seed: 1,
+        shift_ids: r#"["s1"]"#.to_string(),
+        unfilled: "[]".to_string(),
+        created_by: "2".to_string(),
+        created_at: Utc::now(),
+        published_at: None,
+        published_by: None,
+    }
+}
+
+fn make_team_member(user_id: &str, is_lead: bool) -> team::member::Model {
+    team::member::Model {
+        team_id: "t1".to_string(),
+        user_id: user_id.to_string(),
+        is_lead,
+        joined_at: Utc::now(),
+    }
+}
+
+// due to be received by user 1, signed for by someone else
+fn make_delivery() -> vendor::delivery::Model {
+    let now = Utc::now();
+    vendor::delivery::Model {
+        id: "d1".to_string(),
+        event_id: "event-1".to_string(),
+        vendor_id: "v1".to_string(),
+        description: "Ice, 40 bags".to_string(),
+        window_start: now,
+        window_end: now,
+        receiver_id: Some("1".to_string()),
+        dock: "".to_string(),
+        location_id: None,
+        status: vendor::delivery::Status::Received,
+        received_by: Some("2".to_string()),
+        received_at: Some(now),
+        receive_note: None,
+        created_by: "2".to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn make_vendor() -> vendor::Model {
+    let now = Utc::now();
+    vendor::Model {
+        id: "v1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Ice Co".to_string(),
+        category: "catering".to_string(),
+        email: "".to_string(),
+        phone: "".to_string(),
+        website: "".to_string(),
+        notes: "".to_string(),
+        created_by: "2".to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn exec_ok() -> sea_orm::MockExecResult {
+    sea_orm::MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+#[tokio::test]
+async fn test_export_own_data() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_user("John", "john@example.com", Status::Active)]])
+        .append_query_results([vec![make_member("event-1")]])
+        .append_query_results([vec![SkillModel {
+            user_id: "1".to_string(),
+            skill: "first aid".to_string(),
+        }]])
+        .append_query_results([Vec::<AreaModel>::new()])
+        .append_query_results([Vec::<AvailabilityModel>::new()])
+        .append_query_results([vec![make_member("event-1"), make_member("event-2")]])
+        .append_query_results([vec![make_audit_entry("privacy.erase")]])
+        .append_query_results([Vec::<JobModel>::new()])
+        .append_query_results([Vec::<AssigneeModel>::new()])
+        .append_query_results([Vec::<TaskModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([Vec::<ShiftModel>::new()])
+        .append_query_results([vec![roster_assignment::Model {
+            roster_id: "r1".to_string(),
+            shift_id: "s1".to_string(),
+            user_id: "1".to_string(),
+            role: Role::Volunteer,
+        }]])
+        .append_query_results([vec![make_roster()]])
+        .append_query_results([vec![feed::Model {
+            event_id: "event-1".to_string(),
+            user_id: "1".to_string(),
+            kind: feed::Kind::Personal,
+            token: "secret".to_string(),
+            created_at: Utc::now(),
+        }]])
+        .append_query_results([vec![team::Model {
+            id: "t1".to_string(),
+            event_id: "event-1".to_string(),
+            name: "Bar".to_string(),
+            description: "".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }]])
+        .append_query_results([vec![
+            make_team_member("2", true),
+            make_team_member("1", true),
+        ]])
+        .append_query_results([vec![make_checkout()]])
+        .append_query_results([vec![make_asset()]])
+        .append_query_results([vec![history::Model {
+            id: "h1".to_string(),
+            asset_id: "a1".to_string(),
+            from_status: Some(AssetStatus::Delivered),
+            to_status: AssetStatus::InUse,
+            note: "".to_string(),
+            changed_by: "1".to_string(),
+            changed_at: Utc::now(),
+        }]])
+        .append_query_results([vec![make_delivery()]])
+        .append_query_results([vec![make_vendor()]])
+        .append_query_results([vec![movement::Model {
+            id: "m1".to_string(),
+            consumable_id: "c1".to_string(),
+            kind: movement::Kind::Issued,
+            change: -2,
+            stock_after: 8,
+            reason: "".to_string(),
+            moved_by: "1".to_string(),
+            moved_at: Utc::now(),
+        }]])
+        .append_query_results([vec![make_file("f1")]])
+        .append_query_results([vec![make_post()]])
+        .append_query_results([vec![ChannelModel {
+            post_id: "p1".to_string(),
+            channel: "instagram".to_string(),
+        }]])
+        .append_query_results([Vec::<MediaModel>::new()])
+        .append_query_results([Vec::<AssigneeModel>::new()])
+        .append_query_results([Vec::<TaskModel>::new()])
+        .append_query_results([Vec::<ShiftAssignmentModel>::new()])
+        .append_query_results([Vec::<ShiftModel>::new()])
+        .append_query_results([Vec::<roster_assignment::Model>::new()])
+        .append_query_results([Vec::<feed::Model>::new()])
+        .append_query_results([Vec::<team::Model>::new()])
+        .append_query_results([Vec::<checkout::Model>::new()])
+        .append_query_results([Vec::<history::Model>::new()])
+        .append_query_results([Vec::<vendor::delivery::Model>::new()])
+        .append_query_results([Vec::<movement::Model>::new()])
+        .append_query_results([Vec::<FileModel>::new()])
+        .append_query_results([Vec::<PostModel>::new()])
+        .append_query_results([vec![make_audit_entry("privacy.data_export")]])
+        .append_exec_results([exec_ok()])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("1", "volunteer");
+
+    let data = service.export_user_data("1", &claims).await.unwrap();
+
+    assert_eq!(data.user.email, "john@example.com");
+    assert_eq!(data.memberships.len(), 2);
+    assert_eq!(data.audit_entries.len(), 1);
+    assert!(data.export_jobs.is_empty());
+    assert!(data.assigned_tasks.is_empty());
+    assert!(data.shifts.is_empty());
+    assert_eq!(data.checkouts.len(), 1);
+    assert_eq!(data.checkouts[0].asset_name, "Van keys");
+    assert_eq!(data.roster_proposals.len(), 1);
+    assert_eq!(data.roster_proposals[0].assignments[0].shift_id, "s1");
+    assert_eq!(data.calendar_feeds.len(), 1);
+    assert_eq!(data.teams.len(), 1);
+    assert_eq!(data.teams[0].members.len(), 1);
+    assert!(data.teams[0].members[0].is_lead);
+    assert_eq!(data.asset_status_changes.len(), 1);
+    assert_eq!(data.deliveries.len(), 1);
+    assert_eq!(data.deliveries[0].vendor_name, "Ice Co");
+    assert_eq!(data.stock_movements.len(), 1);
+    assert_eq!(data.uploaded_files.len(), 1);
+    assert_eq!(data.uploaded_files[0].id, "f1");
+    assert_eq!(data.social_posts.len(), 1);
+    assert_eq!(data.social_posts[0].channels, vec!["instagram".to_string()]);
+    assert_eq!(
+        data.user.profile.unwrap().skills,
+        vec!["first aid".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_export_other_user_forbidden() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("2", "organizer");
+
+    let result = service.export_user_data("1", &claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
+
+// their other events are out of an event admin's hands
+#[tokio::test]
+async fn test_export_other_user_forbidden_for_event_admin() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("2", "admin");
+
+    let result = service.export_user_data("1", &claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
+
+#[tokio::test]
+async fn test_export_missing_user() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service.export_user_data("1", &claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Not found: User not found");
+}
+
+#[tokio::test]
+async fn test_erase_as_admin() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_user("John", "john@example.com", Status::Active)]])
+        .append_query_results([vec![make_member("event-1")]])
+        .append_query_results([vec![make_user(
+            "Erased",
+            "erased-1@erased.invalid",
+            Status::Inactive,
+        )]])
+        .append_query_results([Vec::<avatar::Model>::new()])
+        .append_query_results([vec![make_audit_entry("privacy.erase")]])
+        .append_exec_results([
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+        ])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let user = service.erase_user("1", true, &claims).await.unwrap();
+
+    assert_eq!(user.id, "1");
+    assert_eq!(user.name, "Erased");
+    assert_eq!(user.status, UserStatus::Inactive);
+}
+
+// uploads and posts belong to the event, they stay attributed to the anonymized account
+#[tokio::test]
+async fn test_erase_keeps_uploads_and_posts() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_user("John", "john@example.com", Status::Active)]])
+            .append_query_results([vec![make_member("event-1")]])
+            .append_query_results([vec![make_user(
+                "Erased",
+                "erased-1@erased.invalid",
+                Status::Inactive,
+            )]])
+            .append_query_results([Vec::<avatar::Model>::new()])
+            .append_query_results([vec![make_audit_entry("privacy.erase")]])
+            .append_exec_results([
+                exec_ok(),
+                exec_ok(),
+                exec_ok(),
+                exec_ok(),
+                exec_ok(),
+                exec_ok(),
+                exec_ok(),
+            ])
+            .into_connection(),
+    );
+    let service = make_service_with_storage(db.clone(), Arc::new(MemoryStorage::new()));
+    let claims = make_claims("1", "volunteer");
+
+    service.erase_user("1", true, &claims).await.unwrap();
+    drop(service);
+
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"UPDATE \"users\""#));
+    assert!(!log.contains(r#"\"files\""#));
+    assert!(!log.contains(r#"\"social_posts\""#));
+}
+
+#[tokio::test]
+async fn test_erase_deletes_avatar_file() {
+    let file = make_file("f1");
+    let storage = Arc::new(MemoryStorage::new());
+    for key in [
+        "abc",
+        "abc-thumbnail.webp",
+        "abc-medium.webp",
+        "abc-original.webp",
+    ] {
+        storage
+            .put(key, b"img".to_vec(), "image/webp")
+            .await
+            .unwrap();
+    }
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_user("John", "john@example.com", Status::Active)]])
+        .append_query_results([vec![make_member("event-1")]])
+        .append_query_results([vec![make_user(
+            "Erased",
+            "erased-1@erased.invalid",
+            Status::Inactive,
+        )]])
+        .append_query_results([vec![avatar::Model {
+            user_id: "1".to_string(),
+            file_id: "f1".to_string(),
+            updated_at: Utc::now(),
+        }]])
+        .append_query_results([vec![file]])
+        .append_query_results([Vec::<FileModel>::new()])
+        .append_query_results([vec![make_audit_entry("privacy.erase")]])
+        .append_exec_results([
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+            exec_ok(),
+        ])
+        .into_connection();
+    let service = make_service_with_storage(Arc::new(db), storage.clone());
+    let claims = make_claims("1", "volunteer");
+
+    service.erase_user("1", true, &claims).await.unwrap();
+
+    assert!(storage.is_empty());
+}
+
+#[tokio::test]
+async fn test_erase_requires_confirmation() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("1", "volunteer");
+
+    let result = service.erase_user("1", false, &claims).await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Erasure cannot be undone, pass confirm=true to continue"
+    );
+}
+
+#[tokio::test]
+async fn test_erase_other_user_forbidden_for_organizer() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("2", "organizer");
+
+    let result = service.erase_user("1", true, &claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
+
+#[tokio::test]
+async fn test_erase_other_user_forbidden_for_event_admin() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let claims = make_claims("2", "admin");
+
+    let result = service.erase_user("1", true, &claims).await;
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
@@ -0,0 +1,3 @@
+mod models_test;
+mod routes_test;
+mod service_test;
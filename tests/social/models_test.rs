@@ -0,0 +1,76 @@
+use chrono::{Duration, TimeZone, Utc};
+use circa_backend::social::models::{PostStatus, SocialPost, calendar};
+
+fn make_post(id: &str, channels: &[&str], hours: Option<i64>) -> SocialPost {
+    let now = Utc::now();
+    let start = Utc.with_ymd_and_hms(2026, 7, 1, 8, 0, 0).unwrap();
+    SocialPost {
+        id: id.to_string(),
+        text: "See you there".to_string(),
+        channels: channels.iter().map(|c| c.to_string()).collect(),
+        media: vec![],
+        scheduled_at: hours.map(|h| start + Duration::hours(h)),
+        status: PostStatus::Approved,
+        reviewed_by: None,
+        reviewed_at: None,
+        review_note: None,
+        published_at: None,
+        published_url: None,
+        failure_reason: None,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[test]
+fn test_status_workflow() {
+    assert!(PostStatus::Draft.can_become(&PostStatus::InReview));
+    assert!(PostStatus::InReview.can_become(&PostStatus::Approved));
+    assert!(PostStatus::InReview.can_become(&PostStatus::Draft));
+    assert!(PostStatus::Approved.can_become(&PostStatus::Scheduled));
+    assert!(PostStatus::Scheduled.can_become(&PostStatus::Published));
+    assert!(PostStatus::Scheduled.can_become(&PostStatus::Failed));
+    assert!(PostStatus::Failed.can_become(&PostStatus::Scheduled));
+
+    assert!(!PostStatus::Draft.can_become(&PostStatus::Approved));
+    assert!(!PostStatus::InReview.can_become(&PostStatus::Scheduled));
+    assert!(!PostStatus::Published.can_become(&PostStatus::Draft));
+}
+
+#[test]
+fn test_calendar_groups_by_day_and_channel() {
+    let days = calendar(vec![
+        make_post("p2", &["instagram"], Some(4)),
+        make_post("p1", &["instagram", "facebook"], Some(1)),
+        make_post("p3", &["facebook"], Some(26)),
+    ]);
+
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0].day.to_string(), "2026-07-01");
+    assert_eq!(days[0].channels.len(), 2);
+    assert_eq!(days[0].channels[0].channel.as_deref(), Some("facebook"));
+    assert_eq!(days[0].channels[0].posts[0].id, "p1");
+    assert_eq!(days[0].channels[1].channel.as_deref(), Some("instagram"));
+    let ids: Vec<&str> = days[0].channels[1]
+        .posts
+        .iter()
+        .map(|p| p.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["p1", "p2"]);
+    assert_eq!(days[1].day.to_string(), "2026-07-02");
+    assert_eq!(days[1].channels[0].posts[0].id, "p3");
+}
+
+#[test]
+fn test_calendar_skips_unscheduled_posts() {
+    let days = calendar(vec![
+        make_post("p1", &["instagram"], None),
+        make_post("p2", &[], Some(2)),
+    ]);
+
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0].channels.len(), 1);
+    assert_eq!(days[0].channels[0].channel, None);
+    assert_eq!(days[0].channels[0].posts[0].id, "p2");
+}
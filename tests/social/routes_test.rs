@@ -0,0 +1,145 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::{Duration, TimeZone, Utc};
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::file::repository::FileRepository;
+use circa_backend::file::signer::UrlSigner;
+use circa_backend::social;
+use circa_backend::social::entity::{Model, Status, channel, media};
+use circa_backend::social::repository::SocialRepository;
+use circa_backend::social::service::SocialService;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<SocialService> {
+    let db = Arc::new(db);
+    web::Data::new(SocialService::new(
+        SocialRepository::new(db.clone()),
+        FileRepository::new(db.clone()),
+        UrlSigner::new(JWT_SECRET, Duration::minutes(15)),
+        AuditService::new(AuditRepository::new(db)),
+    ))
+}
+
+async fn make_token(role: &str) -> String {
+    generate_jwt("1", "event-1", role, JWT_SECRET)
+        .await
+        .unwrap()
+        .token
+}
+
+fn make_post(id: &str, hour: u32) -> Model {
+    let now = Utc::now();
+    Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        text: "Gates open at 10".to_string(),
+        scheduled_at: Some(Utc.with_ymd_and_hms(2026, 7, 1, hour, 0, 0).unwrap()),
+        status: Status::Scheduled,
+        reviewed_by: Some("1".to_string()),
+        reviewed_at: Some(now),
+        review_note: None,
+        published_at: None,
+        published_url: None,
+        failure_reason: None,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[actix_web::test]
+async fn test_get_calendar() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post("p1", 9), make_post("p2", 18)]])
+        .append_query_results([vec![
+            channel::Model {
+                post_id: "p1".to_string(),
+                channel: "instagram".to_string(),
+            },
+            channel::Model {
+                post_id: "p2".to_string(),
+                channel: "instagram".to_string(),
+            },
+            channel::Model {
+                post_id: "p2".to_string(),
+                channel: "discord".to_string(),
+            },
+        ]])
+        .append_query_results([Vec::<media::Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(social::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/socials/calendar?from=2026-07-01T00:00:00Z")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body[0]["day"], "2026-07-01");
+    assert_eq!(body[0]["channels"][0]["channel"], "discord");
+    assert_eq!(body[0]["channels"][1]["channel"], "instagram");
+    assert_eq!(body[0]["channels"][1]["posts"][1]["id"], "p2");
+    assert_eq!(body[0]["channels"][1]["posts"][0]["status"], "scheduled");
+}
+
+#[actix_web::test]
+async fn test_staff_cannot_create_posts() {
+    let token = make_token("staff").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(social::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/socials/posts")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(serde_json::json!({ "text": "Hi", "channels": ["instagram"] }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_get_posts_forbidden_for_volunteers() {
+    let token = make_token("volunteer").await;
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(social::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/socials/posts")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
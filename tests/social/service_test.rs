@@ -0,0 +1,407 @@
+use chrono::{Duration, Utc};
+use circa_backend::audit::entity::Model as AuditModel;
+use circa_backend::audit::{repository::AuditRepository, service::AuditService};
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::file::entity::Model as FileModel;
+use circa_backend::file::repository::FileRepository;
+use circa_backend::file::signer::UrlSigner;
+use circa_backend::social::entity::{Model, Status, channel, media};
+use circa_backend::social::models::{
+    CreatePostRequest, PostFilter, PostStatus, ReviewRequest, ScheduleRequest, UpdatePostRequest,
+};
+use circa_backend::social::repository::SocialRepository;
+use circa_backend::social::service::SocialService;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: Arc<DatabaseConnection>) -> SocialService {
+    SocialService::new(
+        SocialRepository::new(db.clone()),
+        FileRepository::new(db.clone()),
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+        AuditService::new(AuditRepository::new(db)),
+    )
+}
+
+fn make_post(status: Status) -> Model {
+    let now = Utc::now();
+    Model {
+        id: "p1".to_string(),
+        event_id: "event-1".to_string(),
+        text: "Gates open at 10".to_string(),
+        scheduled_at: None,
+        status,
+        reviewed_by: None,
+        reviewed_at: None,
+        review_note: None,
+        published_at: None,
+        published_url: None,
+        failure_reason: None,
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn make_channel(name: &str) -> channel::Model {
+    channel::Model {
+        post_id: "p1".to_string(),
+        channel: name.to_string(),
+    }
+}
+
+fn make_file() -> FileModel {
+    FileModel {
+        id: "f1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "lineup.pdf".to_string(),
+        content_type: "application/pdf".to_string(),
+        size: 4,
+        sha256: "abc".to_string(),
+        storage_key: "abc".to_string(),
+        image_status: None,
+        uploaded_by: "1".to_string(),
+        created_at: Utc::now(),
+    }
+}
+
+fn make_audit_entry() -> AuditModel {
+    AuditModel {
+        id: "a".to_string(),
+        event_id: Some("event-1".to_string()),
+        actor_id: "1".to_string(),
+        action: "social.approve".to_string(),
+        target_type: "social_post".to_string(),
+        target_id: Some("p1".to_string()),
+        details: None,
+        created_at: Utc::now(),
+    }
+}
+
+fn exec_ok() -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+#[tokio::test]
+async fn test_volunteers_cannot_see_posts() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .get_posts(PostFilter::default(), &make_claims("1", "volunteer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_staff_see_posts_with_media_links() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post(Status::Draft)]])
+        .append_query_results([vec![make_channel("instagram")]])
+        .append_query_results([vec![media::Model {
+            post_id: "p1".to_string(),
+            file_id: "f1".to_string(),
+            position: 0,
+        }]])
+        .append_query_results([vec![make_file()]])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let posts = service
+        .get_posts(PostFilter::default(), &make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(posts[0].channels, vec!["instagram"]);
+    assert_eq!(posts[0].media[0].name, "lineup.pdf");
+    assert!(
+        posts[0].media[0]
+            .url
+            .as_ref()
+            .unwrap()
+            .url
+            .starts_with("/files/f1/download?")
+    );
+}
+
+#[tokio::test]
+async fn test_staff_cannot_create_or_approve() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(Arc::new(db));
+    let claims = make_claims("2", "staff");
+
+    let created = service
+        .create_post(
+            CreatePostRequest {
+                text: "Hi".to_string(),
+                channels: vec![],
+                media: vec![],
+                scheduled_at: None,
+            },
+            &claims,
+        )
+        .await;
+    let approved = service
+        .approve_post("p1", ReviewRequest::default(), &claims)
+        .await;
+
+    assert!(matches!(created, Err(AppError::Forbidden)));
+    assert!(matches!(approved, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_create_post_normalizes_channels() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_post(Status::Draft)]])
+            .append_query_results([vec![make_post(Status::Draft)]])
+            .append_query_results([vec![make_channel("instagram")]])
+            .append_query_results([Vec::<media::Model>::new()])
+            .append_exec_results([exec_ok(), exec_ok(), exec_ok(), exec_ok()])
+            .into_connection(),
+    );
+    let service = make_service(db.clone());
+
+    let post = service
+        .create_post(
+            CreatePostRequest {
+                text: "  Gates open at 10 ".to_string(),
+                channels: vec![" Instagram".to_string(), "instagram".to_string()],
+                media: vec![],
+                scheduled_at: None,
+            },
+            &make_claims("1", "organizer"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(post.status, PostStatus::Draft);
+
+    drop(service);
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"String(Some("Gates open at 10"))"#));
+    assert_eq!(log.matches(r#"String(Some("instagram"))"#).count(), 1);
+}
+
+#[tokio::test]
+async fn test_create_post_rejects_unknown_media() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<FileModel>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .create_post(
+            CreatePostRequest {
+                text: "Hi".to_string(),
+                channels: vec![],
+                media: vec!["nope".to_string()],
+                scheduled_at: None,
+            },
+            &make_claims("1", "organizer"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(m)) if m.contains("nope")));
+}
+
+#[tokio::test]
+async fn test_submit_needs_a_channel() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post(Status::Draft)]])
+        .append_query_results([Vec::<channel::Model>::new()])
+        .append_query_results([Vec::<media::Model>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .submit_post("p1", &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_drafts_cannot_be_approved() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post(Status::Draft)]])
+        .append_query_results([vec![make_channel("instagram")]])
+        .append_query_results([Vec::<media::Model>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .approve_post(
+            "p1",
+            ReviewRequest::default(),
+            &make_claims("2", "organizer"),
+        )
+        .await;
+
+    assert!(
+        matches!(result, Err(AppError::BadRequest(m)) if m == "Post can't go from Draft to Approved")
+    );
+}
+
+#[tokio::test]
+async fn test_authors_cannot_approve_their_own_posts() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post(Status::InReview)]])
+        .append_query_results([vec![make_channel("instagram")]])
+        .append_query_results([Vec::<media::Model>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .approve_post(
+            "p1",
+            ReviewRequest::default(),
+            &make_claims("1", "organizer"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_admins_can_approve_their_own_posts() {
+    let mut approved = make_post(Status::Approved);
+    approved.reviewed_by = Some("1".to_string());
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post(Status::InReview)]])
+        .append_query_results([vec![make_channel("instagram")]])
+        .append_query_results([Vec::<media::Model>::new()])
+        .append_query_results([vec![make_post(Status::InReview)]])
+        .append_query_results([vec![approved]])
+        .append_query_results([vec![make_audit_entry()]])
+        .append_exec_results([exec_ok(), exec_ok()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let post = service
+        .approve_post("p1", ReviewRequest::default(), &make_claims("1", "admin"))
+        .await
+        .unwrap();
+
+    assert_eq!(post.status, PostStatus::Approved);
+}
+
+#[tokio::test]
+async fn test_approve_records_audit_entry() {
+    let mut approved = make_post(Status::Approved);
+    approved.reviewed_by = Some("2".to_string());
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_post(Status::InReview)]])
+            .append_query_results([vec![make_channel("instagram")]])
+            .append_query_results([Vec::<media::Model>::new()])
+            .append_query_results([vec![make_post(Status::InReview)]])
+            .append_query_results([vec![approved]])
+            .append_query_results([vec![make_audit_entry()]])
+            .append_exec_results([exec_ok(), exec_ok()])
+            .into_connection(),
+    );
+    let service = make_service(db.clone());
+
+    let post = service
+        .approve_post(
+            "p1",
+            ReviewRequest {
+                note: "Looks good".to_string(),
+            },
+            &make_claims("2", "organizer"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(post.status, PostStatus::Approved);
+    assert_eq!(post.channels, vec!["instagram"]);
+
+    drop(service);
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains("social.approve"));
+    assert!(log.contains("Looks good"));
+}
+
+#[tokio::test]
+async fn test_schedule_rejects_past_times() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post(Status::Approved)]])
+        .append_query_results([vec![make_channel("instagram")]])
+        .append_query_results([Vec::<media::Model>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .schedule_post(
+            "p1",
+            ScheduleRequest {
+                scheduled_at: Some(Utc::now() - Duration::hours(1)),
+            },
+            &make_claims("1", "organizer"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_published_posts_cannot_be_changed() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_post(Status::Published)]])
+        .append_query_results([vec![make_channel("instagram")]])
+        .append_query_results([Vec::<media::Model>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let result = service
+        .update_post(
+            "p1",
+            UpdatePostRequest {
+                text: Some("Edited".to_string()),
+                ..UpdatePostRequest::default()
+            },
+            &make_claims("1", "admin"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_calendar_keeps_to_the_channel_asked_for() {
+    let mut post = make_post(Status::Scheduled);
+    post.scheduled_at = Some(Utc::now() + Duration::days(1));
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![post]])
+        .append_query_results([vec![make_channel("discord"), make_channel("instagram")]])
+        .append_query_results([Vec::<media::Model>::new()])
+        .into_connection();
+    let service = make_service(Arc::new(db));
+
+    let days = service
+        .get_calendar(
+            PostFilter {
+                channel: Some("Discord".to_string()),
+                ..PostFilter::default()
+            },
+            &make_claims("2", "staff"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(days[0].channels.len(), 1);
+    assert_eq!(days[0].channels[0].channel.as_deref(), Some("discord"));
+}
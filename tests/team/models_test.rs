@@ -0,0 +1,42 @@
+use chrono::Utc;
+use circa_backend::team::entity::{Model, member};
+use circa_backend::team::models::{Team, TeamMember};
+
+fn make_team() -> Team {
+    let now = Utc::now();
+    let model = Model {
+        id: "t1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Bar".to_string(),
+        description: "".to_string(),
+        created_at: now,
+        updated_at: now,
+    };
+    let members = vec![
+        member::Model {
+            team_id: "t1".to_string(),
+            user_id: "lead".to_string(),
+            is_lead: true,
+            joined_at: now,
+        },
+        member::Model {
+            team_id: "t1".to_string(),
+            user_id: "member".to_string(),
+            is_lead: false,
+            joined_at: now,
+        },
+    ];
+
+    Team::from_model(model, members.into_iter().map(TeamMember::from).collect())
+}
+
+#[test]
+fn test_team_leads_and_members() {
+    let team = make_team();
+
+    assert_eq!(team.leads(), vec!["lead"]);
+    assert!(team.is_lead("lead"));
+    assert!(!team.is_lead("member"));
+    assert!(team.is_member("member"));
+    assert!(!team.is_member("stranger"));
+}
@@ -0,0 +1,86 @@
+use actix_web::{App, http::StatusCode, test, web};
+use chrono::Utc;
+use circa_backend::auth::service::generate_jwt;
+use circa_backend::team;
+use circa_backend::team::entity::{Model, member};
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::team::service::TeamService;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
+
+const JWT_SECRET: &str = "test_secret";
+
+fn make_jwt_secret() -> web::Data<String> {
+    web::Data::new(JWT_SECRET.to_string())
+}
+
+fn make_app_data(db: DatabaseConnection) -> web::Data<TeamService> {
+    let db = Arc::new(db);
+    web::Data::new(TeamService::new(
+        TeamRepository::new(db.clone()),
+        UserRepository::new(db),
+    ))
+}
+
+#[actix_web::test]
+async fn test_get_teams() {
+    let token = generate_jwt("1", "event-1", "volunteer", JWT_SECRET)
+        .await
+        .unwrap()
+        .token;
+    let now = Utc::now();
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![Model {
+            id: "t1".to_string(),
+            event_id: "event-1".to_string(),
+            name: "Bar".to_string(),
+            description: "".to_string(),
+            created_at: now,
+            updated_at: now,
+        }]])
+        .append_query_results([vec![member::Model {
+            team_id: "t1".to_string(),
+            user_id: "2".to_string(),
+            is_lead: true,
+            joined_at: now,
+        }]])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(team::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/teams")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body[0]["name"], "Bar");
+    assert_eq!(body[0]["members"][0]["is_lead"], true);
+}
+
+#[actix_web::test]
+async fn test_teams_require_auth() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(make_app_data(db))
+            .app_data(make_jwt_secret())
+            .configure(team::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/teams").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,221 @@
+use chrono::Utc;
+use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::member as event_member;
+use circa_backend::modules::user::entity::{Model as UserModel, Role, Status};
+use circa_backend::team::entity::{Model, member};
+use circa_backend::team::models::{AddMemberRequest, CreateTeamRequest, UpdateTeamRequest};
+use circa_backend::team::repository::TeamRepository;
+use circa_backend::team::service::TeamService;
+use circa_backend::user::repository::UserRepository;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use std::sync::Arc;
+
+fn make_claims(sub: &str, role: &str) -> Claims {
+    Claims {
+        sub: sub.to_string(),
+        event: "event-1".to_string(),
+        role: role.to_string(),
+        exp: 9999999999,
+    }
+}
+
+fn make_service(db: DatabaseConnection) -> TeamService {
+    let db = Arc::new(db);
+    TeamService::new(TeamRepository::new(db.clone()), UserRepository::new(db))
+}
+
+fn make_team(id: &str, name: &str) -> Model {
+    let now = Utc::now();
+    Model {
+        id: id.to_string(),
+        event_id: "event-1".to_string(),
+        name: name.to_string(),
+        description: "".to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn make_member(user_id: &str, is_lead: bool) -> member::Model {
+    member::Model {
+        team_id: "t1".to_string(),
+        user_id: user_id.to_string(),
+        is_lead,
+        joined_at: Utc::now(),
+    }
+}
+
+fn make_user(id: &str) -> UserModel {
+    UserModel {
+        id: id.to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: format!("{}@example.com", id),
+        phone: "123".to_string(),
+        status: Status::Active,
+    }
+}
+
+fn make_event_member(user_id: &str, role: Role) -> event_member::Model {
+    event_member::Model {
+        event_id: "event-1".to_string(),
+        user_id: user_id.to_string(),
+        role,
+        joined_at: Utc::now(),
+    }
+}
+
+// the team with a staff lead "2" and a plain member "3"
+fn team_db() -> MockDatabase {
+    MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_team("t1", "Bar")]])
+        .append_query_results([vec![make_member("2", true), make_member("3", false)]])
+}
+
+#[tokio::test]
+async fn test_create_team_forbidden_for_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let req = CreateTeamRequest {
+        name: "Bar".to_string(),
+        description: "".to_string(),
+        leads: vec![],
+    };
+    let result = service.create_team(req, &make_claims("2", "staff")).await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_create_team_rejects_taken_name() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_team("t1", "Bar")]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CreateTeamRequest {
+        name: " Bar ".to_string(),
+        description: "".to_string(),
+        leads: vec![],
+    };
+    let result = service
+        .create_team(req, &make_claims("1", "organizer"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_volunteer_cant_lead() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .append_query_results([vec![make_user("9")]])
+        .append_query_results([vec![make_event_member("9", Role::Volunteer)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CreateTeamRequest {
+        name: "Bar".to_string(),
+        description: "".to_string(),
+        leads: vec!["9".to_string()],
+    };
+    let result = service.create_team(req, &make_claims("1", "admin")).await;
+
+    match result {
+        Err(AppError::BadRequest(msg)) => assert_eq!(msg, "Volunteers can't lead a team"),
+        other => panic!("expected BadRequest, got {:?}", other.map(|t| t.id)),
+    }
+}
+
+#[tokio::test]
+async fn test_lead_adds_member() {
+    let db = team_db()
+        .append_query_results([vec![make_user("4")]])
+        .append_query_results([vec![make_event_member("4", Role::Volunteer)]])
+        .append_query_results([Vec::<member::Model>::new()])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![make_member("4", false)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = AddMemberRequest {
+        user_id: "4".to_string(),
+        is_lead: false,
+    };
+    let member = service
+        .add_member("t1", req, &make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    assert_eq!(member.user_id, "4");
+    assert!(!member.is_lead);
+}
+
+#[tokio::test]
+async fn test_lead_cant_hand_out_lead() {
+    let db = team_db().into_connection();
+    let service = make_service(db);
+
+    let req = AddMemberRequest {
+        user_id: "4".to_string(),
+        is_lead: true,
+    };
+    let result = service
+        .add_member("t1", req, &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_member_cant_run_team() {
+    let db = team_db().into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .update_team(
+            "t1",
+            UpdateTeamRequest::default(),
+            &make_claims("3", "staff"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_lead_cant_remove_other_lead() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_team("t1", "Bar")]])
+        .append_query_results([vec![make_member("2", true), make_member("5", true)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .remove_member("t1", "5", &make_claims("2", "staff"))
+        .await;
+
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_member_can_leave() {
+    let db = team_db()
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .remove_member("t1", "3", &make_claims("3", "volunteer"))
+        .await;
+
+    assert!(result.is_ok());
+}
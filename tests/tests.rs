@@ -1,3 +1,20 @@
+mod audit;
 mod auth;
+mod backup;
+mod branding;
+mod calendar;
+mod consumable;
+mod db_test;
 mod error_test;
+mod event;
+mod export;
+mod file;
+mod label;
+mod location;
+mod logistics;
+mod planner;
+mod privacy;
+mod social;
+mod team;
 mod user;
+mod vendor;
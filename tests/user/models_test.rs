@@ -1,5 +1,8 @@
+use chrono::{Duration, Utc};
 use circa_backend::modules::user::entity::{Model, Role, Status};
-use circa_backend::modules::user::models::{User, UserRole, UserStatus};
+use circa_backend::modules::user::models::{
+    Availability, User, UserProfile, UserRole, UserStatus, normalize_tags,
+};
 
 #[test]
 fn test_role_conversion_to_entity() {
@@ -37,11 +40,10 @@ fn test_model_to_user_conversion() {
         surname: "Strider".to_string(),
         email: "dave@example.com".to_string(),
         phone: "123456789".to_string(),
-        role: Role::Volunteer,
         status: Status::Active,
     };
 
-    let user: User = model.into();
+    let user = User::from_model(model, UserRole::Volunteer);
 
     assert_eq!(user.id, "123");
     assert_eq!(user.name, "Dave");
@@ -60,6 +62,31 @@ fn test_user_role_as_str() {
     assert_eq!(UserRole::Volunteer.as_str(), "volunteer");
 }
 
+#[test]
+fn test_user_role_from_str() {
+    assert_eq!("admin".parse::<UserRole>(), Ok(UserRole::Admin));
+    assert_eq!(" Organizer ".parse::<UserRole>(), Ok(UserRole::Organizer));
+    assert_eq!("STAFF".parse::<UserRole>(), Ok(UserRole::Staff));
+    assert_eq!("volunteer".parse::<UserRole>(), Ok(UserRole::Volunteer));
+    assert!("overlord".parse::<UserRole>().is_err());
+}
+
+#[test]
+fn test_user_role_promoted() {
+    assert_eq!(UserRole::Volunteer.promoted(), UserRole::Staff);
+    assert_eq!(UserRole::Staff.promoted(), UserRole::Organizer);
+    assert_eq!(UserRole::Organizer.promoted(), UserRole::Admin);
+    assert_eq!(UserRole::Admin.promoted(), UserRole::Admin);
+}
+
+#[test]
+fn test_user_role_demoted() {
+    assert_eq!(UserRole::Admin.demoted(), UserRole::Organizer);
+    assert_eq!(UserRole::Organizer.demoted(), UserRole::Staff);
+    assert_eq!(UserRole::Staff.demoted(), UserRole::Volunteer);
+    assert_eq!(UserRole::Volunteer.demoted(), UserRole::Volunteer);
+}
+
 #[test]
 fn test_user_role_display() {
     assert_eq!(format!("{}", UserRole::Admin), "Admin");
@@ -73,3 +100,38 @@ fn test_user_status_display() {
     assert_eq!(format!("{}", UserStatus::Active), "Active");
     assert_eq!(format!("{}", UserStatus::Inactive), "Inactive");
 }
+
+#[test]
+fn test_normalize_tags() {
+    let tags = normalize_tags(&[
+        " First Aid".to_string(),
+        "first aid".to_string(),
+        "".to_string(),
+        "Polish speaker".to_string(),
+    ]);
+
+    assert_eq!(tags, vec!["first aid", "polish speaker"]);
+}
+
+#[test]
+fn test_profile_availability_and_skills() {
+    let now = Utc::now();
+    let profile = UserProfile {
+        skills: vec!["forklift".to_string()],
+        preferred_areas: vec!["bar".to_string()],
+        availability: vec![Availability {
+            id: "w1".to_string(),
+            starts_at: now,
+            ends_at: now + Duration::hours(8),
+            note: "".to_string(),
+        }],
+    };
+
+    assert!(profile.is_available(now + Duration::hours(1), now + Duration::hours(5)));
+    assert!(!profile.is_available(now + Duration::hours(6), now + Duration::hours(10)));
+    assert!(profile.prefers(" Bar"));
+    assert_eq!(
+        profile.missing_skills(&["forklift".to_string(), "first aid".to_string()]),
+        vec!["first aid".to_string()]
+    );
+}
@@ -1,11 +1,19 @@
 use actix_web::{App, http::StatusCode, test, web};
+use chrono::Duration;
 use circa_backend::auth::service::generate_jwt;
-use circa_backend::modules::user::entity::{Model, Role, Status};
+use circa_backend::event::entity::member;
+use circa_backend::file::{repository::FileRepository, signer::UrlSigner};
+use circa_backend::logistics::entity::checkout;
+use circa_backend::logistics::repository::LogisticsRepository;
+use circa_backend::modules::user::entity::{
+    Model, Role, Status, availability, avatar, preferred_area, skill,
+};
 use circa_backend::user;
 use circa_backend::user::models::{CreateUserRequest, UpdateUserRequest, UserRole};
 use circa_backend::user::repository::UserRepository;
 use circa_backend::user::service::UserService;
-use sea_orm::{DatabaseBackend, MockDatabase};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
 
 const JWT_SECRET: &str = "test_secret";
 
@@ -14,12 +22,31 @@ fn make_jwt_secret() -> web::Data<String> {
 }
 
 async fn make_admin_token() -> String {
-    let resp = generate_jwt("admin@example.com", "admin", JWT_SECRET)
+    let resp = generate_jwt("admin@example.com", "event-1", "admin", JWT_SECRET)
         .await
         .unwrap();
     resp.token
 }
 
+fn make_service(db: DatabaseConnection) -> UserService {
+    let db = Arc::new(db);
+    UserService::new(
+        UserRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        FileRepository::new(db),
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+    )
+}
+
+fn make_member(user_id: &str, role: Role) -> member::Model {
+    member::Model {
+        event_id: "event-1".to_string(),
+        user_id: user_id.to_string(),
+        role,
+        joined_at: chrono::Utc::now(),
+    }
+}
+
 fn setup_app_data_with_list() -> web::Data<UserService> {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
         .append_query_results([vec![Model {
@@ -28,32 +55,39 @@ fn setup_app_data_with_list() -> web::Data<UserService> {
             surname: "Doe".to_string(),
             email: "john@example.com".to_string(),
             phone: "123".to_string(),
-            role: Role::Admin,
             status: Status::Active,
         }]])
+        .append_query_results([vec![make_member("1", Role::Admin)]])
         .into_connection();
 
-    web::Data::new(UserService::new(UserRepository::new(db)))
+    web::Data::new(make_service(db))
 }
 
 fn setup_app_data_for_create() -> web::Data<UserService> {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
         .append_query_results([vec![Model {
             id: "1".to_string(),
             name: "John".to_string(),
             surname: "Doe".to_string(),
             email: "john@example.com".to_string(),
             phone: "123".to_string(),
-            role: Role::Organizer,
             status: Status::Active,
         }]])
-        .append_exec_results([sea_orm::MockExecResult {
-            last_insert_id: 1,
-            rows_affected: 1,
-        }])
+        .append_query_results([vec![make_member("1", Role::Organizer)]])
+        .append_exec_results([
+            sea_orm::MockExecResult {
+                last_insert_id: 1,
+                rows_affected: 1,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 1,
+                rows_affected: 1,
+            },
+        ])
         .into_connection();
 
-    web::Data::new(UserService::new(UserRepository::new(db)))
+    web::Data::new(make_service(db))
 }
 
 #[actix_web::test]
@@ -127,9 +161,25 @@ async fn test_create_user_route() {
 async fn test_get_user_by_id_route() {
     let token = make_admin_token().await;
 
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![Model {
+            id: "1".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("1", Role::Admin)]])
+        .append_query_results([Vec::<skill::Model>::new()])
+        .append_query_results([Vec::<preferred_area::Model>::new()])
+        .append_query_results([Vec::<availability::Model>::new()])
+        .append_query_results([Vec::<avatar::Model>::new()])
+        .into_connection();
+
     let app = test::init_service(
         App::new()
-            .app_data(setup_app_data_with_list())
+            .app_data(web::Data::new(make_service(db)))
             .app_data(make_jwt_secret())
             .configure(user::routes::config),
     )
@@ -149,33 +199,30 @@ async fn test_update_user_route() {
     let token = make_admin_token().await;
 
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_query_results([
-            vec![Model {
-                id: "1".to_string(),
-                name: "John".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Admin,
-                status: Status::Active,
-            }],
-            vec![Model {
-                id: "1".to_string(),
-                name: "Jane".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Admin,
-                status: Status::Active,
-            }],
-        ])
+        .append_query_results([vec![Model {
+            id: "1".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("1", Role::Admin)]])
+        .append_query_results([vec![Model {
+            id: "1".to_string(),
+            name: "Jane".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
         .append_exec_results([sea_orm::MockExecResult {
             last_insert_id: 0,
             rows_affected: 1,
         }])
         .into_connection();
 
-    let app_data = web::Data::new(UserService::new(UserRepository::new(db)));
+    let app_data = web::Data::new(make_service(db));
 
     let app = test::init_service(
         App::new()
@@ -209,13 +256,20 @@ async fn test_delete_user_route() {
     let token = make_admin_token().await;
 
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_exec_results([sea_orm::MockExecResult {
-            last_insert_id: 0,
-            rows_affected: 1,
-        }])
+        .append_query_results([Vec::<checkout::Model>::new()])
+        .append_exec_results([
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
         .into_connection();
 
-    let app_data = web::Data::new(UserService::new(UserRepository::new(db)));
+    let app_data = web::Data::new(make_service(db));
 
     let app = test::init_service(
         App::new()
@@ -243,7 +297,7 @@ async fn test_delete_user_route_unauthorized() {
         }])
         .into_connection();
 
-    let app_data = web::Data::new(UserService::new(UserRepository::new(db)));
+    let app_data = web::Data::new(make_service(db));
 
     let app = test::init_service(
         App::new()
@@ -258,3 +312,34 @@ async fn test_delete_user_route_unauthorized() {
 
     assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
 }
+
+#[actix_web::test]
+async fn test_import_users_route_dry_run() {
+    let token = make_admin_token().await;
+
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .into_connection();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(make_service(db)))
+            .app_data(make_jwt_secret())
+            .configure(user::routes::config),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/users/import?dry_run=true&email_column=mail")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header(("Content-Type", "text/csv"))
+        .set_payload("name,surname,mail\nJohn,Doe,john@example.com\n")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(body["rows"][0]["status"], "valid");
+}
@@ -1,20 +1,53 @@
+use chrono::Duration;
 use circa_backend::auth::models::Claims;
+use circa_backend::error::AppError;
+use circa_backend::event::entity::member;
+use circa_backend::file::entity::{ImageStatus, Model as FileModel};
+use circa_backend::file::{repository::FileRepository, signer::UrlSigner};
+use circa_backend::logistics::entity::{
+    Model as AssetModel, Ownership, Status as AssetStatus, checkout,
+};
+use circa_backend::logistics::repository::LogisticsRepository;
 use circa_backend::user::{
-    entity::{Model, Role, Status},
-    models::{CreateUserRequest, UpdateUserRequest, UserRole},
+    entity::{Model, Role, Status, availability, avatar, preferred_area, skill},
+    models::{
+        CreateAvailabilityRequest, CreateUserRequest, ImportRowStatus, ImportUsersQuery,
+        SetAvatarRequest, UpdateProfileRequest, UpdateUserRequest, UserRole, UserStatus,
+    },
     repository::UserRepository,
     service::UserService,
 };
-use sea_orm::{DatabaseBackend, MockDatabase};
+use sea_orm::{ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, MockDatabase};
+use std::sync::Arc;
 
 fn make_claims(sub: &str, role: &str) -> Claims {
     Claims {
         sub: sub.to_string(),
+        event: "event-1".to_string(),
         role: role.to_string(),
         exp: 9999999999,
     }
 }
 
+fn make_service(db: DatabaseConnection) -> UserService {
+    let db = Arc::new(db);
+    UserService::new(
+        UserRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        FileRepository::new(db),
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+    )
+}
+
+fn make_member(user_id: &str, role: Role) -> member::Model {
+    member::Model {
+        event_id: "event-1".to_string(),
+        user_id: user_id.to_string(),
+        role,
+        joined_at: chrono::Utc::now(),
+    }
+}
+
 fn setup_mock_db_with_user() -> sea_orm::DatabaseConnection {
     MockDatabase::new(DatabaseBackend::Sqlite)
         .append_query_results([vec![Model {
@@ -23,16 +56,50 @@ fn setup_mock_db_with_user() -> sea_orm::DatabaseConnection {
             surname: "Doe".to_string(),
             email: "john@example.com".to_string(),
             phone: "123".to_string(),
-            role: Role::Organizer,
             status: Status::Active,
         }]])
-        .append_exec_results([sea_orm::MockExecResult {
-            last_insert_id: 1,
-            rows_affected: 1,
-        }])
+        .append_query_results([vec![make_member("1", Role::Organizer)]])
         .into_connection()
 }
 
+fn make_checkout() -> checkout::Model {
+    let now = chrono::Utc::now();
+    checkout::Model {
+        id: "c1".to_string(),
+        event_id: "event-1".to_string(),
+        asset_id: "a1".to_string(),
+        user_id: "2".to_string(),
+        quantity: 1,
+        note: "".to_string(),
+        checked_out_by: "1".to_string(),
+        checked_out_at: now,
+        due_at: now,
+        checked_in_by: None,
+        checked_in_at: None,
+        condition: None,
+        return_note: None,
+    }
+}
+
+fn make_asset() -> AssetModel {
+    let now = chrono::Utc::now();
+    AssetModel {
+        id: "a1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "Van keys".to_string(),
+        category: "keys".to_string(),
+        quantity: 1,
+        location: "".to_string(),
+        ownership: Ownership::Owned,
+        status: AssetStatus::InUse,
+        notes: "".to_string(),
+        created_by: "1".to_string(),
+        created_at: now,
+        updated_at: now,
+        location_id: None,
+    }
+}
+
 // ── get_users ────────────────────────────────────────────────────────
 
 #[tokio::test]
@@ -45,7 +112,6 @@ async fn test_get_users_success() {
                 surname: "Doe".to_string(),
                 email: "john@example.com".to_string(),
                 phone: "123".to_string(),
-                role: Role::Admin,
                 status: Status::Active,
             },
             Model {
@@ -54,17 +120,22 @@ async fn test_get_users_success() {
                 surname: "Doe".to_string(),
                 email: "jane@example.com".to_string(),
                 phone: "456".to_string(),
-                role: Role::Volunteer,
                 status: Status::Active,
             },
         ]])
+        .append_query_results([vec![
+            make_member("1", Role::Admin),
+            make_member("2", Role::Volunteer),
+        ]])
         .into_connection();
 
-    let service = UserService::new(UserRepository::new(db));
-    let result = service.get_users().await;
+    let service = make_service(db);
+    let result = service.get_users(&make_claims("1", "admin")).await;
 
     assert!(result.is_ok());
-    assert_eq!(result.unwrap().len(), 2);
+    let users = result.unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[1].role, UserRole::Volunteer);
 }
 
 #[tokio::test]
@@ -73,21 +144,51 @@ async fn test_get_users_empty() {
         .append_query_results([Vec::<Model>::new()])
         .into_connection();
 
-    let service = UserService::new(UserRepository::new(db));
-    let result = service.get_users().await;
+    let service = make_service(db);
+    let result = service.get_users(&make_claims("1", "admin")).await;
 
     assert!(result.is_ok());
     assert_eq!(result.unwrap().len(), 0);
 }
 
+#[tokio::test]
+async fn test_get_users_drops_people_outside_the_event() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![
+            make_model("1", "john@example.com"),
+            make_model("2", "jane@example.com"),
+        ]])
+        .append_query_results([vec![make_member("1", Role::Staff)]])
+        .into_connection();
+
+    let service = make_service(db);
+    let users = service.get_users(&make_claims("1", "admin")).await.unwrap();
+
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].role, UserRole::Staff);
+}
+
 // ── get_user ─────────────────────────────────────────────────────────
 
+// skills, preferred areas, availability and the avatar, in the order the repository reads them
+fn with_empty_profile(db: MockDatabase) -> MockDatabase {
+    db.append_query_results([Vec::<skill::Model>::new()])
+        .append_query_results([Vec::<preferred_area::Model>::new()])
+        .append_query_results([Vec::<availability::Model>::new()])
+        .append_query_results([Vec::<avatar::Model>::new()])
+}
+
 #[tokio::test]
 async fn test_get_user_success() {
-    let db = setup_mock_db_with_user();
-    let service = UserService::new(UserRepository::new(db));
+    let db = with_empty_profile(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([vec![make_model("1", "john@example.com")]])
+            .append_query_results([vec![make_member("1", Role::Volunteer)]]),
+    )
+    .into_connection();
+    let service = make_service(db);
 
-    let result = service.get_user("1").await;
+    let result = service.get_user("1", &make_claims("1", "volunteer")).await;
     assert!(result.is_ok());
     assert_eq!(result.unwrap().id, "1");
 }
@@ -97,9 +198,11 @@ async fn test_get_user_not_found() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
         .append_query_results([Vec::<Model>::new()])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
 
-    let result = service.get_user("999").await;
+    let result = service
+        .get_user("999", &make_claims("1", "volunteer"))
+        .await;
     assert!(result.is_err());
     assert_eq!(result.unwrap_err().to_string(), "Not found: User not found");
 }
@@ -109,11 +212,35 @@ async fn test_get_user_not_found() {
 #[tokio::test]
 async fn test_get_user_by_email_success() {
     let db = setup_mock_db_with_user();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
 
-    let result = service.get_user_by_email("john@example.com").await;
+    let result = service
+        .get_user_by_email("john@example.com", &make_claims("1", "admin"))
+        .await;
     assert!(result.is_ok());
-    assert_eq!(result.unwrap().email, "john@example.com");
+    let user = result.unwrap();
+    assert_eq!(user.email, "john@example.com");
+    assert_eq!(user.role, UserRole::Organizer);
+}
+
+#[tokio::test]
+async fn test_get_user_by_email_ignores_case() {
+    let db = Arc::new(setup_mock_db_with_user());
+    let service = UserService::new(
+        UserRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        FileRepository::new(db.clone()),
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+    );
+
+    service
+        .get_user_by_email(" John@Example.com", &make_claims("1", "admin"))
+        .await
+        .unwrap();
+    drop(service);
+
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"String(Some("john@example.com"))"#));
 }
 
 #[tokio::test]
@@ -121,9 +248,11 @@ async fn test_get_user_by_email_not_found() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
         .append_query_results([Vec::<Model>::new()])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
 
-    let result = service.get_user_by_email("nobody@example.com").await;
+    let result = service
+        .get_user_by_email("nobody@example.com", &make_claims("1", "admin"))
+        .await;
     assert!(result.is_err());
     assert_eq!(result.unwrap_err().to_string(), "Not found: User not found");
 }
@@ -132,8 +261,22 @@ async fn test_get_user_by_email_not_found() {
 
 #[tokio::test]
 async fn test_create_user_success() {
-    let db = setup_mock_db_with_user();
-    let service = UserService::new(UserRepository::new(db));
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_member("1", Role::Organizer)]])
+        .append_exec_results([
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
+        .into_connection();
+    let service = make_service(db);
 
     let req = CreateUserRequest {
         name: "John".to_string(),
@@ -143,15 +286,152 @@ async fn test_create_user_success() {
         role: UserRole::Organizer,
     };
 
-    let result = service.create_user(req).await;
+    let result = service.create_user(req, &make_claims("1", "admin")).await;
     assert!(result.is_ok());
     assert_eq!(result.unwrap().name, "John");
 }
 
+#[tokio::test]
+async fn test_create_user_stores_email_lowercase() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([Vec::<Model>::new()])
+            .append_query_results([vec![make_model("1", "john@example.com")]])
+            .append_query_results([vec![make_member("1", Role::Volunteer)]])
+            .append_exec_results([
+                sea_orm::MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+                sea_orm::MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+            ])
+            .into_connection(),
+    );
+    let service = UserService::new(
+        UserRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        FileRepository::new(db.clone()),
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+    );
+
+    let req = CreateUserRequest {
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "John@Example.COM ".to_string(),
+        phone: "123".to_string(),
+        role: UserRole::Volunteer,
+    };
+    service
+        .create_user(req, &make_claims("1", "admin"))
+        .await
+        .unwrap();
+    drop(service);
+
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"String(Some("john@example.com"))"#));
+    assert!(!log.contains("Example"));
+}
+
+#[tokio::test]
+async fn test_create_user_joins_existing_account_to_event() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([Vec::<member::Model>::new()])
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_member("1", Role::Staff)]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CreateUserRequest {
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        role: UserRole::Staff,
+    };
+
+    let user = service
+        .create_user(req, &make_claims("1", "admin"))
+        .await
+        .unwrap();
+    assert_eq!(user.id, "1");
+    assert_eq!(user.role, UserRole::Staff);
+}
+
+#[tokio::test]
+async fn test_create_user_already_in_event() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_member("1", Role::Volunteer)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = CreateUserRequest {
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        role: UserRole::Staff,
+    };
+
+    let result = service.create_user(req, &make_claims("1", "admin")).await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: User is already part of this event"
+    );
+}
+
+#[tokio::test]
+async fn test_create_user_forbidden_for_volunteer() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let req = CreateUserRequest {
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        role: UserRole::Volunteer,
+    };
+
+    let result = service
+        .create_user(req, &make_claims("2", "volunteer"))
+        .await;
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_create_user_only_admins_grant_admin() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+
+    let req = CreateUserRequest {
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        role: UserRole::Admin,
+    };
+
+    let result = service
+        .create_user(req, &make_claims("2", "organizer"))
+        .await;
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
 #[tokio::test]
 async fn test_create_user_empty_email() {
     let db = setup_mock_db_with_user();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
 
     let req = CreateUserRequest {
         name: "John".to_string(),
@@ -161,7 +441,7 @@ async fn test_create_user_empty_email() {
         role: UserRole::Organizer,
     };
 
-    let result = service.create_user(req).await;
+    let result = service.create_user(req, &make_claims("1", "admin")).await;
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err().to_string(),
@@ -174,32 +454,29 @@ async fn test_create_user_empty_email() {
 #[tokio::test]
 async fn test_update_user_as_self() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_query_results([
-            vec![Model {
-                id: "1".to_string(),
-                name: "John".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Volunteer,
-                status: Status::Active,
-            }],
-            vec![Model {
-                id: "1".to_string(),
-                name: "Jane".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Volunteer,
-                status: Status::Active,
-            }],
-        ])
+        .append_query_results([vec![Model {
+            id: "1".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("1", Role::Volunteer)]])
+        .append_query_results([vec![Model {
+            id: "1".to_string(),
+            name: "Jane".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
         .append_exec_results([sea_orm::MockExecResult {
             last_insert_id: 0,
             rows_affected: 1,
         }])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("1", "volunteer");
 
     let req = UpdateUserRequest {
@@ -211,7 +488,7 @@ async fn test_update_user_as_self() {
         status: None,
     };
 
-    let result = service.update_user("1", req, &claims).await;
+    let result = service.update_user("1", req, false, &claims).await;
     assert!(result.is_ok());
     assert_eq!(result.unwrap().name, "Jane");
 }
@@ -219,32 +496,29 @@ async fn test_update_user_as_self() {
 #[tokio::test]
 async fn test_update_user_as_admin() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_query_results([
-            vec![Model {
-                id: "2".to_string(),
-                name: "John".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Volunteer,
-                status: Status::Active,
-            }],
-            vec![Model {
-                id: "2".to_string(),
-                name: "Jane".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Volunteer,
-                status: Status::Active,
-            }],
-        ])
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("2", Role::Volunteer)]])
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "Jane".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
         .append_exec_results([sea_orm::MockExecResult {
             last_insert_id: 0,
             rows_affected: 1,
         }])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("admin-id", "admin");
 
     let req = UpdateUserRequest {
@@ -256,39 +530,158 @@ async fn test_update_user_as_admin() {
         status: None,
     };
 
-    let result = service.update_user("2", req, &claims).await;
+    let result = service.update_user("2", req, false, &claims).await;
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_update_user_email_taken_by_another_account() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![Model {
+            id: "3".to_string(),
+            name: "Jane".to_string(),
+            surname: "Roe".to_string(),
+            email: "jane@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("1", "volunteer");
+
+    let req = UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: Some("Jane@Example.com".to_string()),
+        phone: None,
+        role: None,
+        status: None,
+    };
+
+    let result = service.update_user("1", req, false, &claims).await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Email already in use"
+    );
+}
+
+#[tokio::test]
+async fn test_update_user_keeps_own_email() {
+    let user = Model {
+        id: "1".to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: "john@example.com".to_string(),
+        phone: "123".to_string(),
+        status: Status::Active,
+    };
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![make_member("1", Role::Volunteer)]])
+        .append_query_results([vec![user]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("1", "volunteer");
+
+    let req = UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: Some("John@example.com".to_string()),
+        phone: None,
+        role: None,
+        status: None,
+    };
+
+    let result = service.update_user("1", req, false, &claims).await;
+    assert_eq!(result.unwrap().email, "john@example.com");
+}
+
+// the index still catches an email taken between the check and the write
+#[tokio::test]
+async fn test_update_email_taken_meanwhile() {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    db.execute_unprepared(
+        "CREATE TABLE users (
+            id TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL,
+            surname TEXT NOT NULL,
+            email TEXT NOT NULL,
+            phone TEXT NOT NULL,
+            status TEXT NOT NULL
+        );
+        CREATE UNIQUE INDEX users_email ON users (lower(email));
+        CREATE TABLE event_members (
+            event_id TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            role TEXT NOT NULL,
+            joined_at TEXT NOT NULL,
+            PRIMARY KEY (event_id, user_id)
+        );
+        INSERT INTO users VALUES ('1', 'John', 'Doe', 'john@example.com', '', 'active');
+        INSERT INTO users VALUES ('2', 'Jane', 'Roe', 'jane@example.com', '', 'active');
+        INSERT INTO event_members VALUES ('event-1', '1', 'volunteer', '2026-01-01T00:00:00Z');",
+    )
+    .await
+    .unwrap();
+    let repository = UserRepository::new(db);
+
+    let req = UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: Some("jane@example.com".to_string()),
+        phone: None,
+        role: None,
+        status: None,
+    };
+
+    let result = repository.update("event-1", "1", req).await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Email already in use"
+    );
+}
+
 #[tokio::test]
 async fn test_update_user_as_organizer() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_query_results([
-            vec![Model {
-                id: "2".to_string(),
-                name: "John".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Volunteer,
-                status: Status::Active,
-            }],
-            vec![Model {
-                id: "2".to_string(),
-                name: "Jane".to_string(),
-                surname: "Doe".to_string(),
-                email: "john@example.com".to_string(),
-                phone: "123".to_string(),
-                role: Role::Volunteer,
-                status: Status::Active,
-            }],
-        ])
+        // the target, to see they aren't an admin
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("2", Role::Volunteer)]])
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("2", Role::Volunteer)]])
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "Jane".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
         .append_exec_results([sea_orm::MockExecResult {
             last_insert_id: 0,
             rows_affected: 1,
         }])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("org-id", "organizer");
 
     let req = UpdateUserRequest {
@@ -300,14 +693,14 @@ async fn test_update_user_as_organizer() {
         status: None,
     };
 
-    let result = service.update_user("2", req, &claims).await;
+    let result = service.update_user("2", req, false, &claims).await;
     assert!(result.is_ok());
 }
 
 #[tokio::test]
 async fn test_update_user_forbidden() {
     let db = setup_mock_db_with_user();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("other-user", "volunteer");
 
     let req = UpdateUserRequest {
@@ -319,22 +712,192 @@ async fn test_update_user_forbidden() {
         status: None,
     };
 
-    let result = service.update_user("1", req, &claims).await;
+    let result = service.update_user("1", req, false, &claims).await;
     assert!(result.is_err());
     assert_eq!(result.unwrap_err().to_string(), "Forbidden");
 }
 
-// ── delete_user ──────────────────────────────────────────────────────
+#[tokio::test]
+async fn test_update_user_cannot_raise_own_role() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("1", "volunteer");
+
+    let req = UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: None,
+        phone: None,
+        role: Some(UserRole::Admin),
+        status: None,
+    };
+
+    let result = service.update_user("1", req, false, &claims).await;
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
 
 #[tokio::test]
-async fn test_delete_user_as_self() {
+async fn test_update_user_only_admins_grant_admin() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("org-id", "organizer");
+
+    let req = UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: None,
+        phone: None,
+        role: Some(UserRole::Admin),
+        status: None,
+    };
+
+    let result = service.update_user("2", req, false, &claims).await;
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_organizer_cant_change_an_admin() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("2", Role::Admin)]])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("org-id", "organizer");
+
+    let req = UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: None,
+        phone: None,
+        role: Some(UserRole::Volunteer),
+        status: None,
+    };
+
+    let result = service.update_user("2", req, false, &claims).await;
+    assert!(matches!(result, Err(AppError::Forbidden)));
+}
+
+fn deactivate_request() -> UpdateUserRequest {
+    UpdateUserRequest {
+        name: None,
+        surname: None,
+        email: None,
+        phone: None,
+        role: None,
+        status: Some(UserStatus::Inactive),
+    }
+}
+
+#[tokio::test]
+async fn test_deactivate_user_holding_items_lists_them() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        // the target, to see they aren't an admin
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("2", Role::Staff)]])
+        .append_query_results([vec![make_checkout()]])
+        .append_query_results([vec![make_asset()]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .update_user(
+            "2",
+            deactivate_request(),
+            false,
+            &make_claims("org-id", "organizer"),
+        )
+        .await;
+
+    match result {
+        Err(AppError::Conflict(_, details)) => {
+            assert_eq!(details[0]["asset_name"], "Van keys");
+        }
+        other => panic!("expected a conflict, got {:?}", other.map(|u| u.id)),
+    }
+}
+
+#[tokio::test]
+async fn test_deactivate_user_with_force() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        // the target, to see they aren't an admin
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("2", Role::Staff)]])
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Active,
+        }]])
+        .append_query_results([vec![make_member("2", Role::Staff)]])
+        .append_query_results([vec![Model {
+            id: "2".to_string(),
+            name: "John".to_string(),
+            surname: "Doe".to_string(),
+            email: "john@example.com".to_string(),
+            phone: "123".to_string(),
+            status: Status::Inactive,
+        }]])
         .append_exec_results([sea_orm::MockExecResult {
             last_insert_id: 0,
             rows_affected: 1,
         }])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
+
+    let user = service
+        .update_user(
+            "2",
+            deactivate_request(),
+            true,
+            &make_claims("org-id", "organizer"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(user.status, UserStatus::Inactive);
+}
+
+// ── delete_user ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_delete_user_as_self() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<checkout::Model>::new()])
+        .append_exec_results([
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
+        .into_connection();
+    let service = make_service(db);
     let claims = make_claims("1", "volunteer");
 
     let result = service.delete_user("1", &claims).await;
@@ -344,22 +907,87 @@ async fn test_delete_user_as_self() {
 #[tokio::test]
 async fn test_delete_user_as_admin() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
-        .append_exec_results([sea_orm::MockExecResult {
-            last_insert_id: 0,
-            rows_affected: 1,
-        }])
+        .append_query_results([Vec::<checkout::Model>::new()])
+        .append_exec_results([
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("admin-id", "admin");
 
     let result = service.delete_user("1", &claims).await;
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_delete_user_keeps_account_with_custody_log() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results([Vec::<checkout::Model>::new()])
+            .append_exec_results([
+                sea_orm::MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+                sea_orm::MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                },
+            ])
+            .into_connection(),
+    );
+    let service = UserService::new(
+        UserRepository::new(db.clone()),
+        LogisticsRepository::new(db.clone()),
+        FileRepository::new(db.clone()),
+        UrlSigner::new("test_secret", Duration::minutes(15)),
+    );
+
+    service
+        .delete_user("1", &make_claims("admin-id", "admin"))
+        .await
+        .unwrap();
+    drop(service);
+
+    let log = format!("{:?}", Arc::try_unwrap(db).unwrap().into_transaction_log());
+    assert!(log.contains(r#"NOT IN (SELECT \"user_id\" FROM \"asset_checkouts\")"#));
+}
+
+#[tokio::test]
+async fn test_delete_user_holding_items_conflicts() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_checkout()]])
+        .append_query_results([vec![make_asset()]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .delete_user("1", &make_claims("admin-id", "admin"))
+        .await;
+
+    match result {
+        Err(AppError::Conflict(message, details)) => {
+            assert_eq!(
+                message,
+                "User still holds 1 checked out item(s), check them in first"
+            );
+            assert_eq!(details.as_array().unwrap().len(), 1);
+        }
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+}
+
 #[tokio::test]
 async fn test_delete_user_forbidden() {
     let db = setup_mock_db_with_user();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("other-user", "volunteer");
 
     let result = service.delete_user("1", &claims).await;
@@ -370,7 +998,7 @@ async fn test_delete_user_forbidden() {
 #[tokio::test]
 async fn test_delete_user_forbidden_as_organizer() {
     let db = setup_mock_db_with_user();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("org-id", "organizer");
 
     let result = service.delete_user("1", &claims).await;
@@ -381,15 +1009,373 @@ async fn test_delete_user_forbidden_as_organizer() {
 #[tokio::test]
 async fn test_delete_user_not_found() {
     let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<checkout::Model>::new()])
         .append_exec_results([sea_orm::MockExecResult {
             last_insert_id: 0,
             rows_affected: 0,
         }])
         .into_connection();
-    let service = UserService::new(UserRepository::new(db));
+    let service = make_service(db);
     let claims = make_claims("1", "admin");
 
     let result = service.delete_user("1", &claims).await;
     assert!(result.is_err());
     assert_eq!(result.unwrap_err().to_string(), "Not found: User not found");
 }
+
+// ── import_users ─────────────────────────────────────────────────────
+
+const IMPORT_CSV: &str = "name,surname,email,phone,role
+John,Doe,john@example.com,123,staff
+Jane,Doe,jane@example.com,456,
+Nope,Nobody,,789,volunteer
+";
+
+fn make_model(id: &str, email: &str) -> Model {
+    Model {
+        id: id.to_string(),
+        name: "John".to_string(),
+        surname: "Doe".to_string(),
+        email: email.to_string(),
+        phone: "123".to_string(),
+        status: Status::Active,
+    }
+}
+
+#[tokio::test]
+async fn test_import_users_dry_run() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new()])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("org-id", "organizer");
+
+    let query = ImportUsersQuery {
+        dry_run: true,
+        ..Default::default()
+    };
+
+    let report = service
+        .import_users(IMPORT_CSV, query, &claims)
+        .await
+        .unwrap();
+
+    assert!(!report.committed);
+    assert_eq!(report.total, 3);
+    assert_eq!(report.created, 0);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.rows[0].line, 2);
+    assert_eq!(report.rows[0].status, ImportRowStatus::Valid);
+    assert_eq!(report.rows[2].status, ImportRowStatus::Invalid);
+    assert_eq!(report.rows[2].errors, vec!["Email is required"]);
+}
+
+#[tokio::test]
+async fn test_import_users_detects_existing_and_repeated_emails() {
+    let csv = "name,surname,email
+John,Doe,john@example.com
+Jane,Doe,jane@example.com
+Jane,Again,jane@example.com
+";
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let report = service
+        .import_users(csv, ImportUsersQuery::default(), &claims)
+        .await
+        .unwrap();
+
+    assert!(!report.committed);
+    assert_eq!(report.failed, 2);
+    assert_eq!(report.rows[0].status, ImportRowStatus::Duplicate);
+    assert_eq!(report.rows[1].status, ImportRowStatus::Valid);
+    assert_eq!(report.rows[2].status, ImportRowStatus::Duplicate);
+}
+
+#[tokio::test]
+async fn test_import_users_matches_emails_regardless_of_case() {
+    let csv = "name,surname,email
+John,Doe,JOHN@example.com
+Jane,Doe,Jane@Example.com
+Jane,Again,jane@example.com
+";
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let report = service
+        .import_users(csv, ImportUsersQuery::default(), &claims)
+        .await
+        .unwrap();
+
+    assert_eq!(report.rows[0].email, "john@example.com");
+    assert_eq!(report.rows[0].status, ImportRowStatus::Duplicate);
+    assert_eq!(report.rows[1].status, ImportRowStatus::Valid);
+    assert_eq!(report.rows[2].status, ImportRowStatus::Duplicate);
+}
+
+#[tokio::test]
+async fn test_import_users_skip_invalid_commits_valid_rows() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([Vec::<Model>::new(), Vec::<Model>::new()])
+        .append_query_results([vec![make_model("a", "john@example.com")]])
+        .append_query_results([vec![make_member("a", Role::Staff)]])
+        .append_query_results([vec![make_model("b", "jane@example.com")]])
+        .append_query_results([vec![make_member("b", Role::Volunteer)]])
+        .append_exec_results((0..4).map(|_| sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }))
+        .into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let query = ImportUsersQuery {
+        skip_invalid: true,
+        ..Default::default()
+    };
+
+    let report = service
+        .import_users(IMPORT_CSV, query, &claims)
+        .await
+        .unwrap();
+
+    assert!(report.committed);
+    assert_eq!(report.created, 2);
+    assert_eq!(report.rows[0].status, ImportRowStatus::Created);
+    assert_eq!(report.rows[0].user_id.as_deref(), Some("a"));
+    assert_eq!(report.rows[1].user_id.as_deref(), Some("b"));
+    assert_eq!(report.rows[2].status, ImportRowStatus::Invalid);
+}
+
+#[tokio::test]
+async fn test_import_users_column_mapping() {
+    let csv = "First name,Last name,E-mail,Function
+John,Doe,john@example.com,Bogus
+";
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let query = ImportUsersQuery {
+        dry_run: true,
+        name_column: Some("First name".to_string()),
+        surname_column: Some("Last name".to_string()),
+        email_column: Some("E-mail".to_string()),
+        role_column: Some("Function".to_string()),
+        ..Default::default()
+    };
+
+    let report = service.import_users(csv, query, &claims).await.unwrap();
+
+    assert_eq!(report.rows[0].email, "john@example.com");
+    assert_eq!(report.rows[0].status, ImportRowStatus::Invalid);
+}
+
+#[tokio::test]
+async fn test_import_users_missing_column() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("admin-id", "admin");
+
+    let result = service
+        .import_users(
+            "name,email\nJohn,john@example.com\n",
+            ImportUsersQuery::default(),
+            &claims,
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Missing column 'surname'"
+    );
+}
+
+#[tokio::test]
+async fn test_import_users_forbidden_for_staff() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("staff-id", "staff");
+
+    let result = service
+        .import_users(IMPORT_CSV, ImportUsersQuery::default(), &claims)
+        .await;
+
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
+
+#[tokio::test]
+async fn test_import_users_organizer_cannot_import_admins() {
+    let csv = "name,surname,email,role
+Eve,Doe,eve@example.com,admin
+";
+    let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+    let service = make_service(db);
+    let claims = make_claims("org-id", "organizer");
+
+    let report = service
+        .import_users(csv, ImportUsersQuery::default(), &claims)
+        .await
+        .unwrap();
+
+    assert_eq!(report.rows[0].status, ImportRowStatus::Invalid);
+    assert_eq!(report.rows[0].errors, vec!["Only admins can import admins"]);
+}
+
+// ── profile ──────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_update_profile_normalizes_tags() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_member("1", Role::Volunteer)]])
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_member("1", Role::Volunteer)]])
+        .append_query_results([vec![skill::Model {
+            user_id: "1".to_string(),
+            skill: "first aid".to_string(),
+        }]])
+        .append_query_results([Vec::<preferred_area::Model>::new()])
+        .append_query_results([Vec::<availability::Model>::new()])
+        .append_query_results([Vec::<avatar::Model>::new()])
+        .append_exec_results([
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
+        .into_connection();
+    let service = make_service(db);
+
+    let req = UpdateProfileRequest {
+        skills: Some(vec![" First Aid".to_string(), "first aid".to_string()]),
+        preferred_areas: None,
+    };
+    let user = service
+        .update_profile("1", req, &make_claims("1", "volunteer"))
+        .await
+        .unwrap();
+
+    assert_eq!(user.profile.unwrap().skills, vec!["first aid".to_string()]);
+}
+
+#[tokio::test]
+async fn test_update_profile_of_someone_else_forbidden() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+
+    let result = service
+        .update_profile(
+            "2",
+            UpdateProfileRequest::default(),
+            &make_claims("1", "staff"),
+        )
+        .await;
+
+    assert_eq!(result.unwrap_err().to_string(), "Forbidden");
+}
+
+#[tokio::test]
+async fn test_add_availability_rejects_inverted_window() {
+    let service = make_service(MockDatabase::new(DatabaseBackend::Sqlite).into_connection());
+    let now = chrono::Utc::now();
+
+    let req = CreateAvailabilityRequest {
+        starts_at: now,
+        ends_at: now,
+        note: "".to_string(),
+    };
+    let result = service
+        .add_availability("1", req, &make_claims("1", "organizer"))
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Availability must end after it starts"
+    );
+}
+
+// ── avatars ──────────────────────────────────────────────────────────
+
+fn make_file(content_type: &str, image_status: Option<ImageStatus>) -> FileModel {
+    FileModel {
+        id: "f1".to_string(),
+        event_id: "event-1".to_string(),
+        name: "me.jpg".to_string(),
+        content_type: content_type.to_string(),
+        size: 10,
+        sha256: "abc".to_string(),
+        storage_key: "abc".to_string(),
+        image_status,
+        uploaded_by: "1".to_string(),
+        created_at: chrono::Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_set_avatar_rejects_documents() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_member("1", Role::Volunteer)]])
+        .append_query_results([vec![make_file("application/pdf", None)]])
+        .into_connection();
+    let service = make_service(db);
+
+    let result = service
+        .set_avatar(
+            "1",
+            SetAvatarRequest {
+                file_id: "f1".to_string(),
+            },
+            &make_claims("1", "volunteer"),
+        )
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Bad request: Avatars must be images"
+    );
+}
+
+#[tokio::test]
+async fn test_get_user_links_ready_avatar() {
+    let db = MockDatabase::new(DatabaseBackend::Sqlite)
+        .append_query_results([vec![make_model("1", "john@example.com")]])
+        .append_query_results([vec![make_member("1", Role::Volunteer)]])
+        .append_query_results([Vec::<skill::Model>::new()])
+        .append_query_results([Vec::<preferred_area::Model>::new()])
+        .append_query_results([Vec::<availability::Model>::new()])
+        .append_query_results([vec![avatar::Model {
+            user_id: "1".to_string(),
+            file_id: "f1".to_string(),
+            updated_at: chrono::Utc::now(),
+        }]])
+        .append_query_results([vec![make_file("image/jpeg", Some(ImageStatus::Ready))]])
+        .into_connection();
+    let service = make_service(db);
+
+    let user = service
+        .get_user("1", &make_claims("2", "staff"))
+        .await
+        .unwrap();
+
+    let avatar = user.avatar.unwrap();
+    assert_eq!(avatar.file_id, "f1");
+    assert!(
+        avatar
+            .thumbnail
+            .unwrap()
+            .url
+            .starts_with("/files/f1/download?variant=thumbnail&")
+    );
+}